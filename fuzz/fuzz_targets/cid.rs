@@ -0,0 +1,41 @@
+//! Parses CIDs from bytes and strings, and checks that they format back to what was parsed.
+//! Opaque CIDs must round-trip as well, and agree with `Cid` on the ones it supports.
+
+#![no_main]
+
+use dasl::cid::{Cid, Multibase, OpaqueCid};
+use libfuzzer_sys::fuzz_target;
+
+const BASES: [Multibase; 4] = [
+    Multibase::Base32Lower,
+    Multibase::Base58Btc,
+    Multibase::Base16Lower,
+    Multibase::Base64Url,
+];
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(cid) = Cid::from_bytes_raw(data) {
+        assert_eq!(cid.as_bytes(), data);
+        check(cid);
+    }
+    if let Ok(cid) = Cid::from_bytes(data) {
+        check(cid);
+    }
+    if let Ok(cid) = std::str::from_utf8(data).unwrap_or_default().parse::<Cid>() {
+        check(cid);
+    }
+    if let Ok(opaque) = OpaqueCid::from_bytes_raw(data) {
+        assert_eq!(opaque.as_bytes(), data);
+        assert_eq!(opaque.to_string().parse::<OpaqueCid>().unwrap(), opaque);
+        if let Ok(cid) = Cid::try_from(&opaque) {
+            assert_eq!(OpaqueCid::from(cid), opaque);
+        }
+    }
+});
+
+fn check(cid: Cid) {
+    for base in BASES {
+        let string = cid.to_string_with_base(base);
+        assert_eq!(string.parse::<Cid>().unwrap(), cid, "{string}");
+    }
+}
@@ -0,0 +1,28 @@
+//! Decodes arbitrary bytes with every decoder, none of them may panic.
+
+#![no_main]
+
+use dasl::drisl::{
+    self, Value,
+    de::{self, DecodeOptions},
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let from_slice = drisl::from_slice::<Value>(data);
+    let from_reader = drisl::from_reader::<Value, _>(data);
+    match (&from_slice, &from_reader) {
+        (Ok(from_slice), Ok(from_reader)) => assert_eq!(from_slice, from_reader),
+        (Err(_), Err(_)) => {}
+        _ => panic!("slice and reader disagree: {from_slice:?} {from_reader:?}"),
+    }
+
+    let _ = de::from_slice_ref(data);
+    let _ = de::from_slice_with::<Value>(data, DecodeOptions::new().lenient(true));
+    let _ = drisl::validate(data);
+    for item in de::iter_from_slice::<Value>(data) {
+        if item.is_err() {
+            break;
+        }
+    }
+});
@@ -0,0 +1,17 @@
+//! Encoded values decode to the same value, and encode to the same bytes again.
+
+#![no_main]
+
+use dasl::drisl::{self, Value};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|value: Value| {
+    let bytes = drisl::to_vec(&value).unwrap();
+    assert!(
+        drisl::validate(&bytes).is_ok(),
+        "encoded data that isn't canonical"
+    );
+    let decoded = drisl::from_slice::<Value>(&bytes).unwrap();
+    assert_eq!(decoded, value);
+    assert_eq!(drisl::to_vec(&decoded).unwrap(), bytes);
+});
@@ -0,0 +1,18 @@
+//! Data that decodes is valid DRISL, and the decoded value re-encodes to the same bytes.
+
+#![no_main]
+
+use dasl::drisl::{self, Value};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(value) = drisl::from_slice::<Value>(data) else {
+        return;
+    };
+    assert!(
+        drisl::validate(data).is_ok(),
+        "decoded data that isn't valid"
+    );
+    let encoded = drisl::to_vec(&value).unwrap();
+    assert_eq!(encoded, data);
+});
@@ -0,0 +1,209 @@
+//! ATProto repositories.
+//!
+//! An ATProto repository is exported as a CAR file, whose root is a signed commit. The commit
+//! points to a Merkle Search Tree (MST), which maps record paths (`collection/rkey`) to the CIDs
+//! of the records.
+//!
+//! [Spec](https://atproto.com/specs/repository)
+
+use std::{collections::HashMap, convert::Infallible, io::Read};
+
+use serde::Deserialize;
+use serde_bytes::ByteBuf;
+use thiserror::Error;
+
+use crate::{
+    car::{CarError, CarReader},
+    cid::Cid,
+    drisl::{DecodeError, Value},
+};
+
+/// A signed repository commit.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Commit {
+    /// The DID of the account the repository belongs to.
+    pub did: String,
+    /// The repository format version.
+    pub version: u64,
+    /// The root of the MST.
+    pub data: Cid,
+    /// The revision of the repository. Not present in version 2 repositories.
+    #[serde(default)]
+    pub rev: Option<String>,
+    /// The previous commit, if any.
+    #[serde(default)]
+    pub prev: Option<Cid>,
+    /// The signature over the unsigned commit.
+    pub sig: ByteBuf,
+}
+
+/// A node of the Merkle Search Tree.
+#[derive(Debug, Deserialize)]
+struct Node {
+    /// The left-most subtree.
+    l: Option<Cid>,
+    /// The entries of this node.
+    e: Vec<TreeEntry>,
+}
+
+/// An entry of a MST node.
+#[derive(Debug, Deserialize)]
+struct TreeEntry {
+    /// Number of bytes shared with the previous key in the same node.
+    p: usize,
+    /// The remainder of the key.
+    k: ByteBuf,
+    /// The CID of the record.
+    v: Cid,
+    /// The subtree to the right of this entry.
+    t: Option<Cid>,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RepoError {
+    #[error("CAR error: {_0}")]
+    Car(#[from] CarError),
+    #[error("CAR file has no root")]
+    NoRoot,
+    #[error("Missing block: {_0}")]
+    MissingBlock(Cid),
+    #[error("Block doesn't match its CID: {_0}")]
+    HashMismatch(Cid),
+    #[error("Failed to decode block: {_0}")]
+    Decode(#[from] DecodeError<Infallible>),
+    #[error("Invalid MST key")]
+    InvalidKey,
+}
+
+/// An ATProto repository, loaded from a CAR file.
+///
+/// All blocks are kept in memory, they are verified against their CIDs when loading.
+#[derive(Debug)]
+pub struct Repo {
+    root: Cid,
+    commit: Commit,
+    blocks: HashMap<Vec<u8>, Vec<u8>>,
+}
+
+impl Repo {
+    /// Reads a repository from a CAR file.
+    pub fn from_car<R: Read>(reader: R) -> Result<Self, RepoError> {
+        let mut car = CarReader::new(reader)?;
+        let root = *car.roots().first().ok_or(RepoError::NoRoot)?;
+
+        let mut blocks = HashMap::new();
+        while let Some((cid, data)) = car.next_block()? {
+            if cid.verify(&data).is_err() {
+                return Err(RepoError::HashMismatch(cid));
+            }
+            blocks.insert(cid.as_bytes().to_vec(), data);
+        }
+
+        let commit = decode_block(&blocks, &root)?;
+        Ok(Self {
+            root,
+            commit,
+            blocks,
+        })
+    }
+
+    /// Returns the CID of the commit.
+    pub fn root(&self) -> &Cid {
+        &self.root
+    }
+
+    /// Returns the commit.
+    pub fn commit(&self) -> &Commit {
+        &self.commit
+    }
+
+    /// Returns the raw data of a block.
+    pub fn block(&self, cid: &Cid) -> Option<&[u8]> {
+        self.blocks.get(cid.as_bytes()).map(Vec::as_slice)
+    }
+
+    /// Iterates over all records in key order, as `(record_path, cid, record)`.
+    pub fn entries(&self) -> Entries<'_> {
+        Entries {
+            repo: self,
+            stack: vec![Pending::Node(self.commit.data)],
+        }
+    }
+
+    fn decode<'a, T: Deserialize<'a>>(&'a self, cid: &Cid) -> Result<T, RepoError> {
+        decode_block(&self.blocks, cid)
+    }
+}
+
+/// Iterator over the records of a [`Repo`].
+#[derive(Debug)]
+pub struct Entries<'a> {
+    repo: &'a Repo,
+    stack: Vec<Pending>,
+}
+
+#[derive(Debug)]
+enum Pending {
+    Node(Cid),
+    Record(String, Cid),
+}
+
+impl Entries<'_> {
+    /// Pushes the content of a node onto the stack, so that it is visited in key order.
+    fn push_node(&mut self, cid: &Cid) -> Result<(), RepoError> {
+        let node: Node = self.repo.decode(cid)?;
+
+        let mut pending = Vec::with_capacity(1 + node.e.len() * 2);
+        if let Some(left) = node.l {
+            pending.push(Pending::Node(left));
+        }
+        let mut prev_key: Vec<u8> = Vec::new();
+        for entry in node.e {
+            if entry.p > prev_key.len() {
+                return Err(RepoError::InvalidKey);
+            }
+            let mut key = prev_key[..entry.p].to_vec();
+            key.extend_from_slice(&entry.k);
+            let path = String::from_utf8(key.clone()).map_err(|_| RepoError::InvalidKey)?;
+            prev_key = key;
+
+            pending.push(Pending::Record(path, entry.v));
+            if let Some(right) = entry.t {
+                pending.push(Pending::Node(right));
+            }
+        }
+        self.stack.extend(pending.into_iter().rev());
+        Ok(())
+    }
+}
+
+impl Iterator for Entries<'_> {
+    type Item = Result<(String, Cid, Value), RepoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.pop()? {
+                Pending::Node(cid) => {
+                    if let Err(err) = self.push_node(&cid) {
+                        self.stack.clear();
+                        return Some(Err(err));
+                    }
+                }
+                Pending::Record(path, cid) => {
+                    return Some(self.repo.decode(&cid).map(|value| (path, cid, value)));
+                }
+            }
+        }
+    }
+}
+
+fn decode_block<'a, T: Deserialize<'a>>(
+    blocks: &'a HashMap<Vec<u8>, Vec<u8>>,
+    cid: &Cid,
+) -> Result<T, RepoError> {
+    let block = blocks
+        .get(cid.as_bytes())
+        .ok_or(RepoError::MissingBlock(*cid))?;
+    Ok(crate::drisl::de::from_slice(block)?)
+}
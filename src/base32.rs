@@ -1,3 +0,0 @@
-pub(crate) const BASE32_LOWER: data_encoding::Encoding = data_encoding_macro::new_encoding! {
-    symbols: "abcdefghijklmnopqrstuvwxyz234567",
-};
@@ -0,0 +1,171 @@
+//! Storage of blocks, addressed by their CID.
+
+use std::{
+    collections::HashMap,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{
+        PoisonError, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use crate::{
+    car::{CarError, CarReader},
+    cid::Cid,
+};
+
+/// A store of blocks, addressed by their CID.
+///
+/// Stores don't need to verify the data they return, consumers are expected to verify blocks
+/// against their CIDs.
+pub trait BlockStore {
+    /// Returns the data of the block with the given CID, if present.
+    fn get(&self, cid: &Cid) -> std::io::Result<Option<Vec<u8>>>;
+
+    /// Stores a block.
+    fn put(&self, cid: &Cid, data: &[u8]) -> std::io::Result<()>;
+
+    /// Returns whether a block with the given CID is present.
+    fn has(&self, cid: &Cid) -> std::io::Result<bool> {
+        Ok(self.get(cid)?.is_some())
+    }
+}
+
+impl<S: BlockStore + ?Sized> BlockStore for std::sync::Arc<S> {
+    fn get(&self, cid: &Cid) -> std::io::Result<Option<Vec<u8>>> {
+        (**self).get(cid)
+    }
+
+    fn put(&self, cid: &Cid, data: &[u8]) -> std::io::Result<()> {
+        (**self).put(cid, data)
+    }
+
+    fn has(&self, cid: &Cid) -> std::io::Result<bool> {
+        (**self).has(cid)
+    }
+}
+
+/// A block store that keeps all blocks in memory.
+#[derive(Debug, Default)]
+pub struct MemoryBlockStore {
+    blocks: RwLock<HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl MemoryBlockStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a store that contains all blocks of a CAR file.
+    pub fn from_car<R: Read>(reader: R) -> Result<Self, CarError> {
+        let store = Self::new();
+        for block in CarReader::new(reader)? {
+            let (cid, data) = block?;
+            store.insert(&cid, data);
+        }
+        Ok(store)
+    }
+
+    /// Returns the number of blocks in the store.
+    pub fn len(&self) -> usize {
+        self.blocks
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Returns `true` if the store contains no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert(&self, cid: &Cid, data: Vec<u8>) {
+        self.blocks
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(cid.as_bytes().to_vec(), data);
+    }
+}
+
+impl BlockStore for MemoryBlockStore {
+    fn get(&self, cid: &Cid) -> std::io::Result<Option<Vec<u8>>> {
+        Ok(self
+            .blocks
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(cid.as_bytes())
+            .cloned())
+    }
+
+    fn put(&self, cid: &Cid, data: &[u8]) -> std::io::Result<()> {
+        self.insert(cid, data.to_vec());
+        Ok(())
+    }
+
+    fn has(&self, cid: &Cid) -> std::io::Result<bool> {
+        Ok(self
+            .blocks
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains_key(cid.as_bytes()))
+    }
+}
+
+/// A block store that keeps every block in its own file, named after the CID, in a directory.
+#[derive(Debug, Clone)]
+pub struct DirBlockStore {
+    dir: PathBuf,
+}
+
+impl DirBlockStore {
+    /// Opens a store in the given directory, creating the directory if needed.
+    pub fn open(dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the directory of the store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, cid: &Cid) -> PathBuf {
+        self.dir.join(cid.to_string())
+    }
+}
+
+impl BlockStore for DirBlockStore {
+    fn get(&self, cid: &Cid) -> std::io::Result<Option<Vec<u8>>> {
+        match std::fs::read(self.path(cid)) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn put(&self, cid: &Cid, data: &[u8]) -> std::io::Result<()> {
+        // Counts the temporary files of this process, so that concurrent writers of the same
+        // block, in this process or others, never share one.
+        static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        // Write to a temporary file first, so readers never observe partial blocks.
+        let path = self.path(cid);
+        let tmp = path.with_extension(format!(
+            "{}.{}.tmp",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let result = std::fs::write(&tmp, data).and_then(|()| std::fs::rename(&tmp, path));
+        if result.is_err() {
+            let _ = std::fs::remove_file(tmp);
+        }
+        result
+    }
+
+    fn has(&self, cid: &Cid) -> std::io::Result<bool> {
+        self.path(cid).try_exists()
+    }
+}
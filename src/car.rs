@@ -0,0 +1,189 @@
+//! CAR (Content Addressable aRchive) files.
+//!
+//! A CAR file is a DRISL encoded header, followed by a sequence of blocks. Both the header and
+//! each block are prefixed by their length as an unsigned varint, and each block starts with the
+//! binary encoding of its CID.
+//!
+//! [Spec](https://dasl.ing/car.html)
+
+use std::{
+    collections::TryReserveError,
+    convert::Infallible,
+    io::{Read, Write},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    cid::{Cid, CidParseError},
+    drisl::{DecodeError, EncodeError},
+    varint,
+};
+
+/// The only CAR version that is supported.
+const CAR_VERSION: u64 = 1;
+
+/// The header of a CAR file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CarHeader {
+    /// The root CIDs of the archive.
+    pub roots: Vec<Cid>,
+    /// The CAR format version, always `1`.
+    pub version: u64,
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CarError {
+    #[error("IO error: {_0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid header: {_0}")]
+    InvalidHeader(#[from] DecodeError<Infallible>),
+    #[error("Failed to encode header: {_0}")]
+    EncodeHeader(#[from] EncodeError<TryReserveError>),
+    #[error("Unsupported CAR version: {_0}")]
+    UnsupportedVersion(u64),
+    #[error("Invalid block CID: {_0}")]
+    InvalidCid(#[from] CidParseError),
+    #[error("Unexpected end of file")]
+    UnexpectedEof,
+}
+
+/// Reads the header and blocks of a CAR file.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::{car::{CarReader, CarWriter}, cid::{Cid, Codec}};
+/// let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+/// let mut writer = CarWriter::new(Vec::new(), vec![cid]).unwrap();
+/// writer.write_block(&cid, b"hello").unwrap();
+/// let car = writer.finish().unwrap();
+///
+/// let mut reader = CarReader::new(&car[..]).unwrap();
+/// assert_eq!(reader.roots(), &[cid]);
+/// let (block_cid, data) = reader.next_block().unwrap().unwrap();
+/// assert_eq!(block_cid, cid);
+/// assert_eq!(data, b"hello");
+/// assert!(reader.next_block().unwrap().is_none());
+/// ```
+#[derive(Debug)]
+pub struct CarReader<R> {
+    reader: R,
+    header: CarHeader,
+}
+
+impl<R: Read> CarReader<R> {
+    /// Creates a new reader and reads the CAR header.
+    pub fn new(mut reader: R) -> Result<Self, CarError> {
+        let header = read_section(&mut reader)?.ok_or(CarError::UnexpectedEof)?;
+        let header: CarHeader = crate::drisl::de::from_slice(&header)?;
+        if header.version != CAR_VERSION {
+            return Err(CarError::UnsupportedVersion(header.version));
+        }
+        Ok(Self { reader, header })
+    }
+
+    /// Returns the header of the CAR file.
+    pub fn header(&self) -> &CarHeader {
+        &self.header
+    }
+
+    /// Returns the root CIDs of the CAR file.
+    pub fn roots(&self) -> &[Cid] {
+        &self.header.roots
+    }
+
+    /// Reads the next block, returns `None` at the end of the file.
+    pub fn next_block(&mut self) -> Result<Option<(Cid, Vec<u8>)>, CarError> {
+        let Some(mut section) = read_section(&mut self.reader)? else {
+            return Ok(None);
+        };
+        let cid_len = cid_len(&section)?;
+        let cid = Cid::from_bytes_raw(&section[..cid_len])?;
+        section.drain(..cid_len);
+        Ok(Some((cid, section)))
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for CarReader<R> {
+    type Item = Result<(Cid, Vec<u8>), CarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_block().transpose()
+    }
+}
+
+/// Writes a CAR file.
+#[derive(Debug)]
+pub struct CarWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> CarWriter<W> {
+    /// Creates a new writer and writes the CAR header with the given roots.
+    pub fn new(mut writer: W, roots: Vec<Cid>) -> Result<Self, CarError> {
+        let header = crate::drisl::ser::to_vec(&CarHeader {
+            roots,
+            version: CAR_VERSION,
+        })?;
+        write_varint(&mut writer, header.len() as u64)?;
+        writer.write_all(&header)?;
+        Ok(Self { writer })
+    }
+
+    /// Appends a block to the CAR file.
+    ///
+    /// The data is written as is, it is not verified against the CID.
+    pub fn write_block(&mut self, cid: &Cid, data: &[u8]) -> Result<(), CarError> {
+        let cid = cid.as_bytes();
+        write_varint(&mut self.writer, (cid.len() + data.len()) as u64)?;
+        self.writer.write_all(cid)?;
+        self.writer.write_all(data)?;
+        Ok(())
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, CarError> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads a varint length prefixed section, returns `None` on a clean end of file.
+fn read_section<R: Read>(reader: &mut R) -> Result<Option<Vec<u8>>, CarError> {
+    let Some(len) = varint::read_u64(&mut *reader)? else {
+        return Ok(None);
+    };
+    // Don't preallocate based on the untrusted length, `read_to_end` grows as needed.
+    let mut section = Vec::new();
+    reader.take(len).read_to_end(&mut section)?;
+    if section.len() as u64 != len {
+        return Err(CarError::UnexpectedEof);
+    }
+    Ok(Some(section))
+}
+
+/// Returns the length of the binary CID at the start of a block section.
+fn cid_len(section: &[u8]) -> Result<usize, CarError> {
+    // Version, codec, hash type and digest length are all single bytes for supported CIDs.
+    const PREFIX_LEN: usize = 4;
+
+    let digest_len = *section.get(PREFIX_LEN - 1).ok_or(CidParseError::TooShort)?;
+    let len = PREFIX_LEN + usize::from(digest_len);
+    if section.len() < len {
+        return Err(CidParseError::TooShort.into());
+    }
+    Ok(len)
+}
+
+fn write_varint<W: Write>(writer: &mut W, value: u64) -> std::io::Result<()> {
+    let mut buf = [0u8; varint::MAX_LEN_U64];
+    writer.write_all(varint::encode_u64(value, &mut buf))
+}
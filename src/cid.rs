@@ -2,36 +2,67 @@
 //!
 //! [Spec](https://dasl.ing/cid.html)
 
-use std::{fmt::Display, str::FromStr};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{borrow::Borrow, fmt::Display, hash::Hash, str::FromStr};
 
 use sha2::Digest;
 use thiserror::Error;
 
-use crate::base32::BASE32_LOWER;
-
+use crate::{
+    multibase::{self, base32},
+    varint,
+};
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod blake3;
+mod builder;
+#[cfg(feature = "std")]
+mod io;
+mod macros;
+#[cfg(feature = "cid")]
+mod multiformats;
+mod opaque;
 mod serde;
 
+pub use self::blake3::Blake3HashError;
+pub use self::builder::CidBuilder;
+#[cfg(feature = "std")]
+pub use self::io::{HashingReader, HashingWriter, VerifyingReader};
+#[cfg(feature = "cid")]
+pub use self::multiformats::UnsupportedCidError;
+pub use self::opaque::OpaqueCid;
 pub(crate) use self::serde::{BytesToCidVisitor, CID_SERDE_PRIVATE_IDENTIFIER};
+#[doc(no_inline)]
+pub use crate::multibase::Multibase;
 
 const CID_VERSION: u8 = 1;
 const PREFIX_LEN: usize = 4;
 /// Length of a known hash
 const HASH_LEN: u8 = 32;
 const DATA_LEN: usize = PREFIX_LEN + HASH_LEN as usize;
-const HASH_CODE_SHA2_256: u8 = 0x12;
-const HASH_CODE_BLAKE3: u8 = 0x1e;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
+/// A content identifier.
+///
+/// CIDs are compared, ordered and hashed by their binary encoding (see [`Cid::as_bytes`]), so
+/// they can be used as keys in both `BTreeMap` and `HashMap`, and looked up by their bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Cid {
+    // The binary encoding, only the prefix is used if the hash is empty:
     // - 1 byte CID version
     // - 1 byte Codec
     // - 1 byte hash type
-    // - 1 byte Length
+    // - 1 byte Length, 0 or `HASH_LEN`, which gives the used part of `data`
     // - 32 bytes hash
+    //
+    // It's first so that the derived `Ord` is the order of the encoding, the other fields are
+    // derived from it and only kept so that the accessors don't have to parse it again.
     data: [u8; DATA_LEN],
+    codec: Codec,
+    multihash: Multihash,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 #[repr(u8)]
 pub enum Codec {
@@ -41,14 +72,30 @@ pub enum Codec {
 
 #[derive(Debug, Error)]
 pub enum ParseCodecError {
+    /// The multicodec code of the codec, which may be more than a byte.
     #[error("Unknown codec: 0x{_0:X}")]
-    UnknownCodec(u8),
+    UnknownCodec(u64),
 }
 
 impl TryFrom<u8> for Codec {
     type Error = ParseCodecError;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_code(value as u64)
+    }
+}
+
+impl TryFrom<u64> for Codec {
+    type Error = ParseCodecError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::from_code(value)
+    }
+}
+
+impl Codec {
+    /// Same as `TryFrom<u64>`, usable in const contexts.
+    const fn from_code(value: u64) -> Result<Self, ParseCodecError> {
         match value {
             0x55 => Ok(Self::Raw),
             0x71 => Ok(Self::Drisl),
@@ -57,7 +104,7 @@ impl TryFrom<u8> for Codec {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 #[repr(u8)]
 pub enum Multihash {
@@ -69,9 +116,24 @@ impl TryFrom<u8> for Multihash {
     type Error = MultihashParseError;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_code(value as u64)
+    }
+}
+
+impl TryFrom<u64> for Multihash {
+    type Error = MultihashParseError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Self::from_code(value)
+    }
+}
+
+impl Multihash {
+    /// Same as `TryFrom<u64>`, usable in const contexts.
+    const fn from_code(value: u64) -> Result<Self, MultihashParseError> {
         match value {
-            HASH_CODE_SHA2_256 => Ok(Self::Sha2256),
-            HASH_CODE_BLAKE3 => Ok(Self::Blake3),
+            0x12 => Ok(Self::Sha2256),
+            0x1e => Ok(Self::Blake3),
             _ => Err(MultihashParseError::UnknownHash(value)),
         }
     }
@@ -91,6 +153,16 @@ pub enum CidParseError {
     InvalidMultihash(MultihashParseError),
 }
 
+/// Returned by [`Cid::verify`] if the data doesn't hash to the expected `CID`.
+#[derive(Debug, Error)]
+#[error("CID mismatch: expected {expected}, got {actual}")]
+pub struct CidVerifyError {
+    /// The `CID` that was verified against.
+    pub expected: Cid,
+    /// The `CID` computed from the data.
+    pub actual: Cid,
+}
+
 impl From<ParseCodecError> for CidParseError {
     fn from(err: ParseCodecError) -> Self {
         Self::InvalidCodec(err)
@@ -106,144 +178,304 @@ impl From<MultihashParseError> for CidParseError {
 impl FromStr for Cid {
     type Err = CidParseError;
 
+    /// Parses a `CID` in any of the supported [`Multibase`] encodings.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if !s.starts_with('b') {
-            return Err(CidParseError::InvalidEncoding);
-        }
-
-        // skip base encoding prefix
-        let without_prefix = &s.as_bytes()[1..];
-        let bytes = BASE32_LOWER
-            .decode(without_prefix)
-            .map_err(|_e| CidParseError::InvalidEncoding)?;
-
+        let (_, bytes) = multibase::decode(s).map_err(|_| CidParseError::InvalidEncoding)?;
         Cid::from_bytes_raw(&bytes)
     }
 }
 
 impl Cid {
-    /// Returns the `Multihash` of this `CID`.
-    pub fn hash(&self) -> &[u8] {
-        match self.data[3] {
-            0 => &[][..], // empty hash
-            HASH_LEN => &self.data[PREFIX_LEN..],
-            _ => unreachable!("invalid construction"),
+    /// Creates a `CID` from its components, with an empty hash if `digest` is `None`.
+    ///
+    /// This is the only way `CID`s are constructed, so the fields always agree with `data`.
+    pub(crate) const fn new(
+        codec: Codec,
+        multihash: Multihash,
+        digest: Option<&[u8; HASH_LEN as usize]>,
+    ) -> Self {
+        let mut data = [0u8; DATA_LEN];
+        data[0] = CID_VERSION;
+        data[1] = codec as u8;
+        data[2] = multihash as u8;
+        if let Some(digest) = digest {
+            data[3] = HASH_LEN;
+            // Slices can't be copied in const contexts.
+            let mut i = 0;
+            while i < digest.len() {
+                data[PREFIX_LEN + i] = digest[i];
+                i += 1;
+            }
+        }
+        Cid {
+            data,
+            codec,
+            multihash,
         }
     }
 
+    /// Returns the digest of this `CID`, which is empty for an empty hash.
+    pub fn hash(&self) -> &[u8] {
+        self.as_bytes().split_at(PREFIX_LEN).1
+    }
+
+    /// Returns the `Multihash` type of this `CID`.
     pub fn multihash_type(&self) -> Multihash {
-        Multihash::try_from(self.data[2]).expect("invalid construction")
+        self.multihash
     }
 
     /// Returns the `Codec` of this `CID`.
     pub fn codec(&self) -> Codec {
-        Codec::try_from(self.data[1]).expect("invalid construction")
+        self.codec
     }
 
     /// Tries to decode a `CID` from binary encoding.
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CidParseError> {
-        if bytes.is_empty() {
-            return Err(CidParseError::TooShort);
+    pub const fn from_bytes(bytes: &[u8]) -> Result<Self, CidParseError> {
+        match bytes.split_first() {
+            None => Err(CidParseError::TooShort),
+            Some((0x0, bytes)) => Self::from_bytes_raw(bytes),
+            Some(_) => Err(CidParseError::InvalidEncoding),
         }
-        if bytes[0] != 0x0 {
-            return Err(CidParseError::InvalidEncoding);
-        }
-        Self::from_bytes_raw(&bytes[1..])
     }
 
     /// Tries to decode a `CID` from its raw binary components.
-    pub fn from_bytes_raw(bytes: &[u8]) -> Result<Self, CidParseError> {
-        const MIN_LEN: usize = 3;
-
-        if bytes.len() < MIN_LEN {
+    ///
+    /// The codec and multihash type are varints, so unknown codes of more than a byte are
+    /// reported as such.
+    pub const fn from_bytes_raw(bytes: &[u8]) -> Result<Self, CidParseError> {
+        let Some((&version, rest)) = bytes.split_first() else {
             return Err(CidParseError::TooShort);
+        };
+        if version != CID_VERSION {
+            return Err(CidParseError::InvalidCidVersion(version));
         }
-        if bytes.len() > DATA_LEN {
-            return Err(MultihashParseError::InvalidLength(bytes.len()).into());
-        }
-
-        if bytes[0] != CID_VERSION {
-            return Err(CidParseError::InvalidCidVersion(bytes[0]));
-        }
-        let mut data = [0u8; DATA_LEN];
-        let _codec = Codec::try_from(bytes[1])?;
-        let _multihash = Multihash::try_from(bytes[2])?;
-
-        let len = bytes[3];
-        match len {
-            0 => {
-                if bytes.len() > 4 {
-                    return Err(MultihashParseError::InvalidLength(bytes.len()).into());
+        let (codec, rest) = match read_code(rest) {
+            Ok((code, rest)) => match Codec::from_code(code) {
+                Ok(codec) => (codec, rest),
+                Err(err) => return Err(CidParseError::InvalidCodec(err)),
+            },
+            Err(err) => return Err(err),
+        };
+        let (multihash, rest) = match read_code(rest) {
+            Ok((code, rest)) => match Multihash::from_code(code) {
+                Ok(multihash) => (multihash, rest),
+                Err(err) => return Err(CidParseError::InvalidMultihash(err)),
+            },
+            Err(err) => return Err(err),
+        };
+        // All supported codes are a single byte, so the digest length is at the same offset.
+        let Some((&len, digest)) = rest.split_first() else {
+            return Err(CidParseError::TooShort);
+        };
+
+        let mut hash = [0; HASH_LEN as usize];
+        let digest = match len {
+            0 if digest.is_empty() => None,
+            HASH_LEN if digest.len() == hash.len() => {
+                // Iterators and slice conversions can't be used in const contexts.
+                let mut i = 0;
+                while i < hash.len() {
+                    hash[i] = digest[i];
+                    i += 1;
                 }
-                data[..PREFIX_LEN].copy_from_slice(&bytes[..PREFIX_LEN]);
+                Some(&hash)
             }
-            HASH_LEN => {
-                if bytes.len() != DATA_LEN {
-                    return Err(MultihashParseError::InvalidLength(bytes.len()).into());
-                }
-                data.copy_from_slice(bytes);
+            0 | HASH_LEN => {
+                let err = MultihashParseError::InvalidLength(bytes.len());
+                return Err(CidParseError::InvalidMultihash(err));
             }
-            _ => return Err(MultihashParseError::InvalidLengthPrefix.into()),
-        }
+            _ => {
+                let err = MultihashParseError::InvalidLengthPrefix;
+                return Err(CidParseError::InvalidMultihash(err));
+            }
+        };
+        Ok(Cid::new(codec, multihash, digest))
+    }
 
-        Ok(Cid { data })
+    /// Parses a `CID` in the canonical base32 encoding, in const contexts.
+    ///
+    /// Unlike [`FromStr`], only the base32 multibase (the `b` prefix) is supported. The
+    /// [`cid!`](crate::cid!) macro uses it to parse `CID`s at compile time.
+    ///
+    /// ```
+    /// # use dasl::cid::{Cid, Codec};
+    /// const CID: Cid =
+    ///     match Cid::parse_base32("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy") {
+    ///         Ok(cid) => cid,
+    ///         Err(_) => panic!("invalid CID"),
+    ///     };
+    /// assert_eq!(CID, Cid::digest_sha2(Codec::Raw, b"foo"));
+    /// ```
+    pub const fn parse_base32(s: &str) -> Result<Self, CidParseError> {
+        let Some((b'b', encoded)) = s.as_bytes().split_first() else {
+            return Err(CidParseError::InvalidEncoding);
+        };
+        // One byte more than the longest `CID`, so that too long ones are reported as such.
+        match base32::decode_array::<{ DATA_LEN + 1 }>(encoded) {
+            Some((bytes, len)) => Self::from_bytes_raw(bytes.split_at(len).0),
+            None => Err(CidParseError::InvalidEncoding),
+        }
     }
 
     /// Encode the `CID` in its raw binary format.
     pub fn as_bytes(&self) -> &[u8] {
-        match self.data[3] {
-            0 => &self.data[..PREFIX_LEN],
-            HASH_LEN => &self.data,
-            _ => unreachable!("invalid construction"),
-        }
+        self.data.split_at(PREFIX_LEN + self.data[3] as usize).0
+    }
+
+    /// Returns the string form of this `CID` in the given encoding.
+    ///
+    /// [`Display`] uses [`Multibase::Base32Lower`].
+    pub fn to_string_with_base(&self, base: Multibase) -> String {
+        multibase::encode(base, self.as_bytes())
+    }
+
+    /// Verifies that `data` hashes to this `CID`, using its multihash type and codec.
+    ///
+    /// `CID`s with an empty hash never match.
+    pub fn verify(&self, data: impl AsRef<[u8]>) -> Result<(), CidVerifyError> {
+        let mut hasher = Hasher::new(self.multihash_type());
+        hasher.update(data.as_ref());
+        hasher.verify(*self)
     }
 
     pub fn digest_sha2(codec: Codec, data: impl AsRef<[u8]>) -> Self {
-        let hash = sha2::Sha256::digest(data);
-        let mut data = [0u8; DATA_LEN];
-        data[0] = CID_VERSION;
-        data[1] = codec as u8;
-        data[2] = HASH_CODE_SHA2_256;
-        data[3] = HASH_LEN;
-        data[PREFIX_LEN..].copy_from_slice(&hash);
-        Self { data }
+        CidBuilder::new().codec(codec).digest(data)
     }
 
     pub fn digest_blake3(codec: Codec, data: impl AsRef<[u8]>) -> Self {
-        let hash = blake3::hash(data.as_ref());
-        let mut data = [0u8; DATA_LEN];
-        data[0] = CID_VERSION;
-        data[1] = codec as u8;
-        data[2] = HASH_CODE_BLAKE3;
-        data[3] = HASH_LEN;
-        data[PREFIX_LEN..].copy_from_slice(hash.as_bytes());
-        Self { data }
+        CidBuilder::new()
+            .codec(codec)
+            .hash(Multihash::Blake3)
+            .digest(data)
     }
 
     pub fn empty_sha2_256(codec: Codec) -> Self {
-        let mut data = [0u8; DATA_LEN];
-        data[0] = CID_VERSION;
-        data[1] = codec as u8;
-        data[2] = HASH_CODE_SHA2_256;
-        data[3] = 0;
-        Self { data }
+        CidBuilder::new().codec(codec).empty()
     }
 
     pub fn empty_blake3(codec: Codec) -> Self {
-        let mut data = [0u8; DATA_LEN];
-        data[0] = CID_VERSION;
-        data[1] = codec as u8;
-        data[2] = HASH_CODE_BLAKE3;
-        data[3] = 0;
-        Self { data }
+        CidBuilder::new()
+            .codec(codec)
+            .hash(Multihash::Blake3)
+            .empty()
+    }
+}
+
+/// Decodes a varint code at the front of `bytes`, which must be in its shortest form.
+const fn read_code(bytes: &[u8]) -> Result<(u64, &[u8]), CidParseError> {
+    let Some((value, len)) = varint::decode_u64(bytes) else {
+        // Either the data ends within the varint, or it doesn't fit into a `u64`, which takes
+        // all of the varint's bytes.
+        return Err(if bytes.len() < varint::MAX_LEN_U64 {
+            CidParseError::TooShort
+        } else {
+            CidParseError::InvalidEncoding
+        });
+    };
+    // A trailing zero byte would only add zero bits.
+    if len > 1 && bytes[len - 1] == 0 {
+        return Err(CidParseError::InvalidEncoding);
+    }
+    Ok((value, bytes.split_at(len).1))
+}
+
+/// Incremental hasher for the supported multihash types.
+#[derive(Clone)]
+pub(crate) enum Hasher {
+    Sha2256(sha2::Sha256),
+    Blake3(Box<::blake3::Hasher>),
+}
+
+impl Hasher {
+    pub(crate) fn new(multihash: Multihash) -> Self {
+        match multihash {
+            Multihash::Sha2256 => Self::Sha2256(sha2::Sha256::new()),
+            Multihash::Blake3 => Self::Blake3(Box::new(::blake3::Hasher::new())),
+        }
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha2256(hasher) => hasher.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    pub(crate) fn finalize(self, codec: Codec) -> Cid {
+        match self {
+            Self::Sha2256(hasher) => {
+                Cid::new(codec, Multihash::Sha2256, Some(&hasher.finalize().into()))
+            }
+            Self::Blake3(hasher) => {
+                Cid::new(codec, Multihash::Blake3, Some(hasher.finalize().as_bytes()))
+            }
+        }
+    }
+
+    /// Checks that the hashed data matches `expected`, using its codec.
+    pub(crate) fn verify(self, expected: Cid) -> Result<(), CidVerifyError> {
+        let actual = self.finalize(expected.codec());
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(CidVerifyError { expected, actual })
+        }
+    }
+}
+
+/// Hashes the same as [`Cid::as_bytes`], as required by the [`Borrow`] impl.
+impl Hash for Cid {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state);
+    }
+}
+
+impl AsRef<[u8]> for Cid {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Allows looking up `CID` keys by their raw binary form.
+impl Borrow<[u8]> for Cid {
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// Decodes the raw binary form, like [`Cid::from_bytes_raw`].
+impl TryFrom<&[u8]> for Cid {
+    type Error = CidParseError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Cid::from_bytes_raw(bytes)
+    }
+}
+
+/// Decodes the raw binary form, like [`Cid::from_bytes_raw`].
+impl TryFrom<Vec<u8>> for Cid {
+    type Error = CidParseError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Cid::from_bytes_raw(&bytes)
+    }
+}
+
+/// The raw binary form, padded with zeros for `CID`s with an empty hash. Their length byte is 0, so
+/// only the first 4 bytes are used, which are [`Cid::as_bytes`].
+impl From<Cid> for [u8; DATA_LEN] {
+    fn from(cid: Cid) -> Self {
+        cid.data
     }
 }
 
 impl Display for Cid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "b")?;
         let out = self.as_bytes();
-        BASE32_LOWER.encode_write(out, f)?;
+        base32::encode_write(out, f)?;
 
         Ok(())
     }
@@ -254,8 +486,9 @@ impl Display for Cid {
 pub enum MultihashParseError {
     #[error("Invalid length: {_0}")]
     InvalidLength(usize),
+    /// The multicodec code of the hash function, which may be more than a byte.
     #[error("Unknown hash: {_0:x}")]
-    UnknownHash(u8),
+    UnknownHash(u64),
     #[error("Invalid length prefix")]
     InvalidLengthPrefix,
 }
@@ -299,4 +532,80 @@ mod tests {
         let cid_str = "bafkr4iae4c5tt4yldi76xcpvg3etxykqkvec352im5fqbutolj2xo5yc5e";
         assert_eq!(Cid::digest_blake3(Codec::Raw, b"foo").to_string(), cid_str);
     }
+
+    #[test]
+    fn test_multibase() {
+        let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+        for (base, cid_str) in [
+            (
+                Multibase::Base32Lower,
+                "bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy",
+            ),
+            (
+                Multibase::Base58Btc,
+                "zb2rhZcdMzBSGEjx2xbFsY9pTpMgegGQLEWsNpPnUzbPwCx1T",
+            ),
+            (
+                Multibase::Base16Lower,
+                "f015512202c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae",
+            ),
+            (
+                Multibase::Base64Url,
+                "uAVUSICwmtGto_8aP-ZtFPB0wQTQTQi1wZIO_oPmKXohiZueu",
+            ),
+        ] {
+            assert_eq!(cid.to_string_with_base(base), cid_str);
+            assert_eq!(cid_str.parse::<Cid>().unwrap(), cid);
+        }
+        assert_eq!(
+            cid.to_string(),
+            cid.to_string_with_base(Multibase::default())
+        );
+
+        let empty = Cid::empty_blake3(Codec::Drisl);
+        let encoded = empty.to_string_with_base(Multibase::Base58Btc);
+        assert_eq!(encoded.parse::<Cid>().unwrap(), empty);
+
+        assert!(matches!(
+            "".parse::<Cid>(),
+            Err(CidParseError::InvalidEncoding)
+        ));
+        assert!(matches!(
+            "F015512202C26".parse::<Cid>(),
+            Err(CidParseError::InvalidEncoding)
+        ));
+        assert!(matches!(
+            "z0OIl".parse::<Cid>(),
+            Err(CidParseError::InvalidEncoding)
+        ));
+    }
+
+    #[test]
+    fn test_verify() {
+        let cid = Cid::digest_blake3(Codec::Drisl, b"foo");
+        cid.verify(b"foo").unwrap();
+
+        let err = cid.verify(b"bar").unwrap_err();
+        assert_eq!(err.expected, cid);
+        assert_eq!(err.actual, Cid::digest_blake3(Codec::Drisl, b"bar"));
+
+        Cid::digest_sha2(Codec::Raw, b"foo").verify(b"foo").unwrap();
+        assert!(Cid::empty_sha2_256(Codec::Raw).verify(b"").is_err());
+    }
+
+    #[test]
+    fn test_ord_matches_bytes() {
+        let mut cids = vec![
+            Cid::digest_blake3(Codec::Drisl, b"foo"),
+            Cid::digest_sha2(Codec::Raw, b"foo"),
+            Cid::empty_sha2_256(Codec::Raw),
+            Cid::digest_sha2(Codec::Raw, b"bar"),
+            Cid::empty_blake3(Codec::Drisl),
+            Cid::digest_blake3(Codec::Raw, b"foo"),
+        ];
+        let mut by_bytes = cids.clone();
+        by_bytes.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+        cids.sort();
+        assert_eq!(cids, by_bytes);
+    }
 }
@@ -0,0 +1,22 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::{Cid, Codec, HASH_LEN, Multihash};
+
+/// Generates valid `CID`s with any of the supported codecs and multihash types, with a random
+/// digest or occasionally an empty hash.
+impl<'a> Arbitrary<'a> for Cid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let codec = *u.choose(&[Codec::Raw, Codec::Drisl])?;
+        let multihash = *u.choose(&[Multihash::Sha2256, Multihash::Blake3])?;
+        let digest = if u.ratio(1, 16)? {
+            None
+        } else {
+            Some(u.arbitrary()?)
+        };
+        Ok(Cid::new(codec, multihash, digest.as_ref()))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (3, Some(3 + HASH_LEN as usize))
+    }
+}
@@ -0,0 +1,58 @@
+//! Conversion between [`Cid`] and [`blake3::Hash`].
+//!
+//! A blob in [iroh-blobs](https://docs.rs/iroh-blobs) is addressed by the BLAKE3 hash of its
+//! bytes, so it's referenced by a [`Codec::Raw`] `CID` with [`Multihash::Blake3`]. Its `Hash`
+//! converts from and into [`blake3::Hash`].
+//!
+//! ```
+//! # use dasl::cid::{Cid, Codec};
+//! let hash = blake3::hash(b"hello");
+//! let cid = Cid::from(hash);
+//! assert_eq!(cid, Cid::digest_blake3(Codec::Raw, b"hello"));
+//! assert_eq!(blake3::Hash::try_from(cid), Ok(hash));
+//! ```
+
+use thiserror::Error;
+
+use super::{Cid, Codec, HASH_LEN, Multihash};
+
+/// Returned when converting a `CID` without a BLAKE3 digest into a [`blake3::Hash`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum Blake3HashError {
+    #[error("Expected a BLAKE3 multihash, got {0:?}")]
+    Multihash(Multihash),
+    #[error("The CID has an empty hash")]
+    Empty,
+}
+
+/// Returns the [`Codec::Raw`] `CID` of the blob with this hash.
+impl From<blake3::Hash> for Cid {
+    fn from(hash: blake3::Hash) -> Self {
+        Cid::new(Codec::Raw, Multihash::Blake3, Some(hash.as_bytes()))
+    }
+}
+
+/// The hash of the bytes the `CID` addresses, for any codec.
+impl TryFrom<&Cid> for blake3::Hash {
+    type Error = Blake3HashError;
+
+    fn try_from(cid: &Cid) -> Result<Self, Self::Error> {
+        match cid.multihash_type() {
+            Multihash::Blake3 => {}
+            other => return Err(Blake3HashError::Multihash(other)),
+        }
+        let digest: [u8; HASH_LEN as usize] =
+            cid.hash().try_into().map_err(|_| Blake3HashError::Empty)?;
+        Ok(blake3::Hash::from_bytes(digest))
+    }
+}
+
+/// The hash of the bytes the `CID` addresses, for any codec.
+impl TryFrom<Cid> for blake3::Hash {
+    type Error = Blake3HashError;
+
+    fn try_from(cid: Cid) -> Result<Self, Self::Error> {
+        blake3::Hash::try_from(&cid)
+    }
+}
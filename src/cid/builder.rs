@@ -0,0 +1,74 @@
+//! Builder for constructing CIDs
+
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+
+#[cfg(feature = "std")]
+use super::HashingReader;
+use super::{Cid, Codec, Hasher, Multihash};
+
+/// Constructs [`Cid`]s with a chosen codec and multihash type.
+///
+/// Defaults to [`Codec::Raw`] and [`Multihash::Sha2256`].
+///
+/// ```
+/// # use dasl::cid::{Cid, CidBuilder, Codec, Multihash};
+/// let cid = CidBuilder::new()
+///     .codec(Codec::Drisl)
+///     .hash(Multihash::Blake3)
+///     .digest(b"foo");
+/// assert_eq!(cid, Cid::digest_blake3(Codec::Drisl, b"foo"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CidBuilder {
+    codec: Codec,
+    multihash: Multihash,
+}
+
+impl Default for CidBuilder {
+    fn default() -> Self {
+        Self {
+            codec: Codec::Raw,
+            multihash: Multihash::Sha2256,
+        }
+    }
+}
+
+impl CidBuilder {
+    /// Creates a builder with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the codec.
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Sets the multihash type.
+    pub fn hash(mut self, multihash: Multihash) -> Self {
+        self.multihash = multihash;
+        self
+    }
+
+    /// Builds the `CID` of `data`.
+    pub fn digest(self, data: impl AsRef<[u8]>) -> Cid {
+        let mut hasher = Hasher::new(self.multihash);
+        hasher.update(data.as_ref());
+        hasher.finalize(self.codec)
+    }
+
+    /// Builds the `CID` of everything read from `reader`.
+    #[cfg(feature = "std")]
+    pub fn digest_from_reader(self, reader: impl Read) -> io::Result<Cid> {
+        let mut reader = HashingReader::new(reader, self.multihash);
+        io::copy(&mut reader, &mut io::sink())?;
+        Ok(reader.finalize(self.codec))
+    }
+
+    /// Builds a `CID` with an empty hash.
+    pub fn empty(self) -> Cid {
+        Cid::new(self.codec, self.multihash, None)
+    }
+}
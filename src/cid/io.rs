@@ -0,0 +1,162 @@
+//! Streaming CID computation
+//!
+//! Wrappers around [`Read`] and [`Write`] that hash all bytes passing through them, so the CID
+//! of large content can be computed or verified without holding it in memory.
+
+use std::io::{self, Read, Write};
+
+use super::{Cid, CidVerifyError, Codec, Hasher, Multihash};
+
+/// A [`Read`] adapter that hashes all bytes read through it.
+///
+/// ```
+/// # use std::io::Read;
+/// # use dasl::cid::{Cid, Codec, HashingReader, Multihash};
+/// let mut reader = HashingReader::new(&b"foo"[..], Multihash::Blake3);
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data).unwrap();
+/// assert_eq!(reader.finalize(Codec::Raw), Cid::digest_blake3(Codec::Raw, b"foo"));
+/// ```
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Hasher,
+}
+
+impl<R: Read> HashingReader<R> {
+    /// Wraps `inner`, hashing with the given multihash type.
+    pub fn new(inner: R, multihash: Multihash) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(multihash),
+        }
+    }
+
+    /// Returns a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the `CID` of all bytes read so far.
+    pub fn finalize(self, codec: Codec) -> Cid {
+        self.hasher.finalize(codec)
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
+
+/// A [`Write`] adapter that hashes all bytes written through it.
+///
+/// ```
+/// # use std::io::Write;
+/// # use dasl::cid::{Cid, Codec, HashingWriter, Multihash};
+/// let mut data = Vec::new();
+/// let mut writer = HashingWriter::new(&mut data, Multihash::Sha2256);
+/// writer.write_all(b"foo").unwrap();
+/// assert_eq!(writer.finalize(Codec::Raw), Cid::digest_sha2(Codec::Raw, b"foo"));
+/// assert_eq!(data, b"foo");
+/// ```
+pub struct HashingWriter<W> {
+    inner: W,
+    hasher: Hasher,
+}
+
+impl<W: Write> HashingWriter<W> {
+    /// Wraps `inner`, hashing with the given multihash type.
+    pub fn new(inner: W, multihash: Multihash) -> Self {
+        Self {
+            inner,
+            hasher: Hasher::new(multihash),
+        }
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the `CID` of all bytes written so far.
+    pub fn finalize(self, codec: Codec) -> Cid {
+        self.hasher.finalize(codec)
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// A [`Read`] adapter that verifies the bytes read through it against an expected `CID`.
+///
+/// Reaching the end of the stream returns an [`io::ErrorKind::InvalidData`] error wrapping a
+/// [`CidVerifyError`] if the content doesn't match. Use [`VerifyingReader::finish`] to check
+/// the content when not reading until the end.
+///
+/// ```
+/// # use std::io::Read;
+/// # use dasl::cid::{Cid, Codec, VerifyingReader};
+/// let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+///
+/// let mut data = Vec::new();
+/// VerifyingReader::new(&b"foo"[..], cid).read_to_end(&mut data).unwrap();
+///
+/// let err = VerifyingReader::new(&b"bar"[..], cid).read_to_end(&mut data).unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// ```
+pub struct VerifyingReader<R> {
+    inner: R,
+    expected: Cid,
+    hasher: Hasher,
+}
+
+impl<R: Read> VerifyingReader<R> {
+    /// Wraps `inner`, verifying it against `expected`.
+    pub fn new(inner: R, expected: Cid) -> Self {
+        Self {
+            inner,
+            expected,
+            hasher: Hasher::new(expected.multihash_type()),
+        }
+    }
+
+    /// Returns a reference to the wrapped reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the expected `CID`.
+    pub fn expected(&self) -> &Cid {
+        &self.expected
+    }
+
+    /// Verifies the bytes read so far against the expected `CID`.
+    pub fn finish(self) -> Result<(), CidVerifyError> {
+        self.hasher.verify(self.expected)
+    }
+}
+
+impl<R: Read> Read for VerifyingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if read == 0 && !buf.is_empty() {
+            self.hasher
+                .clone()
+                .verify(self.expected)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        }
+        self.hasher.update(&buf[..read]);
+        Ok(read)
+    }
+}
@@ -0,0 +1,31 @@
+//! The [`cid!`](crate::cid!) macro.
+
+/// Parses a [`Cid`](crate::cid::Cid) from a string literal at compile time.
+///
+/// The `CID` must be in the canonical base32 encoding, see
+/// [`Cid::parse_base32`](crate::cid::Cid::parse_base32). Invalid `CID`s fail to compile instead of
+/// panicking at runtime, and the result can be used in constants.
+///
+/// ```
+/// # use dasl::{cid, cid::{Cid, Codec}};
+/// const ROOT: Cid = cid!("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy");
+/// assert_eq!(ROOT, Cid::digest_sha2(Codec::Raw, b"foo"));
+/// ```
+///
+/// ```compile_fail
+/// # use dasl::cid;
+/// let cid = cid!("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhv");
+/// ```
+#[macro_export]
+macro_rules! cid {
+    ($cid:literal) => {
+        const {
+            match $crate::cid::Cid::parse_base32($cid) {
+                ::core::result::Result::Ok(cid) => cid,
+                ::core::result::Result::Err(_) => {
+                    ::core::panic!(::core::concat!("Invalid CID: ", $cid))
+                }
+            }
+        }
+    };
+}
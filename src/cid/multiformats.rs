@@ -0,0 +1,72 @@
+//! Conversion between [`Cid`] and the CIDs and multihashes of the `cid` and `multihash` crates.
+//!
+//! ```
+//! # use dasl::cid::{Cid, Codec, UnsupportedCidError};
+//! let cid = Cid::digest_sha2(Codec::Drisl, b"hello");
+//! let other = cid::Cid::from(cid);
+//! assert_eq!(other.to_string(), cid.to_string());
+//! assert_eq!(Cid::try_from(other), Ok(cid));
+//!
+//! // DAG-JSON isn't supported.
+//! let dag_json = cid::Cid::new_v1(0x0129, other.hash().to_owned());
+//! assert_eq!(Cid::try_from(dag_json), Err(UnsupportedCidError::Codec(0x0129)));
+//! ```
+
+use ::cid::{CidGeneric, Version, multihash::Multihash as MultihashGeneric};
+use thiserror::Error;
+
+use super::{Cid, Codec, Multihash};
+
+/// Returned when converting a CID of the `cid` crate that this crate doesn't support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum UnsupportedCidError {
+    #[error("CID version 0 is unsupported")]
+    Version0,
+    #[error("Unsupported codec: 0x{0:X}")]
+    Codec(u64),
+    #[error("Unsupported multihash: 0x{0:X}")]
+    Multihash(u64),
+    #[error("Unsupported digest length: {0}")]
+    DigestLength(u8),
+}
+
+/// Every `CID` is a valid CID of the `cid` crate.
+impl From<Cid> for ::cid::Cid {
+    fn from(cid: Cid) -> Self {
+        ::cid::Cid::new_v1(cid.codec() as u64, cid.into())
+    }
+}
+
+/// Fails for CIDs this crate doesn't support, see [`UnsupportedCidError`].
+impl<const S: usize> TryFrom<CidGeneric<S>> for Cid {
+    type Error = UnsupportedCidError;
+
+    fn try_from(cid: CidGeneric<S>) -> Result<Self, Self::Error> {
+        if cid.version() == Version::V0 {
+            return Err(UnsupportedCidError::Version0);
+        }
+        let codec =
+            Codec::try_from(cid.codec()).map_err(|_| UnsupportedCidError::Codec(cid.codec()))?;
+        let hash = cid.hash();
+        let multihash = Multihash::try_from(hash.code())
+            .map_err(|_| UnsupportedCidError::Multihash(hash.code()))?;
+        let digest = match hash.digest() {
+            [] => None,
+            digest => Some(
+                digest
+                    .try_into()
+                    .map_err(|_| UnsupportedCidError::DigestLength(hash.size()))?,
+            ),
+        };
+        Ok(Cid::new(codec, multihash, digest))
+    }
+}
+
+/// The multihash of the `CID`, its hash function and digest.
+impl From<Cid> for MultihashGeneric<64> {
+    fn from(cid: Cid) -> Self {
+        MultihashGeneric::wrap(cid.multihash_type() as u64, cid.hash())
+            .expect("digests fit into the multihash")
+    }
+}
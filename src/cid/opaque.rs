@@ -0,0 +1,230 @@
+//! CIDs with codecs and multihash types this crate doesn't know.
+//!
+//! [`Cid`] only accepts the codecs and hashes of the DASL spec, which keeps it small and lets it
+//! verify data. An [`OpaqueCid`] accepts any CIDv1, so that links written by newer revisions of
+//! the spec, or by other IPLD implementations, can still be inspected and passed along unchanged.
+//!
+//! ```
+//! # use dasl::cid::{Cid, OpaqueCid};
+//! // The DAG-JSON CID of `{}`, its codec is 0x0129.
+//! let opaque: OpaqueCid = "baguqeeraiqjw7i2vwntyuekgvulpp2det2kpwt6cd7tx5ayqybqpmhfk76fa".parse()?;
+//! assert_eq!(opaque.codec(), 0x0129);
+//! assert_eq!(opaque.multihash_code(), 0x12);
+//! assert!(Cid::try_from(&opaque).is_err());
+//! assert_eq!(
+//!     opaque.to_string(),
+//!     "baguqeeraiqjw7i2vwntyuekgvulpp2det2kpwt6cd7tx5ayqybqpmhfk76fa"
+//! );
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use serde::{de, ser};
+
+use super::{
+    CID_SERDE_PRIVATE_IDENTIFIER, CID_VERSION, Cid, CidParseError, MultihashParseError, read_code,
+};
+use crate::multibase::{self, Multibase, base32};
+
+/// A CIDv1 with any codec and multihash type.
+///
+/// It can be parsed, formatted and (de)serialized like a [`Cid`], and converted into one if the
+/// codec and hash are supported. Its encoding is kept as it was, so it round-trips exactly. It
+/// can't be used to hash or verify data, as the hash function may be unknown.
+///
+/// Like `Cid`, it's ordered by its binary encoding.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OpaqueCid {
+    // First, so that the derived `Ord` is the order of the encoding.
+    bytes: Box<[u8]>,
+    codec: u64,
+    multihash: u64,
+    /// Where the digest starts in `bytes`.
+    digest: usize,
+}
+
+impl OpaqueCid {
+    /// Tries to decode a `CID` from binary encoding, with the zero byte prefix used in DRISL.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CidParseError> {
+        match bytes.split_first() {
+            None => Err(CidParseError::TooShort),
+            Some((0x0, bytes)) => Self::from_bytes_raw(bytes),
+            Some(_) => Err(CidParseError::InvalidEncoding),
+        }
+    }
+
+    /// Tries to decode a `CID` from its raw binary components.
+    ///
+    /// The version must be 1, the codec, multihash type and digest length can be any varint, as
+    /// long as the digest has the given length.
+    pub fn from_bytes_raw(bytes: &[u8]) -> Result<Self, CidParseError> {
+        let (&version, rest) = bytes.split_first().ok_or(CidParseError::TooShort)?;
+        if version != CID_VERSION {
+            return Err(CidParseError::InvalidCidVersion(version));
+        }
+        let (codec, rest) = read_code(rest)?;
+        let (multihash, rest) = read_code(rest)?;
+        let (len, digest) = read_code(rest)?;
+        if u64::try_from(digest.len()) != Ok(len) {
+            let err = MultihashParseError::InvalidLength(digest.len());
+            return Err(CidParseError::InvalidMultihash(err));
+        }
+        Ok(OpaqueCid {
+            bytes: bytes.into(),
+            codec,
+            multihash,
+            digest: bytes.len() - digest.len(),
+        })
+    }
+
+    /// Returns the multicodec code of the codec, e.g. `0x71` for DRISL.
+    pub fn codec(&self) -> u64 {
+        self.codec
+    }
+
+    /// Returns the multicodec code of the hash function, e.g. `0x12` for SHA2-256.
+    pub fn multihash_code(&self) -> u64 {
+        self.multihash
+    }
+
+    /// Returns the digest, which may be empty.
+    pub fn hash(&self) -> &[u8] {
+        self.bytes.split_at(self.digest).1
+    }
+
+    /// Returns the raw binary form of the `CID`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the string form of this `CID` in the given encoding.
+    ///
+    /// [`Display`](fmt::Display) uses [`Multibase::Base32Lower`].
+    pub fn to_string_with_base(&self, base: Multibase) -> String {
+        multibase::encode(base, self.as_bytes())
+    }
+}
+
+impl From<Cid> for OpaqueCid {
+    fn from(cid: Cid) -> Self {
+        OpaqueCid {
+            bytes: cid.as_bytes().into(),
+            codec: cid.codec() as u64,
+            multihash: cid.multihash_type() as u64,
+            digest: cid.as_bytes().len() - cid.hash().len(),
+        }
+    }
+}
+
+/// Fails if the codec or multihash type isn't supported by [`Cid`].
+impl TryFrom<&OpaqueCid> for Cid {
+    type Error = CidParseError;
+
+    fn try_from(cid: &OpaqueCid) -> Result<Self, Self::Error> {
+        Cid::from_bytes_raw(cid.as_bytes())
+    }
+}
+
+/// Fails if the codec or multihash type isn't supported by [`Cid`].
+impl TryFrom<OpaqueCid> for Cid {
+    type Error = CidParseError;
+
+    fn try_from(cid: OpaqueCid) -> Result<Self, Self::Error> {
+        Cid::try_from(&cid)
+    }
+}
+
+impl FromStr for OpaqueCid {
+    type Err = CidParseError;
+
+    /// Parses a `CID` in any of the supported [`Multibase`] encodings.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_, bytes) = multibase::decode(s).map_err(|_| CidParseError::InvalidEncoding)?;
+        OpaqueCid::from_bytes_raw(&bytes)
+    }
+}
+
+impl fmt::Display for OpaqueCid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b")?;
+        base32::encode_write(self.as_bytes(), f)
+    }
+}
+
+/// Serialized the same way as a [`Cid`], as a tag 42 in DRISL and a string in human-readable
+/// formats.
+impl ser::Serialize for OpaqueCid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+
+        let value = serde_bytes::Bytes::new(self.as_bytes());
+        serializer.serialize_newtype_struct(CID_SERDE_PRIVATE_IDENTIFIER, value)
+    }
+}
+
+/// Deserialized from the same forms as a [`Cid`].
+impl<'de> de::Deserialize<'de> for OpaqueCid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        /// Accepts a newtype struct wrapping the bytes, and the bytes or string form within it.
+        struct OpaqueCidVisitor;
+
+        impl<'de> de::Visitor<'de> for OpaqueCidVisitor {
+            type Value = OpaqueCid;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                write!(fmt, "a valid CID")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                value
+                    .parse()
+                    .map_err(|err| de::Error::custom(format!("Failed to deserialize CID: {err}")))
+            }
+
+            fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                OpaqueCid::from_bytes_raw(value)
+                    .map_err(|err| de::Error::custom(format!("Failed to deserialize CID: {err}")))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::SeqAccess<'de>,
+            {
+                let mut bytes = Vec::new();
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                self.visit_bytes(&bytes)
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                deserializer.deserialize_bytes(self)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(OpaqueCidVisitor);
+        }
+
+        deserializer.deserialize_newtype_struct(CID_SERDE_PRIVATE_IDENTIFIER, OpaqueCidVisitor)
+    }
+}
@@ -6,37 +6,44 @@
 //!
 //! Based on <https://github.com/multiformats/rust-cid/blob/master/src/serde.rs>
 
+use alloc::{format, vec::Vec};
 use core::fmt;
-use std::{format, vec::Vec};
 
 use serde::{de, ser};
-use serde_bytes::ByteBuf;
 
 use super::Cid;
 
 /// An identifier that is used internally by Serde implementations that support [`Cid`]s.
-// TODO: should this be different than the one in `rust-cid`?
+///
+/// It's the same as the one of the `cid` crate, so that serializers support both.
 pub const CID_SERDE_PRIVATE_IDENTIFIER: &str = "$__private__serde__identifier__for__cid";
 
 /// Serialize a CID into the Serde data model as enum.
 ///
+/// Human-readable formats (e.g. JSON) get the string form instead, as written by its
+/// [`Display`](core::fmt::Display) impl.
+///
 /// Custom types are not supported by Serde, hence we map a CID into an enum that can be identified
 /// as a CID by implementations that support CIDs. The corresponding Rust type would be:
 ///
 /// ```text
 /// struct $__private__serde__identifier__for__cid(serde_bytes::BytesBuf);
 /// ```
+///
+/// The bytes are the raw binary form, without the zero byte prefix of tag 42 in DRISL, which is
+/// added by the serializer. That's the same as the `cid` crate does, so serializers that support
+/// its CIDs, like `serde_ipld_dagcbor`, support these as well and vice versa.
 impl ser::Serialize for Cid {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
-        // Prefix 0x00
-        let raw = self.as_bytes();
-        let mut bytes = vec![0u8; 1 + raw.len()];
-        bytes[1..].copy_from_slice(raw);
-        let value = ByteBuf::from(bytes);
-        serializer.serialize_newtype_struct(CID_SERDE_PRIVATE_IDENTIFIER, &value)
+        if serializer.is_human_readable() {
+            return serializer.collect_str(self);
+        }
+
+        let value = serde_bytes::Bytes::new(self.as_bytes());
+        serializer.serialize_newtype_struct(CID_SERDE_PRIVATE_IDENTIFIER, value)
     }
 }
 
@@ -72,8 +79,49 @@ impl<'de> de::Visitor<'de> for BytesToCidVisitor {
     }
 }
 
+/// Visitor to parse a CID from its string form, used for human-readable formats.
+///
+/// Byte sequences are accepted as well, as that is how CIDs used to be serialized. So are newtype
+/// structs, as Serde's buffering of internally tagged and untagged enums always claims to be
+/// human-readable, even when the original format isn't. Plain byte strings are not, so that
+/// buffered DRISL bytes without the CID tag aren't mistaken for a CID.
+struct StrToCidVisitor;
+
+impl<'de> de::Visitor<'de> for StrToCidVisitor {
+    type Value = Cid;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "a valid CID string")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value
+            .parse()
+            .map_err(|err| de::Error::custom(format!("Failed to deserialize CID: {err}")))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        BytesToCidVisitor.visit_seq(seq)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_bytes(BytesToCidVisitor)
+    }
+}
+
 /// Deserialize a CID into a newtype struct.
 ///
+/// Human-readable formats (e.g. JSON) are expected to contain the string form instead.
+///
 /// Deserialize a CID that was serialized as a newtype struct, so that can be identified as a CID.
 /// Its corresponding Rust type would be:
 ///
@@ -107,6 +155,10 @@ impl<'de> de::Deserialize<'de> for Cid {
             }
         }
 
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(StrToCidVisitor);
+        }
+
         deserializer.deserialize_newtype_struct(CID_SERDE_PRIVATE_IDENTIFIER, MainEntryVisitor)
     }
 }
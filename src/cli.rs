@@ -0,0 +1,38 @@
+//! Subcommands of the `dasl` binary.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+pub mod bench;
+pub mod car;
+pub mod cid;
+pub mod convert;
+pub mod diff;
+pub mod dump;
+pub mod get;
+pub mod grep;
+pub mod hash;
+pub mod inspect;
+pub mod serve;
+pub mod stats;
+pub mod tail;
+pub mod validate;
+
+/// Opens a file for reading, or stdin if the path is missing or `-`.
+fn open_input(path: Option<&Path>) -> io::Result<Box<dyn BufRead>> {
+    match path {
+        Some(path) if path != Path::new("-") => Ok(Box::new(BufReader::new(File::open(path)?))),
+        _ => Ok(Box::new(io::stdin().lock())),
+    }
+}
+
+/// Creates a file for writing, or stdout if the path is missing or `-`.
+fn create_output(path: Option<&Path>) -> io::Result<Box<dyn Write>> {
+    match path {
+        Some(path) if path != Path::new("-") => Ok(Box::new(BufWriter::new(File::create(path)?))),
+        _ => Ok(Box::new(BufWriter::new(io::stdout().lock()))),
+    }
+}
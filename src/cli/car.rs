@@ -0,0 +1,149 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use dasl::{
+    blockstore::{BlockStore, DirBlockStore},
+    car::{CarReader, CarWriter},
+    cid::{Cid, Codec},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum Command {
+    /// Pack files into a CAR file.
+    ///
+    /// Files named after a CID, like the ones in a directory blockstore, are packed as that block
+    /// after verifying their content. Any other file is packed as a raw block.
+    Pack(PackArgs),
+    /// Unpack the blocks of a CAR file into a directory blockstore.
+    Unpack(UnpackArgs),
+    /// List the CIDs and sizes of the blocks in a CAR file.
+    Ls(LsArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct PackArgs {
+    /// Files or directories to pack, directories are walked recursively.
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+    /// The CAR file to write, stdout if missing or `-`.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// A root CID of the archive, can be given multiple times.
+    #[arg(long)]
+    root: Vec<Cid>,
+}
+
+#[derive(Debug, clap::Args)]
+struct UnpackArgs {
+    /// The CAR file, stdin if missing or `-`.
+    archive: Option<PathBuf>,
+    /// The directory to write the blocks to, created if needed.
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+#[derive(Debug, clap::Args)]
+struct LsArgs {
+    /// The CAR file, stdin if missing or `-`.
+    archive: Option<PathBuf>,
+    /// List the root CIDs instead of the blocks.
+    #[arg(long)]
+    roots: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        Command::Pack(args) => pack(args),
+        Command::Unpack(args) => unpack(args),
+        Command::Ls(args) => ls(args),
+    }
+}
+
+fn pack(args: PackArgs) -> Result<(), Box<dyn Error>> {
+    let mut files = Vec::new();
+    for input in &args.inputs {
+        collect_files(input, &mut files)?;
+    }
+
+    let output = super::create_output(args.output.as_deref())?;
+    let mut writer = CarWriter::new(output, args.root)?;
+    let mut packed = HashSet::new();
+    for path in files {
+        let data = fs::read(&path)?;
+        let named = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<Cid>().ok());
+        let cid = match named {
+            Some(cid) => {
+                cid.verify(&data)
+                    .map_err(|err| format!("{}: {err}", path.display()))?;
+                cid
+            }
+            None => Cid::digest_sha2(Codec::Raw, &data),
+        };
+        // Progress goes to stderr, as the archive may be written to stdout.
+        eprintln!("{cid} {}", path.display());
+        if packed.insert(cid) {
+            writer.write_block(&cid, &data)?;
+        }
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Adds `path` if it is a file, or the files below it in order if it is a directory.
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| Ok(entry?.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    for entry in entries {
+        collect_files(&entry, files)?;
+    }
+    Ok(())
+}
+
+fn unpack(args: UnpackArgs) -> Result<(), Box<dyn Error>> {
+    let input = super::open_input(args.archive.as_deref())?;
+    let store = DirBlockStore::open(&args.output)?;
+    let mut count = 0;
+    for block in CarReader::new(input)? {
+        let (cid, data) = block?;
+        cid.verify(&data).map_err(|err| format!("{cid}: {err}"))?;
+        store.put(&cid, &data)?;
+        count += 1;
+    }
+    println!("Unpacked {count} blocks to {}", args.output.display());
+    Ok(())
+}
+
+fn ls(args: LsArgs) -> Result<(), Box<dyn Error>> {
+    let input = super::open_input(args.archive.as_deref())?;
+    let reader = CarReader::new(input)?;
+    if args.roots {
+        for root in reader.roots() {
+            println!("{root}");
+        }
+        return Ok(());
+    }
+    for block in reader {
+        let (cid, data) = block?;
+        println!("{cid} {}", data.len());
+    }
+    Ok(())
+}
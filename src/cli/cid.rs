@@ -0,0 +1,85 @@
+use std::io::Read;
+
+use dasl::{
+    cid::{Codec, Multihash, OpaqueCid},
+    multibase::Multibase,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// A CID string in any supported multibase, or its binary form in hex.
+    ///
+    /// If missing or `-`, a binary or string CID is read from stdin.
+    input: Option<String>,
+}
+
+/// The multibase encodings a CID is shown in.
+const BASES: [(&str, Multibase); 4] = [
+    ("base32", Multibase::Base32Lower),
+    ("base58btc", Multibase::Base58Btc),
+    ("base16", Multibase::Base16Lower),
+    ("base64url", Multibase::Base64Url),
+];
+
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let cid = match args.input.as_deref() {
+        Some(input) if input != "-" => parse(input)?,
+        _ => {
+            let mut bytes = Vec::new();
+            std::io::stdin().lock().read_to_end(&mut bytes)?;
+            match from_binary(&bytes) {
+                Some(cid) => cid,
+                None => parse(std::str::from_utf8(&bytes)?.trim())?,
+            }
+        }
+    };
+
+    // Codecs and hashes that aren't supported are still shown, by their code.
+    let bytes = cid.as_bytes();
+    let codec = match Codec::try_from(cid.codec()) {
+        Ok(Codec::Raw) => "raw",
+        Ok(Codec::Drisl) => "drisl",
+        _ => "unknown",
+    };
+    let hash = match Multihash::try_from(cid.multihash_code()) {
+        Ok(Multihash::Sha2256) => "sha2-256",
+        Ok(Multihash::Blake3) => "blake3",
+        _ => "unknown",
+    };
+    println!("version:   {}", bytes[0]);
+    println!("codec:     {codec} (0x{:02x})", cid.codec());
+    println!("hash:      {hash} (0x{:02x})", cid.multihash_code());
+    if cid.hash().is_empty() {
+        println!("digest:    (empty)");
+    } else {
+        println!("digest:    {}", data_encoding::HEXLOWER.encode(cid.hash()));
+    }
+    println!("binary:    {}", data_encoding::HEXLOWER.encode(bytes));
+    for (name, base) in BASES {
+        println!(
+            "{:<10} {}",
+            format!("{name}:"),
+            cid.to_string_with_base(base)
+        );
+    }
+    Ok(())
+}
+
+/// Parses a CID string, or the hex of a binary CID.
+fn parse(input: &str) -> Result<OpaqueCid, Box<dyn std::error::Error>> {
+    match input.parse() {
+        Ok(cid) => Ok(cid),
+        Err(err) => data_encoding::HEXLOWER_PERMISSIVE
+            .decode(input.as_bytes())
+            .ok()
+            .and_then(|bytes| from_binary(&bytes))
+            .ok_or_else(|| format!("Invalid CID {input:?}: {err}").into()),
+    }
+}
+
+/// Decodes a binary CID, with or without the leading zero byte used in DRISL.
+fn from_binary(bytes: &[u8]) -> Option<OpaqueCid> {
+    OpaqueCid::from_bytes_raw(bytes)
+        .or_else(|_| OpaqueCid::from_bytes(bytes))
+        .ok()
+}
@@ -0,0 +1,98 @@
+use std::{
+    error::Error,
+    io::{Read, Write},
+    path::PathBuf,
+};
+
+use dasl::drisl::{
+    self, Value, diag,
+    json::{self, BytesPolicy, JsonOptions},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The input file, stdin if missing or `-`.
+    input: Option<PathBuf>,
+    /// The format of the input.
+    #[arg(long, value_enum, default_value_t = Format::Drisl)]
+    from: Format,
+    /// The format of the output.
+    #[arg(long, value_enum, default_value_t = Format::Json)]
+    to: Format,
+    /// How byte strings are written as JSON.
+    #[arg(long, value_enum, default_value_t = BytesArg::Base64)]
+    bytes: BytesArg,
+    /// The output file, stdout if missing or `-`.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// The formats of a stream of records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Format {
+    /// Concatenated DRISL values.
+    Drisl,
+    /// JSON values separated by whitespace, written pretty-printed.
+    Json,
+    /// JSON values separated by newlines, written one per line.
+    Ndjson,
+    /// CBOR diagnostic notation, a single value when reading and one per line when writing.
+    Diag,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum BytesArg {
+    /// A string in standard base64 with padding.
+    Base64,
+    /// An array of numbers.
+    Array,
+    /// Fail on byte strings.
+    Reject,
+}
+
+type Records = Box<dyn Iterator<Item = Result<Value, Box<dyn Error>>>>;
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let mut input = super::open_input(args.input.as_deref())?;
+    let records: Records = match args.from {
+        Format::Drisl => {
+            Box::new(drisl::de::iter_from_reader::<Value, _>(input).map(|value| Ok(value?)))
+        }
+        Format::Json | Format::Ndjson => Box::new(
+            serde_json::Deserializer::from_reader(input)
+                .into_iter::<serde_json::Value>()
+                .map(|value| Ok(value?.into())),
+        ),
+        Format::Diag => {
+            let mut text = String::new();
+            input.read_to_string(&mut text)?;
+            Box::new(std::iter::once(diag::from_str(&text).map_err(Into::into)))
+        }
+    };
+
+    let bytes = match args.bytes {
+        BytesArg::Base64 => BytesPolicy::Base64,
+        BytesArg::Array => BytesPolicy::Array,
+        BytesArg::Reject => BytesPolicy::Reject,
+    };
+    let options = JsonOptions::new().bytes(bytes);
+
+    let mut output = super::create_output(args.output.as_deref())?;
+    for value in records {
+        let value = value?;
+        match args.to {
+            Format::Drisl => drisl::to_writer(&mut output, &value)?,
+            Format::Json => {
+                serde_json::to_writer_pretty(&mut output, &json::to_json_with(&value, options)?)?;
+                writeln!(output)?;
+            }
+            Format::Ndjson => {
+                serde_json::to_writer(&mut output, &json::to_json_with(&value, options)?)?;
+                writeln!(output)?;
+            }
+            Format::Diag => writeln!(output, "{}", diag::to_string(&value))?,
+        }
+    }
+    output.flush()?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use dasl::drisl::{
+    self, Value,
+    de::{self, DecodeOptions},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The old DRISL value, stdin if `-`.
+    old: PathBuf,
+    /// The new DRISL value, stdin if `-`.
+    new: PathBuf,
+    /// Exit with status 1 if the values differ.
+    #[arg(long)]
+    exit_code: bool,
+    /// Accept data that isn't canonical DRISL, see `DecodeOptions::lenient`.
+    #[arg(long)]
+    lenient: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let options = DecodeOptions::new().lenient(args.lenient);
+    let (old_bytes, old) = read_value(&args.old, options)?;
+    let (new_bytes, new) = read_value(&args.new, options)?;
+
+    let changes = drisl::diff(&old, &new);
+    for change in &changes {
+        println!("{change}");
+    }
+    if changes.is_empty() && old_bytes != new_bytes {
+        // Only possible with lenient decoding, canonical encodings of equal values are equal.
+        println!(
+            "Equal values with different encodings, {} and {} bytes",
+            old_bytes.len(),
+            new_bytes.len()
+        );
+    }
+    if args.exit_code && !changes.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn read_value(
+    path: &Path,
+    options: DecodeOptions,
+) -> Result<(Vec<u8>, Value), Box<dyn std::error::Error>> {
+    let mut bytes = Vec::new();
+    super::open_input(Some(path))?.read_to_end(&mut bytes)?;
+    let value =
+        de::from_slice_with(&bytes, options).map_err(|err| format!("{}: {err}", path.display()))?;
+    Ok((bytes, value))
+}
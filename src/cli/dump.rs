@@ -0,0 +1,133 @@
+use std::{
+    io::{Read, Write},
+    ops::Range,
+    path::PathBuf,
+};
+
+use dasl::{
+    cid::Cid,
+    drisl::{self, CanonicityError, Node, NodeKind},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// File of one or more concatenated CBOR values, stdin if missing or `-`.
+    input: Option<PathBuf>,
+}
+
+/// Bytes of strings shown per line.
+const CHUNK: usize = 16;
+/// The annotations start after at most this many columns of bytes.
+const MAX_WIDTH: usize = 64;
+
+/// A line of the dump.
+struct Line {
+    depth: usize,
+    bytes: Range<usize>,
+    note: Option<String>,
+}
+
+/// Prints the data item by item. Anything that isn't canonical DRISL is marked with `!`, but still
+/// shown as long as it's well-formed CBOR.
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let mut buf = Vec::new();
+    super::open_input(args.input.as_deref())?.read_to_end(&mut buf)?;
+    let nodes = drisl::inspect(&buf);
+
+    let mut lines = Vec::new();
+    for node in &nodes {
+        let mut note = describe(node);
+        for issue in &node.issues {
+            note.push_str(&format!(" ! {issue}"));
+        }
+        lines.push(Line {
+            depth: node.depth,
+            bytes: node.header.clone(),
+            note: Some(note),
+        });
+        if let NodeKind::Bytes { len: Some(_) } | NodeKind::Text { len: Some(_) } = node.kind {
+            let content = node.content();
+            let mut note = content_note(&buf, &nodes, node);
+            for chunk in content.clone().step_by(CHUNK) {
+                lines.push(Line {
+                    depth: node.depth + 1,
+                    bytes: chunk..content.end.min(chunk + CHUNK),
+                    note: note.take(),
+                });
+            }
+        }
+    }
+
+    let width = lines
+        .iter()
+        .map(|line| 2 * line.depth + 3 * line.bytes.len())
+        .max()
+        .unwrap_or(0)
+        .min(MAX_WIDTH);
+    let mut output = super::create_output(None)?;
+    for line in &lines {
+        let bytes: Vec<_> = buf[line.bytes.clone()]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        let bytes = format!("{:indent$}{}", "", bytes.join(" "), indent = 2 * line.depth);
+        let offset = line.bytes.start;
+        match &line.note {
+            Some(note) => writeln!(output, "{offset:08x}  {bytes:width$}  {note}")?,
+            None => writeln!(output, "{offset:08x}  {bytes}")?,
+        }
+    }
+    output.flush()?;
+
+    match nodes.last() {
+        Some(node) if node.kind == NodeKind::Invalid => Err(CanonicityError {
+            offset: node.range.start,
+            rule: node.issues[0],
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// Describes an item by its header, e.g. `text(3)`.
+fn describe(node: &Node) -> String {
+    let len = |len: Option<u64>| len.map_or("*".to_string(), |len| len.to_string());
+    match node.kind {
+        NodeKind::Unsigned(value) => format!("unsigned({value})"),
+        NodeKind::Negative(value) => format!("negative({value})"),
+        NodeKind::Bytes { len: l } => format!("bytes({})", len(l)),
+        NodeKind::Text { len: l } => format!("text({})", len(l)),
+        NodeKind::Array { len: l } => format!("array({})", len(l)),
+        NodeKind::Map { len: l } => format!("map({})", len(l)),
+        NodeKind::Tag(42) => "tag(42) CID".to_string(),
+        NodeKind::Tag(tag) => format!("tag({tag})"),
+        NodeKind::Bool(value) => value.to_string(),
+        NodeKind::Null => "null".to_string(),
+        NodeKind::Undefined => "undefined".to_string(),
+        NodeKind::Simple(value) => format!("simple({value})"),
+        NodeKind::Float(value) => {
+            // The initial byte and 2, 4 or 8 bytes.
+            let bits = 8 * (node.header.len() - 1);
+            format!("float{bits}({value:?})")
+        }
+        NodeKind::Break => "break".to_string(),
+        _ => "invalid".to_string(),
+    }
+}
+
+/// Shows the contents of a string, or of a CID.
+fn content_note(buf: &[u8], nodes: &[Node], node: &Node) -> Option<String> {
+    let content = &buf[node.content()];
+    match node.kind {
+        NodeKind::Text { .. } => std::str::from_utf8(content)
+            .ok()
+            .map(|text| format!("{text:?}")),
+        _ => {
+            let parent = &nodes[node.parent?];
+            match parent.kind {
+                NodeKind::Tag(42) => Cid::from_bytes(content).ok().map(|cid| cid.to_string()),
+                _ => None,
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use std::{io::Write, path::PathBuf};
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl::{
+        self, Value,
+        diag::{self, DiagOptions},
+    },
+    rasl::blocking::Client,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The base URL of the RASL endpoint.
+    url: String,
+    /// The CID of the content to fetch.
+    cid: Cid,
+    /// Write the verified bytes to this file, instead of printing them.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Write CIDs as strings instead of bytes.
+    #[arg(long)]
+    cid_strings: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = Client::new().fetch_bytes(&args.url, &args.cid)?;
+    if let Some(output) = args.output {
+        std::fs::write(&output, &bytes)?;
+        eprintln!("Wrote {} bytes to {}", bytes.len(), output.display());
+        return Ok(());
+    }
+
+    match args.cid.codec() {
+        Codec::Drisl => {
+            let value: Value = drisl::from_slice(&bytes)?;
+            let options = DiagOptions::new().cid_strings(args.cid_strings);
+            println!("{}", diag::to_string_with(&value, options));
+        }
+        _ => std::io::stdout().write_all(&bytes)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use std::{io::Write, path::PathBuf};
+
+use dasl::drisl::{
+    Value,
+    de::iter_from_reader_indexed,
+    diag::{self, DiagOptions},
+    query::Query,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Query to evaluate against each value, e.g. `.posts[] | select(.likes > 10)`.
+    query: Query,
+    /// File of concatenated DRISL values, stdin if missing or `-`.
+    input: Option<PathBuf>,
+    /// Print the offset and length of the values with matches, instead of the matches.
+    #[arg(long)]
+    offsets: bool,
+    /// Write CIDs as strings instead of bytes.
+    #[arg(long)]
+    cid_strings: bool,
+}
+
+/// Prints the results of a query against each value, and exits with status 1 if there were none,
+/// like grep.
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let input = super::open_input(args.input.as_deref())?;
+    let mut output = super::create_output(None)?;
+    let options = DiagOptions::new().cid_strings(args.cid_strings);
+    let mut found = false;
+    for item in iter_from_reader_indexed::<Value, _>(input) {
+        let (value, range) = item?;
+        let mut matches = args.query.eval(&value).peekable();
+        if matches.peek().is_none() {
+            continue;
+        }
+        found = true;
+        if args.offsets {
+            writeln!(output, "{} {}", range.start, range.end - range.start)?;
+        } else {
+            for value in matches {
+                writeln!(output, "{}", diag::to_string_with(value, options))?;
+            }
+        }
+    }
+    output.flush()?;
+    if !found {
+        std::process::exit(1);
+    }
+    Ok(())
+}
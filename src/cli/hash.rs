@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use dasl::cid::{CidBuilder, Codec, Multihash};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// The file to hash, stdin if missing or `-`.
+    input: Option<PathBuf>,
+    /// The codec of the content.
+    #[arg(long, value_enum, default_value_t = CodecArg::Raw)]
+    codec: CodecArg,
+    /// The hash function.
+    #[arg(long, value_enum, default_value_t = HashArg::Sha2256)]
+    hash: HashArg,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum CodecArg {
+    Raw,
+    Drisl,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum HashArg {
+    #[value(name = "sha2-256")]
+    Sha2256,
+    Blake3,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let codec = match args.codec {
+        CodecArg::Raw => Codec::Raw,
+        CodecArg::Drisl => Codec::Drisl,
+    };
+    let hash = match args.hash {
+        HashArg::Sha2256 => Multihash::Sha2256,
+        HashArg::Blake3 => Multihash::Blake3,
+    };
+    let input = super::open_input(args.input.as_deref())?;
+    let cid = CidBuilder::new()
+        .codec(codec)
+        .hash(hash)
+        .digest_from_reader(input)?;
+    println!("{cid}");
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use dasl::drisl::{
+    Value,
+    de::iter_from_reader,
+    diag::{self, DiagOptions},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// File of one or more concatenated DRISL values, stdin if missing or `-`.
+    input: Option<PathBuf>,
+    /// Write CIDs as strings instead of bytes.
+    #[arg(long)]
+    cid_strings: bool,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let mut input = super::open_input(args.input.as_deref())?;
+    let options = DiagOptions::new().cid_strings(args.cid_strings);
+    for value in iter_from_reader::<Value, _>(&mut input) {
+        println!("{}", diag::to_string_with(&value?, options));
+    }
+    Ok(())
+}
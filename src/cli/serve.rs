@@ -0,0 +1,49 @@
+use std::{net::SocketAddr, path::PathBuf, sync::Arc};
+
+use dasl::{
+    blockstore::{BlockStore, DirBlockStore, MemoryBlockStore},
+    rasl::server,
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// A directory blockstore or a CAR file.
+    source: PathBuf,
+    /// The address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    bind: SocketAddr,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    if args.source.is_dir() {
+        let store = DirBlockStore::open(&args.source)?;
+        println!("Serving directory {}", args.source.display());
+        runtime.block_on(serve(Arc::new(store), args.bind))
+    } else {
+        let file = std::io::BufReader::new(std::fs::File::open(&args.source)?);
+        let store = MemoryBlockStore::from_car(file)?;
+        println!(
+            "Serving {} blocks from {}",
+            store.len(),
+            args.source.display()
+        );
+        runtime.block_on(serve(Arc::new(store), args.bind))
+    }
+}
+
+async fn serve<S>(store: Arc<S>, bind: SocketAddr) -> Result<(), Box<dyn std::error::Error>>
+where
+    S: BlockStore + Send + Sync + 'static,
+{
+    let listener = tokio::net::TcpListener::bind(bind).await?;
+    println!(
+        "Listening on http://{}{}",
+        listener.local_addr()?,
+        dasl::rasl::WELL_KNOWN_PATH
+    );
+    axum::serve(listener, server::router(store)).await?;
+    Ok(())
+}
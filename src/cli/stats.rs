@@ -0,0 +1,252 @@
+use std::{collections::HashMap, error::Error, io::Write, path::PathBuf};
+
+use dasl::{
+    car::CarReader,
+    cid::Codec,
+    drisl::{self, Value, ValueStats},
+};
+use serde::Serialize;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// File of concatenated DRISL values, stdin if missing or `-`.
+    input: Option<PathBuf>,
+    /// Read a CAR file, and decode its DRISL blocks as the records.
+    #[arg(long)]
+    car: bool,
+    /// The format of the report.
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// Number of the most frequent map keys to report.
+    #[arg(long, default_value_t = 20)]
+    top_keys: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum Format {
+    /// Aligned text for reading.
+    Text,
+    /// A JSON object for scripting.
+    Json,
+}
+
+/// The report, written as JSON as is.
+#[derive(Debug, Default, Serialize)]
+struct Report {
+    /// Number of records, or DRISL blocks of a CAR file.
+    records: u64,
+    /// Number of raw blocks of a CAR file, which aren't decoded.
+    raw_blocks: u64,
+    /// Size of the records in bytes.
+    bytes: u64,
+    sizes: Sizes,
+    max_depth: usize,
+    links: u64,
+    kinds: Kinds,
+    map_entries: u64,
+    /// Number of distinct map keys.
+    distinct_keys: usize,
+    /// The most frequent map keys, with how often they occur.
+    keys: Vec<KeyCount>,
+}
+
+/// Distribution of record sizes in bytes.
+#[derive(Debug, Default, Serialize)]
+struct Sizes {
+    min: u64,
+    p50: u64,
+    p90: u64,
+    p99: u64,
+    max: u64,
+    mean: f64,
+}
+
+/// Number of values by kind, over all records.
+#[derive(Debug, Default, Serialize)]
+struct Kinds {
+    integer: u64,
+    bytes: u64,
+    float: u64,
+    text: u64,
+    bool: u64,
+    null: u64,
+    cid: u64,
+    array: u64,
+    map: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct KeyCount {
+    key: String,
+    count: u64,
+}
+
+/// Collects the statistics of records.
+#[derive(Debug, Default)]
+struct Collector {
+    report: Report,
+    sizes: Vec<u64>,
+    keys: HashMap<String, u64>,
+}
+
+impl Collector {
+    fn add(&mut self, value: &Value, size: usize) {
+        let stats = value.stats();
+        let report = &mut self.report;
+        report.records += 1;
+        report.bytes += size as u64;
+        report.max_depth = report.max_depth.max(stats.max_depth);
+        report.links += stats.cids as u64;
+        report.map_entries += stats.map_entries as u64;
+        add_kinds(&mut report.kinds, &stats);
+        self.sizes.push(size as u64);
+
+        // Nested values are walked without recursion, like `Value::stats`.
+        let mut stack = vec![value];
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::Array(items) => stack.extend(items),
+                Value::Map(entries) => {
+                    for (key, value) in entries {
+                        match self.keys.get_mut(key) {
+                            Some(count) => *count += 1,
+                            None => {
+                                self.keys.insert(key.clone(), 1);
+                            }
+                        }
+                        stack.push(value);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn finish(mut self, top_keys: usize) -> Report {
+        let mut report = self.report;
+        self.sizes.sort_unstable();
+        if let (Some(&min), Some(&max)) = (self.sizes.first(), self.sizes.last()) {
+            let percentile = |p: usize| self.sizes[(self.sizes.len() - 1) * p / 100];
+            report.sizes = Sizes {
+                min,
+                p50: percentile(50),
+                p90: percentile(90),
+                p99: percentile(99),
+                max,
+                mean: report.bytes as f64 / report.records as f64,
+            };
+        }
+
+        report.distinct_keys = self.keys.len();
+        let mut keys: Vec<_> = self
+            .keys
+            .into_iter()
+            .map(|(key, count)| KeyCount { key, count })
+            .collect();
+        // Most frequent first, ties in the canonical order of keys.
+        keys.sort_unstable_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.key.len().cmp(&b.key.len()))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        keys.truncate(top_keys);
+        report.keys = keys;
+        report
+    }
+}
+
+fn add_kinds(kinds: &mut Kinds, stats: &ValueStats) {
+    kinds.integer += stats.integers as u64;
+    kinds.bytes += stats.bytes as u64;
+    kinds.float += stats.floats as u64;
+    kinds.text += stats.texts as u64;
+    kinds.bool += stats.bools as u64;
+    kinds.null += stats.nulls as u64;
+    kinds.cid += stats.cids as u64;
+    kinds.array += stats.arrays as u64;
+    kinds.map += stats.maps as u64;
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let input = super::open_input(args.input.as_deref())?;
+    let mut collector = Collector::default();
+    if args.car {
+        for block in CarReader::new(input)? {
+            let (cid, data) = block?;
+            match cid.codec() {
+                Codec::Drisl => {
+                    let value: Value =
+                        drisl::from_slice(&data).map_err(|err| format!("{cid}: {err}"))?;
+                    collector.add(&value, data.len());
+                }
+                _ => collector.report.raw_blocks += 1,
+            }
+        }
+    } else {
+        for item in drisl::de::iter_from_reader_indexed::<Value, _>(input) {
+            let (value, range) = item?;
+            collector.add(&value, (range.end - range.start) as usize);
+        }
+    }
+    let report = collector.finish(args.top_keys);
+
+    let mut output = super::create_output(None)?;
+    match args.format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut output, &report)?;
+            writeln!(output)?;
+        }
+        Format::Text => write_text(&mut output, &report)?,
+    }
+    output.flush()?;
+    Ok(())
+}
+
+fn write_text(output: &mut impl Write, report: &Report) -> std::io::Result<()> {
+    writeln!(output, "records:     {}", report.records)?;
+    if report.raw_blocks > 0 {
+        writeln!(output, "raw blocks:  {}", report.raw_blocks)?;
+    }
+    writeln!(output, "bytes:       {}", report.bytes)?;
+    let sizes = &report.sizes;
+    writeln!(
+        output,
+        "sizes:       min {}, p50 {}, p90 {}, p99 {}, max {}, mean {:.1}",
+        sizes.min, sizes.p50, sizes.p90, sizes.p99, sizes.max, sizes.mean
+    )?;
+    writeln!(output, "max depth:   {}", report.max_depth)?;
+    writeln!(output, "links:       {}", report.links)?;
+    writeln!(output, "map entries: {}", report.map_entries)?;
+
+    let kinds = &report.kinds;
+    writeln!(output, "kinds:")?;
+    for (name, count) in [
+        ("integer", kinds.integer),
+        ("bytes", kinds.bytes),
+        ("float", kinds.float),
+        ("text", kinds.text),
+        ("bool", kinds.bool),
+        ("null", kinds.null),
+        ("cid", kinds.cid),
+        ("array", kinds.array),
+        ("map", kinds.map),
+    ] {
+        writeln!(output, "  {name:<9} {count}")?;
+    }
+
+    writeln!(
+        output,
+        "keys ({} of {} distinct):",
+        report.keys.len(),
+        report.distinct_keys
+    )?;
+    let width = report
+        .keys
+        .first()
+        .map_or(0, |key| key.count.to_string().len());
+    for KeyCount { key, count } in &report.keys {
+        writeln!(output, "  {count:>width$} {key:?}")?;
+    }
+    Ok(())
+}
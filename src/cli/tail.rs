@@ -0,0 +1,93 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::PathBuf,
+    thread,
+    time::Duration,
+};
+
+use dasl::drisl::{
+    self, Value,
+    de::StreamDecoder,
+    diag::{self, DiagOptions},
+};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// File of concatenated DRISL values.
+    input: PathBuf,
+    /// Keep the file open and print values as they are appended.
+    #[arg(short, long)]
+    follow: bool,
+    /// Number of values to print from the end of the file.
+    #[arg(short = 'n', long, default_value_t = 10)]
+    records: usize,
+    /// How often to check for appended data when following, in milliseconds.
+    #[arg(long, default_value_t = 200)]
+    interval: u64,
+    /// Write CIDs as strings instead of bytes.
+    #[arg(long)]
+    cid_strings: bool,
+}
+
+/// Size of the reads from the file.
+const READ_SIZE: usize = 64 * 1024;
+
+/// Prints the last values of a file. A value at the end that isn't complete yet is not printed,
+/// as it's probably still being written, when following it's printed once the rest is appended.
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let options = DiagOptions::new().cid_strings(args.cid_strings);
+    let mut file = File::open(&args.input)?;
+    let mut decoder = StreamDecoder::new();
+    let mut buf = vec![0; READ_SIZE];
+    let mut position = 0;
+
+    // Only the encodings of the last values are kept, they are decoded when printed.
+    let mut last = VecDeque::with_capacity(args.records);
+    loop {
+        let len = file.read(&mut buf)?;
+        if len == 0 {
+            break;
+        }
+        position += len as u64;
+        decoder.feed(&buf[..len]);
+        while let Some(bytes) = decoder.next_encoded()? {
+            if args.records == 0 {
+                continue;
+            }
+            if last.len() == args.records {
+                last.pop_front();
+            }
+            last.push_back(bytes.to_vec());
+        }
+    }
+    for bytes in last {
+        let value: Value = drisl::from_slice(&bytes)?;
+        println!("{}", diag::to_string_with(&value, options));
+    }
+    if !args.follow {
+        return Ok(());
+    }
+
+    loop {
+        thread::sleep(Duration::from_millis(args.interval));
+        if file.metadata()?.len() < position {
+            eprintln!("dasl: {}: file truncated", args.input.display());
+            file.seek(SeekFrom::Start(0))?;
+            position = 0;
+            decoder = StreamDecoder::new();
+        }
+        loop {
+            let len = file.read(&mut buf)?;
+            if len == 0 {
+                break;
+            }
+            position += len as u64;
+            decoder.feed(&buf[..len]);
+            while let Some(value) = decoder.decode::<Value>()? {
+                println!("{}", diag::to_string_with(&value, options));
+            }
+        }
+    }
+}
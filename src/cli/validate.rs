@@ -0,0 +1,15 @@
+use std::{io::Read, path::PathBuf};
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// File of a single DRISL value, stdin if missing or `-`.
+    input: Option<PathBuf>,
+}
+
+pub fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let mut bytes = Vec::new();
+    super::open_input(args.input.as_deref())?.read_to_end(&mut bytes)?;
+    dasl::drisl::validate(&bytes)?;
+    println!("Valid DRISL, {} bytes", bytes.len());
+    Ok(())
+}
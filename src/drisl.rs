@@ -2,32 +2,75 @@
 //!
 //! Implementation originally based on [`serde_ipld_dagcbor`](https://github.com/ipld/serde_ipld_dagcbor)
 //! and parts of [`cbor4ii`](https://docs.rs/cbor4ii).
+//!
+//! Serde treats `Vec<u8>` as a sequence, so use [`ByteBuf`] for binary fields to encode them as
+//! byte strings, or `#[serde(with = "serde_bytes")]`.
 
+mod byte_buf;
 mod cbor4ii_nonpub;
+mod convenience;
+mod diff;
+mod inspect;
+#[cfg(feature = "ipld")]
+mod ipld;
+mod links;
+mod macros;
+mod path;
+mod validate;
 mod value;
-
+mod value_ref;
+
+#[cfg(feature = "arena")]
+pub mod arena;
+#[cfg(feature = "ciborium")]
+pub mod ciborium;
+#[cfg(feature = "codec")]
+pub mod codec;
 pub mod de;
+pub mod diag;
 pub mod error;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod query;
 pub mod ser;
-
+pub mod serde_helpers;
+pub mod sorted_map;
+
+pub use byte_buf::ByteBuf;
+pub use diff::{ApplyError, Change, ChangeKind, apply_changes, diff};
+pub use inspect::{Node, NodeKind, inspect};
+pub use links::{Links, links};
+pub use path::PathSegment;
 #[doc(inline)]
-pub use value::Value;
-
-#[doc(inline)]
-pub use self::de::from_reader;
-// Convenience functions for serialization and deserialization.
+pub use sorted_map::SortedMap;
+pub use validate::{CanonicityError, CanonicityRule, validate};
 #[doc(inline)]
-pub use self::de::from_slice;
+pub use value::{IntegerRangeError, Value, ValueStats, from_value, to_value};
 #[doc(inline)]
-pub use self::error::{DecodeError, EncodeError};
-#[doc(inline)]
-pub use self::ser::to_vec;
+pub use value_ref::ValueRef;
+
+// Convenience functions for serialization and deserialization.
+#[cfg(feature = "mmap")]
+pub use self::convenience::from_file_mmap;
+pub use self::convenience::{
+    cid_of, encoded_len, from_slice, from_slice_partial, from_slice_ref, from_slice_with, to_vec,
+    to_vec_in, to_vec_with, to_vec_with_cid,
+};
+#[cfg(feature = "bytes")]
+pub use self::convenience::{from_bytes, to_bytes};
+#[cfg(feature = "std")]
+pub use self::convenience::{from_reader, from_reader_with, to_writer, to_writer_with};
 #[doc(inline)]
-pub use self::ser::to_writer;
+pub use self::error::{DecodeError, EncodeError, Error, ErrorKind};
 
 /// The CBOR tag that is used for CIDs.
 const CBOR_TAGS_CID: u8 = 42;
 
+/// Compares map keys in the canonical order of DRISL, shorter keys first.
+fn cmp_map_keys(a: &str, b: &str) -> core::cmp::Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
 pub use serde_bytes;
 
 #[cfg(test)]
@@ -0,0 +1,365 @@
+//! Decoding into values that are allocated in an arena.
+//!
+//! An [`ArenaValue`] keeps its arrays, maps, strings and byte strings in a [`Bump`] arena instead
+//! of separate heap allocations. Documents with many small nodes decode without a call to the
+//! allocator for each of them, and they are freed all at once when the arena is reset or dropped.
+//!
+//! ```
+//! # use dasl::{drisl, drisl::arena::{self, ArenaValue, Bump}};
+//! let bytes = drisl::to_vec(&drisl!({ "name": "foo", "tags": ["a", "b"] })).unwrap();
+//!
+//! let mut bump = Bump::new();
+//! for _ in 0..3 {
+//!     let value = arena::from_slice_in(&bytes, &bump).unwrap();
+//!     assert_eq!(value.get("name"), Some(&ArenaValue::Text("foo")));
+//!     assert_eq!(value.to_owned(), drisl::from_slice::<drisl::Value>(&bytes).unwrap());
+//!     bump.reset();
+//! }
+//! ```
+
+use alloc::string::ToString;
+use core::{convert::Infallible, fmt};
+
+#[doc(no_inline)]
+pub use bumpalo::Bump;
+use bumpalo::collections::Vec as BumpVec;
+use serde::{
+    de::{self, DeserializeSeed},
+    ser,
+};
+
+use super::{DecodeError, Value, cmp_map_keys, de::Deserializer, value::MAX_PREALLOC_LEN};
+use crate::cid::{BytesToCidVisitor, Cid};
+
+/// A dynamic DRISL value whose nodes are allocated in an arena.
+///
+/// Strings and byte strings are borrowed from the input if possible, otherwise they are copied
+/// into the arena as well. Map entries are sorted in the canonical order of DRISL.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum ArenaValue<'a> {
+    /// An integer
+    Integer(i128),
+    /// Bytes
+    Bytes(&'a [u8]),
+    /// A float
+    Float(f64),
+    /// A string
+    Text(&'a str),
+    /// A boolean
+    Bool(bool),
+    /// Null
+    Null,
+    /// CID
+    Cid(Cid),
+    /// An array
+    Array(&'a [ArenaValue<'a>]),
+    /// A map, with its entries in canonical order
+    Map(&'a [(&'a str, ArenaValue<'a>)]),
+}
+
+impl<'a> ArenaValue<'a> {
+    /// Returns the value of a map entry.
+    ///
+    /// Returns `None` if it isn't a map or the key doesn't exist.
+    pub fn get(&self, key: &str) -> Option<&ArenaValue<'a>> {
+        let Self::Map(entries) = self else {
+            return None;
+        };
+        let index = entries
+            .binary_search_by(|(entry, _)| cmp_map_keys(entry, key))
+            .ok()?;
+        Some(&entries[index].1)
+    }
+
+    /// Converts it into an owned [`Value`], copying all strings and byte strings.
+    #[allow(clippy::should_implement_trait)]
+    pub fn to_owned(&self) -> Value {
+        match self {
+            Self::Integer(value) => Value::Integer(*value),
+            Self::Bytes(value) => Value::Bytes(value.to_vec()),
+            Self::Float(value) => Value::Float(*value),
+            Self::Text(value) => Value::Text(value.to_string()),
+            Self::Bool(value) => Value::Bool(*value),
+            Self::Null => Value::Null,
+            Self::Cid(value) => Value::Cid(*value),
+            Self::Array(value) => Value::Array(value.iter().map(Self::to_owned).collect()),
+            Self::Map(value) => Value::Map(
+                value
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<ArenaValue<'_>> for Value {
+    fn from(value: ArenaValue<'_>) -> Self {
+        value.to_owned()
+    }
+}
+
+/// Decodes an [`ArenaValue`] from CBOR data in a slice, allocating it in `bump`.
+///
+/// Strings and byte strings are borrowed from the slice.
+pub fn from_slice_in<'a>(
+    buf: &'a [u8],
+    bump: &'a Bump,
+) -> Result<ArenaValue<'a>, DecodeError<Infallible>> {
+    let mut deserializer = Deserializer::from_slice(buf);
+    let value = ArenaSeed(bump).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Decodes an [`ArenaValue`] from CBOR data in a reader, allocating it in `bump`.
+#[cfg(feature = "std")]
+pub fn from_reader_in<'a, R>(
+    reader: R,
+    bump: &'a Bump,
+) -> Result<ArenaValue<'a>, DecodeError<std::io::Error>>
+where
+    R: std::io::BufRead,
+{
+    let mut deserializer = Deserializer::from_reader(super::de::IoReader::new(reader));
+    let value = ArenaSeed(bump).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Deserializes an [`ArenaValue`] into the arena it holds.
+///
+/// Use it with a [`Deserializer`] for other inputs or [`DecodeOptions`](super::de::DecodeOptions).
+///
+/// ```
+/// # use dasl::drisl::{arena::{ArenaSeed, ArenaValue, Bump}, de::{DecodeOptions, Deserializer}};
+/// # use serde::de::DeserializeSeed;
+/// let bump = Bump::new();
+/// let mut deserializer =
+///     Deserializer::from_slice(&[0x63, 0x66, 0x6f, 0x6f]).with_options(DecodeOptions::new());
+/// let value = ArenaSeed(&bump).deserialize(&mut deserializer).unwrap();
+/// assert_eq!(value, ArenaValue::Text("foo"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ArenaSeed<'a>(pub &'a Bump);
+
+impl<'de: 'a, 'a> DeserializeSeed<'de> for ArenaSeed<'a> {
+    type Value = ArenaValue<'a>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de: 'a, 'a> de::Visitor<'de> for ArenaSeed<'a> {
+    type Value = ArenaValue<'a>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("any valid DRISL kind")
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Text(value))
+    }
+
+    #[inline]
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Text(self.0.alloc_str(value)))
+    }
+
+    #[inline]
+    fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Bytes(value))
+    }
+
+    #[inline]
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Bytes(self.0.alloc_slice_copy(value)))
+    }
+
+    #[inline]
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Integer(v.into()))
+    }
+
+    #[inline]
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Integer(v.into()))
+    }
+
+    #[inline]
+    fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Integer(v))
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Float(v))
+    }
+
+    #[inline]
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Bool(v))
+    }
+
+    #[inline]
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Null)
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.deserialize(deserializer)
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(ArenaValue::Null)
+    }
+
+    #[inline]
+    fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::SeqAccess<'de>,
+    {
+        let capacity = visitor.size_hint().unwrap_or(0).min(MAX_PREALLOC_LEN);
+        let mut vec = BumpVec::with_capacity_in(capacity, self.0);
+        while let Some(elem) = visitor.next_element_seed(self)? {
+            vec.push(elem);
+        }
+        Ok(ArenaValue::Array(vec.into_bump_slice()))
+    }
+
+    #[inline]
+    fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+    where
+        V: de::MapAccess<'de>,
+    {
+        let capacity = visitor.size_hint().unwrap_or(0).min(MAX_PREALLOC_LEN);
+        let mut entries = BumpVec::with_capacity_in(capacity, self.0);
+        while let Some(key) = visitor.next_key_seed(KeySeed(self.0))? {
+            let value = visitor.next_value_seed(self)?;
+            entries.push((key, value));
+        }
+
+        // The last one of equal keys wins, see `DuplicateKeys::LastWins`. The sort is stable, so
+        // it stays last.
+        entries.sort_by(|(a, _), (b, _)| cmp_map_keys(a, b));
+        entries.dedup_by(|later, earlier| {
+            let duplicate = later.0 == earlier.0;
+            if duplicate {
+                *earlier = *later;
+            }
+            duplicate
+        });
+        Ok(ArenaValue::Map(entries.into_bump_slice()))
+    }
+
+    /// Newtype structs are only used to deserialize CIDs.
+    #[inline]
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_bytes(BytesToCidVisitor)
+            .map(ArenaValue::Cid)
+    }
+}
+
+/// Deserializes a map key, borrowing it from the input or copying it into the arena.
+struct KeySeed<'a>(&'a Bump);
+
+impl<'de: 'a, 'a> DeserializeSeed<'de> for KeySeed<'a> {
+    type Value = &'a str;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de: 'a, 'a> de::Visitor<'de> for KeySeed<'a> {
+    type Value = &'a str;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("a string map key")
+    }
+
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(value)
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(self.0.alloc_str(value))
+    }
+}
+
+impl ser::Serialize for ArenaValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match &self {
+            Self::Null => serializer.serialize_none(),
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::Integer(value) => serializer.serialize_i128(*value),
+            Self::Float(value) => serializer.serialize_f64(*value),
+            Self::Text(value) => serializer.serialize_str(value),
+            Self::Bytes(value) => serializer.serialize_bytes(value),
+            Self::Array(value) => serializer.collect_seq(value.iter()),
+            Self::Map(value) => {
+                serializer.collect_map(value.iter().map(|(key, value)| (key, value)))
+            }
+            Self::Cid(value) => value.serialize(serializer),
+        }
+    }
+}
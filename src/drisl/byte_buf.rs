@@ -0,0 +1,170 @@
+//! An owned byte string that is always encoded as one.
+
+use alloc::vec::Vec;
+use core::{
+    borrow::{Borrow, BorrowMut},
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Value;
+
+/// An owned byte string, encoded as a CBOR byte string.
+///
+/// Serde treats a `Vec<u8>` as a sequence, so a field of that type is silently encoded as an
+/// array of integers, unless it has `#[serde(with = "serde_bytes")]`. A `ByteBuf` is always
+/// encoded as a byte string, so it's the recommended type for binary fields. It dereferences to
+/// its `Vec<u8>`.
+///
+/// It wraps a [`serde_bytes::ByteBuf`] and converts from and to one, adding the conversions to a
+/// [`Value`] and a more readable `Debug` output.
+///
+/// ```
+/// # use dasl::drisl::{self, ByteBuf};
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Blob {
+///     data: ByteBuf,
+///     // The same without the attribute would be an array.
+///     #[serde(with = "serde_bytes")]
+///     also_data: Vec<u8>,
+/// }
+///
+/// let blob = Blob { data: ByteBuf::from(vec![1, 2, 3]), also_data: vec![1, 2, 3] };
+/// let bytes = drisl::to_vec(&blob).unwrap();
+/// let value: drisl::Value = drisl::from_slice(&bytes).unwrap();
+/// assert_eq!(value.get("data"), value.get("also_data"));
+///
+/// let blob: Blob = drisl::from_slice(&bytes).unwrap();
+/// assert_eq!(blob.data.len(), 3);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteBuf(serde_bytes::ByteBuf);
+
+impl ByteBuf {
+    /// Creates an empty byte string.
+    pub fn new() -> Self {
+        Self(serde_bytes::ByteBuf::new())
+    }
+
+    /// Returns the bytes as a `Vec<u8>`.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0.into_vec()
+    }
+}
+
+/// Formats like a byte string literal, e.g. `b"foo\x00"`.
+impl fmt::Debug for ByteBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b\"")?;
+        for &byte in self.0.iter() {
+            write!(f, "{}", core::ascii::escape_default(byte))?;
+        }
+        write!(f, "\"")
+    }
+}
+
+impl Deref for ByteBuf {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+impl DerefMut for ByteBuf {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+impl AsRef<[u8]> for ByteBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsMut<[u8]> for ByteBuf {
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl Borrow<[u8]> for ByteBuf {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl BorrowMut<[u8]> for ByteBuf {
+    fn borrow_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl From<Vec<u8>> for ByteBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes.into())
+    }
+}
+
+impl From<&[u8]> for ByteBuf {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec().into())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for ByteBuf {
+    fn from(bytes: [u8; N]) -> Self {
+        Self(bytes.to_vec().into())
+    }
+}
+
+impl From<ByteBuf> for Vec<u8> {
+    fn from(bytes: ByteBuf) -> Self {
+        bytes.into_vec()
+    }
+}
+
+impl From<ByteBuf> for Value {
+    fn from(bytes: ByteBuf) -> Self {
+        Self::Bytes(bytes.into_vec())
+    }
+}
+
+impl From<serde_bytes::ByteBuf> for Value {
+    fn from(bytes: serde_bytes::ByteBuf) -> Self {
+        Self::Bytes(bytes.into_vec())
+    }
+}
+
+impl FromIterator<u8> for ByteBuf {
+    fn from_iter<I: IntoIterator<Item = u8>>(iter: I) -> Self {
+        Self(Vec::from_iter(iter).into())
+    }
+}
+
+impl From<serde_bytes::ByteBuf> for ByteBuf {
+    fn from(bytes: serde_bytes::ByteBuf) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ByteBuf> for serde_bytes::ByteBuf {
+    fn from(bytes: ByteBuf) -> Self {
+        bytes.0
+    }
+}
+
+impl Serialize for ByteBuf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_bytes::ByteBuf::deserialize(deserializer).map(Self)
+    }
+}
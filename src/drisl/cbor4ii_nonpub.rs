@@ -3,9 +3,9 @@
 //! Some things needed for the Serde implementation are not public in the cbor4ii crate. Those are
 //! copied into this file.
 
-use cbor4ii::core::{dec, error::Len};
+use cbor4ii::core::dec;
 
-use super::error::DecodeError;
+use super::error::{DecodeError, Len};
 
 // Copy from cbor4ii/core.rs.
 #[allow(dead_code)]
@@ -0,0 +1,144 @@
+//! Conversion between [`Value`] and [`ciborium::Value`].
+//!
+//! Every [`Value`] that can be encoded has a [`ciborium::Value`] of the same data model: CIDs
+//! become tag 42 of their binary form and maps become lists of entries with string keys, in the
+//! canonical order of DRISL. Encoding it with ciborium still isn't canonical DRISL, as ciborium
+//! encodes floats in their shortest form.
+//!
+//! Converting from ciborium fails for items DRISL doesn't support, see [`CiboriumError`]. Floats
+//! are taken as they are, so non-finite ones still fail later when they are encoded.
+//!
+//! ```
+//! # use dasl::{cid::{Cid, Codec}, drisl, drisl::Value};
+//! let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+//! let value = drisl!({ "n": 1, "link": cid });
+//!
+//! let cbor = ciborium::Value::try_from(&value).unwrap();
+//! assert_eq!(Value::try_from(cbor).unwrap(), value);
+//!
+//! let err = Value::try_from(ciborium::Value::Tag(1, Box::new(0.into()))).unwrap_err();
+//! assert!(matches!(err, drisl::ciborium::CiboriumError::Tag(1)));
+//! ```
+
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use thiserror::Error;
+
+use super::{CBOR_TAGS_CID, IntegerRangeError, Value, cmp_map_keys};
+use crate::cid::{Cid, CidParseError};
+
+/// A [`ciborium::Value`] that can't be converted to a [`Value`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CiboriumError {
+    #[error("Tag {0} is unsupported, only tag 42 for CIDs is")]
+    Tag(u64),
+    #[error("Tag 42 must contain a byte string")]
+    CidNotBytes,
+    #[error("Invalid CID: {0}")]
+    Cid(#[from] CidParseError),
+    #[error("Map key {0:?} isn't a string")]
+    NonStringKey(Box<::ciborium::Value>),
+    #[error("Duplicate map key: {0}")]
+    DuplicateKey(String),
+    #[error("Unsupported CBOR item: {0:?}")]
+    Unsupported(Box<::ciborium::Value>),
+}
+
+impl TryFrom<::ciborium::Value> for Value {
+    type Error = CiboriumError;
+
+    fn try_from(value: ::ciborium::Value) -> Result<Self, Self::Error> {
+        Ok(match value {
+            ::ciborium::Value::Integer(int) => Value::Integer(int.into()),
+            ::ciborium::Value::Bytes(bytes) => Value::Bytes(bytes),
+            ::ciborium::Value::Float(float) => Value::Float(float),
+            ::ciborium::Value::Text(text) => Value::Text(text),
+            ::ciborium::Value::Bool(bool) => Value::Bool(bool),
+            ::ciborium::Value::Null => Value::Null,
+            ::ciborium::Value::Tag(tag, inner) if tag == u64::from(CBOR_TAGS_CID) => match *inner {
+                ::ciborium::Value::Bytes(bytes) => Value::Cid(Cid::from_bytes(&bytes)?),
+                _ => return Err(CiboriumError::CidNotBytes),
+            },
+            ::ciborium::Value::Tag(tag, _) => return Err(CiboriumError::Tag(tag)),
+            ::ciborium::Value::Array(array) => Value::Array(
+                array
+                    .into_iter()
+                    .map(Value::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            ::ciborium::Value::Map(entries) => {
+                let mut map = BTreeMap::new();
+                for (key, value) in entries {
+                    let ::ciborium::Value::Text(key) = key else {
+                        return Err(CiboriumError::NonStringKey(Box::new(key)));
+                    };
+                    if map.contains_key(&key) {
+                        return Err(CiboriumError::DuplicateKey(key));
+                    }
+                    map.insert(key, Value::try_from(value)?);
+                }
+                Value::Map(map)
+            }
+            other => return Err(CiboriumError::Unsupported(Box::new(other))),
+        })
+    }
+}
+
+/// Fails for integers outside of [`Value::MIN_INTEGER`] and [`Value::MAX_INTEGER`].
+impl TryFrom<&Value> for ::ciborium::Value {
+    type Error = IntegerRangeError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Value::Integer(int) => ::ciborium::Value::Integer(
+                ::ciborium::value::Integer::try_from(*int).map_err(|_| IntegerRangeError(*int))?,
+            ),
+            Value::Bytes(bytes) => ::ciborium::Value::Bytes(bytes.clone()),
+            Value::Float(float) => ::ciborium::Value::Float(*float),
+            Value::Text(text) => ::ciborium::Value::Text(text.clone()),
+            Value::Bool(bool) => ::ciborium::Value::Bool(*bool),
+            Value::Null => ::ciborium::Value::Null,
+            Value::Cid(cid) => {
+                // The binary form of a CID in tag 42 is prefixed with the identity multibase.
+                let mut bytes = Vec::with_capacity(cid.as_bytes().len() + 1);
+                bytes.push(0);
+                bytes.extend_from_slice(cid.as_bytes());
+                ::ciborium::Value::Tag(
+                    CBOR_TAGS_CID.into(),
+                    Box::new(::ciborium::Value::Bytes(bytes)),
+                )
+            }
+            Value::Array(array) => ::ciborium::Value::Array(
+                array
+                    .iter()
+                    .map(::ciborium::Value::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Map(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_by(|(a, _), (b, _)| cmp_map_keys(a, b));
+                ::ciborium::Value::Map(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| {
+                            Ok((
+                                ::ciborium::Value::Text(key.clone()),
+                                ::ciborium::Value::try_from(value)?,
+                            ))
+                        })
+                        .collect::<Result<_, _>>()?,
+                )
+            }
+        })
+    }
+}
+
+/// Fails for integers outside of [`Value::MIN_INTEGER`] and [`Value::MAX_INTEGER`].
+impl TryFrom<Value> for ::ciborium::Value {
+    type Error = IntegerRangeError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        ::ciborium::Value::try_from(&value)
+    }
+}
@@ -0,0 +1,81 @@
+//! Framing of DRISL values for [`tokio_util::codec`].
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! use dasl::{drisl, drisl::codec::DrislCodec};
+//! use futures_util::{SinkExt, StreamExt};
+//! use tokio_util::codec::Framed;
+//!
+//! let (client, server) = tokio::io::duplex(64);
+//! let mut client = Framed::new(client, DrislCodec::<drisl::Value>::new());
+//! let mut server = Framed::new(server, DrislCodec::<drisl::Value>::new());
+//!
+//! client.send(drisl!({ "hello": "world" })).await.unwrap();
+//! let received = server.next().await.unwrap().unwrap();
+//! assert_eq!(received, drisl!({ "hello": "world" }));
+//! # }
+//! ```
+
+use std::marker::PhantomData;
+
+use serde::{Serialize, de::DeserializeOwned};
+use tokio_util::bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+use super::{de::from_slice, error::CodecError, links::Scanner, ser::to_vec};
+
+/// Encodes and decodes a stream of concatenated DRISL values.
+///
+/// Values aren't length prefixed, the decoder tracks how much of a value has arrived and only
+/// decodes it once it is complete.
+pub struct DrislCodec<T> {
+    scanner: Scanner,
+    output: PhantomData<fn() -> T>,
+}
+
+impl<T> DrislCodec<T> {
+    /// Creates a new codec.
+    pub fn new() -> Self {
+        Self {
+            scanner: Scanner::default(),
+            output: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for DrislCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> std::fmt::Debug for DrislCodec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DrislCodec").finish_non_exhaustive()
+    }
+}
+
+impl<T: DeserializeOwned> Decoder for DrislCodec<T> {
+    type Item = T;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let Some(len) = self.scanner.value_len(src)? else {
+            return Ok(None);
+        };
+        self.scanner = Scanner::default();
+        let frame = src.split_to(len);
+        Ok(Some(from_slice(&frame)?))
+    }
+}
+
+impl<T: Serialize> Encoder<T> for DrislCodec<T> {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let bytes = to_vec(&item)?;
+        dst.extend_from_slice(&bytes);
+        Ok(())
+    }
+}
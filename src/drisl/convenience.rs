@@ -0,0 +1,179 @@
+//! Convenience functions for serialization and deserialization.
+//!
+//! They return the non-generic [`Error`], the forms in [`de`] and [`ser`] return the
+//! [`DecodeError`](super::DecodeError) or [`EncodeError`](super::EncodeError) of their reader or
+//! writer.
+
+use alloc::vec::Vec;
+
+use serde::{Serialize, de::Deserialize};
+
+#[cfg(feature = "std")]
+use super::ser::WriteOptions;
+use super::{Error, ValueRef, de, de::DecodeOptions, ser, ser::EncodeOptions};
+use crate::cid::{Cid, Multihash};
+
+/// Decodes a value from CBOR data in a slice, see [`de::from_slice`].
+///
+/// ```
+/// # use dasl::drisl;
+/// let value: (String, u8) = drisl::from_slice(&[0x82, 0x62, b'h', b'i', 0x01])?;
+/// assert_eq!(value, ("hi".to_string(), 1));
+/// # Ok::<(), drisl::Error>(())
+/// ```
+pub fn from_slice<'a, T>(buf: &'a [u8]) -> Result<T, Error>
+where
+    T: Deserialize<'a>,
+{
+    Ok(de::from_slice(buf)?)
+}
+
+/// Decodes a value from CBOR data in a slice, with the given options, see
+/// [`de::from_slice_with`].
+pub fn from_slice_with<'a, T>(buf: &'a [u8], options: DecodeOptions) -> Result<T, Error>
+where
+    T: Deserialize<'a>,
+{
+    Ok(de::from_slice_with(buf, options)?)
+}
+
+/// Decodes a value from the start of a slice and returns the rest, see
+/// [`de::from_slice_partial`].
+pub fn from_slice_partial<'a, T>(buf: &'a [u8]) -> Result<(T, &'a [u8]), Error>
+where
+    T: Deserialize<'a>,
+{
+    Ok(de::from_slice_partial(buf)?)
+}
+
+/// Decodes a [`ValueRef`] from CBOR data in a slice, see [`de::from_slice_ref`].
+pub fn from_slice_ref(buf: &[u8]) -> Result<ValueRef<'_>, Error> {
+    Ok(de::from_slice_ref(buf)?)
+}
+
+/// Decodes a value from CBOR data in a reader, see [`de::from_reader`].
+#[cfg(feature = "std")]
+pub fn from_reader<T, R>(reader: R) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+    R: std::io::BufRead,
+{
+    Ok(de::from_reader(reader)?)
+}
+
+/// Decodes a value from CBOR data in a reader, with the given options, see
+/// [`de::from_reader_with`].
+#[cfg(feature = "std")]
+pub fn from_reader_with<T, R>(reader: R, options: DecodeOptions) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+    R: std::io::BufRead,
+{
+    Ok(de::from_reader_with(reader, options)?)
+}
+
+/// Decodes a value from CBOR data in `bytes::Bytes`, see `de::from_bytes`.
+#[cfg(feature = "bytes")]
+pub fn from_bytes<'a, T>(input: &'a bytes::Bytes) -> Result<T, Error>
+where
+    T: Deserialize<'a>,
+{
+    Ok(de::from_bytes(input)?)
+}
+
+/// Decodes a value from a memory-mapped file, see `de::from_file_mmap`.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated while it's decoded.
+#[cfg(feature = "mmap")]
+pub unsafe fn from_file_mmap<T>(path: impl AsRef<std::path::Path>) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    // SAFETY: Passed on to the caller.
+    Ok(unsafe { de::from_file_mmap(path)? })
+}
+
+/// Serializes a value to a vector, see [`ser::to_vec`].
+///
+/// ```
+/// # use dasl::drisl;
+/// assert_eq!(drisl::to_vec(&("hi", 1))?, [0x82, 0x62, b'h', b'i', 0x01]);
+/// # Ok::<(), drisl::Error>(())
+/// ```
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, Error>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(ser::to_vec(value)?)
+}
+
+/// Serializes a value to a vector, with the given options, see [`ser::to_vec_with`].
+pub fn to_vec_with<T>(value: &T, options: EncodeOptions) -> Result<Vec<u8>, Error>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(ser::to_vec_with(value, options)?)
+}
+
+/// Serializes a value to the end of a vector, see [`ser::to_vec_in`].
+pub fn to_vec_in<T>(buf: &mut Vec<u8>, value: &T) -> Result<(), Error>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(ser::to_vec_in(buf, value)?)
+}
+
+/// Serializes a value to a vector and computes its `CID`, see [`ser::to_vec_with_cid`].
+pub fn to_vec_with_cid<T>(value: &T, multihash: Multihash) -> Result<(Vec<u8>, Cid), Error>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(ser::to_vec_with_cid(value, multihash)?)
+}
+
+/// Serializes a value to `bytes::Bytes`, see `ser::to_bytes`.
+#[cfg(feature = "bytes")]
+pub fn to_bytes<T>(value: &T) -> Result<bytes::Bytes, Error>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(ser::to_bytes(value)?)
+}
+
+/// Serializes a value to a writer, see [`ser::to_writer`].
+#[cfg(feature = "std")]
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), Error>
+where
+    W: std::io::Write,
+    T: Serialize,
+{
+    Ok(ser::to_writer(writer, value)?)
+}
+
+/// Serializes a value to a writer, with the given options, see [`ser::to_writer_with`].
+#[cfg(feature = "std")]
+pub fn to_writer_with<W, T>(writer: W, value: &T, options: WriteOptions) -> Result<(), Error>
+where
+    W: std::io::Write,
+    T: Serialize,
+{
+    Ok(ser::to_writer_with(writer, value, options)?)
+}
+
+/// Computes the `CID` of a value without keeping its encoding, see [`ser::cid_of`].
+pub fn cid_of<T>(value: &T, multihash: Multihash) -> Result<Cid, Error>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(ser::cid_of(value, multihash)?)
+}
+
+/// Computes the length of a value's encoding without keeping it, see [`ser::encoded_len`].
+pub fn encoded_len<T>(value: &T) -> Result<usize, Error>
+where
+    T: Serialize + ?Sized,
+{
+    Ok(ser::encoded_len(value)?)
+}
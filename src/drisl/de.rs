@@ -1,28 +1,81 @@
 //! Deserialization.
+//!
+//! Untagged, internally tagged and adjacently tagged enums, as well as `#[serde(flatten)]`, work
+//! as Serde buffers the input before picking a variant, CIDs included. The buffer can't hold
+//! 128-bit integers though, so negative integers below `i64::MIN` fail to decode inside of them.
+//!
+//! Values that are ignored, like unknown fields of structs, are skipped by only walking their
+//! lengths. Their structure is checked and the [`DecodeOptions`] limits apply, but their strings
+//! aren't checked to be valid UTF-8.
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeSet, string::String, vec::Vec};
 use core::{
+    cmp::Ordering,
     convert::{Infallible, TryFrom},
     marker::PhantomData,
 };
-use std::borrow::Cow;
 
 use cbor4ii::core::{
-    dec::{self, Decode},
-    error::Len,
-    major, types,
-    utils::{IoReader, SliceReader},
+    dec::{self, Decode, Read as _},
+    major,
 };
 use serde::{
     Deserialize,
-    de::{self, Visitor},
+    de::{
+        self, Visitor,
+        value::{BorrowedStrDeserializer, StrDeserializer},
+    },
 };
 
 use super::{
-    CBOR_TAGS_CID,
+    CBOR_TAGS_CID, ValueRef,
     cbor4ii_nonpub::{marker, peek_one, pull_one},
-    error::DecodeError,
+    cmp_map_keys,
+    error::{DecodeError, Len},
+    path::{PathSegment, format_path},
+    ser::NonFiniteFloats,
 };
 use crate::cid::CID_SERDE_PRIVATE_IDENTIFIER;
 
+#[cfg(feature = "tokio")]
+mod async_read;
+mod iterative;
+mod lazy;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod projection;
+mod read;
+#[cfg(feature = "bytes")]
+mod shared;
+mod stream_decoder;
+
+#[cfg(feature = "tokio")]
+pub use self::async_read::{AsyncStreamDeserializer, from_async_reader, stream_from_async_reader};
+pub use self::iterative::{value_from_slice, value_from_slice_with};
+use self::lazy::LAZY_BYTES_SERDE_PRIVATE_IDENTIFIER;
+pub use self::lazy::LazyBytes;
+#[cfg(feature = "mmap")]
+pub use self::mmap::{MmapFile, from_file_mmap};
+pub use self::options::{DecodeOptions, DuplicateKeys};
+#[cfg(feature = "rayon")]
+pub use self::parallel::{
+    par_iter_from_offsets, par_iter_from_slice, par_iter_from_slice_with, value_offsets,
+};
+pub use self::projection::Projection;
+#[cfg(feature = "std")]
+pub use self::read::IoReader;
+pub use self::read::{Read, Reference, SliceReader};
+#[cfg(feature = "bytes")]
+pub(crate) use self::shared::shared;
+#[cfg(feature = "bytes")]
+pub use self::shared::{from_bytes, from_bytes_with};
+pub use self::stream_decoder::StreamDecoder;
+
+/// Maximum number of bytes allocated upfront for strings that can't be borrowed.
+const PULL_CHUNK_LEN: usize = 16 * 1024;
+
 /// Decodes a value from CBOR data in a slice.
 ///
 /// # Examples
@@ -44,17 +97,120 @@ use crate::cid::CID_SERDE_PRIVATE_IDENTIFIER;
 /// let value: &str = de::from_slice(&v[..]).unwrap();
 /// assert_eq!(value, "foobar");
 /// ```
+///
+/// Byte strings can be borrowed as well, also as fields of structs.
+///
+/// ```
+/// # use dasl::drisl::de;
+/// #[derive(serde::Deserialize)]
+/// struct Record<'a> {
+///     name: &'a str,
+///     #[serde(with = "serde_bytes")]
+///     data: &'a [u8],
+/// }
+///
+/// // {"data": h'0102', "name": "foo"}
+/// let v: Vec<u8> = vec![
+///     0xa2, 0x64, 0x64, 0x61, 0x74, 0x61, 0x42, 0x01, 0x02, 0x64, 0x6e, 0x61, 0x6d, 0x65, 0x63,
+///     0x66, 0x6f, 0x6f,
+/// ];
+/// let record: Record = de::from_slice(&v[..]).unwrap();
+/// assert_eq!(record.name, "foo");
+/// assert_eq!(record.data, [0x01, 0x02]);
+/// ```
 pub fn from_slice<'a, T>(buf: &'a [u8]) -> Result<T, DecodeError<Infallible>>
 where
     T: de::Deserialize<'a>,
 {
-    let reader = SliceReader::new(buf);
-    let mut deserializer = Deserializer::from_reader(reader);
+    from_slice_with(buf, DecodeOptions::default())
+}
+
+/// Decodes a value from CBOR data in a slice, with the given options.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::drisl::{DecodeError, de::{self, DecodeOptions}};
+/// let v: Vec<u8> = vec![0x66, 0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72];
+/// let options = DecodeOptions::new().max_str_len(3);
+/// let err = de::from_slice_with::<String>(&v[..], options).unwrap_err();
+/// assert!(matches!(err, DecodeError::StrLenLimit { len: 6, limit: 3, .. }));
+/// ```
+pub fn from_slice_with<'a, T>(
+    buf: &'a [u8],
+    options: DecodeOptions,
+) -> Result<T, DecodeError<Infallible>>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut deserializer = Deserializer::from_slice(buf).with_options(options);
     let value = serde::Deserialize::deserialize(&mut deserializer)?;
     deserializer.end()?;
     Ok(value)
 }
 
+/// Decodes a value from the front of a slice, and returns it with the rest of the slice.
+///
+/// Unlike [`from_slice`], data after the value isn't an error. This is useful for framing
+/// protocols that pack several values into one buffer, see also [`iter_from_slice`].
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::drisl::de;
+/// // "foo", 10
+/// let v: &[u8] = &[0x63, 0x66, 0x6f, 0x6f, 0x0a];
+/// let (value, rest): (&str, _) = de::from_slice_partial(v).unwrap();
+/// assert_eq!(value, "foo");
+/// assert_eq!(rest, [0x0a]);
+/// ```
+pub fn from_slice_partial<'a, T>(buf: &'a [u8]) -> Result<(T, &'a [u8]), DecodeError<Infallible>>
+where
+    T: de::Deserialize<'a>,
+{
+    from_slice_partial_with(buf, DecodeOptions::default())
+}
+
+/// Decodes a value from the front of a slice with the given options, see [`from_slice_partial`].
+pub fn from_slice_partial_with<'a, T>(
+    buf: &'a [u8],
+    options: DecodeOptions,
+) -> Result<(T, &'a [u8]), DecodeError<Infallible>>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut deserializer = Deserializer::from_slice(buf).with_options(options);
+    let value = serde::Deserialize::deserialize(&mut deserializer)?;
+    Ok((value, slice_rest(&mut deserializer.reader.inner)))
+}
+
+/// Returns the data a slice reader hasn't consumed yet.
+fn slice_rest<'a>(reader: &mut SliceReader<'a>) -> &'a [u8] {
+    // The reader hands out everything that is left when asked for enough bytes.
+    match reader.fill(usize::MAX) {
+        Ok(Reference::Long(rest)) => rest,
+        Ok(Reference::Short(_)) => unreachable!("slice readers borrow from the slice"),
+        Err(never) => match never {},
+    }
+}
+
+/// Decodes a [`ValueRef`] from CBOR data in a slice, borrowing all strings and byte strings.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::drisl::{ValueRef, de};
+/// let v: Vec<u8> = vec![0x82, 0x63, 0x66, 0x6f, 0x6f, 0x42, 0x01, 0x02];
+/// let value = de::from_slice_ref(&v[..]).unwrap();
+/// assert_eq!(
+///     value,
+///     ValueRef::Array(vec![ValueRef::Text("foo"), ValueRef::Bytes(&[0x01, 0x02])])
+/// );
+/// ```
+pub fn from_slice_ref(buf: &[u8]) -> Result<ValueRef<'_>, DecodeError<Infallible>> {
+    from_slice(buf)
+}
+
 /// Decodes a value from CBOR data in a reader.
 ///
 /// # Examples
@@ -76,13 +232,38 @@ where
 /// let value: &str = de::from_reader(&v[..]).unwrap();
 /// assert_eq!(value, "foobar");
 /// ```
+#[cfg(feature = "std")]
 pub fn from_reader<T, R>(reader: R) -> Result<T, DecodeError<std::io::Error>>
+where
+    T: de::DeserializeOwned,
+    R: std::io::BufRead,
+{
+    from_reader_with(reader, DecodeOptions::default())
+}
+
+/// Decodes a value from CBOR data in a reader, with the given options.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::drisl::{DecodeError, de::{self, DecodeOptions}};
+/// // [1, 2, 3]
+/// let v: Vec<u8> = vec![0x83, 0x01, 0x02, 0x03];
+/// let options = DecodeOptions::new().max_collection_len(2);
+/// let err = de::from_reader_with::<Vec<u8>, _>(&v[..], options).unwrap_err();
+/// assert!(matches!(err, DecodeError::CollectionLenLimit { len: 3, limit: 2, .. }));
+/// ```
+#[cfg(feature = "std")]
+pub fn from_reader_with<T, R>(
+    reader: R,
+    options: DecodeOptions,
+) -> Result<T, DecodeError<std::io::Error>>
 where
     T: de::DeserializeOwned,
     R: std::io::BufRead,
 {
     let reader = IoReader::new(reader);
-    let mut deserializer = Deserializer::from_reader(reader);
+    let mut deserializer = Deserializer::from_reader(reader).with_options(options);
     let value = serde::Deserialize::deserialize(&mut deserializer)?;
     deserializer.end()?;
     Ok(value)
@@ -115,6 +296,7 @@ where
 /// assert_eq!(value_2, 10);
 /// assert_eq!(v.len(), reader.position() as usize);
 /// ```
+#[cfg(feature = "std")]
 pub fn from_reader_once<T, R>(reader: R) -> Result<T, DecodeError<std::io::Error>>
 where
     T: de::DeserializeOwned,
@@ -126,6 +308,53 @@ where
     Ok(value)
 }
 
+/// Creates an iterator over the concatenated values in a slice, with the number of bytes each of
+/// them takes up.
+///
+/// As with [`from_slice`], strings and byte strings are borrowed from the slice when the target
+/// type can borrow them, so values can be scanned without allocating. The iterator stops after the
+/// first error.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::drisl::de;
+/// // "foo", 10, "bar"
+/// let v: &[u8] = &[0x63, 0x66, 0x6f, 0x6f, 0x0a, 0x63, 0x62, 0x61, 0x72];
+/// let mut iter = de::iter_from_slice::<dasl::drisl::ValueRef>(v);
+/// assert_eq!(iter.next().unwrap().unwrap(), (dasl::drisl::ValueRef::Text("foo"), 4));
+/// assert_eq!(iter.next().unwrap().unwrap().1, 1);
+///
+/// let strings: Vec<(&str, usize)> = de::iter_from_slice(&v[5..])
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(strings, [("bar", 4)]);
+/// ```
+pub fn iter_from_slice<'de, T>(buf: &'de [u8]) -> SliceStreamDeserializer<'de, T>
+where
+    T: de::Deserialize<'de>,
+{
+    iter_from_slice_with(buf, DecodeOptions::default())
+}
+
+/// Creates an iterator over the concatenated values in a slice, with the given options.
+///
+/// The limits apply to each value separately, see [`iter_from_slice`].
+pub fn iter_from_slice_with<'de, T>(
+    buf: &'de [u8],
+    options: DecodeOptions,
+) -> SliceStreamDeserializer<'de, T>
+where
+    T: de::Deserialize<'de>,
+{
+    SliceStreamDeserializer {
+        de: Deserializer::from_slice(buf).with_options(options),
+        remaining: buf.len(),
+        done: false,
+        output: PhantomData,
+    }
+}
+
 /// Create an iterator over the CBOR values in the reader.
 ///
 /// # Examples
@@ -152,29 +381,159 @@ where
 /// assert_eq!(value_2, "baz");
 /// assert!(iter.next().is_none());
 /// ```
+#[cfg(feature = "std")]
 pub fn iter_from_reader<T, R>(reader: R) -> StreamDeserializer<'static, IoReader<R>, T>
+where
+    T: de::DeserializeOwned,
+    R: std::io::BufRead,
+{
+    iter_from_reader_with(reader, DecodeOptions::default())
+}
+
+/// Create an iterator over the CBOR values in the reader, with the given options.
+///
+/// The limits apply to each value separately.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::drisl::{DecodeError, de::{self, DecodeOptions}};
+/// let v: &[u8] = &[0x63, 0x66, 0x6f, 0x6f, 0x66, 0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72];
+/// let options = DecodeOptions::new().max_size(3);
+/// let mut iter = de::iter_from_reader_with::<String, _>(v, options);
+/// assert_eq!(iter.next().unwrap().unwrap(), "foo");
+/// assert!(matches!(
+///     iter.next().unwrap(),
+///     Err(DecodeError::SizeLimit { limit: 3 })
+/// ));
+/// ```
+#[cfg(feature = "std")]
+pub fn iter_from_reader_with<T, R>(
+    reader: R,
+    options: DecodeOptions,
+) -> StreamDeserializer<'static, IoReader<R>, T>
 where
     T: de::DeserializeOwned,
     R: std::io::BufRead,
 {
     let reader = IoReader::new(reader);
-    Deserializer::from_reader(reader).into_iter()
+    Deserializer::from_reader(reader)
+        .with_options(options)
+        .into_iter()
+}
+
+/// Creates an iterator over the CBOR values in the reader, with the range of bytes each of them
+/// was decoded from.
+///
+/// The offsets count from where the reader was when the iterator was created, so they can be used
+/// to build an index of the values and seek back to them later. The iterator stops after the first
+/// error.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::drisl::de;
+/// // "foo", 10, "bar"
+/// let v: &[u8] = &[0x63, 0x66, 0x6f, 0x6f, 0x0a, 0x63, 0x62, 0x61, 0x72];
+/// let index: Vec<_> = de::iter_from_reader_indexed::<dasl::drisl::Value, _>(v)
+///     .map(|item| item.unwrap().1)
+///     .collect();
+/// assert_eq!(index, [0..4, 4..5, 5..9]);
+/// ```
+#[cfg(feature = "std")]
+pub fn iter_from_reader_indexed<T, R>(reader: R) -> IndexedStreamDeserializer<R, T>
+where
+    T: de::DeserializeOwned,
+    R: std::io::BufRead,
+{
+    iter_from_reader_indexed_with(reader, DecodeOptions::default())
+}
+
+/// Creates an iterator over the CBOR values in the reader with their byte ranges, with the given
+/// options, see [`iter_from_reader_indexed`].
+#[cfg(feature = "std")]
+pub fn iter_from_reader_indexed_with<T, R>(
+    reader: R,
+    options: DecodeOptions,
+) -> IndexedStreamDeserializer<R, T>
+where
+    T: de::DeserializeOwned,
+    R: std::io::BufRead,
+{
+    IndexedStreamDeserializer {
+        de: Deserializer::from_reader(CountingIoReader {
+            reader,
+            position: 0,
+            limit: read::DEPTH_LIMIT,
+            buffer: Vec::new(),
+            buffer_start: 0,
+            recording: false,
+        })
+        .with_options(options),
+        recover: false,
+        pending: None,
+        done: false,
+        output: PhantomData,
+    }
 }
 
 /// A Serde `Deserialize`r of DRISL data.
+///
+/// Besides decoding Rust types, it can be used with
+/// [`serde_transcode`](https://docs.rs/serde-transcode) to convert DRISL into other formats
+/// without decoding into a [`Value`](super::Value) first. Call [`end`](Self::end) afterwards to
+/// check that there is no trailing data.
+///
+/// Serde's data model has no CIDs, generic consumers like `serde_transcode` see them as newtype
+/// structs of their bytes. Transcoding turns CIDs into byte strings, which is lossy, so values
+/// with CIDs are better converted with [`Value`](super::Value) and e.g. the `json` module.
+///
+/// ```
+/// # use dasl::drisl::{self, de::Deserializer};
+/// let bytes = drisl::to_vec(&dasl::drisl!({ "name": "hello", "size": [1, 2.5] })).unwrap();
+///
+/// let mut deserializer = Deserializer::from_slice(&bytes);
+/// let mut json = Vec::new();
+/// serde_transcode::transcode(&mut deserializer, &mut serde_json::Serializer::new(&mut json))
+///     .unwrap();
+/// deserializer.end().unwrap();
+/// assert_eq!(json, br#"{"name":"hello","size":[1,2.5]}"#);
+/// ```
 #[derive(Debug)]
 pub struct Deserializer<R> {
-    reader: R,
+    reader: OffsetReader<R>,
+    options: DecodeOptions,
+    /// Number of arrays and maps that are currently being decoded.
+    depth: usize,
+    /// Size of the current value so far, see [`DecodeOptions::max_size`].
+    size: usize,
+    /// Location within the current value, see [`DecodeOptions::track_path`].
+    path: Vec<PathSegment>,
 }
 
 impl<R> Deserializer<R> {
     /// Constructs a `Deserializer` which reads from a `Read`er.
     pub fn from_reader(reader: R) -> Deserializer<R> {
-        Deserializer { reader }
+        Deserializer {
+            reader: OffsetReader {
+                inner: reader,
+                offset: 0,
+            },
+            options: DecodeOptions::default(),
+            depth: 0,
+            size: 0,
+            path: Vec::new(),
+        }
+    }
+
+    /// Sets the options used for decoding.
+    pub fn with_options(mut self, options: DecodeOptions) -> Self {
+        self.options = options;
+        self
     }
 }
 
-impl<'de, R: dec::Read<'de>> Deserializer<R> {
+impl<'de, R: Read<'de>> Deserializer<R> {
     /// Create an iterator over all the CBOR values in the iterator
     #[allow(clippy::should_implement_trait)]
     pub fn into_iter<T: Deserialize<'de>>(self) -> StreamDeserializer<'de, R, T> {
@@ -186,14 +545,15 @@ impl<'de, R: dec::Read<'de>> Deserializer<R> {
 
 impl<'a> Deserializer<SliceReader<'a>> {
     /// Constructs a `Deserializer` that reads from a slice.
+    ///
+    /// Strings and byte strings are borrowed from the slice, they are never copied when the
+    /// target type can borrow them (e.g. `&'de str`, `&'de [u8]` or `Cow<'de, str>`).
     pub fn from_slice(buf: &'a [u8]) -> Self {
-        Deserializer {
-            reader: SliceReader::new(buf),
-        }
+        Deserializer::from_reader(SliceReader::new(buf))
     }
 }
 
-impl<'de, R: dec::Read<'de>> Deserializer<R> {
+impl<'de, R: Read<'de>> Deserializer<R> {
     #[allow(clippy::type_complexity)]
     #[inline]
     fn try_step<'a>(
@@ -232,6 +592,429 @@ impl<'de, R: dec::Read<'de>> Deserializer<R> {
         }
     }
 
+    /// Decodes the initial byte and argument of an item, the argument is `None` for indefinite
+    /// lengths.
+    ///
+    /// Arguments that aren't encoded in the shortest possible form are rejected, unless decoding is
+    /// lenient.
+    fn decode_head(
+        &mut self,
+        name: &'static str,
+    ) -> Result<(u8, Option<u64>), DecodeError<R::Error>> {
+        let byte = pull_one(name, &mut self.reader)?;
+        let (arg, min) = match byte & 0x1f {
+            info @ 0..=0x17 => return Ok((byte, Some(u64::from(info)))),
+            0x18 => (self.pull_uint(name, 1)?, 0x18),
+            0x19 => (self.pull_uint(name, 2)?, 0x100),
+            0x1a => (self.pull_uint(name, 4)?, 0x1_0000),
+            0x1b => (self.pull_uint(name, 8)?, 0x1_0000_0000),
+            0x1f => return Ok((byte, None)),
+            _ => return Err(DecodeError::Unsupported { name, found: byte }),
+        };
+        if arg < min && !self.options.lenient {
+            return Err(DecodeError::NonMinimal { name });
+        }
+        Ok((byte, Some(arg)))
+    }
+
+    /// Decodes a 64-bit float, `None` for infinity and NaN that are decoded as null.
+    ///
+    /// Infinity and NaN are rejected unless decoding is lenient, then the policy of the options
+    /// applies.
+    fn decode_f64(&mut self) -> Result<Option<f64>, DecodeError<R::Error>> {
+        let value = f64::decode(&mut self.reader).map_err(DecodeError::from_cbor4ii)?;
+        if value.is_finite() {
+            return Ok(Some(value));
+        }
+        match self.options.non_finite_floats {
+            _ if !self.options.lenient => Err(DecodeError::NonFiniteFloat),
+            NonFiniteFloats::Reject => Err(DecodeError::NonFiniteFloat),
+            NonFiniteFloats::Null => Ok(None),
+            NonFiniteFloats::Canonicalize => Ok(Some(NonFiniteFloats::canonicalize(value))),
+        }
+    }
+
+    /// Reads a big-endian unsigned integer of `len` bytes.
+    fn pull_uint(&mut self, name: &'static str, len: usize) -> Result<u64, DecodeError<R::Error>> {
+        let mut value = 0;
+        for _ in 0..len {
+            value = value << 8 | u64::from(pull_one(name, &mut self.reader)?);
+        }
+        Ok(value)
+    }
+
+    /// Decodes an integer, which is in the range of `[-2^64, 2^64 - 1]`.
+    fn decode_int(&mut self, name: &'static str) -> Result<i128, DecodeError<R::Error>> {
+        let (byte, arg) = self.decode_head(name)?;
+        match (dec::if_major(byte), arg) {
+            (major::UNSIGNED, Some(arg)) => Ok(i128::from(arg)),
+            (major::NEGATIVE, Some(arg)) => Ok(-1 - i128::from(arg)),
+            _ => Err(DecodeError::Mismatch { name, found: byte }),
+        }
+    }
+
+    /// Decodes the length of an array, map or string of the given major type, `None` means that it
+    /// has an indefinite length, which is only allowed when decoding is lenient.
+    fn decode_len(
+        &mut self,
+        name: &'static str,
+        major: u8,
+    ) -> Result<Option<usize>, DecodeError<R::Error>> {
+        let (byte, arg) = self.decode_head(name)?;
+        if dec::if_major(byte) != major {
+            return Err(DecodeError::Mismatch { name, found: byte });
+        }
+        match arg {
+            Some(len) => usize::try_from(len)
+                .map(Some)
+                .map_err(|_| DecodeError::CastOverflow { name }),
+            None if self.options.lenient => Ok(None),
+            None => Err(DecodeError::IndefiniteSize),
+        }
+    }
+
+    /// Decodes a byte string or the bytes of a string, borrowing them if possible.
+    ///
+    /// The length is checked against the limits before anything is read.
+    fn decode_bytes(
+        &mut self,
+        name: &'static str,
+        major: u8,
+    ) -> Result<Cow<'de, [u8]>, DecodeError<R::Error>> {
+        if let Some(len) = self.decode_len(name, major)? {
+            self.add_str_len(name, len)?;
+            return self.pull_bytes(name, len);
+        }
+
+        // Indefinite length strings are a sequence of definite length chunks.
+        let mut bytes = Vec::new();
+        loop {
+            if peek_one(name, &mut self.reader)? == marker::BREAK {
+                self.reader.advance(1);
+                return Ok(Cow::Owned(bytes));
+            }
+            let Some(len) = self.decode_len(name, major)? else {
+                return Err(DecodeError::IndefiniteSize);
+            };
+            let limit = self.options.max_str_len;
+            if bytes.len().saturating_add(len) > limit {
+                return Err(DecodeError::StrLenLimit {
+                    name,
+                    len: bytes.len().saturating_add(len),
+                    limit,
+                });
+            }
+            self.add_size(len)?;
+            bytes.extend_from_slice(&self.pull_bytes(name, len)?);
+        }
+    }
+
+    /// Reads `len` bytes, borrowing them if possible.
+    fn pull_bytes(
+        &mut self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Cow<'de, [u8]>, DecodeError<R::Error>> {
+        if let dec::Reference::Long(buf) = self.reader.fill(len)? {
+            if buf.len() >= len {
+                self.reader.advance(len);
+                return Ok(Cow::Borrowed(&buf[..len]));
+            }
+        }
+
+        // Don't trust the length for allocating, the data might be much shorter.
+        let mut bytes = Vec::with_capacity(len.min(PULL_CHUNK_LEN));
+        while bytes.len() < len {
+            let missing = len - bytes.len();
+            let buf = match self.reader.fill(missing)? {
+                dec::Reference::Long(buf) => buf,
+                dec::Reference::Short(buf) => buf,
+            };
+            if buf.is_empty() {
+                return Err(DecodeError::Eof {
+                    name,
+                    expect: Len::new(missing),
+                });
+            }
+            let read = buf.len().min(missing);
+            bytes.extend_from_slice(&buf[..read]);
+            self.reader.advance(read);
+        }
+        Ok(Cow::Owned(bytes))
+    }
+
+    /// Advances past `len` bytes without copying them.
+    fn skip_bytes(&mut self, name: &'static str, len: usize) -> Result<(), DecodeError<R::Error>> {
+        let mut missing = len;
+        while missing > 0 {
+            let available = match self.reader.fill(missing)? {
+                dec::Reference::Long(buf) => buf.len(),
+                dec::Reference::Short(buf) => buf.len(),
+            };
+            if available == 0 {
+                return Err(DecodeError::Eof {
+                    name,
+                    expect: Len::new(missing),
+                });
+            }
+            let read = available.min(missing);
+            self.reader.advance(read);
+            missing -= read;
+        }
+        Ok(())
+    }
+
+    /// Skips a byte string and passes its offset and length to the visitor of [`LazyBytes`].
+    fn deserialize_lazy_bytes<V>(&mut self, visitor: V) -> Result<V::Value, DecodeError<R::Error>>
+    where
+        V: Visitor<'de>,
+    {
+        let name = "bytes";
+        let Some(len) = self.decode_len(name, major::BYTES)? else {
+            return Err(DecodeError::IndefiniteSize);
+        };
+        self.add_str_len(name, len)?;
+        let offset = self.reader.offset;
+        self.skip_bytes(name, len)?;
+        visitor.visit_seq(de::value::SeqDeserializer::new(
+            [offset, len as u64].into_iter(),
+        ))
+    }
+
+    /// Skips a string or byte string of the given major type, `name` is the same as when decoding
+    /// it.
+    ///
+    /// Strings aren't checked to be valid UTF-8, but the limits still apply.
+    fn skip_str(&mut self, name: &'static str, major: u8) -> Result<(), DecodeError<R::Error>> {
+        if let Some(len) = self.decode_len(name, major)? {
+            self.add_str_len(name, len)?;
+            return self.skip_bytes(name, len);
+        }
+
+        // Indefinite length strings are a sequence of definite length chunks.
+        let mut total = 0usize;
+        loop {
+            if peek_one(name, &mut self.reader)? == marker::BREAK {
+                self.reader.advance(1);
+                return Ok(());
+            }
+            let Some(len) = self.decode_len(name, major)? else {
+                return Err(DecodeError::IndefiniteSize);
+            };
+            total = total.saturating_add(len);
+            let limit = self.options.max_str_len;
+            if total > limit {
+                return Err(DecodeError::StrLenLimit {
+                    name,
+                    len: total,
+                    limit,
+                });
+            }
+            self.add_size(len)?;
+            self.skip_bytes(name, len)?;
+        }
+    }
+
+    /// Skips a whole item by only walking the lengths of its parts, nothing is allocated.
+    ///
+    /// The item is still checked to be well-formed DRISL as far as its structure goes and the
+    /// limits apply, but strings aren't checked to be valid UTF-8 and CIDs aren't parsed.
+    fn skip(&mut self) -> Result<(), DecodeError<R::Error>> {
+        let name = "ignored";
+
+        let mut de = self.try_step(name)?;
+        let de = &mut *de;
+
+        let byte = peek_one(name, &mut de.reader)?;
+        match dec::if_major(byte) {
+            major::UNSIGNED | major::NEGATIVE => match de.decode_head(name)? {
+                (_, Some(_)) => Ok(()),
+                (byte, None) => Err(DecodeError::Mismatch { name, found: byte }),
+            },
+            major::BYTES => de.skip_str("bytes", major::BYTES),
+            major::STRING => de.skip_str("str", major::STRING),
+            major::ARRAY => {
+                let len = de.decode_len(name, major::ARRAY)?;
+                de.skip_items(name, len, 1)
+            }
+            major::MAP => {
+                let len = de.decode_len(name, major::MAP)?;
+                de.skip_items(name, len, 2)
+            }
+            // The only supported tag is tag 42 (CID).
+            major::TAG => match de.decode_head(name)? {
+                (_, Some(tag)) if tag == u64::from(CBOR_TAGS_CID) => {
+                    de.skip_str("CID", major::BYTES)
+                }
+                (byte, _) => Err(DecodeError::Mismatch { name, found: byte }),
+            },
+            major::SIMPLE => match byte {
+                marker::FALSE | marker::TRUE | marker::NULL => {
+                    de.reader.advance(1);
+                    Ok(())
+                }
+                marker::F32 => de.skip_bytes(name, 5),
+                marker::F64 => de.skip_bytes(name, 9),
+                _ => Err(DecodeError::Unsupported { name, found: byte }),
+            },
+            _ => Err(DecodeError::Unsupported { name, found: byte }),
+        }
+    }
+
+    /// Skips the items of an array (`items_per_entry` is 1) or map (2), `None` for indefinite
+    /// lengths.
+    fn skip_items(
+        &mut self,
+        name: &'static str,
+        len: Option<usize>,
+        items_per_entry: usize,
+    ) -> Result<(), DecodeError<R::Error>> {
+        let len_or_zero = len.unwrap_or(0);
+        self.enter(
+            name,
+            len_or_zero,
+            len_or_zero.saturating_mul(items_per_entry),
+        )?;
+        let result = self.skip_entries(name, len, items_per_entry);
+        self.depth -= 1;
+        result
+    }
+
+    fn skip_entries(
+        &mut self,
+        name: &'static str,
+        len: Option<usize>,
+        items_per_entry: usize,
+    ) -> Result<(), DecodeError<R::Error>> {
+        let mut index = 0;
+        // The previous map key, to check their order.
+        let mut previous: Option<Cow<'de, str>> = None;
+        loop {
+            match len {
+                Some(len) if index == len => return Ok(()),
+                Some(_) => {}
+                None => {
+                    if peek_one(name, &mut self.reader)? == marker::BREAK {
+                        self.reader.advance(1);
+                        return Ok(());
+                    }
+                    self.add_items(name, index + 1, items_per_entry)?;
+                }
+            }
+            if items_per_entry == 2 {
+                // Map keys have to be strings.
+                let byte = peek_one("map key", &mut self.reader)?;
+                if dec::if_major(byte) != major::STRING {
+                    return Err(DecodeError::Mismatch {
+                        name: "map key",
+                        found: byte,
+                    });
+                }
+                if self.options.lenient {
+                    self.skip_str("str", major::STRING)?;
+                } else {
+                    let offset = self.reader.offset;
+                    let key = self.decode_str()?;
+                    check_key_order(previous.as_deref(), &key, offset)?;
+                    previous = Some(key);
+                }
+            }
+            self.skip()?;
+            index += 1;
+        }
+    }
+
+    /// Accounts for a string or byte string of `len` bytes.
+    fn add_str_len(&mut self, name: &'static str, len: usize) -> Result<(), DecodeError<R::Error>> {
+        let limit = self.options.max_str_len;
+        if len > limit {
+            return Err(DecodeError::StrLenLimit { name, len, limit });
+        }
+        self.add_size(len)
+    }
+
+    fn add_size(&mut self, size: usize) -> Result<(), DecodeError<R::Error>> {
+        self.size = self.size.saturating_add(size);
+        if self.size > self.options.max_size {
+            return Err(DecodeError::SizeLimit {
+                limit: self.options.max_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Decodes a string, borrowing it if possible.
+    fn decode_str(&mut self) -> Result<Cow<'de, str>, DecodeError<R::Error>> {
+        let name = "str";
+        match self.decode_bytes(name, major::STRING)? {
+            Cow::Borrowed(bytes) => core::str::from_utf8(bytes)
+                .map(Cow::Borrowed)
+                .map_err(|_| DecodeError::RequireUtf8 { name }),
+            Cow::Owned(bytes) => String::from_utf8(bytes)
+                .map(Cow::Owned)
+                .map_err(|_| DecodeError::RequireUtf8 { name }),
+        }
+    }
+
+    /// Runs `f` on the nested value at `segment`, errors it returns are annotated with the path if
+    /// path tracking is enabled.
+    fn at_path<T>(
+        &mut self,
+        segment: PathSegment,
+        f: impl FnOnce(&mut Self) -> Result<T, DecodeError<R::Error>>,
+    ) -> Result<T, DecodeError<R::Error>> {
+        if !self.options.track_path {
+            return f(self);
+        }
+        self.path.push(segment);
+        // The innermost value annotates the error, as it knows the full path.
+        let result = f(self).map_err(|error| match error {
+            error @ DecodeError::AtPath { .. } => error,
+            error => DecodeError::AtPath {
+                path: self.format_path(),
+                error: Box::new(error),
+            },
+        });
+        self.path.pop();
+        result
+    }
+
+    fn format_path(&self) -> String {
+        format_path(&self.path)
+    }
+
+    /// Accounts for an array or map with `len` items that is about to be decoded. Every successful
+    /// call must be paired with decrementing the depth once the array or map is done.
+    fn enter(
+        &mut self,
+        name: &'static str,
+        len: usize,
+        items: usize,
+    ) -> Result<(), DecodeError<R::Error>> {
+        self.add_items(name, len, items)?;
+        if self.depth >= self.options.max_depth {
+            return Err(DecodeError::DepthLimit {
+                limit: self.options.max_depth,
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Accounts for `items` more items of an array or map that has `len` items so far.
+    fn add_items(
+        &mut self,
+        name: &'static str,
+        len: usize,
+        items: usize,
+    ) -> Result<(), DecodeError<R::Error>> {
+        let limit = self.options.max_collection_len;
+        if len > limit {
+            return Err(DecodeError::CollectionLenLimit { name, len, limit });
+        }
+        self.add_size(items)
+    }
+
     /// This method should be called after a value has been deserialized to ensure there is no
     /// trailing data in the input source.
     pub fn end(&mut self) -> Result<(), DecodeError<R::Error>> {
@@ -249,7 +1032,7 @@ macro_rules! deserialize_type {
         fn $name<V>(self, visitor: V) -> Result<V::Value, Self::Error>
         where V: Visitor<'de>
         {
-            let value = <$t>::decode(&mut self.reader)?;
+            let value = <$t>::decode(&mut self.reader).map_err(DecodeError::from_cbor4ii)?;
             visitor.$visit(value)
         }
     };
@@ -260,7 +1043,26 @@ macro_rules! deserialize_type {
     };
 }
 
-impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
+macro_rules! deserialize_int {
+    ( @ $t:ty , $name:ident , $visit:ident ) => {
+        #[inline]
+        fn $name<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+        where V: Visitor<'de>
+        {
+            let name = stringify!($t);
+            let value = <$t>::try_from(self.decode_int(name)?)
+                .map_err(|_| DecodeError::CastOverflow { name })?;
+            visitor.$visit(value)
+        }
+    };
+    ( $( $t:ty , $name:ident , $visit:ident );* $( ; )? ) => {
+        $(
+            deserialize_int!(@ $t, $name, $visit);
+        )*
+    };
+}
+
+impl<'de, R: Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
     type Error = DecodeError<R::Error>;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -273,7 +1075,7 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
         let de = &mut *de;
 
         let byte = peek_one(name, &mut de.reader)?;
-        if is_indefinite(byte) {
+        if is_indefinite(byte) && !de.options.lenient {
             return Err(DecodeError::IndefiniteSize);
         }
         match dec::if_major(byte) {
@@ -281,7 +1083,7 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
             major::NEGATIVE => {
                 // CBOR supports negative integers up to -2^64 which is less than i64::MIN. Only
                 // treat it as i128, if it is outside the i64 range.
-                let value = i128::decode(&mut de.reader)?;
+                let value = de.decode_int(name)?;
                 match i64::try_from(value) {
                     Ok(value_i64) => visitor.visit_i64(value_i64),
                     Err(_) => visitor.visit_i128(value),
@@ -316,7 +1118,9 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
 
     deserialize_type!(
         bool,       deserialize_bool,       visit_bool;
+    );
 
+    deserialize_int!(
         i8,         deserialize_i8,         visit_i8;
         i16,        deserialize_i16,        visit_i16;
         i32,        deserialize_i32,        visit_i32;
@@ -328,20 +1132,31 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
         u32,        deserialize_u32,        visit_u32;
         u64,        deserialize_u64,        visit_u64;
         u128,       deserialize_u128,       visit_u128;
-
-        f64,        deserialize_f64,        visit_f64;
     );
 
+    #[inline]
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.decode_f64()? {
+            Some(value) => visitor.visit_f64(value),
+            None => visitor.visit_none(),
+        }
+    }
+
     #[inline]
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        let value = <f64>::decode(&mut self.reader)?;
-        if value <= f32::MAX as f64 && value >= f32::MIN as f64 {
-            visitor.visit_f32(value as f32)
-        } else {
-            Err(DecodeError::CastOverflow { name: "f32" })
+        match self.decode_f64()? {
+            Some(value) if !value.is_finite() => visitor.visit_f32(value as f32),
+            Some(value) if value <= f32::MAX as f64 && value >= f32::MIN as f64 => {
+                visitor.visit_f32(value as f32)
+            }
+            Some(_) => Err(DecodeError::CastOverflow { name: "f32" }),
+            None => visitor.visit_none(),
         }
     }
 
@@ -361,7 +1176,7 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: Visitor<'de>,
     {
-        match <types::Bytes<Cow<[u8]>>>::decode(&mut self.reader)?.0 {
+        match self.decode_bytes("bytes", major::BYTES)? {
             Cow::Borrowed(buf) => visitor.visit_borrowed_bytes(buf),
             Cow::Owned(buf) => visitor.visit_byte_buf(buf),
         }
@@ -380,7 +1195,7 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: Visitor<'de>,
     {
-        match <Cow<str>>::decode(&mut self.reader)? {
+        match self.decode_str()? {
             Cow::Borrowed(buf) => visitor.visit_borrowed_str(buf),
             Cow::Owned(buf) => visitor.visit_string(buf),
         }
@@ -447,6 +1262,8 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
     {
         if name == CID_SERDE_PRIVATE_IDENTIFIER {
             self.deserialize_cid(visitor)
+        } else if name == LAZY_BYTES_SERDE_PRIVATE_IDENTIFIER {
+            self.deserialize_lazy_bytes(visitor)
         } else {
             visitor.visit_newtype_struct(self)
         }
@@ -459,8 +1276,10 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
     {
         let name = &"array";
         let mut de = self.try_step(name)?;
-        let seq = Accessor::array(name, &mut de)?;
-        visitor.visit_seq(seq)
+        let mut seq = Accessor::array(name, &mut de)?;
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
     }
 
     #[inline]
@@ -470,8 +1289,10 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
     {
         let name = &"tuple";
         let mut de = self.try_step(name)?;
-        let seq = Accessor::tuple(name, &mut de, len)?;
-        visitor.visit_seq(seq)
+        let mut seq = Accessor::tuple(name, &mut de, len)?;
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
     }
 
     #[inline]
@@ -485,8 +1306,10 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
         V: Visitor<'de>,
     {
         let mut de = self.try_step(name)?;
-        let seq = Accessor::tuple(name, &mut de, len)?;
-        visitor.visit_seq(seq)
+        let mut seq = Accessor::tuple(name, &mut de, len)?;
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
     }
 
     #[inline]
@@ -496,8 +1319,10 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
     {
         let name = &"map";
         let mut de = self.try_step(name)?;
-        let map = Accessor::map(name, &mut de)?;
-        visitor.visit_map(map)
+        let mut map = Accessor::map(name, &mut de)?;
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
     }
 
     #[inline]
@@ -511,8 +1336,10 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
         V: Visitor<'de>,
     {
         let mut de = self.try_step(name)?;
-        let map = Accessor::map(name, &mut de)?;
-        visitor.visit_map(map)
+        let mut map = Accessor::map(name, &mut de)?;
+        let value = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(value)
     }
 
     #[inline]
@@ -543,7 +1370,8 @@ impl<'de, R: dec::Read<'de>> serde::Deserializer<'de> for &mut Deserializer<R> {
     where
         V: Visitor<'de>,
     {
-        let _ignore = dec::IgnoredAny::decode(&mut self.reader)?;
+        // Only walk the lengths, the limits apply to ignored data as well.
+        self.skip()?;
         visitor.visit_unit()
     }
 
@@ -573,7 +1401,7 @@ impl<R, T> StreamDeserializer<'_, R, T> {
 
 impl<'de, R, T> Iterator for StreamDeserializer<'de, R, T>
 where
-    R: dec::Read<'de>,
+    R: Read<'de>,
     T: de::Deserialize<'de>,
 {
     type Item = Result<T, DecodeError<R::Error>>;
@@ -583,6 +1411,7 @@ where
             return None;
         }
 
+        self.de.size = 0;
         let result = serde::Deserialize::deserialize(&mut self.de);
 
         match result {
@@ -592,26 +1421,372 @@ where
     }
 }
 
-struct Accessor<'a, R> {
-    de: &'a mut Deserializer<R>,
-    len: usize,
+/// An iterator over the concatenated values in a slice, see [`iter_from_slice`].
+pub struct SliceStreamDeserializer<'de, T> {
+    de: Deserializer<SliceReader<'de>>,
+    /// Number of bytes that haven't been decoded yet.
+    remaining: usize,
+    done: bool,
+    output: PhantomData<fn() -> T>,
+}
+
+impl<'de, T> Iterator for SliceStreamDeserializer<'de, T>
+where
+    T: de::Deserialize<'de>,
+{
+    type Item = Result<(T, usize), DecodeError<Infallible>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.remaining == 0 {
+            return None;
+        }
+
+        self.de.size = 0;
+        match T::deserialize(&mut self.de) {
+            Ok(value) => {
+                let remaining = slice_rest(&mut self.de.reader.inner).len();
+                let len = self.remaining - remaining;
+                self.remaining = remaining;
+                Some(Ok((value, len)))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// An iterator over the CBOR values in a reader with their byte ranges, see
+/// [`iter_from_reader_indexed`].
+#[cfg(feature = "std")]
+pub struct IndexedStreamDeserializer<R, T> {
+    de: Deserializer<CountingIoReader<R>>,
+    /// Whether to continue after values that fail to decode, see [`Self::recover`].
+    recover: bool,
+    /// A value found after skipping data, returned after the error.
+    pending: Option<(T, core::ops::Range<u64>)>,
+    done: bool,
+    output: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "std")]
+impl<R, T> IndexedStreamDeserializer<R, T> {
+    /// Sets whether iteration continues after a value fails to decode.
+    ///
+    /// By default the iterator stops after the first error. With recovery, a value that fails to
+    /// decode is reported as [`DecodeError::Skipped`] with the range of bytes that were skipped,
+    /// and iteration continues with the next value that can be decoded. It is found by trying to
+    /// decode `T` at every following byte offset, so a specific type finds the next value more
+    /// reliably than e.g. [`Value`](super::Value), for which most single bytes are valid. I/O
+    /// errors still stop the iterator.
+    ///
+    /// The bytes of the current value are kept in memory to try them again, and a corrupted
+    /// length can make an attempt read far ahead. Limits like [`DecodeOptions::max_size`] keep
+    /// this in check.
+    ///
+    /// ```
+    /// # use dasl::drisl::{self, DecodeError, de};
+    /// #[derive(Debug, PartialEq, serde::Deserialize)]
+    /// struct Entry {
+    ///     id: u64,
+    /// }
+    ///
+    /// // {"id": 1}, a corrupted byte, {"id": 2}
+    /// let v: &[u8] = &[0xa1, 0x62, 0x69, 0x64, 0x01, 0xff, 0xa1, 0x62, 0x69, 0x64, 0x02];
+    /// let mut iter = de::iter_from_reader_indexed::<Entry, _>(v).recover(true);
+    /// assert_eq!(iter.next().unwrap().unwrap(), (Entry { id: 1 }, 0..5));
+    /// assert!(matches!(
+    ///     iter.next().unwrap(),
+    ///     Err(DecodeError::Skipped { range, .. }) if range == (5..6)
+    /// ));
+    /// assert_eq!(iter.next().unwrap().unwrap(), (Entry { id: 2 }, 6..11));
+    /// assert!(iter.next().is_none());
+    /// ```
+    pub fn recover(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
+
+    /// Returns the number of bytes consumed from the reader so far.
+    pub fn position(&self) -> u64 {
+        self.de.reader.inner.position
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.de.reader.inner.reader
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, T> Iterator for IndexedStreamDeserializer<R, T>
+where
+    R: std::io::BufRead,
+    T: de::DeserializeOwned,
+{
+    type Item = Result<(T, core::ops::Range<u64>), DecodeError<std::io::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(pending) = self.pending.take() {
+            return Some(Ok(pending));
+        }
+        if self.done {
+            return None;
+        }
+        if let Ok(()) = self.de.end() {
+            self.done = true;
+            return None;
+        }
+
+        let start = self.de.reader.inner.position;
+        if self.recover {
+            self.de.reader.inner.record();
+        }
+        self.de.size = 0;
+        match T::deserialize(&mut self.de) {
+            Ok(value) => Some(Ok((value, start..self.de.reader.inner.position))),
+            Err(err @ DecodeError::Read(_)) => {
+                self.done = true;
+                Some(Err(err))
+            }
+            Err(err) if self.recover => Some(self.resync(start, err)),
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, T> IndexedStreamDeserializer<R, T>
+where
+    R: std::io::BufRead,
+    T: de::DeserializeOwned,
+{
+    /// Looks for the next value after the value at `start` failed to decode.
+    fn resync(
+        &mut self,
+        start: u64,
+        error: DecodeError<std::io::Error>,
+    ) -> Result<(T, core::ops::Range<u64>), DecodeError<std::io::Error>> {
+        let mut offset = start;
+        let end = loop {
+            offset += 1;
+            if let Err(err) = self.de.reader.inner.rewind(offset) {
+                self.done = true;
+                return Err(DecodeError::Read(err));
+            }
+            self.de.reader.offset = self.de.reader.inner.position;
+            self.de.depth = 0;
+            self.de.size = 0;
+            self.de.path.clear();
+            match self.de.end() {
+                Ok(()) => {
+                    self.done = true;
+                    break self.de.reader.inner.position;
+                }
+                Err(DecodeError::Read(err)) => {
+                    self.done = true;
+                    return Err(DecodeError::Read(err));
+                }
+                Err(_) => {}
+            }
+            match T::deserialize(&mut self.de) {
+                Ok(value) => {
+                    self.pending = Some((value, offset..self.de.reader.inner.position));
+                    break offset;
+                }
+                Err(DecodeError::Read(err)) => {
+                    self.done = true;
+                    return Err(DecodeError::Read(err));
+                }
+                Err(_) => {}
+            }
+        };
+        Err(DecodeError::Skipped {
+            range: start..end,
+            error: Box::new(error),
+        })
+    }
 }
 
-impl<'de, 'a, R: dec::Read<'de>> Accessor<'a, R> {
+/// Wraps the reader of a [`Deserializer`] to know the offset of errors, and to decode with
+/// cbor4ii, which isn't part of the public API.
+#[derive(Debug)]
+struct OffsetReader<R> {
+    inner: R,
+    /// Number of bytes consumed.
+    offset: u64,
+}
+
+impl<'de, R: Read<'de>> dec::Read<'de> for OffsetReader<R> {
+    type Error = R::Error;
+
     #[inline]
-    fn array(
-        _name: &'static str,
-        de: &'a mut Deserializer<R>,
-    ) -> Result<Accessor<'a, R>, DecodeError<R::Error>> {
-        let len = types::Array::len(&mut de.reader)?;
+    fn fill<'b>(&'b mut self, want: usize) -> Result<dec::Reference<'de, 'b>, Self::Error> {
+        match self.inner.fill(want)? {
+            Reference::Long(buf) => Ok(dec::Reference::Long(buf)),
+            Reference::Short(buf) => Ok(dec::Reference::Short(buf)),
+        }
+    }
 
-        match len {
-            None => {
-                // Indefinite length objects are disallowed according to CBORc
-                Err(DecodeError::IndefiniteSize)
+    #[inline]
+    fn advance(&mut self, n: usize) {
+        self.inner.advance(n);
+        self.offset += n as u64;
+    }
+
+    #[inline]
+    fn step_in(&mut self) -> bool {
+        self.inner.step_in()
+    }
+
+    #[inline]
+    fn step_out(&mut self) {
+        self.inner.step_out()
+    }
+}
+
+/// Like [`IoReader`], but keeps track of the number of bytes consumed.
+///
+/// It can also record the consumed bytes, to read them again after a [`rewind`](Self::rewind).
+#[cfg(feature = "std")]
+struct CountingIoReader<R> {
+    reader: R,
+    position: u64,
+    limit: usize,
+    /// Recorded bytes, which are read before the reader if the position is within them.
+    buffer: Vec<u8>,
+    /// Position of the first byte of the buffer.
+    buffer_start: u64,
+    /// Whether bytes consumed from the reader are added to the buffer.
+    recording: bool,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> CountingIoReader<R> {
+    /// Starts recording at the current position, dropping the bytes recorded before it.
+    fn record(&mut self) {
+        self.drop_recorded();
+        self.recording = true;
+    }
+
+    /// Moves to a recorded position again, or forward to `position` if it wasn't consumed yet.
+    fn rewind(&mut self, position: u64) -> std::io::Result<()> {
+        let mut end = self.buffer_start + self.buffer.len() as u64;
+        while end < position {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                break;
             }
-            Some(len) => Ok(Accessor { de, len }),
+            let n = buf.len().min((position - end) as usize);
+            self.buffer.extend_from_slice(&buf[..n]);
+            self.reader.consume(n);
+            end += n as u64;
         }
+        self.position = position.min(end);
+        self.limit = read::DEPTH_LIMIT;
+        self.drop_recorded();
+        Ok(())
+    }
+
+    /// Drops the recorded bytes before the current position.
+    fn drop_recorded(&mut self) {
+        let consumed = (self.position - self.buffer_start).min(self.buffer.len() as u64);
+        self.buffer.drain(..consumed as usize);
+        self.buffer_start = self.position;
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, R: std::io::BufRead> Read<'de> for CountingIoReader<R> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn fill<'b>(&'b mut self, _want: usize) -> Result<Reference<'de, 'b>, Self::Error> {
+        let offset = (self.position - self.buffer_start) as usize;
+        if offset < self.buffer.len() {
+            return Ok(Reference::Short(&self.buffer[offset..]));
+        }
+        Ok(Reference::Short(self.reader.fill_buf()?))
+    }
+
+    #[inline]
+    fn advance(&mut self, n: usize) {
+        let offset = (self.position - self.buffer_start) as usize;
+        if offset >= self.buffer.len() {
+            if self.recording {
+                // The bytes were returned by the last `fill`, so they are still buffered.
+                if let Ok(buf) = self.reader.fill_buf() {
+                    self.buffer.extend_from_slice(&buf[..n]);
+                }
+            }
+            self.reader.consume(n);
+        }
+        self.position += n as u64;
+    }
+
+    #[inline]
+    fn step_in(&mut self) -> bool {
+        read::step_in(&mut self.limit)
+    }
+
+    #[inline]
+    fn step_out(&mut self) {
+        self.limit += 1;
+    }
+}
+
+struct Accessor<'a, 'de, R> {
+    de: &'a mut Deserializer<R>,
+    name: &'static str,
+    /// Number of items left, `None` for indefinite lengths.
+    len: Option<usize>,
+    /// Index of the next item.
+    index: usize,
+    /// Key of the map value that is decoded next, only set when tracking the path.
+    key: Option<String>,
+    /// The previous key of the map, to check their order.
+    previous: Option<Cow<'de, str>>,
+    /// Keys of the map so far, to find duplicates when decoding is lenient and the order isn't
+    /// checked.
+    keys: BTreeSet<Cow<'de, str>>,
+}
+
+impl<'de, 'a, R: Read<'de>> Accessor<'a, 'de, R> {
+    #[inline]
+    fn new(
+        name: &'static str,
+        de: &'a mut Deserializer<R>,
+        len: Option<usize>,
+        items_per_entry: usize,
+    ) -> Result<Accessor<'a, 'de, R>, DecodeError<R::Error>> {
+        let len_or_zero = len.unwrap_or(0);
+        de.enter(
+            name,
+            len_or_zero,
+            len_or_zero.saturating_mul(items_per_entry),
+        )?;
+        Ok(Accessor {
+            de,
+            name,
+            len,
+            index: 0,
+            key: None,
+            previous: None,
+            keys: BTreeSet::new(),
+        })
+    }
+
+    #[inline]
+    fn array(
+        name: &'static str,
+        de: &'a mut Deserializer<R>,
+    ) -> Result<Accessor<'a, 'de, R>, DecodeError<R::Error>> {
+        let len = de.decode_len(name, major::ARRAY)?;
+        Accessor::new(name, de, len, 1)
     }
 
     #[inline]
@@ -619,44 +1794,73 @@ impl<'de, 'a, R: dec::Read<'de>> Accessor<'a, R> {
         name: &'static str,
         de: &'a mut Deserializer<R>,
         len: usize,
-    ) -> Result<Accessor<'a, R>, DecodeError<R::Error>> {
-        let array_len = types::Array::len(&mut de.reader)?;
+    ) -> Result<Accessor<'a, 'de, R>, DecodeError<R::Error>> {
+        let array_len = de.decode_len(name, major::ARRAY)?;
 
         match array_len {
-            Some(array_len) => {
-                // array_len can be shorter, if defaults are being used
-                if array_len <= len {
-                    return Ok(Accessor { de, len: array_len });
-                }
-                Err(DecodeError::RequireLength {
-                    name,
-                    found: Len::new(array_len),
-                })
-            }
-            None => Err(DecodeError::IndefiniteSize),
+            // array_len can be shorter, if defaults are being used
+            Some(array_len) if array_len > len => Err(DecodeError::RequireLength {
+                name,
+                found: Len::new(array_len),
+            }),
+            array_len => Accessor::new(name, de, array_len, 1),
         }
     }
 
     #[inline]
     pub fn map(
-        _name: &'static str,
+        name: &'static str,
         de: &'a mut Deserializer<R>,
-    ) -> Result<Accessor<'a, R>, DecodeError<R::Error>> {
-        let len = types::Map::len(&mut de.reader)?;
+    ) -> Result<Accessor<'a, 'de, R>, DecodeError<R::Error>> {
+        let len = de.decode_len(name, major::MAP)?;
+        Accessor::new(name, de, len, 2)
+    }
 
-        match len {
+    /// Returns whether there is another item, and accounts for it.
+    #[inline]
+    fn next_item(&mut self, items_per_entry: usize) -> Result<bool, DecodeError<R::Error>> {
+        match &mut self.len {
+            Some(0) => return Ok(false),
+            Some(len) => *len -= 1,
             None => {
-                // Indefinite length objects are disallowed according to CBORc
-                Err(DecodeError::IndefiniteSize)
+                if peek_one(self.name, &mut self.de.reader)? == marker::BREAK {
+                    self.de.reader.advance(1);
+                    self.len = Some(0);
+                    return Ok(false);
+                }
+                self.de
+                    .add_items(self.name, self.index + 1, items_per_entry)?;
             }
-            Some(len) => Ok(Accessor { de, len }),
         }
+        self.index += 1;
+        Ok(true)
+    }
+
+    /// Checks that an indefinite length array or map was read until its end.
+    #[inline]
+    fn end(&mut self) -> Result<(), DecodeError<R::Error>> {
+        if self.len.is_none() {
+            let byte = pull_one(self.name, &mut self.de.reader)?;
+            if byte != marker::BREAK {
+                return Err(DecodeError::Mismatch {
+                    name: self.name,
+                    found: byte,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R> Drop for Accessor<'_, '_, R> {
+    fn drop(&mut self) {
+        self.de.depth -= 1;
     }
 }
 
-impl<'de, R> de::SeqAccess<'de> for Accessor<'_, R>
+impl<'de, R> de::SeqAccess<'de> for Accessor<'_, 'de, R>
 where
-    R: dec::Read<'de>,
+    R: Read<'de>,
 {
     type Error = DecodeError<R::Error>;
 
@@ -665,21 +1869,23 @@ where
     where
         T: de::DeserializeSeed<'de>,
     {
-        if self.len > 0 {
-            self.len -= 1;
-            Ok(Some(seed.deserialize(&mut *self.de)?))
-        } else {
-            Ok(None)
+        if !self.next_item(1)? {
+            return Ok(None);
         }
+        let index = self.index - 1;
+        let value = self
+            .de
+            .at_path(PathSegment::Index(index), |de| seed.deserialize(de))?;
+        Ok(Some(value))
     }
 
     #[inline]
     fn size_hint(&self) -> Option<usize> {
-        Some(self.len)
+        self.len
     }
 }
 
-impl<'de, R: dec::Read<'de>> de::MapAccess<'de> for Accessor<'_, R> {
+impl<'de, R: Read<'de>> de::MapAccess<'de> for Accessor<'_, 'de, R> {
     type Error = DecodeError<R::Error>;
 
     #[inline]
@@ -689,22 +1895,67 @@ impl<'de, R: dec::Read<'de>> de::MapAccess<'de> for Accessor<'_, R> {
     {
         let name = "map key";
 
-        if self.len > 0 {
-            self.len -= 1;
+        // Decode the key upfront to find duplicates and to know the path, the seed then gets it
+        // from memory.
+        let key = loop {
+            if !self.next_item(2)? {
+                return Ok(None);
+            }
             let de = &mut *self.de;
+            let offset = de.reader.offset;
 
             // Verify that the key is a string
             let byte = peek_one(name, &mut de.reader)?;
-            let major = dec::if_major(byte);
-            if major == major::STRING {
-                let value = seed.deserialize(de)?;
-                Ok(Some(value))
-            } else {
-                Err(DecodeError::Mismatch { name, found: byte })
+            if dec::if_major(byte) != major::STRING {
+                return Err(DecodeError::Mismatch { name, found: byte });
+            }
+
+            let key = de.decode_str()?;
+            if !de.options.lenient {
+                check_key_order(self.previous.as_deref(), &key, offset)?;
+                break key;
+            }
+            if !self.keys.contains(&key) {
+                break key;
             }
+            match de.options.duplicate_keys {
+                DuplicateKeys::Reject => {}
+                DuplicateKeys::FirstWins => {
+                    de.skip()?;
+                    continue;
+                }
+                DuplicateKeys::LastWins => break key,
+            }
+            return Err(DecodeError::DuplicateKey {
+                key: key.into_owned(),
+                offset,
+            });
+        };
+
+        // The key is only kept afterwards, so it doesn't need to be cloned.
+        let deserialize_key = |seed: K| -> Result<K::Value, Self::Error> {
+            match &key {
+                Cow::Borrowed(key) => seed.deserialize(BorrowedStrDeserializer::new(key)),
+                Cow::Owned(key) => seed.deserialize(StrDeserializer::new(key)),
+            }
+        };
+        let de = &mut *self.de;
+        let value = if de.options.track_path {
+            let path_key = String::from(&*key);
+            let value = de.at_path(PathSegment::Key(path_key.clone()), |_| {
+                deserialize_key(seed)
+            })?;
+            self.key = Some(path_key);
+            value
+        } else {
+            deserialize_key(seed)?
+        };
+        if self.de.options.lenient {
+            self.keys.insert(key);
         } else {
-            Ok(None)
+            self.previous = Some(key);
         }
+        Ok(Some(value))
     }
 
     #[inline]
@@ -712,20 +1963,46 @@ impl<'de, R: dec::Read<'de>> de::MapAccess<'de> for Accessor<'_, R> {
     where
         V: de::DeserializeSeed<'de>,
     {
-        seed.deserialize(&mut *self.de)
+        match self.key.take() {
+            Some(key) => self
+                .de
+                .at_path(PathSegment::Key(key), |de| seed.deserialize(de)),
+            None => seed.deserialize(&mut *self.de),
+        }
     }
 
     #[inline]
     fn size_hint(&self) -> Option<usize> {
-        Some(self.len)
+        self.len
+    }
+}
+
+/// Checks that a map `key` at `offset` sorts after the `previous` one, as DRISL requires.
+fn check_key_order<E>(
+    previous: Option<&str>,
+    key: &str,
+    offset: u64,
+) -> Result<(), DecodeError<E>> {
+    match previous.map(|previous| cmp_map_keys(previous, key)) {
+        None | Some(Ordering::Less) => Ok(()),
+        Some(Ordering::Equal) => Err(DecodeError::DuplicateKey {
+            key: key.into(),
+            offset,
+        }),
+        Some(Ordering::Greater) => Err(DecodeError::UnsortedKey {
+            key: key.into(),
+            offset,
+        }),
     }
 }
 
 struct EnumAccessor<'a, R> {
     de: &'a mut Deserializer<R>,
+    /// Whether the enum is encoded as a map, which counts towards the depth.
+    is_map: bool,
 }
 
-impl<'de, 'a, R: dec::Read<'de>> EnumAccessor<'a, R> {
+impl<'de, 'a, R: Read<'de>> EnumAccessor<'a, R> {
     #[inline]
     pub fn enum_(
         name: &'static str,
@@ -734,20 +2011,29 @@ impl<'de, 'a, R: dec::Read<'de>> EnumAccessor<'a, R> {
         let byte = peek_one(name, &mut de.reader)?;
         match dec::if_major(byte) {
             // string
-            major::STRING => Ok(EnumAccessor { de }),
+            major::STRING => Ok(EnumAccessor { de, is_map: false }),
             // 1 length map
             major::MAP if byte == (major::MAP << 5) | 1 => {
                 de.reader.advance(1);
-                Ok(EnumAccessor { de })
+                de.enter(name, 1, 2)?;
+                Ok(EnumAccessor { de, is_map: true })
             }
             _ => Err(DecodeError::Mismatch { name, found: byte }),
         }
     }
 }
 
+impl<R> Drop for EnumAccessor<'_, R> {
+    fn drop(&mut self) {
+        if self.is_map {
+            self.de.depth -= 1;
+        }
+    }
+}
+
 impl<'de, 'a, R> de::EnumAccess<'de> for EnumAccessor<'a, R>
 where
-    R: dec::Read<'de>,
+    R: Read<'de>,
 {
     type Error = DecodeError<R::Error>;
     type Variant = EnumAccessor<'a, R>;
@@ -764,7 +2050,7 @@ where
 
 impl<'de, R> de::VariantAccess<'de> for EnumAccessor<'_, R>
 where
-    R: dec::Read<'de>,
+    R: Read<'de>,
 {
     type Error = DecodeError<R::Error>;
 
@@ -816,7 +2102,7 @@ where
 /// parsing over the tag to determine whether it is a CID or not and go from there.
 struct CidDeserializer<'a, R>(&'a mut Deserializer<R>);
 
-impl<'de, 'a, R: dec::Read<'de>> de::Deserializer<'de> for &'a mut CidDeserializer<'a, R> {
+impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for &'a mut CidDeserializer<'a, R> {
     type Error = DecodeError<R::Error>;
 
     fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
@@ -826,7 +2112,7 @@ impl<'de, 'a, R: dec::Read<'de>> de::Deserializer<'de> for &'a mut CidDeserializ
     #[inline]
     fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
         // CBOR encoded CIDs have a zero byte prefix we have to remove.
-        match <types::Bytes<Cow<[u8]>>>::decode(&mut self.0.reader)?.0 {
+        match self.0.decode_bytes("CID", major::BYTES)? {
             Cow::Borrowed(buf) => {
                 if buf.len() <= 1 || buf[0] != 0 {
                     Err(DecodeError::Msg("Invalid CID".into()))
@@ -0,0 +1,170 @@
+//! Deserialization from Tokio's [`AsyncRead`].
+
+use std::{
+    future::poll_fn,
+    io,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use futures_core::Stream;
+use serde::de;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use super::StreamDecoder;
+use crate::drisl::error::{DecodeError, Len};
+
+/// Number of bytes read from the reader at once.
+const READ_CHUNK_LEN: usize = 8 * 1024;
+
+/// Decodes a value from CBOR data in an async reader.
+///
+/// The value is buffered internally until it is complete, then decoded. Fails if the reader
+/// contains anything after the value.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// # use dasl::drisl::de;
+/// let v: &[u8] = &[0x66, 0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72];
+/// let value: String = de::from_async_reader(v).await.unwrap();
+/// assert_eq!(value, "foobar");
+/// # }
+/// ```
+pub async fn from_async_reader<T, R>(reader: R) -> Result<T, DecodeError<io::Error>>
+where
+    T: de::DeserializeOwned,
+    R: AsyncRead + Unpin,
+{
+    let mut reader = ValueReader::new(reader);
+    let value = poll_fn(|cx| reader.poll_value(cx))
+        .await?
+        .ok_or(DecodeError::Eof {
+            name: "value",
+            expect: Len::new(1),
+        })?;
+    match poll_fn(|cx| reader.poll_value(cx)).await {
+        Ok(None) => {}
+        Err(DecodeError::Read(err)) => return Err(DecodeError::Read(err)),
+        Ok(Some(_)) | Err(_) => return Err(DecodeError::TrailingData),
+    }
+    super::from_slice(&value).map_err(DecodeError::into_read_error)
+}
+
+/// Creates a stream over the CBOR values in an async reader.
+///
+/// Each value is buffered internally until it is complete, then decoded. The stream ends after an
+/// error of the reader or of malformed data, values that merely fail to deserialize into `T` don't
+/// end it.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// # use dasl::drisl::de;
+/// use futures_util::StreamExt;
+///
+/// let v: &[u8] = &[
+///     0x66, 0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72, 0x63, 0x62, 0x61, 0x7A,
+/// ];
+/// let mut stream = de::stream_from_async_reader::<String, _>(v);
+/// let mut values = Vec::new();
+/// while let Some(value) = stream.next().await {
+///     values.push(value.unwrap());
+/// }
+/// assert_eq!(values, ["foobar", "baz"]);
+/// # }
+/// ```
+pub fn stream_from_async_reader<T, R>(reader: R) -> AsyncStreamDeserializer<R, T>
+where
+    T: de::DeserializeOwned,
+    R: AsyncRead + Unpin,
+{
+    AsyncStreamDeserializer {
+        reader: ValueReader::new(reader),
+        done: false,
+        output: PhantomData,
+    }
+}
+
+/// A [`Stream`] of values decoded from an async reader, see [`stream_from_async_reader`].
+pub struct AsyncStreamDeserializer<R, T> {
+    reader: ValueReader<R>,
+    done: bool,
+    output: PhantomData<fn() -> T>,
+}
+
+impl<R, T> Stream for AsyncStreamDeserializer<R, T>
+where
+    T: de::DeserializeOwned,
+    R: AsyncRead + Unpin,
+{
+    type Item = Result<T, DecodeError<io::Error>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        match ready!(this.reader.poll_value(cx)) {
+            Ok(Some(value)) => Poll::Ready(Some(
+                super::from_slice(&value).map_err(DecodeError::into_read_error),
+            )),
+            Ok(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Err(err) => {
+                this.done = true;
+                Poll::Ready(Some(Err(err)))
+            }
+        }
+    }
+}
+
+/// Splits the data of an async reader into encoded values.
+pub(crate) struct ValueReader<R> {
+    reader: R,
+    decoder: StreamDecoder,
+}
+
+impl<R: AsyncRead + Unpin> ValueReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            decoder: StreamDecoder::new(),
+        }
+    }
+
+    /// Reads until the next value is complete and returns its encoding.
+    ///
+    /// Returns `None` if the reader ends before another value starts.
+    pub(crate) fn poll_value(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<Vec<u8>>, DecodeError<io::Error>>> {
+        loop {
+            let value = self
+                .decoder
+                .next_encoded()
+                .map_err(DecodeError::into_read_error)?;
+            if let Some(value) = value {
+                return Poll::Ready(Ok(Some(value.to_vec())));
+            }
+
+            let mut read_buf = ReadBuf::new(self.decoder.spare(READ_CHUNK_LEN));
+            ready!(Pin::new(&mut self.reader).poll_read(cx, &mut read_buf))?;
+            let read = read_buf.filled().len();
+            self.decoder.commit(read);
+
+            if read == 0 {
+                return Poll::Ready(
+                    self.decoder
+                        .finish()
+                        .map(|()| None)
+                        .map_err(DecodeError::into_read_error),
+                );
+            }
+        }
+    }
+}
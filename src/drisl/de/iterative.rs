@@ -0,0 +1,255 @@
+//! Decoding of [`Value`]s without recursion.
+
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::convert::Infallible;
+
+use cbor4ii::core::{
+    dec::{self, Read as _},
+    major,
+};
+use serde::Deserialize as _;
+
+use super::{DecodeOptions, Deserializer, DuplicateKeys, Read, check_key_order};
+use crate::drisl::{
+    PathSegment, Value,
+    cbor4ii_nonpub::{marker, peek_one},
+    error::DecodeError,
+    path::format_path,
+    value::MAX_PREALLOC_LEN,
+};
+
+/// Decodes a [`Value`] from CBOR data in a slice, without recursing into arrays and maps.
+///
+/// Unlike [`from_slice`](super::from_slice), the nesting of the data is only limited by
+/// [`DecodeOptions::max_depth`] and the available memory, not by the stack. See
+/// [`Deserializer::decode_value`].
+///
+/// ```
+/// # use dasl::drisl::{Value, de};
+/// // 10,000 nested arrays.
+/// let mut v = vec![0x81; 10_000];
+/// v.push(0x01);
+/// let value = de::value_from_slice(&v).unwrap();
+/// assert!(matches!(value, Value::Array(_)));
+/// ```
+pub fn value_from_slice(buf: &[u8]) -> Result<Value, DecodeError<Infallible>> {
+    value_from_slice_with(buf, DecodeOptions::default())
+}
+
+/// Decodes a [`Value`] from CBOR data in a slice without recursion, with the given options, see
+/// [`value_from_slice`].
+pub fn value_from_slice_with(
+    buf: &[u8],
+    options: DecodeOptions,
+) -> Result<Value, DecodeError<Infallible>> {
+    let mut deserializer = Deserializer::from_slice(buf).with_options(options);
+    let value = deserializer.decode_value()?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// An array or map that is being decoded.
+enum Frame {
+    Array {
+        items: Vec<Value>,
+        /// Number of items left, `None` for indefinite lengths.
+        len: Option<usize>,
+    },
+    Map {
+        entries: BTreeMap<String, Value>,
+        /// Number of entries left, `None` for indefinite lengths.
+        len: Option<usize>,
+        /// Number of entries so far, including skipped duplicates.
+        index: usize,
+        /// Key of the value that is decoded next.
+        key: Option<String>,
+        /// The previous key, to check their order.
+        previous: Option<String>,
+    },
+}
+
+impl<'de, R: Read<'de>> Deserializer<R> {
+    /// Decodes a [`Value`] without recursing into arrays and maps.
+    ///
+    /// Serde decodes nested data recursively, so the depth is limited by the reader (256 levels)
+    /// to protect the stack. This keeps the arrays and maps that are being decoded on the heap
+    /// instead, so the depth is only limited by [`DecodeOptions::max_depth`]. Otherwise the
+    /// options apply the same as when decoding a `Value` with Serde.
+    ///
+    /// Dropping a `Value` and encoding it with
+    /// [`value_to_vec`](crate::drisl::ser::value_to_vec) don't recurse either, but comparing it
+    /// does, so set a [`max_depth`](DecodeOptions::max_depth) when decoding untrusted data.
+    pub fn decode_value(&mut self) -> Result<Value, DecodeError<R::Error>> {
+        let depth = self.depth;
+        let path_len = self.path.len();
+        let mut stack = Vec::new();
+        let result = self.decode_value_on(&mut stack);
+        let result = result.map_err(|error| match error {
+            error @ DecodeError::AtPath { .. } => error,
+            error if self.options.track_path && self.path.len() > path_len => DecodeError::AtPath {
+                path: format_path(&self.path),
+                error: Box::new(error),
+            },
+            error => error,
+        });
+        self.depth = depth;
+        self.path.truncate(path_len);
+        result
+    }
+
+    fn decode_value_on(&mut self, stack: &mut Vec<Frame>) -> Result<Value, DecodeError<R::Error>> {
+        loop {
+            let has_next = match stack.last_mut() {
+                Some(frame) => self.next_item_of(frame)?,
+                None => true,
+            };
+            let value = match stack.last_mut() {
+                Some(_) if !has_next => {
+                    self.depth -= 1;
+                    match stack.pop() {
+                        Some(Frame::Array { items, .. }) => Value::Array(items),
+                        Some(Frame::Map { entries, .. }) => Value::Map(entries),
+                        None => unreachable!("the frame was just checked"),
+                    }
+                }
+                Some(Frame::Map {
+                    entries,
+                    key,
+                    previous,
+                    ..
+                }) => {
+                    let Some(next) = self.decode_map_key(entries, previous)? else {
+                        // A duplicate that was skipped.
+                        continue;
+                    };
+                    if self.options.track_path {
+                        self.path.push(PathSegment::Key(next.clone()));
+                    }
+                    *key = Some(next);
+                    match self.decode_item(stack)? {
+                        Some(value) => value,
+                        None => continue,
+                    }
+                }
+                Some(Frame::Array { items, .. }) => {
+                    if self.options.track_path {
+                        self.path.push(PathSegment::Index(items.len()));
+                    }
+                    match self.decode_item(stack)? {
+                        Some(value) => value,
+                        None => continue,
+                    }
+                }
+                None => match self.decode_item(stack)? {
+                    Some(value) => value,
+                    None => continue,
+                },
+            };
+
+            match stack.last_mut() {
+                None => return Ok(value),
+                Some(Frame::Array { items, .. }) => items.push(value),
+                Some(Frame::Map { entries, key, .. }) => {
+                    let key = key.take().expect("the key is decoded before the value");
+                    entries.insert(key, value);
+                }
+            }
+            if self.options.track_path {
+                self.path.pop();
+            }
+        }
+    }
+
+    /// Decodes an item, or starts decoding an array or map and returns `None`.
+    fn decode_item(
+        &mut self,
+        stack: &mut Vec<Frame>,
+    ) -> Result<Option<Value>, DecodeError<R::Error>> {
+        let byte = peek_one("any", &mut self.reader)?;
+        match dec::if_major(byte) {
+            major::ARRAY => {
+                let name = "array";
+                let len = self.decode_len(name, major::ARRAY)?;
+                let len_or_zero = len.unwrap_or(0);
+                self.enter(name, len_or_zero, len_or_zero)?;
+                stack.push(Frame::Array {
+                    items: Vec::with_capacity(len_or_zero.min(MAX_PREALLOC_LEN)),
+                    len,
+                });
+                Ok(None)
+            }
+            major::MAP => {
+                let name = "map";
+                let len = self.decode_len(name, major::MAP)?;
+                let len_or_zero = len.unwrap_or(0);
+                self.enter(name, len_or_zero, len_or_zero.saturating_mul(2))?;
+                stack.push(Frame::Map {
+                    entries: BTreeMap::new(),
+                    len,
+                    index: 0,
+                    key: None,
+                    previous: None,
+                });
+                Ok(None)
+            }
+            // Anything else isn't nested.
+            _ => Value::deserialize(&mut *self).map(Some),
+        }
+    }
+
+    /// Returns whether the array or map has another item, and accounts for it, see
+    /// `Accessor::next_item`.
+    fn next_item_of(&mut self, frame: &mut Frame) -> Result<bool, DecodeError<R::Error>> {
+        let (name, len, index, items_per_entry) = match frame {
+            Frame::Array { items, len } => ("array", len, items.len(), 1),
+            Frame::Map { len, index, .. } => ("map", len, *index, 2),
+        };
+        match len {
+            Some(0) => return Ok(false),
+            Some(len) => *len -= 1,
+            None => {
+                if peek_one(name, &mut self.reader)? == marker::BREAK {
+                    self.reader.advance(1);
+                    return Ok(false);
+                }
+                self.add_items(name, index + 1, items_per_entry)?;
+            }
+        }
+        if let Frame::Map { index, .. } = frame {
+            *index += 1;
+        }
+        Ok(true)
+    }
+
+    /// Decodes a map key, `None` if it's a duplicate whose entry was skipped.
+    fn decode_map_key(
+        &mut self,
+        entries: &BTreeMap<String, Value>,
+        previous: &mut Option<String>,
+    ) -> Result<Option<String>, DecodeError<R::Error>> {
+        let name = "map key";
+        let offset = self.reader.offset;
+        let byte = peek_one(name, &mut self.reader)?;
+        if dec::if_major(byte) != major::STRING {
+            return Err(DecodeError::Mismatch { name, found: byte });
+        }
+
+        let key = self.decode_str()?.into_owned();
+        if !self.options.lenient {
+            check_key_order(previous.as_deref(), &key, offset)?;
+            *previous = Some(key.clone());
+            return Ok(Some(key));
+        }
+        if !entries.contains_key(&key) {
+            return Ok(Some(key));
+        }
+        match self.options.duplicate_keys {
+            DuplicateKeys::Reject => Err(DecodeError::DuplicateKey { key, offset }),
+            DuplicateKeys::FirstWins => {
+                self.skip()?;
+                Ok(None)
+            }
+            DuplicateKeys::LastWins => Ok(Some(key)),
+        }
+    }
+}
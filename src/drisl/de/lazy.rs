@@ -0,0 +1,146 @@
+//! Byte strings that are skipped while decoding and read later from the source.
+
+use core::{fmt, ops::Range};
+
+use serde::de;
+
+/// An identifier that is used internally by the [`Deserializer`](super::Deserializer) to recognize
+/// [`LazyBytes`].
+pub(crate) const LAZY_BYTES_SERDE_PRIVATE_IDENTIFIER: &str =
+    "$__private__serde__identifier__for__lazy_bytes";
+
+/// The location of a byte string in the decoded input, instead of its contents.
+///
+/// A field of this type isn't buffered while decoding, the byte string is skipped and only its
+/// position is recorded. That way values with large byte strings can be decoded from a file with
+/// `from_reader`, and the bytes read from the file afterwards with `reader` (both need the `std`
+/// feature).
+///
+/// The offset is relative to where the deserializer started reading. The limits of
+/// [`DecodeOptions`](super::DecodeOptions) apply as for other byte strings. Indefinite length byte
+/// strings aren't contiguous in the input, so they can't be decoded lazily.
+///
+/// `LazyBytes` can only be decoded by the deserializer of this crate, not from a
+/// [`Value`](crate::drisl::Value) or by other Serde formats.
+///
+/// ```
+/// # use dasl::drisl::{self, de::LazyBytes};
+/// #[derive(serde::Deserialize)]
+/// struct Record {
+///     name: String,
+///     data: LazyBytes,
+/// }
+///
+/// #[derive(serde::Serialize)]
+/// struct Original<'a> {
+///     name: &'a str,
+///     #[serde(with = "serde_bytes")]
+///     data: &'a [u8],
+/// }
+///
+/// let bytes = drisl::to_vec(&Original { name: "blob", data: &[7; 1000] }).unwrap();
+/// let record: Record = drisl::from_slice(&bytes).unwrap();
+/// assert_eq!(record.name, "blob");
+/// assert_eq!(record.data.len(), 1000);
+/// assert_eq!(record.data.slice(&bytes), Some(&[7; 1000][..]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LazyBytes {
+    offset: u64,
+    len: u64,
+}
+
+impl LazyBytes {
+    /// Returns the offset of the first byte in the input.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the length in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns whether the byte string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the range of the bytes in the input.
+    pub fn range(&self) -> Range<u64> {
+        self.offset..self.offset + self.len
+    }
+
+    /// Returns the bytes from the input they were decoded from, `None` if it's too short.
+    ///
+    /// ```
+    /// # use dasl::drisl::{self, de::LazyBytes};
+    /// let bytes = drisl::to_vec(&serde_bytes::Bytes::new(b"hello")).unwrap();
+    /// let lazy: LazyBytes = drisl::from_slice(&bytes).unwrap();
+    /// assert_eq!(lazy.slice(&bytes), Some(&b"hello"[..]));
+    /// ```
+    pub fn slice<'a>(&self, input: &'a [u8]) -> Option<&'a [u8]> {
+        let start = usize::try_from(self.offset).ok()?;
+        let end = usize::try_from(self.offset.checked_add(self.len)?).ok()?;
+        input.get(start..end)
+    }
+
+    /// Seeks `source` to the bytes and returns a reader over them.
+    ///
+    /// `source` must be the input they were decoded from, positioned as it was when decoding
+    /// started at offset 0.
+    ///
+    /// ```
+    /// # use std::io::{Cursor, Read};
+    /// # use dasl::drisl::{self, de::LazyBytes};
+    /// let bytes = drisl::to_vec(&serde_bytes::Bytes::new(&[7; 1000])).unwrap();
+    /// let mut source = Cursor::new(bytes);
+    /// let lazy: LazyBytes = drisl::from_reader(&mut source).unwrap();
+    ///
+    /// let mut data = Vec::new();
+    /// lazy.reader(&mut source).unwrap().read_to_end(&mut data).unwrap();
+    /// assert_eq!(data, [7; 1000]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn reader<R>(&self, mut source: R) -> std::io::Result<std::io::Take<R>>
+    where
+        R: std::io::Read + std::io::Seek,
+    {
+        source.seek(std::io::SeekFrom::Start(self.offset))?;
+        Ok(source.take(self.len))
+    }
+}
+
+impl<'de> de::Deserialize<'de> for LazyBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer
+            .deserialize_newtype_struct(LAZY_BYTES_SERDE_PRIVATE_IDENTIFIER, LazyBytesVisitor)
+    }
+}
+
+/// Visits the offset and length the [`Deserializer`](super::Deserializer) passes as a sequence.
+struct LazyBytesVisitor;
+
+impl<'de> de::Visitor<'de> for LazyBytesVisitor {
+    type Value = LazyBytes;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "a byte string decoded by the DRISL deserializer")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let offset = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let len = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(LazyBytes { offset, len })
+    }
+}
@@ -0,0 +1,140 @@
+//! Decoding from memory-mapped files.
+
+use std::{convert::Infallible, fs::File, io, path::Path};
+
+use memmap2::Mmap;
+use serde::de;
+
+use super::{DecodeOptions, SliceStreamDeserializer};
+use crate::drisl::error::DecodeError;
+
+/// Decodes a value from a memory-mapped file.
+///
+/// The file is decoded from memory as with [`from_slice`](super::from_slice), instead of being
+/// read in chunks like with [`from_reader`](super::from_reader). To borrow strings and byte
+/// strings from the file, decode from an [`MmapFile`] instead.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated while it's decoded, see [`MmapFile::open`].
+///
+/// ```
+/// # use dasl::drisl;
+/// # let dir = std::env::temp_dir().join("dasl-from-file-mmap");
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("value.drisl");
+/// std::fs::write(&path, drisl::to_vec(&("foo", 1)).unwrap()).unwrap();
+///
+/// // SAFETY: Nothing else writes to the file.
+/// let value: (String, u8) = unsafe { drisl::from_file_mmap(&path) }.unwrap();
+/// assert_eq!(value, ("foo".to_string(), 1));
+/// ```
+pub unsafe fn from_file_mmap<T>(path: impl AsRef<Path>) -> Result<T, DecodeError<io::Error>>
+where
+    T: de::DeserializeOwned,
+{
+    // SAFETY: Passed on to the caller.
+    let file = unsafe { MmapFile::open(path)? };
+    file.decode().map_err(DecodeError::into_read_error)
+}
+
+/// A read-only memory-mapped file to decode values from.
+///
+/// Decoded values can borrow strings and byte strings from the file, the same as from a slice.
+/// Files of concatenated values, like record files, can be iterated over with
+/// [`iter`](Self::iter).
+///
+/// The file must not be modified while it's mapped, the contents would change underneath the
+/// decoded values, or reading them might even fail with a signal if the file is truncated. This
+/// can't be checked, so mapping a file is unsafe.
+///
+/// ```
+/// # use dasl::drisl::{self, de::MmapFile};
+/// # let dir = std::env::temp_dir().join("dasl-mmap-file");
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("records.drisl");
+/// let mut records = drisl::to_vec("foo").unwrap();
+/// records.extend(drisl::to_vec("bar").unwrap());
+/// std::fs::write(&path, records).unwrap();
+///
+/// // SAFETY: Nothing else writes to the file.
+/// let file = unsafe { MmapFile::open(&path) }.unwrap();
+/// let records: Vec<&str> = file
+///     .iter()
+///     .map(|record| record.map(|(record, _len)| record))
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(records, ["foo", "bar"]);
+/// ```
+#[derive(Debug)]
+pub struct MmapFile {
+    map: Mmap,
+}
+
+impl MmapFile {
+    /// Opens and maps the file at `path`.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it's mapped, neither by this process nor
+    /// by others, see [`Mmap::map`].
+    pub unsafe fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        // SAFETY: Passed on to the caller.
+        unsafe { Self::from_file(&File::open(path)?) }
+    }
+
+    /// Maps an open file.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`open`](Self::open).
+    pub unsafe fn from_file(file: &File) -> io::Result<Self> {
+        // SAFETY: The mapping is read-only and the caller ensures that the file isn't modified
+        // while it's mapped.
+        let map = unsafe { Mmap::map(file)? };
+        Ok(Self { map })
+    }
+
+    /// Returns the contents of the file.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map
+    }
+
+    /// Decodes the file as a single value, see [`from_slice`](super::from_slice).
+    pub fn decode<'a, T>(&'a self) -> Result<T, DecodeError<Infallible>>
+    where
+        T: de::Deserialize<'a>,
+    {
+        super::from_slice(self.as_bytes())
+    }
+
+    /// Decodes the file as a single value with the given options, see
+    /// [`from_slice_with`](super::from_slice_with).
+    pub fn decode_with<'a, T>(
+        &'a self,
+        options: DecodeOptions,
+    ) -> Result<T, DecodeError<Infallible>>
+    where
+        T: de::Deserialize<'a>,
+    {
+        super::from_slice_with(self.as_bytes(), options)
+    }
+
+    /// Creates an iterator over the concatenated values in the file, see
+    /// [`iter_from_slice`](super::iter_from_slice).
+    pub fn iter<'a, T>(&'a self) -> SliceStreamDeserializer<'a, T>
+    where
+        T: de::Deserialize<'a>,
+    {
+        super::iter_from_slice(self.as_bytes())
+    }
+
+    /// Creates an iterator over the concatenated values in the file with the given options, see
+    /// [`iter_from_slice_with`](super::iter_from_slice_with).
+    pub fn iter_with<'a, T>(&'a self, options: DecodeOptions) -> SliceStreamDeserializer<'a, T>
+    where
+        T: de::Deserialize<'a>,
+    {
+        super::iter_from_slice_with(self.as_bytes(), options)
+    }
+}
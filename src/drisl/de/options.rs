@@ -0,0 +1,219 @@
+//! Options for decoding.
+
+use crate::drisl::ser::NonFiniteFloats;
+
+/// Options for decoding DRISL, see [`from_slice_with`](super::from_slice_with).
+///
+/// By default nothing is limited beyond the built-in recursion limit. When decoding untrusted
+/// input, the limits bound how much work and memory a single value may cost.
+///
+/// ```
+/// # use dasl::drisl::{DecodeError, de::{self, DecodeOptions}};
+/// let options = DecodeOptions::new().max_depth(1);
+/// // [[1]]
+/// let err = de::from_slice_with::<Vec<Vec<u8>>>(&[0x81, 0x81, 0x01], options).unwrap_err();
+/// assert!(matches!(err, DecodeError::DepthLimit { limit: 1 }));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    pub(crate) max_depth: usize,
+    pub(crate) max_str_len: usize,
+    pub(crate) max_collection_len: usize,
+    pub(crate) max_size: usize,
+    pub(crate) track_path: bool,
+    pub(crate) lenient: bool,
+    pub(crate) non_finite_floats: NonFiniteFloats,
+    pub(crate) duplicate_keys: DuplicateKeys,
+}
+
+impl Default for DecodeOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: usize::MAX,
+            max_str_len: usize::MAX,
+            max_collection_len: usize::MAX,
+            max_size: usize::MAX,
+            track_path: false,
+            lenient: false,
+            non_finite_floats: NonFiniteFloats::Reject,
+            duplicate_keys: DuplicateKeys::Reject,
+        }
+    }
+}
+
+impl DecodeOptions {
+    /// Creates options without any limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of nested arrays and maps, e.g. `[[1]]` has a depth of 2.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets the maximum length of a string or byte string in bytes.
+    pub fn max_str_len(mut self, len: usize) -> Self {
+        self.max_str_len = len;
+        self
+    }
+
+    /// Sets the maximum number of items of an array or entries of a map.
+    pub fn max_collection_len(mut self, len: usize) -> Self {
+        self.max_collection_len = len;
+        self
+    }
+
+    /// Sets the maximum total size of a value.
+    ///
+    /// Each array item, map key and map value counts as one, plus the length of every string and
+    /// byte string. This is a lower bound of the encoded size.
+    pub fn max_size(mut self, size: usize) -> Self {
+        self.max_size = size;
+        self
+    }
+
+    /// Sets whether errors record where in the value they occurred, see [`DecodeError::AtPath`].
+    ///
+    /// This is off by default, as it costs extra work for every map key.
+    ///
+    /// ```
+    /// # use dasl::drisl::{DecodeError, de::{self, DecodeOptions}};
+    /// #[derive(Debug, serde::Deserialize)]
+    /// struct Entry {
+    ///     size: u8,
+    /// }
+    ///
+    /// // {"entries": [{"size": 1}, {"size": "big"}]}
+    /// let v: Vec<u8> = vec![
+    ///     0xa1, 0x67, 0x65, 0x6e, 0x74, 0x72, 0x69, 0x65, 0x73, 0x82, 0xa1, 0x64, 0x73, 0x69, 0x7a,
+    ///     0x65, 0x01, 0xa1, 0x64, 0x73, 0x69, 0x7a, 0x65, 0x63, 0x62, 0x69, 0x67,
+    /// ];
+    /// let options = DecodeOptions::new().track_path(true);
+    /// let err = de::from_slice_with::<std::collections::BTreeMap<String, Vec<Entry>>>(&v, options)
+    ///     .unwrap_err();
+    /// assert_eq!(err.path(), Some("entries[1].size"));
+    /// assert!(matches!(err.without_path(), DecodeError::Mismatch { .. }));
+    /// ```
+    ///
+    /// [`DecodeError::AtPath`]: crate::drisl::DecodeError::AtPath
+    pub fn track_path(mut self, track: bool) -> Self {
+        self.track_path = track;
+        self
+    }
+
+    /// Sets whether data that isn't canonical DRISL is accepted.
+    ///
+    /// Lenient decoding accepts CBOR as produced by older DAG-CBOR encoders: indefinite length
+    /// strings, arrays and maps, unsorted map keys, as well as integers and lengths that aren't
+    /// encoded in their shortest form. The decoded values are the same as if the data was
+    /// canonical. By default such data is rejected, unsorted map keys with
+    /// [`DecodeError::UnsortedKey`]. Infinite and NaN floats are handled according to
+    /// [`non_finite_floats`](Self::non_finite_floats), duplicate map keys according to
+    /// [`duplicate_keys`](Self::duplicate_keys).
+    ///
+    /// ```
+    /// # use dasl::drisl::{DecodeError, Value, de::{self, DecodeOptions}};
+    /// // [_ 1, 2], an indefinite length array
+    /// let v: Vec<u8> = vec![0x9f, 0x01, 0x02, 0xff];
+    /// let err = de::from_slice::<Value>(&v).unwrap_err();
+    /// assert!(matches!(err, DecodeError::IndefiniteSize));
+    ///
+    /// let options = DecodeOptions::new().lenient(true);
+    /// let value: Value = de::from_slice_with(&v, options).unwrap();
+    /// assert_eq!(value, dasl::drisl!([1, 2]));
+    ///
+    /// // {"b": 1, "a": 2}, unsorted keys
+    /// let v: Vec<u8> = vec![0xa2, 0x61, 0x62, 0x01, 0x61, 0x61, 0x02];
+    /// let err = de::from_slice::<Value>(&v).unwrap_err();
+    /// assert!(matches!(err, DecodeError::UnsortedKey { key, offset: 4 } if key == "a"));
+    /// let value: Value = de::from_slice_with(&v, options).unwrap();
+    /// assert_eq!(value, dasl::drisl!({"a": 2, "b": 1}));
+    /// ```
+    ///
+    /// [`DecodeError::UnsortedKey`]: crate::drisl::DecodeError::UnsortedKey
+    /// [`Value`]: crate::drisl::Value
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets how infinite and NaN floats are handled when decoding is lenient, like
+    /// [`EncodeOptions::non_finite_floats`] does for encoding.
+    ///
+    /// They are always rejected unless decoding is [`lenient`](Self::lenient), which is also the
+    /// default policy. [`NonFiniteFloats::Null`] decodes them as null, which only types that accept
+    /// null, like [`Value`], can be decoded from.
+    ///
+    /// ```
+    /// # use dasl::drisl::{DecodeError, Value, de::{self, DecodeOptions}, ser::NonFiniteFloats};
+    /// // [1.5, NaN]
+    /// let v = hex::decode("82fb3ff8000000000000fb7ff8000000000000").unwrap();
+    /// let err = de::from_slice::<Value>(&v).unwrap_err();
+    /// assert!(matches!(err, DecodeError::NonFiniteFloat));
+    ///
+    /// let options = DecodeOptions::new()
+    ///     .lenient(true)
+    ///     .non_finite_floats(NonFiniteFloats::Null);
+    /// let value: Value = de::from_slice_with(&v, options).unwrap();
+    /// assert_eq!(value, dasl::drisl!([1.5, null]));
+    /// ```
+    ///
+    /// [`EncodeOptions::non_finite_floats`]: crate::drisl::ser::EncodeOptions::non_finite_floats
+    /// [`Value`]: crate::drisl::Value
+    pub fn non_finite_floats(mut self, policy: NonFiniteFloats) -> Self {
+        self.non_finite_floats = policy;
+        self
+    }
+
+    /// Sets how maps with the same key more than once are handled when decoding is lenient.
+    ///
+    /// They are always rejected with [`DecodeError::DuplicateKey`] unless decoding is
+    /// [`lenient`](Self::lenient), which is also the default policy. When decoding is lenient,
+    /// keys of values that are skipped, like unknown fields of structs, aren't checked.
+    ///
+    /// ```
+    /// # use dasl::drisl::{DecodeError, Value, de::{self, DecodeOptions, DuplicateKeys}};
+    /// // {"a": 1, "a": 2}
+    /// let v: Vec<u8> = vec![0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02];
+    /// let err = de::from_slice::<Value>(&v).unwrap_err();
+    /// assert!(matches!(err, DecodeError::DuplicateKey { key, offset: 4 } if key == "a"));
+    ///
+    /// let options = DecodeOptions::new().lenient(true);
+    /// let first: Value = de::from_slice_with(&v, options.duplicate_keys(DuplicateKeys::FirstWins))
+    ///     .unwrap();
+    /// assert_eq!(first, dasl::drisl!({"a": 1}));
+    /// let last: Value = de::from_slice_with(&v, options.duplicate_keys(DuplicateKeys::LastWins))
+    ///     .unwrap();
+    /// assert_eq!(last, dasl::drisl!({"a": 2}));
+    /// ```
+    ///
+    /// [`DecodeError::DuplicateKey`]: crate::drisl::DecodeError::DuplicateKey
+    pub fn duplicate_keys(mut self, policy: DuplicateKeys) -> Self {
+        self.duplicate_keys = policy;
+        self
+    }
+}
+
+/// How maps with the same key more than once are handled, see
+/// [`DecodeOptions::duplicate_keys`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DuplicateKeys {
+    /// Fail with an error, as DRISL requires.
+    #[default]
+    Reject,
+    /// Keep the first entry and skip the later ones with the same key.
+    FirstWins,
+    /// Pass every entry on, so that the type that is decoded keeps the last one.
+    ///
+    /// Maps like [`Value`](crate::drisl::Value) or `BTreeMap` overwrite earlier entries, but
+    /// derived structs reject duplicate fields.
+    ///
+    /// [`Value`](crate::drisl::Value), [`ValueRef`](crate::drisl::ValueRef), `ArenaValue` and
+    /// [`SortedMap`](crate::drisl::SortedMap) leave rejecting duplicate keys to the deserializer
+    /// and keep the last one of equal keys. This also applies when they are decoded from other
+    /// formats, e.g. JSON.
+    LastWins,
+}
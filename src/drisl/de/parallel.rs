@@ -0,0 +1,118 @@
+//! Decoding concatenated values in parallel with [`rayon`].
+
+use std::convert::Infallible;
+
+use rayon::prelude::*;
+use serde::de;
+
+use super::{DecodeOptions, from_slice_with};
+use crate::drisl::{
+    error::{DecodeError, Len},
+    links::Scanner,
+};
+
+/// Creates a parallel iterator over the concatenated values in a slice.
+///
+/// The slice is scanned for the boundaries of the values first, which is much faster than
+/// decoding them. The values are then decoded in parallel, in the order of the slice. Fails if
+/// the data is malformed or ends within a value, as no boundaries can be found after that.
+///
+/// ```
+/// # use dasl::drisl::{self, de};
+/// use rayon::prelude::*;
+///
+/// let mut bytes = Vec::new();
+/// for i in 0..100u32 {
+///     bytes.extend(drisl::to_vec(&(i, "record")).unwrap());
+/// }
+/// let records: Vec<(u32, &str)> = de::par_iter_from_slice(&bytes)
+///     .unwrap()
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(records.len(), 100);
+/// assert_eq!(records[42], (42, "record"));
+/// ```
+pub fn par_iter_from_slice<'de, T>(
+    buf: &'de [u8],
+) -> Result<
+    impl IndexedParallelIterator<Item = Result<T, DecodeError<Infallible>>> + 'de,
+    DecodeError<Infallible>,
+>
+where
+    T: de::Deserialize<'de> + Send,
+{
+    par_iter_from_slice_with(buf, DecodeOptions::default())
+}
+
+/// Creates a parallel iterator over the concatenated values in a slice, with the given options.
+///
+/// The limits apply to each value separately, see [`par_iter_from_slice`]. The boundaries are
+/// found with a strict scan, so lenient decoding doesn't allow indefinite lengths here.
+pub fn par_iter_from_slice_with<'de, T>(
+    buf: &'de [u8],
+    options: DecodeOptions,
+) -> Result<
+    impl IndexedParallelIterator<Item = Result<T, DecodeError<Infallible>>> + 'de,
+    DecodeError<Infallible>,
+>
+where
+    T: de::Deserialize<'de> + Send,
+{
+    let offsets = value_offsets(buf)?;
+    Ok(par_iter_from_offsets(buf, offsets, options))
+}
+
+/// Returns the offsets at which the concatenated values in a slice start.
+///
+/// The values aren't decoded, only their structure is checked. The offsets can be stored as an
+/// index, to skip the scan with [`par_iter_from_offsets`] later.
+///
+/// ```
+/// # use dasl::drisl::{self, de};
+/// let mut bytes = drisl::to_vec("foo").unwrap();
+/// bytes.extend(drisl::to_vec(&[1, 2]).unwrap());
+/// assert_eq!(de::value_offsets(&bytes).unwrap(), [0, 4]);
+/// ```
+pub fn value_offsets(buf: &[u8]) -> Result<Vec<usize>, DecodeError<Infallible>> {
+    let mut offsets = Vec::new();
+    let mut offset = 0;
+    while offset < buf.len() {
+        let rest = &buf[offset..];
+        let len = Scanner::default()
+            .value_len(rest)?
+            .ok_or_else(|| DecodeError::Eof {
+                name: "value",
+                expect: Len::new(1),
+            })?;
+        offsets.push(offset);
+        offset += len;
+    }
+    Ok(offsets)
+}
+
+/// Creates a parallel iterator over concatenated values in a slice, that start at the given
+/// offsets.
+///
+/// Each value extends to the next offset, the last one to the end of the slice. Values whose
+/// offsets are out of order or out of bounds, or that don't take up exactly the space until the
+/// next offset, fail to decode. Use [`DecodeOptions::default()`] for the default options.
+pub fn par_iter_from_offsets<'de, T, O>(
+    buf: &'de [u8],
+    offsets: O,
+    options: DecodeOptions,
+) -> impl IndexedParallelIterator<Item = Result<T, DecodeError<Infallible>>> + 'de
+where
+    T: de::Deserialize<'de> + Send,
+    O: AsRef<[usize]> + Send + Sync + 'de,
+{
+    let len = offsets.as_ref().len();
+    (0..len).into_par_iter().map(move |index| {
+        let offsets = offsets.as_ref();
+        let start = offsets[index];
+        let end = offsets.get(index + 1).copied().unwrap_or(buf.len());
+        let value = buf
+            .get(start..end)
+            .ok_or_else(|| DecodeError::Msg(format!("Invalid value offsets: {start}..{end}")))?;
+        from_slice_with(value, options)
+    })
+}
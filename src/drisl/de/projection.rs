@@ -0,0 +1,441 @@
+//! Decoding only selected parts of a value.
+
+use alloc::{
+    borrow::{Cow, ToOwned},
+    collections::BTreeMap,
+    string::String,
+};
+use core::{convert::Infallible, fmt};
+
+use serde::de::{
+    self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    value::{BorrowedStrDeserializer, StringDeserializer},
+};
+
+use super::{DecodeError, DecodeOptions, Deserializer};
+
+/// A set of paths to decode, everything else is skipped without being decoded into memory.
+///
+/// Paths are slash-separated like in [`Value::at`](crate::drisl::Value::at): segments are map keys
+/// for maps and indices for arrays, `*` matches every key or index. A path selects the whole value
+/// at its end.
+///
+/// The projection can decode into a [`Value`](crate::drisl::Value), which then only contains the
+/// selected paths, or into any type that only declares the fields it cares about. Array elements
+/// that aren't selected are left out, so indices in the result can shift. A path that continues
+/// below a scalar, like `title/deeper` for a string title, still selects the scalar.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::{drisl, drisl::{Value, de::Projection}};
+/// let bytes = drisl::to_vec(&drisl!({
+///     "title": "Hello",
+///     "body": "A long text",
+///     "author": { "name": "alice", "bio": "…" },
+///     "tags": [{ "name": "a", "id": 1 }, { "name": "b", "id": 2 }],
+/// }))
+/// .unwrap();
+///
+/// let projection = Projection::new(["title", "author/name", "tags/*/name"]);
+/// let value: Value = projection.from_slice(&bytes).unwrap();
+/// assert_eq!(
+///     value,
+///     drisl!({
+///         "title": "Hello",
+///         "author": { "name": "alice" },
+///         "tags": [{ "name": "a" }, { "name": "b" }],
+///     })
+/// );
+///
+/// #[derive(serde::Deserialize)]
+/// struct Title<'a> {
+///     title: &'a str,
+/// }
+/// let title: Title = projection.from_slice(&bytes).unwrap();
+/// assert_eq!(title.title, "Hello");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Projection {
+    root: Node,
+}
+
+/// A segment of the selected paths.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    /// Whether a path ends here, so that everything below is selected.
+    all: bool,
+    /// The selected children, including `*` which matches all of them.
+    children: BTreeMap<String, Node>,
+}
+
+/// The segment that matches every key or index.
+const WILDCARD: &str = "*";
+
+impl Node {
+    fn child(&self, key: &str) -> Option<&Node> {
+        self.children
+            .get(key)
+            .or_else(|| self.children.get(WILDCARD))
+    }
+
+    /// Looks up an array index without allocating its string form.
+    fn index_child(&self, mut index: usize) -> Option<&Node> {
+        let mut buf = [0; 20];
+        let mut start = buf.len();
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (index % 10) as u8;
+            index /= 10;
+            if index == 0 {
+                break;
+            }
+        }
+        self.child(core::str::from_utf8(&buf[start..]).expect("digits are ASCII"))
+    }
+}
+
+impl Projection {
+    /// Creates a projection that selects the given paths.
+    ///
+    /// An empty path selects the whole value.
+    pub fn new<I, S>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut root = Node::default();
+        for path in paths {
+            let path = path.as_ref();
+            let path = path.strip_prefix('/').unwrap_or(path);
+            let node = path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .fold(&mut root, |node, segment| {
+                    node.children.entry(segment.to_owned()).or_default()
+                });
+            node.all = true;
+        }
+        Self { root }
+    }
+
+    /// Decodes the selected paths from CBOR data in a slice.
+    pub fn from_slice<'a, T>(&self, buf: &'a [u8]) -> Result<T, DecodeError<Infallible>>
+    where
+        T: de::Deserialize<'a>,
+    {
+        self.from_slice_with(buf, DecodeOptions::default())
+    }
+
+    /// Decodes the selected paths from CBOR data in a slice, with the given options.
+    ///
+    /// The limits apply to the skipped parts as well.
+    pub fn from_slice_with<'a, T>(
+        &self,
+        buf: &'a [u8],
+        options: DecodeOptions,
+    ) -> Result<T, DecodeError<Infallible>>
+    where
+        T: de::Deserialize<'a>,
+    {
+        let mut deserializer = Deserializer::from_slice(buf).with_options(options);
+        let value = self.deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(value)
+    }
+
+    /// Decodes the selected paths with any self-describing deserializer.
+    pub fn deserialize<'de, D, T>(&self, deserializer: D) -> Result<T, D::Error>
+    where
+        D: de::Deserializer<'de>,
+        T: de::Deserialize<'de>,
+    {
+        Projected {
+            seed: core::marker::PhantomData::<T>,
+            node: &self.root,
+        }
+        .deserialize(deserializer)
+    }
+}
+
+/// A seed that only passes on the parts of the input selected by `node`.
+struct Projected<'p, S> {
+    seed: S,
+    node: &'p Node,
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Projected<'_, S> {
+    type Value = S::Value;
+
+    fn deserialize<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<S::Value, D::Error> {
+        if self.node.all {
+            return self.seed.deserialize(deserializer);
+        }
+        self.seed.deserialize(ProjectedDeserializer {
+            de: deserializer,
+            node: self.node,
+        })
+    }
+}
+
+/// Passes every request on to the wrapped deserializer, with a visitor that filters maps and
+/// arrays.
+struct ProjectedDeserializer<'p, D> {
+    de: D,
+    node: &'p Node,
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error> {
+                self.de.$method($($arg,)* ProjectedVisitor { visitor, node: self.node })
+            }
+        )*
+    };
+}
+
+impl<'de, D: de::Deserializer<'de>> de::Deserializer<'de> for ProjectedDeserializer<'_, D> {
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any();
+        deserialize_bool();
+        deserialize_i8();
+        deserialize_i16();
+        deserialize_i32();
+        deserialize_i64();
+        deserialize_i128();
+        deserialize_u8();
+        deserialize_u16();
+        deserialize_u32();
+        deserialize_u64();
+        deserialize_u128();
+        deserialize_f32();
+        deserialize_f64();
+        deserialize_char();
+        deserialize_str();
+        deserialize_string();
+        deserialize_bytes();
+        deserialize_byte_buf();
+        deserialize_option();
+        deserialize_unit();
+        deserialize_unit_struct(name: &'static str);
+        deserialize_newtype_struct(name: &'static str);
+        deserialize_seq();
+        deserialize_tuple(len: usize);
+        deserialize_tuple_struct(name: &'static str, len: usize);
+        deserialize_map();
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]);
+        deserialize_identifier();
+        deserialize_ignored_any();
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, D::Error> {
+        // Enums are passed on as a whole.
+        self.de.deserialize_enum(name, variants, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.de.is_human_readable()
+    }
+}
+
+/// Passes everything on to the wrapped visitor, but only the selected map entries and array
+/// elements.
+struct ProjectedVisitor<'p, V> {
+    visitor: V,
+    node: &'p Node,
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty);)*) => {
+        $(
+            fn $method<E: de::Error>(self, v: $ty) -> Result<V::Value, E> {
+                self.visitor.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for ProjectedVisitor<'_, V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool);
+        visit_i8(i8);
+        visit_i16(i16);
+        visit_i32(i32);
+        visit_i64(i64);
+        visit_i128(i128);
+        visit_u8(u8);
+        visit_u16(u16);
+        visit_u32(u32);
+        visit_u64(u64);
+        visit_u128(u128);
+        visit_f32(f32);
+        visit_f64(f64);
+        visit_char(char);
+        visit_str(&str);
+        visit_borrowed_str(&'de str);
+        visit_string(String);
+        visit_bytes(&[u8]);
+        visit_borrowed_bytes(&'de [u8]);
+        visit_byte_buf(alloc::vec::Vec<u8>);
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<V::Value, E> {
+        self.visitor.visit_none()
+    }
+
+    fn visit_some<D: de::Deserializer<'de>>(self, deserializer: D) -> Result<V::Value, D::Error> {
+        self.visitor.visit_some(ProjectedDeserializer {
+            de: deserializer,
+            node: self.node,
+        })
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<V::Value, E> {
+        self.visitor.visit_unit()
+    }
+
+    /// Newtype structs are CIDs, which are passed on as a whole.
+    fn visit_newtype_struct<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<V::Value, D::Error> {
+        self.visitor.visit_newtype_struct(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_seq(ProjectedSeq {
+            seq,
+            node: self.node,
+            index: 0,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_map(ProjectedMap {
+            map,
+            node: self.node,
+            next: None,
+        })
+    }
+
+    fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<V::Value, A::Error> {
+        self.visitor.visit_enum(data)
+    }
+}
+
+struct ProjectedSeq<'p, A> {
+    seq: A,
+    node: &'p Node,
+    index: usize,
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for ProjectedSeq<'_, A> {
+    type Error = A::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, A::Error> {
+        loop {
+            let index = self.index;
+            self.index += 1;
+            match self.node.index_child(index) {
+                Some(node) => return self.seq.next_element_seed(Projected { seed, node }),
+                None => {
+                    if self.seq.next_element::<IgnoredAny>()?.is_none() {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct ProjectedMap<'p, A> {
+    map: A,
+    node: &'p Node,
+    /// The node of the entry whose key was returned last.
+    next: Option<&'p Node>,
+}
+
+impl<'de, 'p, A: MapAccess<'de>> MapAccess<'de> for ProjectedMap<'p, A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        while let Some(key) = self.map.next_key_seed(KeySeed)? {
+            match self.node.child(&key) {
+                Some(node) => {
+                    self.next = Some(node);
+                    return match key {
+                        Cow::Borrowed(key) => seed.deserialize(BorrowedStrDeserializer::new(key)),
+                        Cow::Owned(key) => seed.deserialize(StringDeserializer::new(key)),
+                    }
+                    .map(Some);
+                }
+                None => {
+                    self.map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, A::Error> {
+        let node = self
+            .next
+            .take()
+            .ok_or_else(|| de::Error::custom("Map value without a key"))?;
+        self.map.next_value_seed(Projected { seed, node })
+    }
+}
+
+/// Decodes a map key, borrowing it if possible.
+struct KeySeed;
+
+impl<'de> DeserializeSeed<'de> for KeySeed {
+    type Value = Cow<'de, str>;
+
+    fn deserialize<D: de::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeySeed {
+    type Value = Cow<'de, str>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string map key")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(Cow::Borrowed(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(v.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Cow::Owned(v))
+    }
+}
@@ -0,0 +1,168 @@
+//! Sources that a [`Deserializer`](super::Deserializer) reads from.
+
+use core::convert::Infallible;
+
+/// Nesting limit of the readers, arrays and maps nested deeper fail with
+/// [`DecodeError::DepthOverflow`](crate::drisl::DecodeError::DepthOverflow).
+pub(crate) const DEPTH_LIMIT: usize = 256;
+
+/// A source of CBOR data.
+pub trait Read<'de> {
+    /// The error of reading from the source.
+    type Error: core::error::Error + 'static;
+
+    /// Returns at least `want` bytes, unless the source ends before. Returning more is fine.
+    ///
+    /// The bytes stay available until they are consumed with [`advance`](Self::advance).
+    fn fill<'short>(&'short mut self, want: usize) -> Result<Reference<'de, 'short>, Self::Error>;
+
+    /// Consumes `n` bytes returned by the last [`fill`](Self::fill).
+    fn advance(&mut self, n: usize);
+
+    /// Called before decoding a nested array or map, returns `false` to stop at that depth.
+    #[inline]
+    fn step_in(&mut self) -> bool {
+        true
+    }
+
+    /// Called after decoding a nested array or map.
+    #[inline]
+    fn step_out(&mut self) {}
+}
+
+impl<'de, R: Read<'de> + ?Sized> Read<'de> for &mut R {
+    type Error = R::Error;
+
+    #[inline]
+    fn fill<'short>(&'short mut self, want: usize) -> Result<Reference<'de, 'short>, Self::Error> {
+        (**self).fill(want)
+    }
+
+    #[inline]
+    fn advance(&mut self, n: usize) {
+        (**self).advance(n)
+    }
+
+    #[inline]
+    fn step_in(&mut self) -> bool {
+        (**self).step_in()
+    }
+
+    #[inline]
+    fn step_out(&mut self) {
+        (**self).step_out()
+    }
+}
+
+/// Bytes returned by [`Read::fill`].
+#[derive(Debug)]
+pub enum Reference<'de, 'short> {
+    /// Bytes that live as long as the source, which can be borrowed by the decoded value.
+    Long(&'de [u8]),
+    /// Bytes that are only valid until the next read.
+    Short(&'short [u8]),
+}
+
+/// Reads from a slice.
+#[derive(Debug)]
+pub struct SliceReader<'a> {
+    buf: &'a [u8],
+    limit: usize,
+}
+
+impl SliceReader<'_> {
+    /// Creates a reader of `buf`.
+    pub fn new(buf: &[u8]) -> SliceReader<'_> {
+        SliceReader {
+            buf,
+            limit: DEPTH_LIMIT,
+        }
+    }
+}
+
+impl<'de> Read<'de> for SliceReader<'de> {
+    type Error = Infallible;
+
+    #[inline]
+    fn fill<'b>(&'b mut self, want: usize) -> Result<Reference<'de, 'b>, Self::Error> {
+        let len = core::cmp::min(self.buf.len(), want);
+        Ok(Reference::Long(&self.buf[..len]))
+    }
+
+    #[inline]
+    fn advance(&mut self, n: usize) {
+        let len = core::cmp::min(self.buf.len(), n);
+        self.buf = &self.buf[len..];
+    }
+
+    #[inline]
+    fn step_in(&mut self) -> bool {
+        step_in(&mut self.limit)
+    }
+
+    #[inline]
+    fn step_out(&mut self) {
+        self.limit += 1;
+    }
+}
+
+/// Reads from a [`std::io::BufRead`].
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoReader<R> {
+    reader: R,
+    limit: usize,
+}
+
+#[cfg(feature = "std")]
+impl<R> IoReader<R> {
+    /// Creates a reader of `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            limit: DEPTH_LIMIT,
+        }
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, R: std::io::BufRead> Read<'de> for IoReader<R> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn fill<'b>(&'b mut self, _want: usize) -> Result<Reference<'de, 'b>, Self::Error> {
+        Ok(Reference::Short(self.reader.fill_buf()?))
+    }
+
+    #[inline]
+    fn advance(&mut self, n: usize) {
+        self.reader.consume(n);
+    }
+
+    #[inline]
+    fn step_in(&mut self) -> bool {
+        step_in(&mut self.limit)
+    }
+
+    #[inline]
+    fn step_out(&mut self) {
+        self.limit += 1;
+    }
+}
+
+/// Takes one level off the nesting limit, returns `false` if there's none left.
+#[inline]
+pub(crate) fn step_in(limit: &mut usize) -> bool {
+    match limit.checked_sub(1) {
+        Some(left) => {
+            *limit = left;
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,77 @@
+//! Decoding from [`Bytes`], sharing its buffer with the decoded byte strings.
+
+use std::{cell::RefCell, convert::Infallible};
+
+use bytes::Bytes;
+use serde::de;
+
+use super::{DecodeOptions, from_slice_with};
+use crate::drisl::error::DecodeError;
+
+std::thread_local! {
+    /// The input of the innermost `from_bytes` call on this thread.
+    static SOURCE: RefCell<Option<Bytes>> = const { RefCell::new(None) };
+}
+
+/// Decodes a value from CBOR data in [`Bytes`].
+///
+/// This is the same as [`from_slice`](super::from_slice), except that fields using the
+/// [`shared_bytes`](crate::drisl::serde_helpers::shared_bytes) adapter get a `Bytes` that
+/// shares the buffer of `input`, instead of a copy. Other fields can still borrow from `input`.
+///
+/// ```
+/// # use bytes::Bytes;
+/// # use dasl::drisl::{self, serde_helpers};
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Packet {
+///     id: u32,
+///     #[serde(with = "serde_helpers::shared_bytes")]
+///     payload: Bytes,
+/// }
+///
+/// let input = drisl::to_bytes(&Packet { id: 1, payload: Bytes::from(vec![7; 1000]) }).unwrap();
+/// let packet: Packet = drisl::from_bytes(&input).unwrap();
+/// assert_eq!(packet.payload, [7; 1000][..]);
+/// // The payload points into the input, nothing was copied.
+/// assert!(input.as_ptr_range().contains(&packet.payload.as_ptr()));
+/// ```
+pub fn from_bytes<'a, T>(input: &'a Bytes) -> Result<T, DecodeError<Infallible>>
+where
+    T: de::Deserialize<'a>,
+{
+    from_bytes_with(input, DecodeOptions::default())
+}
+
+/// Decodes a value from CBOR data in [`Bytes`], with the given options.
+///
+/// See [`from_bytes`].
+pub fn from_bytes_with<'a, T>(
+    input: &'a Bytes,
+    options: DecodeOptions,
+) -> Result<T, DecodeError<Infallible>>
+where
+    T: de::Deserialize<'a>,
+{
+    let previous = SOURCE.with(|source| source.replace(Some(input.clone())));
+    // Restore the outer input, also if decoding panics.
+    let _guard = scopeguard::guard(previous, |previous| {
+        SOURCE.with(|source| source.replace(previous));
+    });
+    from_slice_with(input, options)
+}
+
+/// Returns the bytes as a `Bytes` sharing the input of [`from_bytes`], or a copy if they aren't
+/// part of it.
+pub(crate) fn shared(bytes: &[u8]) -> Bytes {
+    SOURCE.with(|source| match &*source.borrow() {
+        Some(source) if contains(source, bytes) => source.slice_ref(bytes),
+        _ => Bytes::copy_from_slice(bytes),
+    })
+}
+
+/// Returns whether `bytes` lies within `source`.
+fn contains(source: &[u8], bytes: &[u8]) -> bool {
+    let source = source.as_ptr_range();
+    let bytes = bytes.as_ptr_range();
+    source.start <= bytes.start && bytes.end <= source.end
+}
@@ -0,0 +1,181 @@
+//! Push-style decoding of values from chunks of data.
+
+use alloc::vec::Vec;
+use core::convert::Infallible;
+
+use serde::de;
+
+use super::{DecodeOptions, from_slice_with};
+use crate::drisl::{
+    error::{DecodeError, Len},
+    links::Scanner,
+};
+
+/// Decodes a stream of concatenated values from chunks of data, without doing any IO itself.
+///
+/// Data is pushed in with [`feed`](Self::feed) as it arrives, e.g. from a QUIC stream or a custom
+/// event loop, and values are taken out with [`decode`](Self::decode) once they are complete.
+/// Incomplete values are buffered, [`needed`](Self::needed) tells how many more bytes are needed
+/// at least to complete the next one.
+///
+/// Malformed data is detected as soon as it's fed, even if the value isn't complete yet. After
+/// such an error the stream can't be resynchronized, calling [`decode`](Self::decode) again
+/// returns the same error.
+///
+/// ```
+/// # use dasl::drisl::{self, de::StreamDecoder};
+/// let mut bytes = drisl::to_vec("foo").unwrap();
+/// bytes.extend(drisl::to_vec(&[1, 2, 3]).unwrap());
+///
+/// let mut decoder = StreamDecoder::new();
+/// decoder.feed(&bytes[..2]);
+/// assert_eq!(decoder.decode::<String>().unwrap(), None);
+/// assert_eq!(decoder.needed().unwrap(), 2);
+///
+/// decoder.feed(&bytes[2..]);
+/// assert_eq!(decoder.decode::<String>().unwrap().as_deref(), Some("foo"));
+/// assert_eq!(decoder.decode::<Vec<u8>>().unwrap(), Some(vec![1, 2, 3]));
+/// assert_eq!(decoder.decode::<String>().unwrap(), None);
+/// decoder.finish().unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StreamDecoder {
+    /// Data fed so far, followed by space for the next read, see `spare`.
+    buf: Vec<u8>,
+    /// Start of the data that hasn't been returned yet.
+    start: usize,
+    /// End of the data.
+    filled: usize,
+    /// Scans the next value, relative to `start`.
+    scanner: Scanner,
+    options: DecodeOptions,
+}
+
+impl StreamDecoder {
+    /// Creates a new decoder with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the options used for decoding.
+    ///
+    /// [`DecodeOptions::max_size`] also limits how much of a value is buffered before it's
+    /// complete.
+    pub fn with_options(mut self, options: DecodeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Appends a chunk of data.
+    pub fn feed(&mut self, data: &[u8]) {
+        self.compact();
+        self.buf.truncate(self.filled);
+        self.buf.extend_from_slice(data);
+        self.filled = self.buf.len();
+    }
+
+    /// Returns the number of bytes that were fed but not returned as a value yet.
+    pub fn buffered(&self) -> usize {
+        self.filled - self.start
+    }
+
+    /// Returns how many more bytes are needed at least to complete the next value.
+    ///
+    /// Returns `0` if a value is complete already, and `1` if nothing is buffered. The actual
+    /// number of bytes can be higher, as the lengths of nested items are only known once their
+    /// headers were fed.
+    pub fn needed(&mut self) -> Result<usize, DecodeError<Infallible>> {
+        match self.scan()? {
+            Some(_) => Ok(0),
+            None => Ok(usize::try_from(self.scanner.missing()).unwrap_or(usize::MAX)),
+        }
+    }
+
+    /// Returns the encoding of the next value, or `None` if it isn't complete yet.
+    ///
+    /// The value is checked to be well-formed, but not decoded.
+    pub fn next_encoded(&mut self) -> Result<Option<&[u8]>, DecodeError<Infallible>> {
+        let Some(len) = self.scan()? else {
+            return Ok(None);
+        };
+        let value = &self.buf[self.start..self.start + len];
+        self.start += len;
+        self.scanner = Scanner::default();
+        Ok(Some(value))
+    }
+
+    /// Decodes the next value, or returns `None` if it isn't complete yet.
+    ///
+    /// Values that fail to deserialize into `T` are skipped, the next call continues with the
+    /// value after it.
+    pub fn decode<'a, T>(&'a mut self) -> Result<Option<T>, DecodeError<Infallible>>
+    where
+        T: de::Deserialize<'a>,
+    {
+        let options = self.options;
+        match self.next_encoded()? {
+            Some(value) => from_slice_with(value, options).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Checks that the data ended after a complete value, nothing is buffered.
+    pub fn finish(&self) -> Result<(), DecodeError<Infallible>> {
+        match self.buffered() {
+            0 => Ok(()),
+            _ => Err(DecodeError::Eof {
+                name: "value",
+                expect: Len::new(1),
+            }),
+        }
+    }
+
+    /// Returns space at the end of the buffer to read at least `len` bytes into, for readers
+    /// that fill a buffer, see [`commit`](Self::commit).
+    #[cfg(feature = "tokio")]
+    pub(crate) fn spare(&mut self, len: usize) -> &mut [u8] {
+        self.compact();
+        if self.buf.len() - self.filled < len {
+            self.buf.resize(self.filled + len, 0);
+        }
+        &mut self.buf[self.filled..]
+    }
+
+    /// Marks `len` bytes of the space returned by [`spare`](Self::spare) as fed.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn commit(&mut self, len: usize) {
+        self.filled += len;
+    }
+
+    /// Scans the buffered data, returns the length of the next value if it's complete.
+    fn scan(&mut self) -> Result<Option<usize>, DecodeError<Infallible>> {
+        let checkpoint = self.scanner.clone();
+        let result = self.scanner.value_len(&self.buf[self.start..self.filled]);
+        let limit = self.options.max_size;
+        let too_large = match result {
+            Ok(Some(len)) => len > limit,
+            Ok(None) => {
+                let missing = usize::try_from(self.scanner.missing()).unwrap_or(usize::MAX);
+                self.buffered().saturating_add(missing) > limit
+            }
+            Err(_) => false,
+        };
+        if too_large {
+            self.scanner = checkpoint;
+            return Err(DecodeError::SizeLimit { limit });
+        }
+        if result.is_err() {
+            self.scanner = checkpoint;
+        }
+        result
+    }
+
+    /// Drops the data of values that were returned already.
+    fn compact(&mut self) {
+        if self.start > 0 {
+            self.buf.copy_within(self.start..self.filled, 0);
+            self.filled -= self.start;
+            self.start = 0;
+        }
+    }
+}
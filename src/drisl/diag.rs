@@ -0,0 +1,498 @@
+//! CBOR diagnostic notation, as described in [RFC 8949 section 8](https://www.rfc-editor.org/rfc/rfc8949#section-8).
+//!
+//! Diagnostic notation is a human readable form of CBOR, similar to JSON. Byte strings are written
+//! as `h'0001'` and CIDs as their tag with the byte string, e.g. `42(h'0001551220…')`. Map
+//! entries are written in the order they are encoded in.
+//!
+//! [`from_str`] parses diagnostic notation back into a [`Value`], which makes it easy to write test
+//! data by hand.
+//!
+//! ```
+//! # use dasl::{drisl, drisl::diag};
+//! let value = drisl!({ "bb": [1.5, null], "a": vec![0u8, 1] });
+//! assert_eq!(diag::to_string(&value), r#"{"a": h'0001', "bb": [1.5, null]}"#);
+//!
+//! let bytes = drisl::to_vec(&value).unwrap();
+//! assert_eq!(diag::slice_to_string(&bytes).unwrap(), diag::to_string(&value));
+//! ```
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::Infallible, fmt::Write, mem};
+
+use thiserror::Error;
+
+use super::{CBOR_TAGS_CID, DecodeError, Value, de::from_slice};
+use crate::cid::Cid;
+
+/// Options for writing diagnostic notation, see [`to_string_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DiagOptions {
+    pub(crate) cid_strings: bool,
+}
+
+impl DiagOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether CIDs are written as strings, e.g. `42("bafkrei…")`, instead of bytes.
+    ///
+    /// This isn't standard diagnostic notation, but easier to compare with CIDs from elsewhere.
+    pub fn cid_strings(mut self, cid_strings: bool) -> Self {
+        self.cid_strings = cid_strings;
+        self
+    }
+}
+
+/// Writes a value in diagnostic notation.
+pub fn to_string(value: &Value) -> String {
+    to_string_with(value, DiagOptions::default())
+}
+
+/// Writes a value in diagnostic notation with the given options.
+///
+/// ```
+/// # use dasl::{cid::{Cid, Codec}, drisl, drisl::diag::{self, DiagOptions}};
+/// let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+/// let options = DiagOptions::new().cid_strings(true);
+/// assert_eq!(
+///     diag::to_string_with(&drisl!([cid]), options),
+///     format!("[42(\"{cid}\")]"),
+/// );
+/// ```
+pub fn to_string_with(value: &Value, options: DiagOptions) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, options);
+    out
+}
+
+/// Decodes DRISL data and writes it in diagnostic notation.
+pub fn slice_to_string(bytes: &[u8]) -> Result<String, DecodeError<Infallible>> {
+    slice_to_string_with(bytes, DiagOptions::default())
+}
+
+/// Decodes DRISL data and writes it in diagnostic notation with the given options.
+pub fn slice_to_string_with(
+    bytes: &[u8],
+    options: DiagOptions,
+) -> Result<String, DecodeError<Infallible>> {
+    Ok(to_string_with(&from_slice(bytes)?, options))
+}
+
+fn write_value(out: &mut String, value: &Value, options: DiagOptions) {
+    match value {
+        Value::Integer(int) => write!(out, "{int}").unwrap(),
+        Value::Bytes(bytes) => write_bytes(out, bytes),
+        Value::Float(float) => write_float(out, *float),
+        Value::Text(text) => write_text(out, text),
+        Value::Bool(bool) => write!(out, "{bool}").unwrap(),
+        Value::Null => out.push_str("null"),
+        Value::Cid(cid) => {
+            write!(out, "{CBOR_TAGS_CID}(").unwrap();
+            if options.cid_strings {
+                write_text(out, &cid.to_string());
+            } else {
+                // The byte string of a CID is prefixed with the identity multibase.
+                out.push_str("h'00");
+                write_hex(out, cid.as_bytes());
+                out.push('\'');
+            }
+            out.push(')');
+        }
+        Value::Array(array) => {
+            out.push('[');
+            for (i, item) in array.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_value(out, item, options);
+            }
+            out.push(']');
+        }
+        Value::Map(map) => {
+            // Keys are encoded sorted by length first, unlike the order of the `BTreeMap`.
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| (a.len(), a).cmp(&(b.len(), b)));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_text(out, key);
+                out.push_str(": ");
+                write_value(out, value, options);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_bytes(out: &mut String, bytes: &[u8]) {
+    out.push_str("h'");
+    write_hex(out, bytes);
+    out.push('\'');
+}
+
+fn write_hex(out: &mut String, bytes: &[u8]) {
+    for byte in bytes {
+        write!(out, "{byte:02x}").unwrap();
+    }
+}
+
+fn write_float(out: &mut String, float: f64) {
+    if float.is_nan() {
+        out.push_str("NaN");
+    } else if float.is_infinite() {
+        out.push_str(if float > 0.0 { "Infinity" } else { "-Infinity" });
+    } else {
+        // `Debug` always includes a fraction or exponent, so floats can't be mistaken for
+        // integers.
+        write!(out, "{float:?}").unwrap();
+    }
+}
+
+fn write_text(out: &mut String, text: &str) {
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => write!(out, "\\u{:04x}", u32::from(c)).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// What went wrong when parsing diagnostic notation, see [`DiagError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum DiagErrorKind {
+    #[error("Unexpected end of input")]
+    UnexpectedEnd,
+    #[error("Unexpected character {0:?}")]
+    UnexpectedChar(char),
+    #[error("Invalid number")]
+    InvalidNumber,
+    #[error("Invalid escape sequence")]
+    InvalidEscape,
+    #[error("Invalid hex digit")]
+    InvalidHex,
+    #[error("Unsupported tag {0}")]
+    UnsupportedTag(u64),
+    #[error("Invalid CID")]
+    InvalidCid,
+    #[error("Map key isn't a string")]
+    NonStringKey,
+    #[error("Duplicate map key")]
+    DuplicateKey,
+    #[error("Input continues after the value")]
+    TrailingData,
+}
+
+/// Returned by [`from_str`] for invalid diagnostic notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("{kind} at offset {offset}")]
+pub struct DiagError {
+    /// Position in the input in bytes.
+    pub offset: usize,
+    /// What went wrong.
+    pub kind: DiagErrorKind,
+}
+
+/// Parses diagnostic notation into a value.
+///
+/// Supported are integers, floats (including `NaN`, `Infinity` and `-Infinity`), strings with
+/// JSON escapes, byte strings in hex (`h'00ff'`), arrays, maps with string keys, `true`, `false`,
+/// `null` and CIDs with tag 42, either as byte string or in their string form as written with
+/// [`DiagOptions::cid_strings`]. A number is a float if it has a fraction or an exponent.
+///
+/// ```
+/// # use dasl::{drisl, drisl::diag};
+/// let value = diag::from_str(r#"{"a": [1, h'ff'], "b": -2.5}"#).unwrap();
+/// assert_eq!(value, drisl!({ "a": [1, vec![0xff_u8]], "b": -2.5 }));
+/// ```
+pub fn from_str(input: &str) -> Result<Value, DiagError> {
+    let mut parser = Parser { input, pos: 0 };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.pos != input.len() {
+        return Err(parser.error(DiagErrorKind::TrailingData));
+    }
+    Ok(value)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, kind: DiagErrorKind) -> DiagError {
+        DiagError {
+            offset: self.pos,
+            kind,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn next(&mut self) -> Result<char, DiagError> {
+        let c = self
+            .peek()
+            .ok_or(self.error(DiagErrorKind::UnexpectedEnd))?;
+        self.pos += c.len_utf8();
+        Ok(c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), DiagError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += c.len_utf8();
+                Ok(())
+            }
+            Some(c) => Err(self.error(DiagErrorKind::UnexpectedChar(c))),
+            None => Err(self.error(DiagErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Consumes `word` if the input continues with it.
+    fn eat(&mut self, word: &str) -> bool {
+        let matches = self.input[self.pos..].starts_with(word);
+        if matches {
+            self.pos += word.len();
+        }
+        matches
+    }
+
+    fn value(&mut self) -> Result<Value, DiagError> {
+        self.skip_whitespace();
+        let Some(c) = self.peek() else {
+            return Err(self.error(DiagErrorKind::UnexpectedEnd));
+        };
+        match c {
+            '[' => self.array(),
+            '{' => self.map(),
+            '"' => self.text().map(Value::Text),
+            'h' if self.eat("h'") => self.bytes().map(Value::Bytes),
+            _ if self.eat("true") => Ok(Value::Bool(true)),
+            _ if self.eat("false") => Ok(Value::Bool(false)),
+            _ if self.eat("null") => Ok(Value::Null),
+            _ if self.eat("NaN") => Ok(Value::Float(f64::NAN)),
+            _ if self.eat("Infinity") => Ok(Value::Float(f64::INFINITY)),
+            _ if self.eat("-Infinity") => Ok(Value::Float(f64::NEG_INFINITY)),
+            '-' | '0'..='9' => self.number(),
+            c => Err(self.error(DiagErrorKind::UnexpectedChar(c))),
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, DiagError> {
+        self.expect('[')?;
+        let mut array = Vec::new();
+        self.skip_whitespace();
+        if self.eat("]") {
+            return Ok(Value::Array(array));
+        }
+        loop {
+            array.push(self.value()?);
+            self.skip_whitespace();
+            if self.eat("]") {
+                return Ok(Value::Array(array));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn map(&mut self) -> Result<Value, DiagError> {
+        self.expect('{')?;
+        let mut map = BTreeMap::new();
+        self.skip_whitespace();
+        if self.eat("}") {
+            return Ok(Value::Map(map));
+        }
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            let Value::Text(key) = &mut self.value()? else {
+                self.pos = start;
+                return Err(self.error(DiagErrorKind::NonStringKey));
+            };
+            let key = mem::take(key);
+            self.skip_whitespace();
+            self.expect(':')?;
+            let value = self.value()?;
+            if map.insert(key, value).is_some() {
+                self.pos = start;
+                return Err(self.error(DiagErrorKind::DuplicateKey));
+            }
+            self.skip_whitespace();
+            if self.eat("}") {
+                return Ok(Value::Map(map));
+            }
+            self.expect(',')?;
+        }
+    }
+
+    fn text(&mut self) -> Result<String, DiagError> {
+        self.expect('"')?;
+        let mut text = String::new();
+        loop {
+            match self.next()? {
+                '"' => return Ok(text),
+                '\\' => {
+                    let start = self.pos;
+                    let c = match self.next()? {
+                        '"' => '"',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape()?,
+                        _ => {
+                            self.pos = start;
+                            return Err(self.error(DiagErrorKind::InvalidEscape));
+                        }
+                    };
+                    text.push(c);
+                }
+                c => text.push(c),
+            }
+        }
+    }
+
+    /// Parses the digits of a `\u` escape, which may be followed by a second one for surrogates.
+    fn unicode_escape(&mut self) -> Result<char, DiagError> {
+        let start = self.pos;
+        let high = self.hex4()?;
+        let code = if (0xd800..0xdc00).contains(&high) {
+            if !self.eat("\\u") {
+                return Err(self.error(DiagErrorKind::InvalidEscape));
+            }
+            let low = self.hex4()?;
+            if !(0xdc00..0xe000).contains(&low) {
+                return Err(self.error(DiagErrorKind::InvalidEscape));
+            }
+            0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or(DiagError {
+            offset: start,
+            kind: DiagErrorKind::InvalidEscape,
+        })
+    }
+
+    fn hex4(&mut self) -> Result<u32, DiagError> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or(self.error(DiagErrorKind::InvalidEscape))?;
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).expect("checked to be hex digits"))
+    }
+
+    /// Parses the rest of a byte string after `h'`, whitespace between digits is ignored.
+    fn bytes(&mut self) -> Result<Vec<u8>, DiagError> {
+        let mut bytes = Vec::new();
+        let mut high = None;
+        loop {
+            let start = self.pos;
+            let c = self.next()?;
+            let nibble = match c {
+                '\'' if high.is_none() => return Ok(bytes),
+                c if c.is_whitespace() => continue,
+                c => c.to_digit(16).ok_or(DiagError {
+                    offset: start,
+                    kind: DiagErrorKind::InvalidHex,
+                })? as u8,
+            };
+            match high.take() {
+                Some(high) => bytes.push(high << 4 | nibble),
+                None => high = Some(nibble),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, DiagError> {
+        let start = self.pos;
+        self.eat("-");
+        let digits = |parser: &mut Self| {
+            let rest = &parser.input[parser.pos..];
+            let len = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            parser.pos += len;
+            len
+        };
+        if digits(self) == 0 {
+            return Err(self.error(DiagErrorKind::InvalidNumber));
+        }
+        let mut is_float = false;
+        if self.eat(".") {
+            is_float = true;
+            if digits(self) == 0 {
+                return Err(self.error(DiagErrorKind::InvalidNumber));
+            }
+        }
+        if self.eat("e") || self.eat("E") {
+            is_float = true;
+            let _ = self.eat("+") || self.eat("-");
+            if digits(self) == 0 {
+                return Err(self.error(DiagErrorKind::InvalidNumber));
+            }
+        }
+        let number = &self.input[start..self.pos];
+        let invalid = DiagError {
+            offset: start,
+            kind: DiagErrorKind::InvalidNumber,
+        };
+        if is_float {
+            return number.parse().map(Value::Float).map_err(|_| invalid);
+        }
+        let int = number.parse::<i128>().map_err(|_| invalid)?;
+        self.skip_whitespace();
+        if self.peek() != Some('(') {
+            return Ok(Value::Integer(int));
+        }
+        if int != i128::from(CBOR_TAGS_CID) {
+            return Err(DiagError {
+                offset: start,
+                kind: DiagErrorKind::UnsupportedTag(u64::try_from(int).unwrap_or(u64::MAX)),
+            });
+        }
+        self.expect('(')?;
+        self.skip_whitespace();
+        let content = self.pos;
+        let cid = match &self.value()? {
+            Value::Bytes(bytes) => Cid::from_bytes(bytes).ok(),
+            Value::Text(text) => text.parse().ok(),
+            _ => None,
+        };
+        let Some(cid) = cid else {
+            return Err(DiagError {
+                offset: content,
+                kind: DiagErrorKind::InvalidCid,
+            });
+        };
+        self.skip_whitespace();
+        self.expect(')')?;
+        Ok(Value::Cid(cid))
+    }
+}
@@ -0,0 +1,280 @@
+//! Structural differences between [`Value`]s.
+
+use alloc::{string::String, vec, vec::Vec};
+use core::{cmp::Ordering, fmt};
+
+use thiserror::Error;
+
+use super::{
+    Value, diag,
+    path::{PathSegment, format_path},
+};
+
+/// A difference between two values, see [`diff`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// The path to the changed value, empty for the values themselves.
+    pub path: Vec<PathSegment>,
+    /// What changed at the path.
+    pub kind: ChangeKind,
+}
+
+/// What changed at the path of a [`Change`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    /// A map entry or array element that is only in the new value.
+    Added(Value),
+    /// A map entry or array element that is only in the old value.
+    Removed(Value),
+    /// A value that is different in the new value.
+    Replaced {
+        /// The value in the old value.
+        old: Value,
+        /// The value in the new value.
+        new: Value,
+    },
+}
+
+/// Writes the change as a line like `~ entries[1].size: 1 -> 2`, prefixed with `+` for added,
+/// `-` for removed and `~` for replaced values. The values are in diagnostic notation.
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = display_path(&self.path);
+        match &self.kind {
+            ChangeKind::Added(value) => write!(f, "+ {path}: {}", diag::to_string(value)),
+            ChangeKind::Removed(value) => write!(f, "- {path}: {}", diag::to_string(value)),
+            ChangeKind::Replaced { old, new } => write!(
+                f,
+                "~ {path}: {} -> {}",
+                diag::to_string(old),
+                diag::to_string(new)
+            ),
+        }
+    }
+}
+
+/// Returns the differences between two values.
+///
+/// Maps are compared entry by entry and arrays element by element, by index. Any other values
+/// that aren't equal, including values of different kinds, are replaced as a whole.
+///
+/// Changes are ordered by path, with map keys in the order of [`Value::Map`], except that array
+/// elements removed from the end are listed from the last one. This way the changes can be applied
+/// in order, see [`apply_changes`].
+///
+/// ```
+/// # use dasl::{drisl, drisl::{ChangeKind, PathSegment}};
+/// let old = drisl!({ "name": "a", "tags": ["x", "y"] });
+/// let new = drisl!({ "name": "b", "tags": ["x"] });
+///
+/// let changes = drisl::diff(&old, &new);
+/// assert_eq!(changes.len(), 2);
+/// assert_eq!(changes[0].path, [PathSegment::Key("name".into())]);
+/// assert_eq!(
+///     changes[0].kind,
+///     ChangeKind::Replaced { old: drisl!("a"), new: drisl!("b") }
+/// );
+/// assert_eq!(changes[1].to_string(), r#"- tags[1]: "y""#);
+/// ```
+pub fn diff(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_values(&mut vec![], old, new, &mut changes);
+    changes
+}
+
+fn diff_values(path: &mut Vec<PathSegment>, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Map(old), Value::Map(new)) => {
+            let mut old = old.iter().peekable();
+            let mut new = new.iter().peekable();
+            loop {
+                let order = match (old.peek(), new.peek()) {
+                    (Some((old_key, _)), Some((new_key, _))) => old_key.cmp(new_key),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => break,
+                };
+                match order {
+                    Ordering::Less => {
+                        let (key, value) = old.next().expect("peeked");
+                        path.push(PathSegment::Key(key.clone()));
+                        push(path, ChangeKind::Removed(value.clone()), changes);
+                    }
+                    Ordering::Greater => {
+                        let (key, value) = new.next().expect("peeked");
+                        path.push(PathSegment::Key(key.clone()));
+                        push(path, ChangeKind::Added(value.clone()), changes);
+                    }
+                    Ordering::Equal => {
+                        let (key, old_value) = old.next().expect("peeked");
+                        let (_, new_value) = new.next().expect("peeked");
+                        path.push(PathSegment::Key(key.clone()));
+                        diff_values(path, old_value, new_value, changes);
+                    }
+                }
+                path.pop();
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            let common = old.len().min(new.len());
+            for (index, (old, new)) in old.iter().zip(new).enumerate() {
+                path.push(PathSegment::Index(index));
+                diff_values(path, old, new, changes);
+                path.pop();
+            }
+            for (index, new) in new.iter().enumerate().skip(common) {
+                path.push(PathSegment::Index(index));
+                push(path, ChangeKind::Added(new.clone()), changes);
+                path.pop();
+            }
+            for (index, old) in old.iter().enumerate().skip(common).rev() {
+                path.push(PathSegment::Index(index));
+                push(path, ChangeKind::Removed(old.clone()), changes);
+                path.pop();
+            }
+        }
+        _ if old != new => push(
+            path,
+            ChangeKind::Replaced {
+                old: old.clone(),
+                new: new.clone(),
+            },
+            changes,
+        ),
+        _ => {}
+    }
+}
+
+fn push(path: &[PathSegment], kind: ChangeKind, changes: &mut Vec<Change>) {
+    changes.push(Change {
+        path: path.to_vec(),
+        kind,
+    });
+}
+
+/// Formats a path for display, with `.` for the empty path.
+fn display_path(path: &[PathSegment]) -> String {
+    let formatted = format_path(path);
+    if formatted.is_empty() {
+        ".".into()
+    } else {
+        formatted
+    }
+}
+
+/// Returned by [`apply_changes`] if a change doesn't fit the value.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ApplyError {
+    /// The path of a change, or the map or array it is in, doesn't exist.
+    #[error("No value at {path}")]
+    Missing { path: String },
+    /// The value at the path isn't the old value of the change, or an added value already exists.
+    #[error("Conflicting value at {path}")]
+    Conflict { path: String },
+}
+
+/// Applies changes, as returned by [`diff`], to a value in order.
+///
+/// Every change is checked against the value: removed and replaced values have to be equal to the
+/// old value of the change, and added map entries must not exist yet. Added array elements are
+/// inserted at their index, which can be at most the length of the array. This makes it possible
+/// to detect changes that conflict with other modifications of the value.
+///
+/// If a change can't be applied, the error is returned and the changes before it remain applied.
+///
+/// ```
+/// # use dasl::{drisl, drisl::{ApplyError, apply_changes, diff}};
+/// let old = drisl!({ "title": "Hello", "tags": ["a", "b"] });
+/// let new = drisl!({ "title": "Hi", "tags": ["a"] });
+/// let changes = diff(&old, &new);
+///
+/// let mut value = old.clone();
+/// apply_changes(&mut value, changes.clone()).unwrap();
+/// assert_eq!(value, new);
+///
+/// // Someone else changed the title in the meantime.
+/// let mut value = drisl!({ "title": "Hey", "tags": ["a", "b"] });
+/// assert_eq!(
+///     apply_changes(&mut value, changes),
+///     Err(ApplyError::Conflict { path: "title".into() })
+/// );
+/// ```
+pub fn apply_changes(value: &mut Value, changes: Vec<Change>) -> Result<(), ApplyError> {
+    for change in changes {
+        apply_change(value, change)?;
+    }
+    Ok(())
+}
+
+fn apply_change(value: &mut Value, change: Change) -> Result<(), ApplyError> {
+    let missing = || ApplyError::Missing {
+        path: display_path(&change.path),
+    };
+    let conflict = || ApplyError::Conflict {
+        path: display_path(&change.path),
+    };
+
+    let Some((last, parents)) = change.path.split_last() else {
+        // Only the value itself can be replaced, it can't be added or removed.
+        return match change.kind {
+            ChangeKind::Replaced { old, new } if *value == old => {
+                *value = new;
+                Ok(())
+            }
+            _ => Err(conflict()),
+        };
+    };
+    let mut parent = value;
+    for segment in parents {
+        parent = match (parent, segment) {
+            (Value::Map(map), PathSegment::Key(key)) => map.get_mut(key),
+            (Value::Array(array), PathSegment::Index(index)) => array.get_mut(*index),
+            _ => None,
+        }
+        .ok_or_else(missing)?;
+    }
+
+    match (parent, last, &change.kind) {
+        (Value::Map(map), PathSegment::Key(key), ChangeKind::Added(new)) => {
+            if map.contains_key(key) {
+                return Err(conflict());
+            }
+            map.insert(key.clone(), new.clone());
+        }
+        (Value::Map(map), PathSegment::Key(key), ChangeKind::Removed(old)) => {
+            if map.get(key).ok_or_else(missing)? != old {
+                return Err(conflict());
+            }
+            map.remove(key);
+        }
+        (Value::Map(map), PathSegment::Key(key), ChangeKind::Replaced { old, new }) => {
+            let current = map.get_mut(key).ok_or_else(missing)?;
+            if current != old {
+                return Err(conflict());
+            }
+            *current = new.clone();
+        }
+        (Value::Array(array), PathSegment::Index(index), ChangeKind::Added(new)) => {
+            if *index > array.len() {
+                return Err(missing());
+            }
+            array.insert(*index, new.clone());
+        }
+        (Value::Array(array), PathSegment::Index(index), ChangeKind::Removed(old)) => {
+            if array.get(*index).ok_or_else(missing)? != old {
+                return Err(conflict());
+            }
+            array.remove(*index);
+        }
+        (Value::Array(array), PathSegment::Index(index), ChangeKind::Replaced { old, new }) => {
+            let current = array.get_mut(*index).ok_or_else(missing)?;
+            if current != old {
+                return Err(conflict());
+            }
+            *current = new.clone();
+        }
+        _ => return Err(missing()),
+    }
+    Ok(())
+}
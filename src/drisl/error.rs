@@ -1,23 +1,77 @@
 //! When serializing or deserializing DRISL goes wrong.
 
-use core::{convert::Infallible, fmt};
-use std::{
+use alloc::{
+    boxed::Box,
     collections::TryReserveError,
     string::{String, ToString},
 };
+use core::convert::Infallible;
+use core::fmt;
 
-pub use cbor4ii::core::error::Len;
 use serde::{de, ser};
 
+/// A length in a [`DecodeError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Len {
+    /// Indefinite length.
+    Indefinite,
+    /// A length that fits into 16 bits.
+    Small(u16),
+    /// A larger length.
+    Big,
+}
+
+impl fmt::Display for Len {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Len::Indefinite => f.write_str("an indefinite number of"),
+            Len::Small(len) => write!(f, "{len}"),
+            Len::Big => write!(f, "more than {}", u16::MAX),
+        }
+    }
+}
+
+impl Len {
+    /// Creates a length, which is [`Big`](Self::Big) if it doesn't fit into 16 bits.
+    pub fn new(len: usize) -> Self {
+        match u16::try_from(len) {
+            Ok(len) => Len::Small(len),
+            Err(_) => Len::Big,
+        }
+    }
+
+    /// Converts the length of an error of the underlying CBOR implementation.
+    fn from_cbor4ii(len: cbor4ii::core::error::Len) -> Self {
+        match len {
+            cbor4ii::core::error::Len::Indefinite => Len::Indefinite,
+            cbor4ii::core::error::Len::Small(len) => Len::Small(len),
+            cbor4ii::core::error::Len::Big => Len::Big,
+        }
+    }
+}
+
 /// An encoding error.
 #[derive(Debug)]
 pub enum EncodeError<E> {
     /// Custom error message.
     Msg(String),
+    /// An integer outside of [`Value::MIN_INTEGER`](super::Value::MIN_INTEGER) and
+    /// [`Value::MAX_INTEGER`](super::Value::MAX_INTEGER), which CBOR can't represent.
+    IntegerOutOfRange,
     /// IO Error.
     Write(E),
 }
 
+impl<E> EncodeError<E> {
+    /// Returns the category of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            EncodeError::Msg(_) | EncodeError::IntegerOutOfRange => ErrorKind::Data,
+            EncodeError::Write(_) => ErrorKind::Io,
+        }
+    }
+}
+
 impl<E> From<E> for EncodeError<E> {
     fn from(err: E) -> EncodeError<E> {
         EncodeError::Write(err)
@@ -33,20 +87,25 @@ impl<E: core::error::Error + 'static> ser::Error for EncodeError<E> {
 impl<E: core::error::Error + 'static> core::error::Error for EncodeError<E> {
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         match self {
-            EncodeError::Msg(_) => None,
+            EncodeError::Msg(_) | EncodeError::IntegerOutOfRange => None,
             EncodeError::Write(err) => Some(err),
         }
     }
 }
 
-impl<E: fmt::Debug> fmt::Display for EncodeError<E> {
+impl<E: fmt::Display> fmt::Display for EncodeError<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
+        match self {
+            EncodeError::Msg(msg) => f.write_str(msg),
+            EncodeError::IntegerOutOfRange => f.write_str("Integer is out of the range of CBOR"),
+            EncodeError::Write(err) => write!(f, "Write failed: {err}"),
+        }
     }
 }
 
-impl<E: fmt::Debug> From<cbor4ii::core::error::EncodeError<E>> for EncodeError<E> {
-    fn from(err: cbor4ii::core::error::EncodeError<E>) -> EncodeError<E> {
+impl<E: fmt::Debug> EncodeError<E> {
+    /// Converts an error of cbor4ii, which isn't part of the public API.
+    pub(crate) fn from_cbor4ii(err: cbor4ii::core::error::EncodeError<E>) -> EncodeError<E> {
         match err {
             cbor4ii::core::error::EncodeError::Write(e) => EncodeError::Write(e),
             // Needed as `cbor4ii::core::error::EncodeError` is marks as non_exhaustive
@@ -105,6 +164,71 @@ pub enum DecodeError<E> {
     TrailingData,
     /// Indefinite sized item was encountered.
     IndefiniteSize,
+    /// An integer or length isn't encoded in its shortest form.
+    NonMinimal {
+        /// Type name.
+        name: &'static str,
+    },
+    /// A float is infinite or NaN, which DRISL doesn't allow.
+    NonFiniteFloat,
+    /// A map contains the same key more than once, which DRISL doesn't allow.
+    DuplicateKey {
+        /// The repeated key.
+        key: String,
+        /// Byte offset of its second occurrence.
+        offset: u64,
+    },
+    /// A map key doesn't sort after the previous one, DRISL requires them to be sorted by length
+    /// first and then bytewise.
+    UnsortedKey {
+        /// The key that is out of order.
+        key: String,
+        /// Byte offset of the key.
+        offset: u64,
+    },
+    /// Arrays and maps are nested deeper than the configured limit.
+    DepthLimit {
+        /// Maximum depth.
+        limit: usize,
+    },
+    /// A string or byte string is longer than the configured limit.
+    StrLenLimit {
+        /// Type name.
+        name: &'static str,
+        /// Length of the string.
+        len: usize,
+        /// Maximum length.
+        limit: usize,
+    },
+    /// An array or map has more items than the configured limit.
+    CollectionLenLimit {
+        /// Type name.
+        name: &'static str,
+        /// Number of items.
+        len: usize,
+        /// Maximum number of items.
+        limit: usize,
+    },
+    /// The value is larger than the configured limit.
+    SizeLimit {
+        /// Maximum size.
+        limit: usize,
+    },
+    /// An error within a nested value, only returned when path tracking is enabled.
+    AtPath {
+        /// Location of the error, e.g. `header.entries[3].cid`.
+        path: String,
+        /// The error that occurred there.
+        error: Box<DecodeError<E>>,
+    },
+    /// Data that failed to decode and was skipped, only returned by iterators that recover from
+    /// errors.
+    Skipped {
+        /// Byte offsets of the skipped data, starting with the value that failed to decode.
+        range: core::ops::Range<u64>,
+        /// The error the value failed with.
+        error: Box<DecodeError<E>>,
+    },
 }
 
 impl<E> From<E> for DecodeError<E> {
@@ -113,7 +237,109 @@ impl<E> From<E> for DecodeError<E> {
     }
 }
 
-impl<E: std::error::Error + 'static> de::Error for DecodeError<E> {
+impl<E> DecodeError<E> {
+    /// Returns the category of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            DecodeError::Read(_) => ErrorKind::Io,
+            DecodeError::Eof { .. } => ErrorKind::Eof,
+            DecodeError::RequireUtf8 { .. }
+            | DecodeError::Unsupported { .. }
+            | DecodeError::TrailingData
+            | DecodeError::IndefiniteSize
+            | DecodeError::NonMinimal { .. }
+            | DecodeError::NonFiniteFloat
+            | DecodeError::DuplicateKey { .. }
+            | DecodeError::UnsortedKey { .. } => ErrorKind::Syntax,
+            DecodeError::Msg(_)
+            | DecodeError::Mismatch { .. }
+            | DecodeError::CastOverflow { .. }
+            | DecodeError::Overflow { .. }
+            | DecodeError::RequireBorrowed { .. }
+            | DecodeError::RequireLength { .. } => ErrorKind::Data,
+            DecodeError::DepthOverflow { .. }
+            | DecodeError::DepthLimit { .. }
+            | DecodeError::StrLenLimit { .. }
+            | DecodeError::CollectionLenLimit { .. }
+            | DecodeError::SizeLimit { .. } => ErrorKind::Limit,
+            DecodeError::AtPath { error, .. } | DecodeError::Skipped { error, .. } => error.kind(),
+        }
+    }
+
+    /// Returns the byte offset of the error, if it's known.
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            DecodeError::DuplicateKey { offset, .. } | DecodeError::UnsortedKey { offset, .. } => {
+                Some(*offset)
+            }
+            DecodeError::Skipped { range, .. } => Some(range.start),
+            DecodeError::AtPath { error, .. } => error.offset(),
+            _ => None,
+        }
+    }
+
+    /// Returns the location of the error, if it was recorded.
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            DecodeError::AtPath { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Returns the error without the location it occurred at.
+    pub fn without_path(&self) -> &DecodeError<E> {
+        match self {
+            DecodeError::AtPath { error, .. } => error,
+            error => error,
+        }
+    }
+}
+
+#[cfg(any(feature = "tokio", feature = "mmap"))]
+impl DecodeError<Infallible> {
+    /// Converts an error of decoding from memory into one of decoding from a reader.
+    pub(crate) fn into_read_error<E>(self) -> DecodeError<E> {
+        match self {
+            DecodeError::Msg(msg) => DecodeError::Msg(msg),
+            DecodeError::Read(never) => match never {},
+            DecodeError::Eof { name, expect } => DecodeError::Eof { name, expect },
+            DecodeError::Mismatch { name, found } => DecodeError::Mismatch { name, found },
+            DecodeError::CastOverflow { name } => DecodeError::CastOverflow { name },
+            DecodeError::Overflow { name } => DecodeError::Overflow { name },
+            DecodeError::RequireBorrowed { name } => DecodeError::RequireBorrowed { name },
+            DecodeError::RequireLength { name, found } => {
+                DecodeError::RequireLength { name, found }
+            }
+            DecodeError::RequireUtf8 { name } => DecodeError::RequireUtf8 { name },
+            DecodeError::Unsupported { name, found } => DecodeError::Unsupported { name, found },
+            DecodeError::DepthOverflow { name } => DecodeError::DepthOverflow { name },
+            DecodeError::TrailingData => DecodeError::TrailingData,
+            DecodeError::IndefiniteSize => DecodeError::IndefiniteSize,
+            DecodeError::NonMinimal { name } => DecodeError::NonMinimal { name },
+            DecodeError::NonFiniteFloat => DecodeError::NonFiniteFloat,
+            DecodeError::DuplicateKey { key, offset } => DecodeError::DuplicateKey { key, offset },
+            DecodeError::UnsortedKey { key, offset } => DecodeError::UnsortedKey { key, offset },
+            DecodeError::DepthLimit { limit } => DecodeError::DepthLimit { limit },
+            DecodeError::StrLenLimit { name, len, limit } => {
+                DecodeError::StrLenLimit { name, len, limit }
+            }
+            DecodeError::CollectionLenLimit { name, len, limit } => {
+                DecodeError::CollectionLenLimit { name, len, limit }
+            }
+            DecodeError::SizeLimit { limit } => DecodeError::SizeLimit { limit },
+            DecodeError::AtPath { path, error } => DecodeError::AtPath {
+                path,
+                error: Box::new(error.into_read_error()),
+            },
+            DecodeError::Skipped { range, error } => DecodeError::Skipped {
+                range,
+                error: Box::new(error.into_read_error()),
+            },
+        }
+    }
+}
+
+impl<E: core::error::Error + 'static> de::Error for DecodeError<E> {
     fn custom<T: fmt::Display>(msg: T) -> Self {
         DecodeError::Msg(msg.to_string())
     }
@@ -124,29 +350,89 @@ impl<E: core::error::Error + 'static> core::error::Error for DecodeError<E> {
         match self {
             DecodeError::Msg(_) => None,
             DecodeError::Read(err) => Some(err),
+            DecodeError::AtPath { error, .. } => Some(error),
+            DecodeError::Skipped { error, .. } => Some(error),
             _ => None,
         }
     }
 }
 
-impl<E: fmt::Debug> fmt::Display for DecodeError<E> {
+impl<E: fmt::Display> fmt::Display for DecodeError<E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
+        match self {
+            DecodeError::Msg(msg) => f.write_str(msg),
+            DecodeError::Read(err) => write!(f, "Read failed: {err}"),
+            DecodeError::Eof { name, expect } => {
+                write!(
+                    f,
+                    "Unexpected end of data in {name}, expected {expect} more bytes"
+                )
+            }
+            DecodeError::Mismatch { name, found } => {
+                write!(f, "Unexpected byte 0x{found:02x} for {name}")
+            }
+            DecodeError::CastOverflow { name } => write!(f, "Integer doesn't fit into {name}"),
+            DecodeError::Overflow { name } => write!(f, "Integer overflows {name}"),
+            DecodeError::RequireBorrowed { name } => {
+                write!(f, "Can't borrow {name} from the input")
+            }
+            DecodeError::RequireLength { name, found } => {
+                write!(f, "Array of {found} items is too long for {name}")
+            }
+            DecodeError::RequireUtf8 { name } => write!(f, "Invalid UTF-8 in {name}"),
+            DecodeError::Unsupported { name, found } => {
+                write!(f, "Unsupported byte 0x{found:02x} for {name}")
+            }
+            DecodeError::DepthOverflow { name } => write!(f, "Recursion limit reached in {name}"),
+            DecodeError::TrailingData => f.write_str("Trailing data after the value"),
+            DecodeError::IndefiniteSize => f.write_str("Indefinite lengths aren't allowed"),
+            DecodeError::NonMinimal { name } => {
+                write!(f, "{name} isn't encoded in its shortest form")
+            }
+            DecodeError::NonFiniteFloat => f.write_str("Non-finite floats aren't allowed"),
+            DecodeError::DuplicateKey { key, offset } => {
+                write!(f, "Duplicate map key {key:?} at offset {offset}")
+            }
+            DecodeError::UnsortedKey { key, offset } => {
+                write!(f, "Map key {key:?} isn't sorted at offset {offset}")
+            }
+            DecodeError::DepthLimit { limit } => {
+                write!(f, "Nested deeper than the limit of {limit}")
+            }
+            DecodeError::StrLenLimit { name, len, limit } => {
+                write!(f, "{name} of length {len} exceeds the limit of {limit}")
+            }
+            DecodeError::CollectionLenLimit { name, len, limit } => {
+                write!(f, "{name} of {len} items exceeds the limit of {limit}")
+            }
+            DecodeError::SizeLimit { limit } => {
+                write!(f, "Value exceeds the size limit of {limit} bytes")
+            }
+            DecodeError::AtPath { path, error } => write!(f, "{error} at {path}"),
+            DecodeError::Skipped { range, error } => {
+                write!(f, "Skipped bytes {}..{}: {error}", range.start, range.end)
+            }
+        }
     }
 }
 
-impl<E: fmt::Debug> From<cbor4ii::core::error::DecodeError<E>> for DecodeError<E> {
-    fn from(err: cbor4ii::core::error::DecodeError<E>) -> DecodeError<E> {
+impl<E: fmt::Debug> DecodeError<E> {
+    /// Converts an error of cbor4ii, which isn't part of the public API.
+    pub(crate) fn from_cbor4ii(err: cbor4ii::core::error::DecodeError<E>) -> DecodeError<E> {
         use cbor4ii::core::error::DecodeError as IDecodeError;
         match err {
             IDecodeError::Read(read) => DecodeError::Read(read),
-            IDecodeError::Eof { name, expect } => DecodeError::Eof { name, expect },
+            IDecodeError::Eof { name, expect } => DecodeError::Eof {
+                name,
+                expect: Len::from_cbor4ii(expect),
+            },
             IDecodeError::Mismatch { name, found } => DecodeError::Mismatch { name, found },
             IDecodeError::CastOverflow { name } => DecodeError::CastOverflow { name },
             IDecodeError::RequireBorrowed { name } => DecodeError::RequireBorrowed { name },
-            IDecodeError::RequireLength { name, found } => {
-                DecodeError::RequireLength { name, found }
-            }
+            IDecodeError::RequireLength { name, found } => DecodeError::RequireLength {
+                name,
+                found: Len::from_cbor4ii(found),
+            },
             IDecodeError::Unsupported { name, found } => DecodeError::Unsupported { name, found },
             IDecodeError::DepthOverflow { name } => DecodeError::DepthOverflow { name },
             IDecodeError::RequireUtf8 { name } => DecodeError::RequireUtf8 { name },
@@ -157,6 +443,7 @@ impl<E: fmt::Debug> From<cbor4ii::core::error::DecodeError<E>> for DecodeError<E
 }
 
 /// Encode and Decode error combined.
+#[cfg(feature = "std")]
 #[derive(Debug)]
 pub enum CodecError {
     /// A decoding error.
@@ -167,8 +454,11 @@ pub enum CodecError {
     DecodeIo(DecodeError<std::io::Error>),
     /// An encoding error.
     EncodeIo(EncodeError<std::io::Error>),
+    /// An IO error of the underlying transport.
+    Io(std::io::Error),
 }
 
+#[cfg(feature = "std")]
 impl fmt::Display for CodecError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -176,32 +466,247 @@ impl fmt::Display for CodecError {
             Self::Encode(error) => write!(f, "encode error: {error}"),
             Self::DecodeIo(error) => write!(f, "decode io error: {error}"),
             Self::EncodeIo(error) => write!(f, "encode io error: {error}"),
+            Self::Io(error) => write!(f, "io error: {error}"),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for CodecError {}
 
+#[cfg(feature = "std")]
 impl From<DecodeError<Infallible>> for CodecError {
     fn from(error: DecodeError<Infallible>) -> Self {
         Self::Decode(error)
     }
 }
 
+#[cfg(feature = "std")]
 impl From<DecodeError<std::io::Error>> for CodecError {
     fn from(error: DecodeError<std::io::Error>) -> Self {
         Self::DecodeIo(error)
     }
 }
 
+#[cfg(feature = "std")]
 impl From<EncodeError<TryReserveError>> for CodecError {
     fn from(error: EncodeError<TryReserveError>) -> Self {
         Self::Encode(error)
     }
 }
 
+#[cfg(feature = "std")]
 impl From<EncodeError<std::io::Error>> for CodecError {
     fn from(error: EncodeError<std::io::Error>) -> Self {
         Self::EncodeIo(error)
     }
 }
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for CodecError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// The category of an [`Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The data ended within a value.
+    Eof,
+    /// The data isn't valid DRISL, e.g. it's malformed or not canonical.
+    Syntax,
+    /// The value doesn't fit the type, e.g. a string where a number is expected, or an integer
+    /// that is out of range.
+    Data,
+    /// A limit of the [`DecodeOptions`](super::de::DecodeOptions) was exceeded.
+    Limit,
+    /// Reading or writing failed, or the output couldn't be allocated.
+    Io,
+}
+
+/// An error of encoding or decoding, for applications that don't need the generic forms.
+///
+/// All of [`DecodeError`], [`EncodeError`] and `CodecError` convert into it, so `?` works for any
+/// of them in functions that return this error.
+///
+/// ```
+/// # use dasl::drisl::{self, Error, ErrorKind, Value};
+/// fn roundtrip(bytes: &[u8]) -> Result<Vec<u8>, Error> {
+///     let value: Value = drisl::from_slice(bytes)?;
+///     Ok(drisl::to_vec(&value)?)
+/// }
+///
+/// assert_eq!(roundtrip(&[0x82, 0x01]).unwrap_err().kind(), ErrorKind::Eof);
+/// ```
+#[derive(Debug)]
+pub struct Error {
+    repr: Repr,
+}
+
+#[derive(Debug)]
+enum Repr {
+    Decode(DecodeError<Infallible>),
+    Encode(EncodeError<TryReserveError>),
+    #[cfg(feature = "std")]
+    DecodeIo(DecodeError<std::io::Error>),
+    #[cfg(feature = "std")]
+    EncodeIo(EncodeError<std::io::Error>),
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl Error {
+    /// Returns the category of the error.
+    pub fn kind(&self) -> ErrorKind {
+        match &self.repr {
+            Repr::Decode(error) => error.kind(),
+            Repr::Encode(error) => error.kind(),
+            #[cfg(feature = "std")]
+            Repr::DecodeIo(error) => error.kind(),
+            #[cfg(feature = "std")]
+            Repr::EncodeIo(error) => error.kind(),
+            #[cfg(feature = "std")]
+            Repr::Io(_) => ErrorKind::Io,
+        }
+    }
+
+    /// Returns the location of the error within the value, if it was recorded, see
+    /// [`DecodeOptions::track_path`](super::de::DecodeOptions::track_path).
+    pub fn path(&self) -> Option<&str> {
+        match &self.repr {
+            Repr::Decode(error) => error.path(),
+            #[cfg(feature = "std")]
+            Repr::DecodeIo(error) => error.path(),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte offset of the error, if it's known.
+    pub fn offset(&self) -> Option<u64> {
+        match &self.repr {
+            Repr::Decode(error) => error.offset(),
+            #[cfg(feature = "std")]
+            Repr::DecodeIo(error) => error.offset(),
+            _ => None,
+        }
+    }
+
+    /// Returns the IO error that caused this error, if any.
+    #[cfg(feature = "std")]
+    pub fn io_error(&self) -> Option<&std::io::Error> {
+        match &self.repr {
+            Repr::DecodeIo(error) => match error.without_path() {
+                DecodeError::Read(error) => Some(error),
+                DecodeError::Skipped { error, .. } => match error.without_path() {
+                    DecodeError::Read(error) => Some(error),
+                    _ => None,
+                },
+                _ => None,
+            },
+            Repr::EncodeIo(EncodeError::Write(error)) | Repr::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.repr {
+            Repr::Decode(error) => error.fmt(f),
+            Repr::Encode(error) => error.fmt(f),
+            #[cfg(feature = "std")]
+            Repr::DecodeIo(error) => error.fmt(f),
+            #[cfg(feature = "std")]
+            Repr::EncodeIo(error) => error.fmt(f),
+            #[cfg(feature = "std")]
+            Repr::Io(error) => error.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match &self.repr {
+            Repr::Decode(error) => error.source(),
+            Repr::Encode(error) => error.source(),
+            #[cfg(feature = "std")]
+            Repr::DecodeIo(error) => error.source(),
+            #[cfg(feature = "std")]
+            Repr::EncodeIo(error) => error.source(),
+            #[cfg(feature = "std")]
+            Repr::Io(error) => error.source(),
+        }
+    }
+}
+
+impl From<DecodeError<Infallible>> for Error {
+    fn from(error: DecodeError<Infallible>) -> Self {
+        Self {
+            repr: Repr::Decode(error),
+        }
+    }
+}
+
+impl From<EncodeError<TryReserveError>> for Error {
+    fn from(error: EncodeError<TryReserveError>) -> Self {
+        Self {
+            repr: Repr::Encode(error),
+        }
+    }
+}
+
+impl From<EncodeError<Infallible>> for Error {
+    fn from(error: EncodeError<Infallible>) -> Self {
+        let error = match error {
+            EncodeError::Msg(msg) => EncodeError::Msg(msg),
+            EncodeError::IntegerOutOfRange => EncodeError::IntegerOutOfRange,
+            EncodeError::Write(never) => match never {},
+        };
+        Self {
+            repr: Repr::Encode(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<DecodeError<std::io::Error>> for Error {
+    fn from(error: DecodeError<std::io::Error>) -> Self {
+        Self {
+            repr: Repr::DecodeIo(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<EncodeError<std::io::Error>> for Error {
+    fn from(error: EncodeError<std::io::Error>) -> Self {
+        Self {
+            repr: Repr::EncodeIo(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self {
+            repr: Repr::Io(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<CodecError> for Error {
+    fn from(error: CodecError) -> Self {
+        let repr = match error {
+            CodecError::Decode(error) => Repr::Decode(error),
+            CodecError::Encode(error) => Repr::Encode(error),
+            CodecError::DecodeIo(error) => Repr::DecodeIo(error),
+            CodecError::EncodeIo(error) => Repr::EncodeIo(error),
+            CodecError::Io(error) => Repr::Io(error),
+        };
+        Self { repr }
+    }
+}
@@ -0,0 +1,374 @@
+//! Decoding the structure of data with the bytes of each item.
+
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, ops::Range};
+
+use cbor4ii::core::major;
+
+use super::{CBOR_TAGS_CID, CanonicityRule, cbor4ii_nonpub::marker};
+use crate::cid::Cid;
+
+/// A single item of encoded data, see [`inspect`].
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct Node {
+    /// The kind of item, with its argument.
+    pub kind: NodeKind,
+    /// The bytes of the item, including its header and any nested items.
+    pub range: Range<usize>,
+    /// The bytes of the header, i.e. the initial byte and argument.
+    pub header: Range<usize>,
+    /// Number of arrays, maps, tags and indefinite length strings the item is in.
+    pub depth: usize,
+    /// Index of the array, map, tag or indefinite length string the item is in.
+    pub parent: Option<usize>,
+    /// The rules of canonical DRISL the item violates.
+    pub issues: Vec<CanonicityRule>,
+}
+
+impl Node {
+    /// Returns the bytes after the header, e.g. the contents of a string or the items of an array.
+    pub fn content(&self) -> Range<usize> {
+        self.header.end..self.range.end
+    }
+}
+
+/// The kind of a [`Node`].
+///
+/// Lengths are `None` for indefinite lengths, whose items end with a [`Break`](Self::Break).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum NodeKind {
+    Unsigned(u64),
+    Negative(i128),
+    Bytes {
+        len: Option<u64>,
+    },
+    Text {
+        len: Option<u64>,
+    },
+    Array {
+        len: Option<u64>,
+    },
+    /// A map, its length is the number of entries.
+    Map {
+        len: Option<u64>,
+    },
+    Tag(u64),
+    Bool(bool),
+    Null,
+    Undefined,
+    Simple(u8),
+    /// A float, the header tells whether it was encoded with 16, 32 or 64 bits.
+    Float(f64),
+    Break,
+    /// Data that isn't well-formed, from where it starts to the end of the data.
+    Invalid,
+}
+
+/// Decodes the structure of encoded data, with the bytes each item was decoded from.
+///
+/// Nodes are returned in the order of the data, nested items after the array, map or tag they
+/// are in. The data can be any CBOR, anything that isn't canonical DRISL is reported in
+/// [`Node::issues`] instead of failing. Concatenated values each start at depth `0`. If the data
+/// isn't well-formed, the last node is [`NodeKind::Invalid`], with the reason as its issue.
+///
+/// ```
+/// # use dasl::{drisl, drisl::{CanonicityRule, NodeKind}};
+/// let nodes = drisl::inspect(&drisl::to_vec(&drisl!({ "a": [1, -2] })).unwrap());
+/// assert_eq!(nodes[4].kind, NodeKind::Negative(-2));
+/// assert_eq!(nodes[4].range, 5..6);
+/// assert_eq!(nodes[4].depth, 2);
+/// assert_eq!(nodes[nodes[4].parent.unwrap()].kind, NodeKind::Array { len: Some(2) });
+///
+/// // [1] with its length encoded in two bytes.
+/// let nodes = drisl::inspect(&[0x98, 0x01, 0x01]);
+/// assert_eq!(nodes[0].range, 0..3);
+/// assert_eq!(nodes[0].header, 0..2);
+/// assert_eq!(nodes[0].issues, [CanonicityRule::NonMinimal]);
+/// ```
+pub fn inspect(bytes: &[u8]) -> Vec<Node> {
+    let mut inspector = Inspector {
+        buf: bytes,
+        pos: 0,
+        nodes: Vec::new(),
+        stack: Vec::new(),
+    };
+    while inspector.pos < bytes.len() || !inspector.stack.is_empty() {
+        let start = inspector.pos;
+        if let Err(rule) = inspector.item() {
+            inspector.pos = start;
+            inspector.push(NodeKind::Invalid, inspector.pos, inspector.pos, vec![rule]);
+            inspector.nodes.last_mut().unwrap().range.end = bytes.len();
+            for frame in inspector.stack.drain(..) {
+                inspector.nodes[frame.node].range.end = bytes.len();
+            }
+            break;
+        }
+    }
+    inspector.nodes
+}
+
+/// An array, map, tag or indefinite length string whose items are being inspected.
+struct Frame {
+    /// Index of its node.
+    node: usize,
+    /// Number of items, keys and values count separately. `None` for indefinite lengths.
+    len: Option<u64>,
+    /// Number of items so far.
+    index: u64,
+    /// Whether it's a map, whose even items are keys.
+    map: bool,
+    /// Index of the node of the current key and the range of the previous key.
+    key: Option<usize>,
+    previous_key: Option<Range<usize>>,
+}
+
+struct Inspector<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    nodes: Vec<Node>,
+    stack: Vec<Frame>,
+}
+
+impl Inspector<'_> {
+    fn push(
+        &mut self,
+        kind: NodeKind,
+        start: usize,
+        header_end: usize,
+        issues: Vec<CanonicityRule>,
+    ) {
+        self.nodes.push(Node {
+            kind,
+            range: start..self.pos,
+            header: start..header_end,
+            depth: self.stack.len(),
+            parent: self.stack.last().map(|frame| frame.node),
+            issues,
+        });
+    }
+
+    fn take(&mut self, len: u64) -> Result<&[u8], CanonicityRule> {
+        let rest = &self.buf[self.pos..];
+        match usize::try_from(len) {
+            Ok(len) if len <= rest.len() => {
+                self.pos += len;
+                Ok(&rest[..len])
+            }
+            _ => Err(CanonicityRule::Truncated),
+        }
+    }
+
+    /// Reads the initial byte and argument of an item, the argument is `None` for indefinite
+    /// lengths. Floats and simple values have their bits as the argument.
+    fn head(
+        &mut self,
+        issues: &mut Vec<CanonicityRule>,
+    ) -> Result<(u8, Option<u64>), CanonicityRule> {
+        let byte = self.take(1)?[0];
+        let (arg, min) = match byte & 0x1f {
+            info @ 0..=0x17 => return Ok((byte, Some(u64::from(info)))),
+            0x18 => (u64::from(self.take(1)?[0]), 0x18),
+            0x19 => (
+                u64::from(u16::from_be_bytes(self.take(2)?.try_into().unwrap())),
+                0x100,
+            ),
+            0x1a => (
+                u64::from(u32::from_be_bytes(self.take(4)?.try_into().unwrap())),
+                0x1_0000,
+            ),
+            0x1b => (
+                u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+                0x1_0000_0000,
+            ),
+            0x1f if (major::BYTES..=major::MAP).contains(&(byte >> 5)) => {
+                issues.push(CanonicityRule::IndefiniteLength);
+                return Ok((byte, None));
+            }
+            _ => return Err(CanonicityRule::InvalidHead),
+        };
+        // Floats have fixed sizes.
+        if arg < min && byte >> 5 != major::SIMPLE {
+            issues.push(CanonicityRule::NonMinimal);
+        }
+        Ok((byte, Some(arg)))
+    }
+
+    /// Inspects a single item, and completes the arrays, maps and tags it ends.
+    fn item(&mut self) -> Result<(), CanonicityRule> {
+        let start = self.pos;
+        let Some(&byte) = self.buf.get(start) else {
+            return Err(CanonicityRule::Truncated);
+        };
+        let parent = self.stack.last();
+        let is_key = parent.is_some_and(|frame| frame.map && frame.index % 2 == 0);
+        let in_cid = parent.is_some_and(|frame| {
+            self.nodes[frame.node].kind == NodeKind::Tag(u64::from(CBOR_TAGS_CID))
+        });
+
+        if byte == marker::BREAK {
+            // A break ends the innermost indefinite length item, but not between a key and its
+            // value.
+            if !parent.is_some_and(|frame| frame.len.is_none() && (!frame.map || is_key)) {
+                return Err(CanonicityRule::InvalidHead);
+            }
+            self.pos += 1;
+            self.push(NodeKind::Break, start, self.pos, Vec::new());
+            let frame = self.stack.pop().unwrap();
+            self.nodes[frame.node].range.end = self.pos;
+            self.complete();
+            return Ok(());
+        }
+
+        let mut issues = Vec::new();
+        let (byte, arg) = self.head(&mut issues)?;
+        let header_end = self.pos;
+        let mut frame_len = None;
+        let kind = match (byte >> 5, arg) {
+            (major::UNSIGNED, Some(arg)) => NodeKind::Unsigned(arg),
+            (major::NEGATIVE, Some(arg)) => NodeKind::Negative(-1 - i128::from(arg)),
+            (major::BYTES, len) => {
+                match len {
+                    Some(len) => {
+                        let bytes = self.take(len)?;
+                        if in_cid && Cid::from_bytes(bytes).is_err() {
+                            issues.push(CanonicityRule::InvalidCid);
+                        }
+                    }
+                    None => frame_len = Some(None),
+                }
+                NodeKind::Bytes { len }
+            }
+            (major::STRING, len) => {
+                match len {
+                    Some(len) => {
+                        if core::str::from_utf8(self.take(len)?).is_err() {
+                            issues.push(CanonicityRule::InvalidUtf8);
+                        }
+                    }
+                    None => frame_len = Some(None),
+                }
+                NodeKind::Text { len }
+            }
+            (major::ARRAY, len) => {
+                frame_len = Some(len);
+                NodeKind::Array { len }
+            }
+            (major::MAP, len) => {
+                frame_len = Some(len.map(|len| len.saturating_mul(2)));
+                NodeKind::Map { len }
+            }
+            (major::TAG, Some(tag)) => {
+                if tag != u64::from(CBOR_TAGS_CID) {
+                    issues.push(CanonicityRule::UnsupportedTag);
+                }
+                frame_len = Some(Some(1));
+                NodeKind::Tag(tag)
+            }
+            (_, Some(arg)) => match byte {
+                marker::FALSE => NodeKind::Bool(false),
+                marker::TRUE => NodeKind::Bool(true),
+                marker::NULL => NodeKind::Null,
+                marker::F16 | marker::F32 | marker::F64 => {
+                    let value = match byte {
+                        marker::F16 => f16_to_f64(arg as u16),
+                        marker::F32 => f64::from(f32::from_bits(arg as u32)),
+                        _ => f64::from_bits(arg),
+                    };
+                    if byte != marker::F64 {
+                        issues.push(CanonicityRule::NonDoubleFloat);
+                    } else if !value.is_finite() {
+                        issues.push(CanonicityRule::NonFiniteFloat);
+                    }
+                    NodeKind::Float(value)
+                }
+                _ => {
+                    issues.push(CanonicityRule::UnsupportedSimple);
+                    match byte {
+                        marker::UNDEFINED => NodeKind::Undefined,
+                        _ => NodeKind::Simple(arg as u8),
+                    }
+                }
+            },
+            (_, None) => unreachable!("only strings, arrays and maps have indefinite lengths"),
+        };
+        if is_key && !matches!(kind, NodeKind::Text { .. }) {
+            issues.push(CanonicityRule::NonStringKey);
+        }
+        if in_cid && !matches!(kind, NodeKind::Bytes { len: Some(_) }) {
+            issues.push(CanonicityRule::InvalidCid);
+        }
+
+        let node = self.nodes.len();
+        self.push(kind, start, header_end, issues);
+        if is_key {
+            if let Some(frame) = self.stack.last_mut() {
+                frame.key = Some(node);
+            }
+        }
+        match frame_len {
+            // Empty arrays and maps are complete already.
+            Some(len) if len != Some(0) => self.stack.push(Frame {
+                node,
+                len,
+                index: 0,
+                map: matches!(kind, NodeKind::Map { .. }),
+                key: None,
+                previous_key: None,
+            }),
+            _ => self.complete(),
+        }
+        Ok(())
+    }
+
+    /// Counts an item that ended as an item of its parent, e.g. the last entry of a map completes
+    /// the map.
+    fn complete(&mut self) {
+        while let Some(frame) = self.stack.last_mut() {
+            let key = if frame.map && frame.index % 2 == 0 {
+                frame.key.take()
+            } else {
+                None
+            };
+            if let Some(key) = key {
+                let range = self.nodes[key].range.clone();
+                if let Some(previous) = frame.previous_key.replace(range.clone()) {
+                    let (previous, current) = (&self.buf[previous], &self.buf[range]);
+                    // Keys are sorted by the length of their encoding first.
+                    let order = (current.len(), current).cmp(&(previous.len(), previous));
+                    match order {
+                        Ordering::Less => self.nodes[key].issues.push(CanonicityRule::UnsortedKeys),
+                        Ordering::Equal => {
+                            self.nodes[key].issues.push(CanonicityRule::DuplicateKey)
+                        }
+                        Ordering::Greater => {}
+                    }
+                }
+            }
+            frame.index += 1;
+            if frame.len != Some(frame.index) {
+                return;
+            }
+            let node = frame.node;
+            self.stack.pop();
+            self.nodes[node].range.end = self.pos;
+        }
+    }
+}
+
+/// Converts the bits of a half precision float, without `powi`, which needs `std`.
+fn f16_to_f64(half: u16) -> f64 {
+    let sign = u64::from(half >> 15) << 63;
+    let exponent = u64::from((half >> 10) & 0x1f);
+    let mantissa = u64::from(half & 0x3ff);
+    match exponent {
+        // Subnormal numbers are the mantissa times 2^-24.
+        0 => {
+            let value = mantissa as f64 / f64::from(1 << 24);
+            if sign == 0 { value } else { -value }
+        }
+        0x1f => f64::from_bits(sign | 0x7ff << 52 | mantissa << 42),
+        _ => f64::from_bits(sign | (exponent + 1023 - 15) << 52 | mantissa << 42),
+    }
+}
@@ -0,0 +1,62 @@
+//! Conversion between [`Value`] and [`Ipld`], the data model of `ipld-core`.
+//!
+//! Both have the same kinds of values, only CIDs that this crate doesn't support can't be
+//! converted to a [`Value`]. Serializing and deserializing [`Ipld`] with this crate works as well,
+//! as the CIDs of both use the same Serde representation.
+
+use core::mem;
+
+use ipld_core::ipld::Ipld;
+
+use super::Value;
+use crate::cid::{Cid, UnsupportedCidError};
+
+impl From<Value> for Ipld {
+    fn from(mut value: Value) -> Self {
+        match &mut value {
+            Value::Integer(int) => Ipld::Integer(*int),
+            Value::Bytes(bytes) => Ipld::Bytes(mem::take(bytes)),
+            Value::Float(float) => Ipld::Float(*float),
+            Value::Text(text) => Ipld::String(mem::take(text)),
+            Value::Bool(bool) => Ipld::Bool(*bool),
+            Value::Null => Ipld::Null,
+            Value::Cid(cid) => Ipld::Link((*cid).into()),
+            Value::Array(array) => {
+                Ipld::List(mem::take(array).into_iter().map(Ipld::from).collect())
+            }
+            Value::Map(map) => Ipld::Map(
+                mem::take(map)
+                    .into_iter()
+                    .map(|(key, value)| (key, Ipld::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Fails for CIDs this crate doesn't support.
+impl TryFrom<Ipld> for Value {
+    type Error = UnsupportedCidError;
+
+    fn try_from(ipld: Ipld) -> Result<Self, Self::Error> {
+        Ok(match ipld {
+            Ipld::Null => Value::Null,
+            Ipld::Bool(bool) => Value::Bool(bool),
+            Ipld::Integer(int) => Value::Integer(int),
+            Ipld::Float(float) => Value::Float(float),
+            Ipld::String(text) => Value::Text(text),
+            Ipld::Bytes(bytes) => Value::Bytes(bytes),
+            Ipld::List(list) => Value::Array(
+                list.into_iter()
+                    .map(Value::try_from)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Ipld::Map(map) => Value::Map(
+                map.into_iter()
+                    .map(|(key, value)| Ok((key, Value::try_from(value)?)))
+                    .collect::<Result<_, Self::Error>>()?,
+            ),
+            Ipld::Link(cid) => Value::Cid(Cid::try_from(cid)?),
+        })
+    }
+}
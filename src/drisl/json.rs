@@ -0,0 +1,194 @@
+//! Conversion between [`Value`] and [`serde_json::Value`].
+//!
+//! JSON can't represent everything DRISL can, so converting to JSON maps some values:
+//!
+//! | DRISL                  | JSON                                               |
+//! |------------------------|----------------------------------------------------|
+//! | integer                | number, if it fits into an `i64` or `u64`, see [`IntegerPolicy`] |
+//! | float                  | number, non-finite floats are an error             |
+//! | byte string            | see [`BytesPolicy`]                                |
+//! | CID                    | its string form, e.g. `bafkrei…`                   |
+//! | string, bool, null, array, map | the same                                   |
+//!
+//! Converting from JSON never fails: numbers become integers if they are integral and floats
+//! otherwise, everything else maps to the same kind of value. Strings are never turned back into
+//! byte strings or CIDs, as they can't be told apart from other strings.
+//!
+//! ```
+//! # use dasl::{drisl, drisl::{Value, json::{BytesPolicy, JsonOptions}}};
+//! let value = drisl!({ "n": 1, "data": vec![b'h', b'i'] });
+//!
+//! let json = serde_json::Value::try_from(&value).unwrap();
+//! assert_eq!(json, serde_json::json!({ "n": 1, "data": "aGk=" }));
+//!
+//! let options = JsonOptions::new().bytes(BytesPolicy::Array);
+//! let json = drisl::json::to_json_with(&value, options).unwrap();
+//! assert_eq!(json, serde_json::json!({ "n": 1, "data": [104, 105] }));
+//!
+//! let back = Value::from(json);
+//! assert_eq!(back, drisl!({ "n": 1, "data": [104, 105] }));
+//! ```
+
+use alloc::string::ToString;
+
+use thiserror::Error;
+
+use super::Value;
+
+/// How byte strings are converted to JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum BytesPolicy {
+    /// A string of the bytes in standard base64 with padding.
+    #[default]
+    Base64,
+    /// An array of numbers, one per byte.
+    Array,
+    /// Byte strings are an error.
+    Reject,
+}
+
+/// How integers that don't fit into an `i64` or `u64` are converted to JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum IntegerPolicy {
+    /// Such integers are an error.
+    #[default]
+    Reject,
+    /// A float, which loses precision.
+    Float,
+    /// A string of the integer in decimal.
+    String,
+}
+
+/// Options for converting to JSON, see [`to_json_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JsonOptions {
+    pub(crate) bytes: BytesPolicy,
+    pub(crate) integers: IntegerPolicy,
+}
+
+impl JsonOptions {
+    /// Creates the default options, which encode bytes as base64 and reject out of range integers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how byte strings are converted.
+    pub fn bytes(mut self, policy: BytesPolicy) -> Self {
+        self.bytes = policy;
+        self
+    }
+
+    /// Sets how integers outside of the range of `i64` and `u64` are converted.
+    pub fn integers(mut self, policy: IntegerPolicy) -> Self {
+        self.integers = policy;
+        self
+    }
+}
+
+/// A value that can't be converted to JSON.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum JsonError {
+    #[error("Byte strings are rejected")]
+    Bytes,
+    #[error("Integer {0} is out of range")]
+    IntegerRange(i128),
+    #[error("Float {0} isn't finite")]
+    NonFiniteFloat(f64),
+}
+
+/// Converts a value to JSON with the default options.
+pub fn to_json(value: &Value) -> Result<serde_json::Value, JsonError> {
+    to_json_with(value, JsonOptions::default())
+}
+
+/// Converts a value to JSON, see the [module docs](self) for how values are mapped.
+pub fn to_json_with(value: &Value, options: JsonOptions) -> Result<serde_json::Value, JsonError> {
+    Ok(match value {
+        Value::Integer(int) => integer_to_json(*int, options.integers)?,
+        Value::Bytes(bytes) => match options.bytes {
+            BytesPolicy::Base64 => data_encoding::BASE64.encode(bytes).into(),
+            BytesPolicy::Array => bytes
+                .iter()
+                .map(|&byte| serde_json::Value::from(byte))
+                .collect(),
+            BytesPolicy::Reject => return Err(JsonError::Bytes),
+        },
+        Value::Float(float) => serde_json::Number::from_f64(*float)
+            .ok_or(JsonError::NonFiniteFloat(*float))?
+            .into(),
+        Value::Text(text) => text.clone().into(),
+        Value::Bool(bool) => (*bool).into(),
+        Value::Null => serde_json::Value::Null,
+        Value::Cid(cid) => cid.to_string().into(),
+        Value::Array(array) => array
+            .iter()
+            .map(|value| to_json_with(value, options))
+            .collect::<Result<_, _>>()?,
+        Value::Map(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, value)| Ok((key.clone(), to_json_with(value, options)?)))
+                .collect::<Result<_, _>>()?,
+        ),
+    })
+}
+
+fn integer_to_json(int: i128, policy: IntegerPolicy) -> Result<serde_json::Value, JsonError> {
+    if let Ok(int) = i64::try_from(int) {
+        return Ok(int.into());
+    }
+    if let Ok(int) = u64::try_from(int) {
+        return Ok(int.into());
+    }
+    match policy {
+        IntegerPolicy::Reject => Err(JsonError::IntegerRange(int)),
+        IntegerPolicy::Float => Ok((int as f64).into()),
+        IntegerPolicy::String => Ok(int.to_string().into()),
+    }
+}
+
+impl TryFrom<&Value> for serde_json::Value {
+    type Error = JsonError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        to_json(value)
+    }
+}
+
+impl TryFrom<Value> for serde_json::Value {
+    type Error = JsonError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        to_json(&value)
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(bool) => Value::Bool(bool),
+            serde_json::Value::Number(number) => {
+                if let Some(int) = number.as_i64() {
+                    Value::Integer(int.into())
+                } else if let Some(int) = number.as_u64() {
+                    Value::Integer(int.into())
+                } else {
+                    // Without `arbitrary_precision` every number is one of the three.
+                    Value::Float(number.as_f64().unwrap_or(f64::NAN))
+                }
+            }
+            serde_json::Value::String(text) => Value::Text(text),
+            serde_json::Value::Array(array) => {
+                Value::Array(array.into_iter().map(Value::from).collect())
+            }
+            serde_json::Value::Object(map) => Value::Map(
+                map.into_iter()
+                    .map(|(key, value)| (key, Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! Extracting CID links from encoded DRISL.
+
+use alloc::format;
+use core::convert::Infallible;
+
+use cbor4ii::core::major;
+
+use super::{
+    CBOR_TAGS_CID,
+    error::{DecodeError, Len},
+};
+use crate::cid::Cid;
+
+/// Returns the CIDs linked from an encoded DRISL value, in the order they appear.
+///
+/// The data is only scanned, scalars and strings are skipped over without decoding them. The
+/// structure is checked, but not whether the encoding is canonical.
+///
+/// ```
+/// # use dasl::{cid::{Cid, Codec}, drisl};
+/// let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+/// let bytes = drisl::to_vec(&drisl!({ "name": "foo", "links": [cid, cid] })).unwrap();
+/// let links: Vec<Cid> = drisl::links(&bytes).collect::<Result<_, _>>().unwrap();
+/// assert_eq!(links, [cid, cid]);
+/// ```
+pub fn links(bytes: &[u8]) -> Links<'_> {
+    Links {
+        buf: bytes,
+        scanner: Scanner::default(),
+        done: false,
+    }
+}
+
+/// Iterator over the CIDs linked from an encoded DRISL value, see [`links`].
+///
+/// Stops after the first error.
+#[derive(Debug, Clone)]
+pub struct Links<'a> {
+    buf: &'a [u8],
+    scanner: Scanner,
+    done: bool,
+}
+
+impl Iterator for Links<'_> {
+    type Item = Result<Cid, DecodeError<Infallible>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if self.scanner.remaining == 0 {
+                self.done = true;
+                if self.scanner.pos != self.buf.len() {
+                    return Some(Err(DecodeError::TrailingData));
+                }
+                break;
+            }
+            match self.scanner.step(self.buf) {
+                Ok(Some(cid)) => return Some(Ok(cid)),
+                Ok(None) => {}
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Scans over a single encoded value, item by item.
+#[derive(Debug, Clone)]
+pub(crate) struct Scanner {
+    /// Position in the buffer.
+    pos: usize,
+    /// Number of items left to scan, a tag and its content count as one.
+    remaining: u64,
+    /// Number of bytes missing at the end of the buffer, when scanning stopped there.
+    missing: u64,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self {
+            pos: 0,
+            remaining: 1,
+            missing: 0,
+        }
+    }
+}
+
+impl Scanner {
+    /// Returns the length of the value at the start of `buf`, or `None` if `buf` ends before it.
+    ///
+    /// Can be called again once more data was appended to `buf`, scanning continues where it
+    /// stopped. [`missing`](Self::missing) returns how many more bytes are needed at least.
+    pub(crate) fn value_len(
+        &mut self,
+        buf: &[u8],
+    ) -> Result<Option<usize>, DecodeError<Infallible>> {
+        while self.remaining > 0 {
+            let checkpoint = self.clone();
+            match self.step(buf) {
+                Ok(_) => {}
+                Err(DecodeError::Eof { .. }) => {
+                    let missing = self.missing;
+                    *self = checkpoint;
+                    self.missing = missing;
+                    return Ok(None);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        self.missing = 0;
+        Ok(Some(self.pos))
+    }
+
+    /// Returns how many bytes were missing at least when [`value_len`](Self::value_len) last
+    /// reached the end of the buffer, `0` if the value was complete.
+    pub(crate) fn missing(&self) -> u64 {
+        self.missing
+    }
+
+    fn take<'a>(
+        &mut self,
+        buf: &'a [u8],
+        name: &'static str,
+        len: u64,
+    ) -> Result<&'a [u8], DecodeError<Infallible>> {
+        let rest = &buf[self.pos..];
+        match usize::try_from(len) {
+            Ok(len) if len <= rest.len() => {
+                self.pos += len;
+                Ok(&rest[..len])
+            }
+            _ => {
+                self.missing = len - rest.len() as u64;
+                Err(eof(name, len))
+            }
+        }
+    }
+
+    /// Reads the initial byte and argument of an item.
+    fn header(
+        &mut self,
+        buf: &[u8],
+        name: &'static str,
+    ) -> Result<(u8, u64), DecodeError<Infallible>> {
+        let byte = self.take(buf, name, 1)?[0];
+        let arg = match byte & 0x1f {
+            info @ 0..=0x17 => u64::from(info),
+            0x18 => u64::from(self.take(buf, name, 1)?[0]),
+            0x19 => u64::from(u16::from_be_bytes(
+                self.take(buf, name, 2)?.try_into().unwrap(),
+            )),
+            0x1a => u64::from(u32::from_be_bytes(
+                self.take(buf, name, 4)?.try_into().unwrap(),
+            )),
+            0x1b => u64::from_be_bytes(self.take(buf, name, 8)?.try_into().unwrap()),
+            0x1f => return Err(DecodeError::IndefiniteSize),
+            _ => return Err(DecodeError::Unsupported { name, found: byte }),
+        };
+        Ok((byte >> 5, arg))
+    }
+
+    /// Adds items of an array or map to be scanned.
+    fn push_items(
+        &mut self,
+        buf: &[u8],
+        name: &'static str,
+        count: u64,
+    ) -> Result<(), DecodeError<Infallible>> {
+        // Every item takes at least one byte, which also bounds huge lengths.
+        let available = (buf.len() - self.pos) as u64;
+        match self.remaining.checked_add(count) {
+            Some(remaining) if remaining <= available => {
+                self.remaining = remaining;
+                Ok(())
+            }
+            remaining => {
+                self.missing = remaining.unwrap_or(u64::MAX) - available;
+                Err(eof(name, count))
+            }
+        }
+    }
+
+    /// Scans a single item, returns the CID if it is one.
+    fn step(&mut self, buf: &[u8]) -> Result<Option<Cid>, DecodeError<Infallible>> {
+        self.remaining -= 1;
+        let (major, arg) = self.header(buf, "item")?;
+        match major {
+            major::UNSIGNED | major::NEGATIVE | major::SIMPLE => Ok(None),
+            major::BYTES => self.take(buf, "bytes", arg).map(|_| None),
+            major::STRING => self.take(buf, "str", arg).map(|_| None),
+            major::ARRAY => self.push_items(buf, "array", arg).map(|_| None),
+            major::MAP => self
+                .push_items(buf, "map", arg.saturating_mul(2))
+                .map(|_| None),
+            major::TAG => {
+                let name = "CBOR tag";
+                if arg != u64::from(CBOR_TAGS_CID) {
+                    return Err(DecodeError::Mismatch {
+                        name,
+                        found: u8::try_from(arg).unwrap_or(u8::MAX),
+                    });
+                }
+                let (major, len) = self.header(buf, "CID")?;
+                if major != major::BYTES {
+                    return Err(DecodeError::Mismatch {
+                        name: "CID",
+                        found: major << 5,
+                    });
+                }
+                let bytes = self.take(buf, "CID", len)?;
+                Cid::from_bytes(bytes)
+                    .map(Some)
+                    .map_err(|err| DecodeError::Msg(format!("Invalid CID: {err}")))
+            }
+            _ => unreachable!("major types only have three bits"),
+        }
+    }
+}
+
+fn eof(name: &'static str, len: u64) -> DecodeError<Infallible> {
+    DecodeError::Eof {
+        name,
+        expect: Len::new(usize::try_from(len).unwrap_or(usize::MAX)),
+    }
+}
@@ -0,0 +1,263 @@
+//! The [`drisl!`](crate::drisl!) macro.
+//!
+//! Adapted from the `json!` macro of [`serde_json`](https://github.com/serde-rs/json).
+
+/// Constructs a [`Value`](crate::drisl::Value) from a JSON-like literal.
+///
+/// Map keys must be strings, values can be any expression that converts into a `Value`,
+/// including [`Cid`](crate::cid::Cid)s and other `Value`s.
+///
+/// ```
+/// # use dasl::{cid::{Cid, Codec}, drisl, drisl::Value};
+/// let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+/// let name = "bar";
+/// let value = drisl!({
+///     "name": name,
+///     "link": cid,
+///     "bytes": vec![0x01u8, 0x02],
+///     "tags": ["a", "b", null],
+///     "nested": {
+///         "count": 3,
+///         "ratio": 0.5,
+///         "active": true,
+///     },
+/// });
+///
+/// let Value::Map(map) = &value else { unreachable!() };
+/// assert_eq!(map["link"], Value::Cid(cid));
+/// assert_eq!(map["bytes"], Value::Bytes(vec![0x01, 0x02]));
+/// ```
+#[macro_export]
+macro_rules! drisl {
+    // Hide distracting implementation details from the generated rustdoc.
+    ($($drisl:tt)+) => {
+        $crate::drisl_internal!($($drisl)+)
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! drisl_internal {
+    //////////////////////////////////////////////////////////////////////////
+    // TT muncher for parsing the inside of an array [...]. Produces a vec![...]
+    // of the elements.
+    //
+    // Must be invoked as: drisl_internal!(@array [] $($tt)*)
+    //////////////////////////////////////////////////////////////////////////
+
+    // Done with trailing comma.
+    (@array [$($elems:expr,)*]) => {
+        $crate::__private::vec![$($elems,)*]
+    };
+
+    // Done without trailing comma.
+    (@array [$($elems:expr),*]) => {
+        $crate::__private::vec![$($elems),*]
+    };
+
+    // Next element is `null`.
+    (@array [$($elems:expr,)*] null $($rest:tt)*) => {
+        $crate::drisl_internal!(@array [$($elems,)* $crate::drisl_internal!(null)] $($rest)*)
+    };
+
+    // Next element is `true`.
+    (@array [$($elems:expr,)*] true $($rest:tt)*) => {
+        $crate::drisl_internal!(@array [$($elems,)* $crate::drisl_internal!(true)] $($rest)*)
+    };
+
+    // Next element is `false`.
+    (@array [$($elems:expr,)*] false $($rest:tt)*) => {
+        $crate::drisl_internal!(@array [$($elems,)* $crate::drisl_internal!(false)] $($rest)*)
+    };
+
+    // Next element is an array.
+    (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
+        $crate::drisl_internal!(@array [$($elems,)* $crate::drisl_internal!([$($array)*])] $($rest)*)
+    };
+
+    // Next element is a map.
+    (@array [$($elems:expr,)*] {$($map:tt)*} $($rest:tt)*) => {
+        $crate::drisl_internal!(@array [$($elems,)* $crate::drisl_internal!({$($map)*})] $($rest)*)
+    };
+
+    // Next element is an expression followed by comma.
+    (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+        $crate::drisl_internal!(@array [$($elems,)* $crate::drisl_internal!($next),] $($rest)*)
+    };
+
+    // Last element is an expression with no trailing comma.
+    (@array [$($elems:expr,)*] $last:expr) => {
+        $crate::drisl_internal!(@array [$($elems,)* $crate::drisl_internal!($last)])
+    };
+
+    // Comma after the most recent element.
+    (@array [$($elems:expr),*] , $($rest:tt)*) => {
+        $crate::drisl_internal!(@array [$($elems,)*] $($rest)*)
+    };
+
+    // Unexpected token after most recent element.
+    (@array [$($elems:expr),*] $unexpected:tt $($rest:tt)*) => {
+        $crate::drisl_unexpected!($unexpected)
+    };
+
+    //////////////////////////////////////////////////////////////////////////
+    // TT muncher for parsing the inside of an object {...}. Each entry is
+    // inserted into the given map variable.
+    //
+    // Must be invoked as: drisl_internal!(@object $map () ($($tt)*) ($($tt)*))
+    //
+    // We require two copies of the input tokens so that we can match on one
+    // copy and trigger errors on the other copy.
+    //////////////////////////////////////////////////////////////////////////
+
+    // Done.
+    (@object $object:ident () () ()) => {};
+
+    // Insert the current entry followed by trailing comma.
+    (@object $object:ident [$($key:tt)+] ($value:expr) , $($rest:tt)*) => {
+        let _ = $object.insert(($($key)+).into(), $value);
+        $crate::drisl_internal!(@object $object () ($($rest)*) ($($rest)*));
+    };
+
+    // Current entry followed by unexpected token.
+    (@object $object:ident [$($key:tt)+] ($value:expr) $unexpected:tt $($rest:tt)*) => {
+        $crate::drisl_unexpected!($unexpected);
+    };
+
+    // Insert the last entry without trailing comma.
+    (@object $object:ident [$($key:tt)+] ($value:expr)) => {
+        let _ = $object.insert(($($key)+).into(), $value);
+    };
+
+    // Next value is `null`.
+    (@object $object:ident ($($key:tt)+) (: null $($rest:tt)*) $copy:tt) => {
+        $crate::drisl_internal!(@object $object [$($key)+] ($crate::drisl_internal!(null)) $($rest)*);
+    };
+
+    // Next value is `true`.
+    (@object $object:ident ($($key:tt)+) (: true $($rest:tt)*) $copy:tt) => {
+        $crate::drisl_internal!(@object $object [$($key)+] ($crate::drisl_internal!(true)) $($rest)*);
+    };
+
+    // Next value is `false`.
+    (@object $object:ident ($($key:tt)+) (: false $($rest:tt)*) $copy:tt) => {
+        $crate::drisl_internal!(@object $object [$($key)+] ($crate::drisl_internal!(false)) $($rest)*);
+    };
+
+    // Next value is an array.
+    (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
+        $crate::drisl_internal!(@object $object [$($key)+] ($crate::drisl_internal!([$($array)*])) $($rest)*);
+    };
+
+    // Next value is a map.
+    (@object $object:ident ($($key:tt)+) (: {$($map:tt)*} $($rest:tt)*) $copy:tt) => {
+        $crate::drisl_internal!(@object $object [$($key)+] ($crate::drisl_internal!({$($map)*})) $($rest)*);
+    };
+
+    // Next value is an expression followed by comma.
+    (@object $object:ident ($($key:tt)+) (: $value:expr , $($rest:tt)*) $copy:tt) => {
+        $crate::drisl_internal!(@object $object [$($key)+] ($crate::drisl_internal!($value)) , $($rest)*);
+    };
+
+    // Last value is an expression with no trailing comma.
+    (@object $object:ident ($($key:tt)+) (: $value:expr) $copy:tt) => {
+        $crate::drisl_internal!(@object $object [$($key)+] ($crate::drisl_internal!($value)));
+    };
+
+    // Missing value for last entry. Trigger a reasonable error message.
+    (@object $object:ident ($($key:tt)+) (:) $copy:tt) => {
+        // "unexpected end of macro invocation"
+        $crate::drisl_internal!();
+    };
+
+    // Missing colon and value for last entry. Trigger a reasonable error
+    // message.
+    (@object $object:ident ($($key:tt)+) () $copy:tt) => {
+        // "unexpected end of macro invocation"
+        $crate::drisl_internal!();
+    };
+
+    // Misplaced colon. Trigger a reasonable error message.
+    (@object $object:ident () (: $($rest:tt)*) ($colon:tt $($copy:tt)*)) => {
+        // Takes no arguments so "no rules expected the token `:`".
+        $crate::drisl_unexpected!($colon);
+    };
+
+    // Found a comma inside a key. Trigger a reasonable error message.
+    (@object $object:ident ($($key:tt)*) (, $($rest:tt)*) ($comma:tt $($copy:tt)*)) => {
+        // Takes no arguments so "no rules expected the token `,`".
+        $crate::drisl_unexpected!($comma);
+    };
+
+    // Key is fully parenthesized. This avoids clippy double_parens false
+    // positives because the parenthesization may be necessary here.
+    (@object $object:ident () (($key:expr) : $($rest:tt)*) $copy:tt) => {
+        $crate::drisl_internal!(@object $object ($key) (: $($rest)*) (: $($rest)*));
+    };
+
+    // Refuse to absorb colon token into key expression.
+    (@object $object:ident ($($key:tt)*) (: $($unexpected:tt)+) $copy:tt) => {
+        $crate::drisl_expect_expr_comma!($($unexpected)+);
+    };
+
+    // Munch a token into the current key.
+    (@object $object:ident ($($key:tt)*) ($tt:tt $($rest:tt)*) $copy:tt) => {
+        $crate::drisl_internal!(@object $object ($($key)* $tt) ($($rest)*) ($($rest)*));
+    };
+
+    //////////////////////////////////////////////////////////////////////////
+    // The main implementation.
+    //
+    // Must be invoked as: drisl_internal!($($drisl)+)
+    //////////////////////////////////////////////////////////////////////////
+
+    (null) => {
+        $crate::drisl::Value::Null
+    };
+
+    (true) => {
+        $crate::drisl::Value::Bool(true)
+    };
+
+    (false) => {
+        $crate::drisl::Value::Bool(false)
+    };
+
+    ([]) => {
+        $crate::drisl::Value::Array($crate::__private::vec![])
+    };
+
+    ([ $($tt:tt)+ ]) => {
+        $crate::drisl::Value::Array($crate::drisl_internal!(@array [] $($tt)+))
+    };
+
+    ({}) => {
+        $crate::drisl::Value::Map($crate::__private::BTreeMap::new())
+    };
+
+    ({ $($tt:tt)+ }) => {
+        $crate::drisl::Value::Map({
+            let mut object = $crate::__private::BTreeMap::new();
+            $crate::drisl_internal!(@object object () ($($tt)+) ($($tt)+));
+            object
+        })
+    };
+
+    // Anything that converts into a `Value`: numbers, strings, CIDs, variables etc.
+    // Must be below every other rule.
+    ($other:expr) => {
+        $crate::drisl::Value::from($other)
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! drisl_unexpected {
+    () => {};
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! drisl_expect_expr_comma {
+    ($e:expr , $($tt:tt)*) => {};
+}
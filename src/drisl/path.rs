@@ -0,0 +1,33 @@
+//! Paths to nested values.
+
+use alloc::string::{String, ToString};
+
+/// A step of the path to a nested value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PathSegment {
+    /// The map entry with the given key.
+    Key(String),
+    /// The array element at the given index.
+    Index(usize),
+}
+
+/// Formats a path like `entries[1].size`, the empty path is the empty string.
+pub(crate) fn format_path(path: &[PathSegment]) -> String {
+    let mut formatted = String::new();
+    for segment in path {
+        match segment {
+            PathSegment::Key(key) => {
+                if !formatted.is_empty() {
+                    formatted.push('.');
+                }
+                formatted.push_str(key);
+            }
+            PathSegment::Index(index) => {
+                formatted.push('[');
+                formatted.push_str(&index.to_string());
+                formatted.push(']');
+            }
+        }
+    }
+    formatted
+}
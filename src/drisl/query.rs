@@ -0,0 +1,495 @@
+//! A small jq-like query language over [`Value`]s.
+//!
+//! A query is a pipeline of stages separated by `|`. Every stage is evaluated against each result
+//! of the previous stage, starting with the input value.
+//!
+//! | Syntax                | Meaning                                                       |
+//! |-----------------------|---------------------------------------------------------------|
+//! | `.`                   | The value itself                                              |
+//! | `.key`, `."any key"`  | The map entry with the given key                              |
+//! | `.[2]`, `.[-1]`       | The array element at the given index, negative from the end  |
+//! | `.[]`                 | All array elements or map values                              |
+//! | `select(.path OP v)`  | The value itself, if any value at `.path` compares true to `v` |
+//! | `select(.path \| has("k"))` | The value itself, if any value at `.path` is a map with key `k` |
+//! | `select(.path \| contains("s"))` | The value itself, if any string at `.path` contains `s` |
+//!
+//! Path segments can be chained, e.g. `.posts[0].author`. `OP` is one of `==`, `!=`, `<`, `<=`,
+//! `>` and `>=` and `v` is a string, number, `true`, `false`, `null` or `cid("...")` literal.
+//! Integers and floats compare numerically, values of different kinds never compare true (except
+//! with `!=`). The path can be left out of `has` and `contains` to test the value itself, e.g.
+//! `select(has("email"))`.
+//!
+//! # Examples
+//!
+//! ```
+//! # use dasl::{drisl, drisl::query::Query};
+//! let value = drisl!({
+//!     "posts": [
+//!         { "author": "alice", "likes": 3 },
+//!         { "author": "bob", "likes": 12 },
+//!     ],
+//! });
+//!
+//! let query: Query = ".posts[] | select(.likes > 10) | .author".parse().unwrap();
+//! let authors: Vec<_> = query.eval(&value).filter_map(|v| v.as_str()).collect();
+//! assert_eq!(authors, ["bob"]);
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{cmp::Ordering, str::FromStr};
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+use thiserror::Error;
+
+use super::Value;
+#[cfg(feature = "std")]
+use super::{DecodeError, de::iter_from_reader};
+
+/// An error when parsing a query.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum QueryError {
+    #[error("Unexpected end of query")]
+    UnexpectedEnd,
+    #[error("Unexpected character {found:?} at position {position}")]
+    Unexpected { position: usize, found: char },
+    #[error("Invalid number at position {position}")]
+    InvalidNumber { position: usize },
+    #[error("Invalid CID at position {position}")]
+    InvalidCid { position: usize },
+}
+
+/// A compiled query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Key(String),
+    Index(i64),
+    Iterate,
+    Select(Filter),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Filter {
+    path: Vec<Step>,
+    test: Test,
+}
+
+/// What a filter tests the values at its path for.
+#[derive(Debug, Clone, PartialEq)]
+enum Test {
+    Compare(Op, Value),
+    Has(String),
+    Contains(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Query {
+    /// Compiles a query.
+    pub fn parse(query: &str) -> Result<Self, QueryError> {
+        let mut parser = Parser {
+            input: query,
+            pos: 0,
+        };
+        let steps = parser.parse_query()?;
+        Ok(Self { steps })
+    }
+
+    /// Evaluates the query against a value, yielding all matching sub-values.
+    pub fn eval<'a>(&self, value: &'a Value) -> impl Iterator<Item = &'a Value> + use<'a> {
+        run(&self.steps, value).into_iter()
+    }
+
+    /// Lazily evaluates the query against a stream of concatenated DRISL values.
+    ///
+    /// Values are decoded one at a time, the matches of each value are yielded before the next
+    /// one is decoded.
+    #[cfg(feature = "std")]
+    pub fn eval_reader<R: BufRead>(
+        &self,
+        reader: R,
+    ) -> impl Iterator<Item = Result<Value, DecodeError<std::io::Error>>> {
+        iter_from_reader::<Value, R>(reader).flat_map(move |item| match item {
+            Ok(value) => self.eval(&value).cloned().map(Ok).collect::<Vec<_>>(),
+            Err(err) => vec![Err(err)],
+        })
+    }
+}
+
+impl FromStr for Query {
+    type Err = QueryError;
+
+    fn from_str(query: &str) -> Result<Self, Self::Err> {
+        Self::parse(query)
+    }
+}
+
+/// Applies all steps to a value.
+fn run<'a>(steps: &[Step], value: &'a Value) -> Vec<&'a Value> {
+    let mut current = vec![value];
+    for step in steps {
+        let mut next = Vec::new();
+        for value in current {
+            step.apply(value, &mut next);
+        }
+        current = next;
+    }
+    current
+}
+
+impl Step {
+    fn apply<'a>(&self, value: &'a Value, out: &mut Vec<&'a Value>) {
+        match self {
+            Self::Key(key) => out.extend(value.get(key)),
+            Self::Index(index) => {
+                let Some(array) = value.as_array() else {
+                    return;
+                };
+                let index = if *index < 0 {
+                    array.len().checked_sub(index.unsigned_abs() as usize)
+                } else {
+                    Some(*index as usize)
+                };
+                out.extend(index.and_then(|index| array.get(index)));
+            }
+            Self::Iterate => match value {
+                Value::Array(array) => out.extend(array),
+                Value::Map(map) => out.extend(map.values()),
+                _ => {}
+            },
+            Self::Select(filter) => {
+                if filter.matches(value) {
+                    out.push(value);
+                }
+            }
+        }
+    }
+}
+
+impl Filter {
+    fn matches(&self, value: &Value) -> bool {
+        run(&self.path, value)
+            .into_iter()
+            .any(|value| match &self.test {
+                Test::Compare(op, literal) => op.test(compare(value, literal)),
+                Test::Has(key) => value.get(key).is_some(),
+                Test::Contains(needle) => {
+                    value.as_str().is_some_and(|s| s.contains(needle.as_str()))
+                }
+            })
+    }
+}
+
+impl Op {
+    fn test(self, ordering: Option<Ordering>) -> bool {
+        match self {
+            Self::Eq => ordering == Some(Ordering::Equal),
+            Self::Ne => ordering != Some(Ordering::Equal),
+            Self::Lt => ordering == Some(Ordering::Less),
+            Self::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Self::Gt => ordering == Some(Ordering::Greater),
+            Self::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        }
+    }
+}
+
+/// Compares two scalars, returns `None` if they aren't comparable.
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::Integer(a), Value::Integer(b)) => Some(a.cmp(b)),
+        (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+        (Value::Integer(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+        (Value::Float(a), Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+        (Value::Text(a), Value::Text(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        (Value::Null, Value::Null) => Some(Ordering::Equal),
+        (Value::Cid(a), Value::Cid(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.input[self.pos..].starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn unexpected(&self) -> QueryError {
+        match self.peek() {
+            Some(found) => QueryError::Unexpected {
+                position: self.pos,
+                found,
+            },
+            None => QueryError::UnexpectedEnd,
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), QueryError> {
+        if self.peek() == Some(c) {
+            self.bump();
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn parse_query(&mut self) -> Result<Vec<Step>, QueryError> {
+        let mut steps = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.eat("select") {
+                steps.push(Step::Select(self.parse_filter()?));
+            } else {
+                steps.extend(self.parse_path()?);
+            }
+            self.skip_whitespace();
+            match self.peek() {
+                None => return Ok(steps),
+                Some('|') => {
+                    self.bump();
+                }
+                Some(_) => return Err(self.unexpected()),
+            }
+        }
+    }
+
+    fn parse_filter(&mut self) -> Result<Filter, QueryError> {
+        self.skip_whitespace();
+        self.expect('(')?;
+        self.skip_whitespace();
+        // `has` and `contains` test the value itself if there is no path.
+        let path = match self.peek() {
+            Some('.') => Some(self.parse_path()?),
+            _ => None,
+        };
+        self.skip_whitespace();
+        let test = match path {
+            Some(_) if !self.eat("|") => {
+                let op = self.parse_op()?;
+                self.skip_whitespace();
+                Test::Compare(op, self.parse_literal()?)
+            }
+            _ => {
+                self.skip_whitespace();
+                self.parse_test()?
+            }
+        };
+        self.skip_whitespace();
+        self.expect(')')?;
+        Ok(Filter {
+            path: path.unwrap_or_default(),
+            test,
+        })
+    }
+
+    /// Parses `has("key")` or `contains("string")`.
+    fn parse_test(&mut self) -> Result<Test, QueryError> {
+        let test = if self.eat("has") {
+            Test::Has
+        } else if self.eat("contains") {
+            Test::Contains
+        } else {
+            return Err(self.unexpected());
+        };
+        Ok(test(self.parse_argument()?))
+    }
+
+    /// Parses a string in parentheses, the argument of a function.
+    fn parse_argument(&mut self) -> Result<String, QueryError> {
+        self.skip_whitespace();
+        self.expect('(')?;
+        self.skip_whitespace();
+        let argument = self.parse_string()?;
+        self.skip_whitespace();
+        self.expect(')')?;
+        Ok(argument)
+    }
+
+    fn parse_path(&mut self) -> Result<Vec<Step>, QueryError> {
+        self.expect('.')?;
+        let mut steps = Vec::new();
+        if self.peek().is_some_and(|c| c == '"' || is_key_char(c)) {
+            steps.push(Step::Key(self.parse_key()?));
+        }
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.bump();
+                    if self.peek() != Some('[') {
+                        steps.push(Step::Key(self.parse_key()?));
+                    }
+                }
+                Some('[') => {
+                    self.bump();
+                    steps.push(self.parse_bracket()?);
+                }
+                _ => return Ok(steps),
+            }
+        }
+    }
+
+    fn parse_key(&mut self) -> Result<String, QueryError> {
+        if self.peek() == Some('"') {
+            return self.parse_string();
+        }
+        let start = self.pos;
+        while self.peek().is_some_and(is_key_char) {
+            self.bump();
+        }
+        if self.pos == start {
+            return Err(self.unexpected());
+        }
+        Ok(self.input[start..self.pos].to_string())
+    }
+
+    /// Parses the rest of a bracket after the `[`.
+    fn parse_bracket(&mut self) -> Result<Step, QueryError> {
+        self.skip_whitespace();
+        let step = match self.peek() {
+            Some(']') => Step::Iterate,
+            Some('"') => Step::Key(self.parse_string()?),
+            _ => {
+                let position = self.pos;
+                match self.parse_number()? {
+                    Value::Integer(index) => Step::Index(
+                        index
+                            .try_into()
+                            .map_err(|_| QueryError::InvalidNumber { position })?,
+                    ),
+                    _ => return Err(QueryError::InvalidNumber { position }),
+                }
+            }
+        };
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(step)
+    }
+
+    fn parse_op(&mut self) -> Result<Op, QueryError> {
+        for (token, op) in [
+            ("==", Op::Eq),
+            ("!=", Op::Ne),
+            ("<=", Op::Le),
+            (">=", Op::Ge),
+            ("<", Op::Lt),
+            (">", Op::Gt),
+        ] {
+            if self.eat(token) {
+                return Ok(op);
+            }
+        }
+        Err(self.unexpected())
+    }
+
+    fn parse_literal(&mut self) -> Result<Value, QueryError> {
+        let position = self.pos;
+        if self.peek() == Some('"') {
+            Ok(Value::Text(self.parse_string()?))
+        } else if self.eat("true") {
+            Ok(Value::Bool(true))
+        } else if self.eat("false") {
+            Ok(Value::Bool(false))
+        } else if self.eat("null") {
+            Ok(Value::Null)
+        } else if self.eat("cid") {
+            let cid = self.parse_argument()?;
+            cid.parse()
+                .map(Value::Cid)
+                .map_err(|_| QueryError::InvalidCid { position })
+        } else {
+            self.parse_number()
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, QueryError> {
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.bump();
+        }
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.unexpected());
+        }
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-'))
+        {
+            self.bump();
+        }
+        let number = &self.input[start..self.pos];
+        let invalid = || QueryError::InvalidNumber { position: start };
+        if number.contains(['.', 'e', 'E']) {
+            number.parse().map(Value::Float).map_err(|_| invalid())
+        } else {
+            number.parse().map(Value::Integer).map_err(|_| invalid())
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, QueryError> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.bump().ok_or(QueryError::UnexpectedEnd)? {
+                '"' => return Ok(string),
+                '\\' => {
+                    let position = self.pos;
+                    let escaped = match self.bump().ok_or(QueryError::UnexpectedEnd)? {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        c @ ('"' | '\\') => c,
+                        found => return Err(QueryError::Unexpected { position, found }),
+                    };
+                    string.push(escaped);
+                }
+                c => string.push(c),
+            }
+        }
+    }
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
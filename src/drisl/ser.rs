@@ -1,15 +1,31 @@
 //! Serialization.
-use std::{collections::TryReserveError, string::ToString, vec::Vec};
+use alloc::{collections::TryReserveError, format, string::ToString, vec::Vec};
+use core::convert::Infallible;
 
-pub use cbor4ii::core::utils::{BufWriter, IoWriter};
-use cbor4ii::core::{
-    enc::{self, Encode},
-    types,
-};
+use cbor4ii::core::types;
 use serde::{Serialize, ser};
 
-use super::{CBOR_TAGS_CID, error::EncodeError};
-use crate::cid::CID_SERDE_PRIVATE_IDENTIFIER;
+use super::{CBOR_TAGS_CID, Value, error::EncodeError};
+use crate::cid::{CID_SERDE_PRIVATE_IDENTIFIER, Cid, Codec, Hasher, Multihash};
+
+mod encoder;
+#[cfg(feature = "std")]
+mod io;
+mod iterative;
+mod options;
+mod write;
+
+#[cfg(feature = "std")]
+pub use self::io::{BufIoWriter, WriteOptions};
+#[cfg(feature = "std")]
+pub use self::write::IoWriter;
+pub(crate) use self::write::{array_header, encode, map_header};
+pub use self::{
+    encoder::Encoder,
+    iterative::{value_to_vec, value_to_vec_with},
+    options::{EncodeOptions, NonFiniteFloats},
+    write::{BufWriter, Write},
+};
 
 /// Serializes a value to a vector.
 pub fn to_vec<T>(value: &T) -> Result<Vec<u8>, EncodeError<TryReserveError>>
@@ -22,25 +38,293 @@ where
     Ok(serializer.into_inner().into_inner())
 }
 
+/// Serializes a value to [`bytes::Bytes`], which takes over the encoded vector without copying it.
+///
+/// Byte strings that are `Bytes` themselves can be written without copying them first with the
+/// [`shared_bytes`](crate::drisl::serde_helpers::shared_bytes) adapter.
+#[cfg(feature = "bytes")]
+pub fn to_bytes<T>(value: &T) -> Result<bytes::Bytes, EncodeError<TryReserveError>>
+where
+    T: Serialize + ?Sized,
+{
+    to_vec(value).map(bytes::Bytes::from)
+}
+
+/// Serializes a value to a vector, with the given options.
+pub fn to_vec_with<T>(
+    value: &T,
+    options: EncodeOptions,
+) -> Result<Vec<u8>, EncodeError<TryReserveError>>
+where
+    T: Serialize + ?Sized,
+{
+    let writer = BufWriter::new(Vec::new());
+    let mut serializer = Serializer::new(writer).with_options(options);
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner().into_inner())
+}
+
+/// Serializes a value to the end of a vector.
+///
+/// Unlike [`to_vec`], this reuses the vector's allocation, which pays off when encoding many
+/// values in a row. Nothing is appended if encoding fails.
+///
+/// ```
+/// # use dasl::drisl;
+/// let mut buf = Vec::new();
+/// for record in [("a", 1), ("b", 2)] {
+///     buf.clear();
+///     drisl::ser::to_vec_in(&mut buf, &record).unwrap();
+///     assert_eq!(buf, drisl::to_vec(&record).unwrap());
+/// }
+/// ```
+pub fn to_vec_in<T>(buf: &mut Vec<u8>, value: &T) -> Result<(), EncodeError<TryReserveError>>
+where
+    T: Serialize + ?Sized,
+{
+    let len = buf.len();
+    let mut serializer = Serializer::new(BufWriter::new(core::mem::take(buf)));
+    let result = value.serialize(&mut serializer);
+    *buf = serializer.into_inner().into_inner();
+    if result.is_err() {
+        buf.truncate(len);
+    }
+    result
+}
+
 /// Serializes a value to a writer.
+///
+/// Small writes are batched in a buffer, see [`WriteOptions`] for the defaults.
+#[cfg(feature = "std")]
 pub fn to_writer<W, T>(writer: W, value: &T) -> Result<(), EncodeError<std::io::Error>>
 where
     W: std::io::Write,
     T: Serialize,
 {
-    let mut serializer = Serializer::new(IoWriter::new(writer));
-    value.serialize(&mut serializer)
+    to_writer_with(writer, value, WriteOptions::default())
+}
+
+/// Serializes a value to a writer, with the given options.
+#[cfg(feature = "std")]
+pub fn to_writer_with<W, T>(
+    writer: W,
+    value: &T,
+    options: WriteOptions,
+) -> Result<(), EncodeError<std::io::Error>>
+where
+    W: std::io::Write,
+    T: Serialize,
+{
+    let mut serializer = Serializer::new(BufIoWriter::new(writer, options));
+    value.serialize(&mut serializer)?;
+    serializer.into_inner().flush().map_err(EncodeError::Write)
+}
+
+/// Serializes a value to an async writer.
+///
+/// The value is encoded into memory first, then written in one go.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// # use dasl::drisl::ser;
+/// let mut out = Vec::new();
+/// ser::to_async_writer(&mut out, "foobar").await.unwrap();
+/// assert_eq!(out, [0x66, 0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72]);
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn to_async_writer<W, T>(
+    mut writer: W,
+    value: &T,
+) -> Result<(), EncodeError<std::io::Error>>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+    T: Serialize + ?Sized,
+{
+    use tokio::io::AsyncWriteExt;
+
+    let bytes = to_vec(value).map_err(|err| match err {
+        EncodeError::Msg(msg) => EncodeError::Msg(msg),
+        EncodeError::IntegerOutOfRange => EncodeError::IntegerOutOfRange,
+        EncodeError::Write(err) => {
+            EncodeError::Write(std::io::Error::new(std::io::ErrorKind::OutOfMemory, err))
+        }
+    })?;
+    writer.write_all(&bytes).await?;
+    Ok(())
+}
+
+/// Serializes a value to a vector, computing its `CID` in the same pass.
+///
+/// ```
+/// # use dasl::{cid::{Cid, Codec, Multihash}, drisl};
+/// let (bytes, cid) = drisl::ser::to_vec_with_cid(&("hello", 1), Multihash::Blake3).unwrap();
+/// assert_eq!(bytes, drisl::to_vec(&("hello", 1)).unwrap());
+/// assert_eq!(cid, Cid::digest_blake3(Codec::Drisl, &bytes));
+/// ```
+pub fn to_vec_with_cid<T>(
+    value: &T,
+    multihash: Multihash,
+) -> Result<(Vec<u8>, Cid), EncodeError<TryReserveError>>
+where
+    T: Serialize + ?Sized,
+{
+    let writer = HashingBufWriter {
+        buffer: BufWriter::new(Vec::new()),
+        hasher: Hasher::new(multihash),
+    };
+    let mut serializer = Serializer::new(writer);
+    value.serialize(&mut serializer)?;
+    let HashingBufWriter { buffer, hasher } = serializer.into_inner();
+    Ok((buffer.into_inner(), hasher.finalize(Codec::Drisl)))
+}
+
+/// A [`BufWriter`] that hashes everything pushed into it.
+struct HashingBufWriter {
+    buffer: BufWriter,
+    hasher: Hasher,
+}
+
+impl Write for HashingBufWriter {
+    type Error = TryReserveError;
+
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+        self.buffer.push(input)?;
+        self.hasher.update(input);
+        Ok(())
+    }
+}
+
+/// Computes the `CID` of a value without keeping its encoding.
+///
+/// The encoding is streamed into the hasher through a small fixed-size buffer.
+///
+/// ```
+/// # use dasl::{cid::{Cid, Codec, Multihash}, drisl};
+/// let cid = drisl::ser::cid_of(&("hello", 1), Multihash::Sha2256).unwrap();
+/// let bytes = drisl::to_vec(&("hello", 1)).unwrap();
+/// assert_eq!(cid, Cid::digest_sha2(Codec::Drisl, &bytes));
+/// ```
+pub fn cid_of<T>(value: &T, multihash: Multihash) -> Result<Cid, EncodeError<Infallible>>
+where
+    T: Serialize + ?Sized,
+{
+    let writer = HashingSink {
+        buffer: [0; HASHING_SINK_LEN],
+        len: 0,
+        hasher: Hasher::new(multihash),
+    };
+    let mut serializer = Serializer::new(writer);
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner().finalize())
+}
+
+/// Computes the length of a value's encoding without keeping it.
+///
+/// The result is exactly the length of [`to_vec`]'s output. Only the entries of maps are buffered,
+/// as their keys have to be sorted.
+///
+/// ```
+/// # use dasl::drisl;
+/// let len = drisl::ser::encoded_len(&("hello", 1)).unwrap();
+/// assert_eq!(len, drisl::to_vec(&("hello", 1)).unwrap().len());
+/// ```
+pub fn encoded_len<T>(value: &T) -> Result<usize, EncodeError<Infallible>>
+where
+    T: Serialize + ?Sized,
+{
+    let mut serializer = Serializer::new(CountingSink(0));
+    value.serialize(&mut serializer)?;
+    Ok(serializer.into_inner().0)
+}
+
+/// Counts the bytes pushed into it.
+struct CountingSink(usize);
+
+impl Write for CountingSink {
+    type Error = Infallible;
+
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+        self.0 += input.len();
+        Ok(())
+    }
+}
+
+/// Size of the buffer used by [`HashingSink`].
+const HASHING_SINK_LEN: usize = 1024;
+
+/// Hashes everything pushed into it, batching small writes in a buffer.
+struct HashingSink {
+    buffer: [u8; HASHING_SINK_LEN],
+    len: usize,
+    hasher: Hasher,
+}
+
+impl HashingSink {
+    fn flush(&mut self) {
+        self.hasher.update(&self.buffer[..self.len]);
+        self.len = 0;
+    }
+
+    fn finalize(mut self) -> Cid {
+        self.flush();
+        self.hasher.finalize(Codec::Drisl)
+    }
+}
+
+impl Write for HashingSink {
+    type Error = Infallible;
+
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+        if self.len + input.len() > HASHING_SINK_LEN {
+            self.flush();
+        }
+        if input.len() > HASHING_SINK_LEN {
+            self.hasher.update(input);
+        } else {
+            self.buffer[self.len..self.len + input.len()].copy_from_slice(input);
+            self.len += input.len();
+        }
+        Ok(())
+    }
 }
 
 /// A structure for serializing Rust values to DRISL.
+///
+/// It can also be used with [`serde_transcode`](https://docs.rs/serde-transcode) to convert other
+/// formats into canonical DRISL without building a [`Value`] first. Map entries are
+/// sorted as DRISL requires, which buffers each map until it is complete.
+///
+/// ```
+/// # use dasl::drisl::{self, ser::{BufWriter, Serializer}};
+/// let json = r#"{"size": [1, 2.5], "name": "hello"}"#;
+///
+/// let mut serializer = Serializer::new(BufWriter::new(Vec::new()));
+/// let mut deserializer = serde_json::Deserializer::from_str(json);
+/// serde_transcode::transcode(&mut deserializer, &mut serializer).unwrap();
+/// deserializer.end().unwrap();
+/// let bytes = serializer.into_inner().into_inner();
+/// assert_eq!(bytes, drisl::to_vec(&dasl::drisl!({ "name": "hello", "size": [1, 2.5] })).unwrap());
+/// ```
 pub struct Serializer<W> {
     writer: W,
+    options: EncodeOptions,
 }
 
 impl<W> Serializer<W> {
     /// Creates a new CBOR serializer.
     pub fn new(writer: W) -> Serializer<W> {
-        Serializer { writer }
+        Serializer {
+            writer,
+            options: EncodeOptions::default(),
+        }
+    }
+
+    /// Sets the options used for encoding.
+    pub fn with_options(mut self, options: EncodeOptions) -> Self {
+        self.options = options;
+        self
     }
 
     /// Returns the underlying writer.
@@ -49,7 +333,7 @@ impl<W> Serializer<W> {
     }
 }
 
-impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
+impl<'a, W: Write> serde::Serializer for &'a mut Serializer<W> {
     type Ok = ();
     type Error = EncodeError<W::Error>;
 
@@ -63,55 +347,55 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
 
     #[inline]
     fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
@@ -123,15 +407,25 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
 
     #[inline]
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
-        // In DRISL only finite floats are supported.
+        // DRISL doesn't reduce floats: whole-valued floats stay floats instead of becoming
+        // integers, and floats that fit in 16 or 32 bits are still encoded as f64. Only finite
+        // floats are supported, unless the options say otherwise.
         if !v.is_finite() {
-            Err(EncodeError::Msg(
-                "Float must be a finite number, not Infinity or NaN".into(),
-            ))
-        } else {
-            v.encode(&mut self.writer)?;
-            Ok(())
+            match self.options.non_finite_floats {
+                NonFiniteFloats::Reject => {
+                    return Err(EncodeError::Msg(
+                        "Float must be a finite number, not Infinity or NaN".into(),
+                    ));
+                }
+                NonFiniteFloats::Null => return self.serialize_unit(),
+                NonFiniteFloats::Canonicalize => {
+                    encode(NonFiniteFloats::canonicalize(v), &mut self.writer)?;
+                    return Ok(());
+                }
+            }
         }
+        encode(v, &mut self.writer)?;
+        Ok(())
     }
 
     #[inline]
@@ -142,19 +436,19 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
 
     #[inline]
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        types::Bytes(v).encode(&mut self.writer)?;
+        encode(types::Bytes(v), &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
-        types::Null.encode(&mut self.writer)?;
+        encode(types::Null, &mut self.writer)?;
         Ok(())
     }
 
@@ -167,7 +461,7 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
         // The cbor4ii Serde implementation encodes unit as an empty array, for DRISL we encode
         // it as `NULL`.
-        types::Null.encode(&mut self.writer)?;
+        encode(types::Null, &mut self.writer)?;
         Ok(())
     }
 
@@ -207,18 +501,18 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
         variant: &'static str,
         value: &T,
     ) -> Result<Self::Ok, Self::Error> {
-        types::Map::bounded(1, &mut self.writer)?;
-        variant.encode(&mut self.writer)?;
+        map_header(1, &mut self.writer)?;
+        encode(variant, &mut self.writer)?;
         value.serialize(self)
     }
 
     #[inline]
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
         let mem_ser = if let Some(len) = len {
-            types::Array::bounded(len, &mut self.writer)?;
+            array_header(len, &mut self.writer)?;
             None
         } else {
-            Some(Serializer::new(BufWriter::new(Vec::new())))
+            Some(Serializer::new(BufWriter::new(Vec::new())).with_options(self.options))
         };
         Ok(CollectSeq {
             ser: self,
@@ -229,7 +523,7 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
 
     #[inline]
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
-        types::Array::bounded(len, &mut self.writer)?;
+        array_header(len, &mut self.writer)?;
         Ok(BoundedCollect { ser: self })
     }
 
@@ -250,15 +544,21 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        types::Map::bounded(1, &mut self.writer)?;
-        variant.encode(&mut self.writer)?;
-        types::Array::bounded(len, &mut self.writer)?;
+        map_header(1, &mut self.writer)?;
+        encode(variant, &mut self.writer)?;
+        array_header(len, &mut self.writer)?;
         Ok(BoundedCollect { ser: self })
     }
 
     #[inline]
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
-        Ok(CollectMap::new(self))
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        match len {
+            Some(len) if self.options.sorted_keys => {
+                map_header(len, &mut self.writer)?;
+                Ok(CollectMap::streaming(self, len))
+            }
+            _ => Ok(CollectMap::new(self)),
+        }
     }
 
     #[inline]
@@ -267,8 +567,8 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
         _name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStruct, Self::Error> {
-        types::Map::bounded(len, &mut self.writer)?;
-        Ok(CollectMap::new(self))
+        map_header(len, &mut self.writer)?;
+        Ok(CollectMap::for_struct(self, len))
     }
 
     #[inline]
@@ -279,32 +579,28 @@ impl<'a, W: enc::Write> serde::Serializer for &'a mut Serializer<W> {
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
-        types::Map::bounded(1, &mut self.writer)?;
-        variant.encode(&mut self.writer)?;
-        types::Map::bounded(len, &mut self.writer)?;
-        Ok(CollectMap::new(self))
+        map_header(1, &mut self.writer)?;
+        encode(variant, &mut self.writer)?;
+        map_header(len, &mut self.writer)?;
+        Ok(CollectMap::for_struct(self, len))
     }
 
     #[inline]
     fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
-        if !(u64::MAX as i128 >= v && -(u64::MAX as i128 + 1) <= v) {
-            return Err(EncodeError::Msg(
-                "Integer must be within [-u64::MAX-1, u64::MAX] range".into(),
-            ));
+        if !(Value::MIN_INTEGER..=Value::MAX_INTEGER).contains(&v) {
+            return Err(EncodeError::IntegerOutOfRange);
         }
 
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
     #[inline]
     fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
         if (u64::MAX as u128) < v {
-            return Err(EncodeError::Msg(
-                "Unsigned integer must be within [0, u64::MAX] range".into(),
-            ));
+            return Err(EncodeError::IntegerOutOfRange);
         }
-        v.encode(&mut self.writer)?;
+        encode(v, &mut self.writer)?;
         Ok(())
     }
 
@@ -329,7 +625,7 @@ pub struct BoundedCollect<'a, W> {
     ser: &'a mut Serializer<W>,
 }
 
-impl<W: enc::Write> serde::ser::SerializeSeq for CollectSeq<'_, W> {
+impl<W: Write> serde::ser::SerializeSeq for CollectSeq<'_, W> {
     type Ok = ();
     type Error = EncodeError<W::Error>;
 
@@ -337,9 +633,7 @@ impl<W: enc::Write> serde::ser::SerializeSeq for CollectSeq<'_, W> {
     fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
         self.count += 1;
         if let Some(ser) = self.mem_ser.as_mut() {
-            value
-                .serialize(&mut *ser)
-                .map_err(|_| EncodeError::Msg("List element cannot be serialized".to_string()))
+            value.serialize(&mut *ser).map_err(buffered)
         } else {
             value.serialize(&mut *self.ser)
         }
@@ -350,7 +644,7 @@ impl<W: enc::Write> serde::ser::SerializeSeq for CollectSeq<'_, W> {
         // Data was buffered in order to be able to write out the number of elements before they
         // are serialized.
         if let Some(ser) = self.mem_ser {
-            types::Array::bounded(self.count, &mut self.ser.writer)?;
+            array_header(self.count, &mut self.ser.writer)?;
             self.ser.writer.push(&ser.into_inner().into_inner())?;
         }
 
@@ -358,7 +652,7 @@ impl<W: enc::Write> serde::ser::SerializeSeq for CollectSeq<'_, W> {
     }
 }
 
-impl<W: enc::Write> serde::ser::SerializeTuple for BoundedCollect<'_, W> {
+impl<W: Write> serde::ser::SerializeTuple for BoundedCollect<'_, W> {
     type Ok = ();
     type Error = EncodeError<W::Error>;
 
@@ -373,7 +667,7 @@ impl<W: enc::Write> serde::ser::SerializeTuple for BoundedCollect<'_, W> {
     }
 }
 
-impl<W: enc::Write> serde::ser::SerializeTupleStruct for BoundedCollect<'_, W> {
+impl<W: Write> serde::ser::SerializeTupleStruct for BoundedCollect<'_, W> {
     type Ok = ();
     type Error = EncodeError<W::Error>;
 
@@ -388,7 +682,7 @@ impl<W: enc::Write> serde::ser::SerializeTupleStruct for BoundedCollect<'_, W> {
     }
 }
 
-impl<W: enc::Write> serde::ser::SerializeTupleVariant for BoundedCollect<'_, W> {
+impl<W: Write> serde::ser::SerializeTupleVariant for BoundedCollect<'_, W> {
     type Ok = ();
     type Error = EncodeError<W::Error>;
 
@@ -408,53 +702,137 @@ impl<W: enc::Write> serde::ser::SerializeTupleVariant for BoundedCollect<'_, W>
 /// with existing data.
 /// We first serialize each map entry (the key and the value) into a buffer and then sort those
 /// buffers. Once sorted they are written to the actual output.
+///
+/// As all entries are collected before anything is written, this also handles maps whose length
+/// isn't known up front, e.g. structs with `#[serde(flatten)]` fields. Those may end up with
+/// the same key twice, which is rejected.
+///
+/// With [`EncodeOptions::sorted_keys`], entries are written directly instead, and only their keys
+/// are checked to be in order.
 pub struct CollectMap<'a, W> {
     buffer: BufWriter,
-    entries: Vec<Vec<u8>>,
+    /// The encoded entries along with the length of their encoded key.
+    entries: Vec<(usize, Vec<u8>)>,
+    /// The number of entries, if they are written directly.
+    streaming: Option<usize>,
+    /// The encoded key of the last entry that was written directly.
+    last_key: Vec<u8>,
+    /// Number of entries written directly so far.
+    streamed: usize,
     ser: &'a mut Serializer<W>,
 }
 
 impl<'a, W> CollectMap<'a, W>
 where
-    W: enc::Write,
+    W: Write,
 {
     fn new(ser: &'a mut Serializer<W>) -> Self {
         Self {
             buffer: BufWriter::new(Vec::new()),
             entries: Vec::new(),
+            streaming: None,
+            last_key: Vec::new(),
+            streamed: 0,
             ser,
         }
     }
 
+    /// Writes `len` entries directly, after the header has been written.
+    fn streaming(ser: &'a mut Serializer<W>, len: usize) -> Self {
+        let mut map = Self::new(ser);
+        map.streaming = Some(len);
+        map
+    }
+
+    /// Collects the fields of a struct with `len` fields, after the header has been written.
+    fn for_struct(ser: &'a mut Serializer<W>, len: usize) -> Self {
+        if ser.options.sorted_keys {
+            Self::streaming(ser, len)
+        } else {
+            Self::new(ser)
+        }
+    }
+
+    /// Writes a key directly, if it's after the previous one.
+    fn stream_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), EncodeError<W::Error>> {
+        let mut mem_serializer = Serializer::new(&mut self.buffer).with_options(self.ser.options);
+        key.serialize(&mut mem_serializer).map_err(buffered)?;
+        // Encoded keys compare bytewise in canonical order, see `end`.
+        let key = self.buffer.buffer();
+        if key <= &self.last_key[..] {
+            let msg = match super::from_slice::<&str>(key) {
+                Ok(key) => format!("Map key isn't in canonical order: {key}"),
+                Err(_) => "Map key isn't in canonical order".to_string(),
+            };
+            return Err(EncodeError::Msg(msg));
+        }
+        self.ser.writer.push(key)?;
+        self.last_key.clear();
+        self.last_key.extend_from_slice(key);
+        self.buffer.clear();
+        self.streamed += 1;
+        Ok(())
+    }
+
     fn serialize<T: Serialize + ?Sized>(
         &mut self,
         maybe_key: Option<&'static str>,
         value: &T,
     ) -> Result<(), EncodeError<W::Error>> {
+        if self.streaming.is_some() {
+            if let Some(key) = maybe_key {
+                self.stream_key(key)?;
+            }
+            return value.serialize(&mut *self.ser);
+        }
+
         // Instantiate a new serializer, so that the buffer can be reused.
-        let mut mem_serializer = Serializer::new(&mut self.buffer);
+        let mut mem_serializer = Serializer::new(&mut self.buffer).with_options(self.ser.options);
         if let Some(key) = maybe_key {
-            key.serialize(&mut mem_serializer)
-                .map_err(|_| EncodeError::Msg("Struct key cannot be serialized.".to_string()))?;
+            key.serialize(&mut mem_serializer).map_err(buffered)?;
         }
-        value
-            .serialize(&mut mem_serializer)
-            .map_err(|_| EncodeError::Msg("Struct value cannot be serialized.".to_string()))?;
+        // For maps the key was already written by `serialize_key`.
+        let key_len = mem_serializer.writer.buffer().len();
+        value.serialize(&mut mem_serializer).map_err(buffered)?;
 
-        self.entries.push(self.buffer.buffer().to_vec());
+        self.entries.push((key_len, self.buffer.buffer().to_vec()));
         self.buffer.clear();
 
         Ok(())
     }
 
     fn end(mut self) -> Result<(), EncodeError<W::Error>> {
+        if let Some(len) = self.streaming {
+            if self.streamed != len {
+                return Err(EncodeError::Msg(format!(
+                    "Expected {len} map entries, got {}",
+                    self.streamed
+                )));
+            }
+            return Ok(());
+        }
+
         // This sorting step makes sure we have the expected order of the keys. Byte-wise
         // comparison over the encoded forms gives us the right order as keys in DRISL are
         // always (text) strings, hence have the same CBOR major type 3. The length of the string
         // is encoded in the prefix bits along with the major type. This means that a shorter string
         // always sorts before a longer string even with the compact length representation.
-        self.entries.sort_unstable();
-        for entry in self.entries {
+        self.entries.sort_unstable_by(|(_, a), (_, b)| a.cmp(b));
+        // Encoded keys are self-delimiting, so entries with the same key end up next to each
+        // other.
+        if let Some(pair) = self
+            .entries
+            .windows(2)
+            .find(|pair| pair[0].1[..pair[0].0] == pair[1].1[..pair[1].0])
+        {
+            let (key_len, entry) = &pair[0];
+            let msg = match super::from_slice::<&str>(&entry[..*key_len]) {
+                Ok(key) => format!("Duplicate map key: {key}"),
+                Err(_) => "Duplicate map key".to_string(),
+            };
+            return Err(EncodeError::Msg(msg));
+        }
+        for (_, entry) in self.entries {
             self.ser.writer.push(&entry)?;
         }
         Ok(())
@@ -463,18 +841,20 @@ where
 
 impl<W> serde::ser::SerializeMap for CollectMap<'_, W>
 where
-    W: enc::Write,
+    W: Write,
 {
     type Ok = ();
     type Error = EncodeError<W::Error>;
 
     #[inline]
     fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        if self.streaming.is_some() {
+            return self.stream_key(key);
+        }
         // The key needs to be add to the buffer without any further operations. Serializing the
         // value will then do the necessary flushing etc.
-        let mut mem_serializer = Serializer::new(&mut self.buffer);
-        key.serialize(&mut mem_serializer)
-            .map_err(|_| EncodeError::Msg("Map key cannot be serialized.".to_string()))?;
+        let mut mem_serializer = Serializer::new(&mut self.buffer).with_options(self.ser.options);
+        key.serialize(&mut mem_serializer).map_err(buffered)?;
         Ok(())
     }
 
@@ -485,14 +865,16 @@ where
 
     #[inline]
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        types::Map::bounded(self.entries.len(), &mut self.ser.writer)?;
+        if self.streaming.is_none() {
+            map_header(self.entries.len(), &mut self.ser.writer)?;
+        }
         self.end()
     }
 }
 
 impl<W> serde::ser::SerializeStruct for CollectMap<'_, W>
 where
-    W: enc::Write,
+    W: Write,
 {
     type Ok = ();
     type Error = EncodeError<W::Error>;
@@ -514,7 +896,7 @@ where
 
 impl<W> serde::ser::SerializeStructVariant for CollectMap<'_, W>
 where
-    W: enc::Write,
+    W: Write,
 {
     type Ok = ();
     type Error = EncodeError<W::Error>;
@@ -534,10 +916,19 @@ where
     }
 }
 
+/// Passes on an error from serializing into an in-memory buffer.
+fn buffered<E>(err: EncodeError<TryReserveError>) -> EncodeError<E> {
+    match err {
+        EncodeError::Msg(msg) => EncodeError::Msg(msg),
+        EncodeError::IntegerOutOfRange => EncodeError::IntegerOutOfRange,
+        EncodeError::Write(err) => EncodeError::Msg(err.to_string()),
+    }
+}
+
 /// Serializing a CID correctly as DRISL.
 struct CidSerializer<'a, W>(&'a mut Serializer<W>);
 
-impl<'a, W: enc::Write> ser::Serializer for &'a mut CidSerializer<'a, W>
+impl<'a, W: Write> ser::Serializer for &'a mut CidSerializer<'a, W>
 where
     W::Error: core::fmt::Debug,
 {
@@ -593,8 +984,15 @@ where
     }
 
     fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
-        // CIDs are serialized with CBOR tag 42.
-        types::Tag(CBOR_TAGS_CID as _, types::Bytes(value)).encode(&mut self.0.writer)?;
+        // CIDs are serialized with CBOR tag 42, prefixed with a zero byte for the identity
+        // multibase.
+        let mut bytes = Vec::with_capacity(1 + value.len());
+        bytes.push(0);
+        bytes.extend_from_slice(value);
+        encode(
+            types::Tag(CBOR_TAGS_CID as _, types::Bytes(&bytes[..])),
+            &mut self.0.writer,
+        )?;
         Ok(())
     }
 
@@ -0,0 +1,218 @@
+//! Push-based encoding.
+
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+
+use serde::{Serialize, Serializer as _};
+
+use super::{Serializer, Write, array_header, map_header};
+use crate::{cid::Cid, drisl::error::EncodeError};
+
+/// Writes DRISL incrementally, one item at a time.
+///
+/// Arrays and maps are started with their number of items, which is enforced as the items are
+/// written. Map keys have to be written in canonical order, which is shorter keys first and keys of
+/// the same length in bytewise order. Nothing is buffered, so documents of any size can be written.
+///
+/// ```
+/// # use dasl::{drisl, drisl::ser::{BufWriter, Encoder}};
+/// let mut encoder = Encoder::new(BufWriter::new(Vec::new()));
+/// encoder
+///     .map_start(2)?
+///     .key("id")?
+///     .int(7)?
+///     .key("tags")?
+///     .array_start(2)?
+///     .text("a")?
+///     .text("b")?
+///     .end()?
+///     .end()?;
+/// let bytes = encoder.finish()?.into_inner();
+/// assert_eq!(bytes, drisl::to_vec(&drisl!({ "id": 7, "tags": ["a", "b"] }))?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct Encoder<W> {
+    ser: Serializer<W>,
+    stack: Vec<Container>,
+}
+
+/// An array or map that is being written.
+enum Container {
+    Array {
+        remaining: usize,
+    },
+    Map {
+        remaining: usize,
+        last_key: Option<String>,
+        expect_key: bool,
+    },
+}
+
+impl<W: Write> Encoder<W> {
+    /// Creates a new encoder.
+    pub fn new(writer: W) -> Self {
+        Self {
+            ser: Serializer::new(writer),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Starts an array with `len` items, finish it with [`Encoder::end`].
+    pub fn array_start(&mut self, len: usize) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.before_value()?;
+        array_header(len, &mut self.ser.writer)?;
+        self.stack.push(Container::Array { remaining: len });
+        Ok(self)
+    }
+
+    /// Starts a map with `len` entries, finish it with [`Encoder::end`].
+    pub fn map_start(&mut self, len: usize) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.before_value()?;
+        map_header(len, &mut self.ser.writer)?;
+        self.stack.push(Container::Map {
+            remaining: len,
+            last_key: None,
+            expect_key: true,
+        });
+        Ok(self)
+    }
+
+    /// Finishes the innermost array or map, fails if not all of its items were written.
+    pub fn end(&mut self) -> Result<&mut Self, EncodeError<W::Error>> {
+        match self.stack.last() {
+            Some(Container::Array { remaining: 0 })
+            | Some(Container::Map {
+                remaining: 0,
+                expect_key: true,
+                ..
+            }) => {
+                self.stack.pop();
+                Ok(self)
+            }
+            Some(_) => Err(EncodeError::Msg(
+                "Not all items of the array or map were written".into(),
+            )),
+            None => Err(EncodeError::Msg("No array or map to end".into())),
+        }
+    }
+
+    /// Writes a map key.
+    pub fn key(&mut self, key: &str) -> Result<&mut Self, EncodeError<W::Error>> {
+        let Some(Container::Map {
+            remaining,
+            last_key,
+            expect_key,
+        }) = self.stack.last_mut()
+        else {
+            return Err(EncodeError::Msg("Keys can only be written in maps".into()));
+        };
+        if !*expect_key {
+            return Err(EncodeError::Msg("Expected a value, not a key".into()));
+        }
+        if *remaining == 0 {
+            return Err(EncodeError::Msg(
+                "Map has more entries than declared".into(),
+            ));
+        }
+        if let Some(last_key) = last_key {
+            if (key.len(), key.as_bytes()) <= (last_key.len(), last_key.as_bytes()) {
+                return Err(EncodeError::Msg(format!(
+                    "Map key {key:?} must come after {last_key:?}"
+                )));
+            }
+        }
+        *remaining -= 1;
+        *expect_key = false;
+        *last_key = Some(key.to_owned());
+        self.ser.serialize_str(key)?;
+        Ok(self)
+    }
+
+    /// Writes an integer, it must be within [`Value::MIN_INTEGER`](crate::drisl::Value::MIN_INTEGER)
+    /// and [`Value::MAX_INTEGER`](crate::drisl::Value::MAX_INTEGER).
+    pub fn int(&mut self, value: impl Into<i128>) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.before_value()?;
+        self.ser.serialize_i128(value.into())?;
+        Ok(self)
+    }
+
+    /// Writes a float, it must be finite.
+    pub fn float(&mut self, value: f64) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.before_value()?;
+        self.ser.serialize_f64(value)?;
+        Ok(self)
+    }
+
+    /// Writes a boolean.
+    pub fn bool(&mut self, value: bool) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.before_value()?;
+        self.ser.serialize_bool(value)?;
+        Ok(self)
+    }
+
+    /// Writes `null`.
+    pub fn null(&mut self) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.before_value()?;
+        self.ser.serialize_none()?;
+        Ok(self)
+    }
+
+    /// Writes a string.
+    pub fn text(&mut self, value: &str) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.before_value()?;
+        self.ser.serialize_str(value)?;
+        Ok(self)
+    }
+
+    /// Writes a byte string.
+    pub fn bytes(&mut self, value: &[u8]) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.before_value()?;
+        self.ser.serialize_bytes(value)?;
+        Ok(self)
+    }
+
+    /// Writes a CID link.
+    pub fn link(&mut self, cid: &Cid) -> Result<&mut Self, EncodeError<W::Error>> {
+        self.value(cid)
+    }
+
+    /// Writes any serializable value as a single item.
+    pub fn value<T>(&mut self, value: &T) -> Result<&mut Self, EncodeError<W::Error>>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.before_value()?;
+        value.serialize(&mut self.ser)?;
+        Ok(self)
+    }
+
+    /// Returns the underlying writer, fails if an array or map wasn't finished.
+    pub fn finish(self) -> Result<W, EncodeError<W::Error>> {
+        if !self.stack.is_empty() {
+            return Err(EncodeError::Msg(
+                "Not all arrays and maps were ended".into(),
+            ));
+        }
+        Ok(self.ser.into_inner())
+    }
+
+    /// Accounts for a value that is about to be written in the innermost container.
+    fn before_value(&mut self) -> Result<(), EncodeError<W::Error>> {
+        match self.stack.last_mut() {
+            None => Ok(()),
+            Some(Container::Array { remaining: 0 }) => Err(EncodeError::Msg(
+                "Array has more items than declared".into(),
+            )),
+            Some(Container::Array { remaining }) => {
+                *remaining -= 1;
+                Ok(())
+            }
+            Some(Container::Map {
+                expect_key: true, ..
+            }) => Err(EncodeError::Msg("Expected a map key, not a value".into())),
+            Some(Container::Map { expect_key, .. }) => {
+                *expect_key = true;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Buffered writing to `std::io` writers.
+
+use std::io::{self, IoSlice, Write};
+
+/// Default buffer size of [`WriteOptions`].
+const DEFAULT_BUFFER_LEN: usize = 8 * 1024;
+
+/// Options for writing DRISL, see [`to_writer_with`](super::to_writer_with).
+///
+/// ```
+/// # use dasl::drisl::ser::{self, WriteOptions};
+/// let mut out = Vec::new();
+/// let options = WriteOptions::new().buffer_len(64 * 1024).vectored(true);
+/// ser::to_writer_with(&mut out, &("hello", 1), options).unwrap();
+/// assert_eq!(out, dasl::drisl::to_vec(&("hello", 1)).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteOptions {
+    pub(crate) buffer_len: usize,
+    pub(crate) vectored: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            buffer_len: DEFAULT_BUFFER_LEN,
+            vectored: false,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Creates the default options, with a buffer of 8 KiB and without vectored writes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the size of the buffer that small writes are batched in.
+    ///
+    /// Writes that don't fit into the buffer, i.e. large strings and byte strings, are passed on
+    /// to the writer directly instead of being copied. A size of zero disables the buffering.
+    pub fn buffer_len(mut self, len: usize) -> Self {
+        self.buffer_len = len;
+        self
+    }
+
+    /// Sets whether writes that don't fit into the buffer are written together with the buffered
+    /// data in a single vectored write, see [`Write::write_vectored`].
+    pub fn vectored(mut self, vectored: bool) -> Self {
+        self.vectored = vectored;
+        self
+    }
+}
+
+/// A writer that batches small writes into a buffer before writing them to a `std::io` writer.
+///
+/// Buffered data has to be written out with [`flush`](Self::flush) or
+/// [`into_inner`](Self::into_inner), it is lost otherwise.
+#[derive(Debug)]
+pub struct BufIoWriter<W> {
+    writer: W,
+    buffer: Vec<u8>,
+    vectored: bool,
+}
+
+impl<W: Write> BufIoWriter<W> {
+    /// Creates a new writer with the given options.
+    pub fn new(writer: W, options: WriteOptions) -> Self {
+        Self {
+            writer,
+            buffer: Vec::with_capacity(options.buffer_len),
+            vectored: options.vectored,
+        }
+    }
+
+    /// Writes out the buffered data.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.write_all(&self.buffer)?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Writes out the buffered data and returns the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Writes the buffered data followed by `input`, with as few vectored writes as possible.
+    fn write_all_vectored(&mut self, input: &[u8]) -> io::Result<()> {
+        let mut buffered = 0;
+        let mut written = 0;
+        while buffered < self.buffer.len() {
+            let bufs = [
+                IoSlice::new(&self.buffer[buffered..]),
+                IoSlice::new(&input[written..]),
+            ];
+            match self.writer.write_vectored(&bufs) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(len) => {
+                    let from_buffer = len.min(self.buffer.len() - buffered);
+                    buffered += from_buffer;
+                    written += len - from_buffer;
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.buffer.clear();
+        self.writer.write_all(&input[written..])
+    }
+}
+
+impl<W: Write> super::Write for BufIoWriter<W> {
+    type Error = io::Error;
+
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+        if self.buffer.len() + input.len() <= self.buffer.capacity() {
+            self.buffer.extend_from_slice(input);
+            return Ok(());
+        }
+        if input.len() < self.buffer.capacity() {
+            self.flush()?;
+            self.buffer.extend_from_slice(input);
+            return Ok(());
+        }
+        if self.vectored && !self.buffer.is_empty() {
+            return self.write_all_vectored(input);
+        }
+        self.flush()?;
+        self.writer.write_all(input)
+    }
+}
@@ -0,0 +1,94 @@
+//! Encoding of [`Value`]s without recursion.
+
+use alloc::{collections::TryReserveError, string::String, vec::Vec};
+use core::slice;
+
+use serde::Serialize;
+
+use super::{BufWriter, EncodeOptions, Serializer, Write, array_header, encode, map_header};
+use crate::drisl::{Value, cmp_map_keys, error::EncodeError};
+
+/// Serializes a [`Value`] to a vector, without recursing into arrays and maps.
+///
+/// Unlike [`to_vec`](super::to_vec), values that are nested deeper than the stack allows can be
+/// encoded. See [`Serializer::encode_value`].
+///
+/// ```
+/// # use dasl::drisl::{self, Value, ser};
+/// let mut value = Value::Null;
+/// for _ in 0..10_000 {
+///     value = Value::Array(vec![value]);
+/// }
+/// let bytes = ser::value_to_vec(&value).unwrap();
+/// assert_eq!(bytes.len(), 10_001);
+/// ```
+pub fn value_to_vec(value: &Value) -> Result<Vec<u8>, EncodeError<TryReserveError>> {
+    value_to_vec_with(value, EncodeOptions::default())
+}
+
+/// Serializes a [`Value`] to a vector without recursion, with the given options, see
+/// [`value_to_vec`].
+pub fn value_to_vec_with(
+    value: &Value,
+    options: EncodeOptions,
+) -> Result<Vec<u8>, EncodeError<TryReserveError>> {
+    let mut serializer = Serializer::new(BufWriter::new(Vec::new())).with_options(options);
+    serializer.encode_value(value)?;
+    Ok(serializer.into_inner().into_inner())
+}
+
+/// The remaining items of an array or map that is being encoded.
+enum Frame<'a> {
+    Array(slice::Iter<'a, Value>),
+    /// Entries in canonical order.
+    Map(alloc::vec::IntoIter<(&'a String, &'a Value)>),
+}
+
+impl<W: Write> Serializer<W> {
+    /// Encodes a [`Value`] without recursing into arrays and maps.
+    ///
+    /// Serde encodes nested data recursively, which can overflow the stack for deeply nested
+    /// values that were built in code. This keeps the arrays and maps that are being encoded on
+    /// the heap instead. Entries of maps are written in canonical order without buffering them,
+    /// so the output is the same as with Serde.
+    ///
+    /// Comparing a `Value` is still recursive.
+    pub fn encode_value(&mut self, value: &Value) -> Result<(), EncodeError<W::Error>> {
+        let mut stack = Vec::new();
+        let mut next = Some(value);
+        loop {
+            match next {
+                Some(Value::Array(items)) => {
+                    array_header(items.len(), &mut self.writer)?;
+                    stack.push(Frame::Array(items.iter()));
+                }
+                Some(Value::Map(entries)) => {
+                    map_header(entries.len(), &mut self.writer)?;
+                    let mut entries: Vec<_> = entries.iter().collect();
+                    entries.sort_unstable_by(|(a, _), (b, _)| cmp_map_keys(a, b));
+                    stack.push(Frame::Map(entries.into_iter()));
+                }
+                // Anything else isn't nested.
+                Some(value) => value.serialize(&mut *self)?,
+                None => {}
+            }
+
+            let Some(frame) = stack.last_mut() else {
+                return Ok(());
+            };
+            next = match frame {
+                Frame::Array(items) => items.next(),
+                Frame::Map(entries) => match entries.next() {
+                    Some((key, value)) => {
+                        encode(key.as_str(), &mut self.writer)?;
+                        Some(value)
+                    }
+                    None => None,
+                },
+            };
+            if next.is_none() {
+                stack.pop();
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Options for encoding.
+
+/// Bits of the NaN that [`NonFiniteFloats::Canonicalize`] turns every NaN into.
+const CANONICAL_NAN: u64 = 0x7ff8_0000_0000_0000;
+
+/// Options for encoding DRISL, see [`to_vec_with`](super::to_vec_with).
+///
+/// The defaults produce canonical DRISL, other options trade that for compatibility with data
+/// that can't be represented otherwise.
+///
+/// ```
+/// # use dasl::drisl::{self, ser::{EncodeOptions, NonFiniteFloats}};
+/// let options = EncodeOptions::new().non_finite_floats(NonFiniteFloats::Null);
+/// let bytes = drisl::ser::to_vec_with(&[1.5, f64::NAN], options).unwrap();
+/// assert_eq!(bytes, drisl::to_vec(&dasl::drisl!([1.5, null])).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EncodeOptions {
+    pub(crate) non_finite_floats: NonFiniteFloats,
+    pub(crate) sorted_keys: bool,
+}
+
+impl EncodeOptions {
+    /// Creates the default options, which produce canonical DRISL.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how infinite and NaN floats are handled, which DRISL doesn't allow.
+    pub fn non_finite_floats(mut self, policy: NonFiniteFloats) -> Self {
+        self.non_finite_floats = policy;
+        self
+    }
+
+    /// Sets whether the fields of structs and the keys of maps are already in canonical order.
+    ///
+    /// By default the entries of each struct and map are buffered and sorted before they are
+    /// written. With this option they are written directly, which fails if a key isn't after the
+    /// previous one. Fields of structs are in canonical order if they are declared sorted by length
+    /// first, then bytewise, e.g. `id`, `name`, `size`, `created`. Maps without a known length,
+    /// like structs with `#[serde(flatten)]` fields, are still buffered.
+    ///
+    /// ```
+    /// # use dasl::drisl::{self, ser::EncodeOptions};
+    /// #[derive(serde::Serialize)]
+    /// struct Sorted {
+    ///     id: u8,
+    ///     name: &'static str,
+    /// }
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Unsorted {
+    ///     name: &'static str,
+    ///     id: u8,
+    /// }
+    ///
+    /// let options = EncodeOptions::new().sorted_keys(true);
+    /// let bytes = drisl::ser::to_vec_with(&Sorted { id: 1, name: "foo" }, options).unwrap();
+    /// assert_eq!(bytes, drisl::to_vec(&Sorted { id: 1, name: "foo" }).unwrap());
+    /// assert!(drisl::ser::to_vec_with(&Unsorted { name: "foo", id: 1 }, options).is_err());
+    /// ```
+    pub fn sorted_keys(mut self, sorted: bool) -> Self {
+        self.sorted_keys = sorted;
+        self
+    }
+}
+
+/// How infinite and NaN floats are handled, see [`EncodeOptions::non_finite_floats`] and
+/// [`DecodeOptions::non_finite_floats`](crate::drisl::de::DecodeOptions::non_finite_floats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NonFiniteFloats {
+    /// Fail with an error, as DRISL requires.
+    #[default]
+    Reject,
+    /// Replace them with null.
+    Null,
+    /// Keep infinities and turn every NaN into the same quiet NaN, `0x7ff8000000000000`, so
+    /// that equal values still have equal encodings.
+    Canonicalize,
+}
+
+impl NonFiniteFloats {
+    /// Returns what a non-finite float becomes with [`NonFiniteFloats::Canonicalize`].
+    pub(crate) fn canonicalize(float: f64) -> f64 {
+        if float.is_nan() {
+            f64::from_bits(CANONICAL_NAN)
+        } else {
+            float
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Destinations that a [`Serializer`](super::Serializer) writes to.
+
+use alloc::{collections::TryReserveError, vec::Vec};
+
+use cbor4ii::core::{
+    enc::{self, Encode},
+    types,
+};
+
+use crate::drisl::error::EncodeError;
+
+/// A destination of CBOR data.
+pub trait Write {
+    /// The error of writing to the destination.
+    type Error: core::error::Error + 'static;
+
+    /// Writes all of `input`.
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error>;
+}
+
+impl<W: Write + ?Sized> Write for &mut W {
+    type Error = W::Error;
+
+    #[inline]
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+        (**self).push(input)
+    }
+}
+
+/// Writes to a `Vec<u8>`.
+#[derive(Debug, Default)]
+pub struct BufWriter(Vec<u8>);
+
+impl BufWriter {
+    /// Creates a writer that appends to `buf`.
+    pub fn new(buf: Vec<u8>) -> Self {
+        BufWriter(buf)
+    }
+
+    /// Returns the data written so far.
+    pub fn buffer(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the underlying vector.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Discards the data written so far.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl Write for BufWriter {
+    type Error = TryReserveError;
+
+    #[inline]
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+        self.0.try_reserve(input.len())?;
+        self.0.extend_from_slice(input);
+        Ok(())
+    }
+}
+
+/// Writes to a [`std::io::Write`], without buffering, see
+/// [`BufIoWriter`](super::BufIoWriter) for a buffered one.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoWriter<W>(W);
+
+#[cfg(feature = "std")]
+impl<W> IoWriter<W> {
+    /// Creates a writer to `writer`.
+    pub fn new(writer: W) -> Self {
+        IoWriter(writer)
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> Write for IoWriter<W> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(input)
+    }
+}
+
+/// Adapts a [`Write`] for encoding with cbor4ii, which isn't part of the public API.
+struct Cbor4iiWriter<'a, W>(&'a mut W);
+
+impl<W: Write> enc::Write for Cbor4iiWriter<'_, W> {
+    type Error = W::Error;
+
+    #[inline]
+    fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+        self.0.push(input)
+    }
+}
+
+/// Encodes `value` with cbor4ii.
+#[inline]
+pub(crate) fn encode<W: Write>(
+    value: impl Encode,
+    writer: &mut W,
+) -> Result<(), EncodeError<W::Error>> {
+    value
+        .encode(&mut Cbor4iiWriter(writer))
+        .map_err(EncodeError::from_cbor4ii)
+}
+
+/// Encodes the header of an array of `len` items.
+#[inline]
+pub(crate) fn array_header<W: Write>(
+    len: usize,
+    writer: &mut W,
+) -> Result<(), EncodeError<W::Error>> {
+    types::Array::bounded(len, &mut Cbor4iiWriter(writer)).map_err(EncodeError::from_cbor4ii)
+}
+
+/// Encodes the header of a map of `len` entries.
+#[inline]
+pub(crate) fn map_header<W: Write>(
+    len: usize,
+    writer: &mut W,
+) -> Result<(), EncodeError<W::Error>> {
+    types::Map::bounded(len, &mut Cbor4iiWriter(writer)).map_err(EncodeError::from_cbor4ii)
+}
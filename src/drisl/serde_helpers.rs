@@ -0,0 +1,237 @@
+//! Adapters for common field shapes, to be used with `#[serde(with = "...")]`.
+//!
+//! CIDs don't need an adapter to be encoded as tag 42, also within an `Option` or a `Vec`. The
+//! `cid_string` adapters are for fields that hold a CID as its string form instead, like the
+//! `cid` of an atproto strong reference, and `cid_key_map` for maps keyed by CIDs, as DRISL only
+//! allows string keys. The bytes adapters encode byte arrays and optional bytes as CBOR byte
+//! strings instead of arrays of integers, using [`serde_bytes`]. Byte vectors don't need an
+//! adapter when using [`ByteBuf`](super::ByteBuf).
+//!
+//! With the `chrono` feature, the `timestamp` adapters encode a `chrono::DateTime<Utc>` as an
+//! integer of Unix time in seconds or milliseconds, instead of the RFC 3339 string of its own
+//! `Serialize` impl. With the `uuid` feature, the `uuid_bytes` adapters encode a `uuid::Uuid` as
+//! a byte string of its 16 bytes, instead of its 36 character string form. With the `bytes`
+//! feature, the `shared_bytes` adapters decode a `bytes::Bytes` that shares the buffer of the
+//! input of `drisl::from_bytes`.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! use dasl::{
+//!     cid::{Cid, Codec},
+//!     drisl::{self, serde_helpers},
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Record {
+//!     #[serde(with = "serde_helpers::cid_string")]
+//!     cid: Cid,
+//!     #[serde(with = "serde_helpers::option_cid_string")]
+//!     parent: Option<Cid>,
+//!     #[serde(with = "serde_helpers::cid_key_map")]
+//!     sizes: BTreeMap<Cid, u64>,
+//!     #[serde(with = "serde_helpers::byte_array")]
+//!     key: [u8; 4],
+//!     #[serde(with = "serde_helpers::option_bytes", default)]
+//!     signature: Option<Vec<u8>>,
+//! }
+//!
+//! let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+//! let record = Record {
+//!     cid,
+//!     parent: None,
+//!     sizes: BTreeMap::from([(cid, 3)]),
+//!     key: [1, 2, 3, 4],
+//!     signature: None,
+//! };
+//! let bytes = drisl::to_vec(&record)?;
+//! assert_eq!(drisl::from_slice::<Record>(&bytes)?, record);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use alloc::{format, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::cid::Cid;
+
+#[cfg(feature = "bytes")]
+mod shared;
+#[cfg(feature = "chrono")]
+mod time;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "bytes")]
+pub use self::shared::{option_shared_bytes, shared_bytes};
+#[cfg(feature = "chrono")]
+pub use self::time::{
+    option_timestamp_millis, option_timestamp_seconds, timestamp_millis, timestamp_seconds,
+};
+#[cfg(feature = "uuid")]
+pub use self::uuid::{option_uuid_bytes, uuid_bytes};
+
+/// A `CID` (de)serialized as its string form.
+struct CidString(Cid);
+
+impl Serialize for CidString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CidString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CidStringVisitor;
+
+        impl de::Visitor<'_> for CidStringVisitor {
+            type Value = CidString;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                write!(fmt, "a CID string")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value
+                    .parse()
+                    .map(CidString)
+                    .map_err(|err| E::custom(format!("Failed to deserialize CID {value:?}: {err}")))
+            }
+        }
+
+        deserializer.deserialize_str(CidStringVisitor)
+    }
+}
+
+/// A [`Cid`] as its string form, e.g. `"bafkrei..."`.
+pub mod cid_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(cid: &Cid, serializer: S) -> Result<S::Ok, S::Error> {
+        CidString(*cid).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Cid, D::Error> {
+        CidString::deserialize(deserializer).map(|cid| cid.0)
+    }
+}
+
+/// An `Option<Cid>` as its string form or null.
+///
+/// Add `#[serde(default)]` to also accept a missing field.
+pub mod option_cid_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(cid: &Option<Cid>, serializer: S) -> Result<S::Ok, S::Error> {
+        cid.map(CidString).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Cid>, D::Error> {
+        Option::<CidString>::deserialize(deserializer).map(|cid| cid.map(|cid| cid.0))
+    }
+}
+
+/// A `Vec<Cid>` as an array of strings.
+pub mod vec_cid_string {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(cids: &[Cid], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(cids.iter().map(|cid| CidString(*cid)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Cid>, D::Error> {
+        let cids = Vec::<CidString>::deserialize(deserializer)?;
+        Ok(cids.into_iter().map(|cid| cid.0).collect())
+    }
+}
+
+/// A map keyed by [`Cid`]s, such as a `BTreeMap<Cid, V>` or `HashMap<Cid, V>`, with the string
+/// forms as keys.
+pub mod cid_key_map {
+    use super::*;
+
+    pub fn serialize<'a, M, V, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        &'a M: IntoIterator<Item = (&'a Cid, &'a V)>,
+        V: Serialize + 'a,
+        S: Serializer,
+    {
+        serializer.collect_map(map.into_iter().map(|(cid, value)| (CidString(*cid), value)))
+    }
+
+    pub fn deserialize<'de, M, V, D>(deserializer: D) -> Result<M, D::Error>
+    where
+        M: Default + Extend<(Cid, V)>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        struct MapVisitor<M, V>(PhantomData<(M, V)>);
+
+        impl<'de, M, V> de::Visitor<'de> for MapVisitor<M, V>
+        where
+            M: Default + Extend<(Cid, V)>,
+            V: Deserialize<'de>,
+        {
+            type Value = M;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                write!(fmt, "a map with CID strings as keys")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut access: A) -> Result<M, A::Error> {
+                let mut map = M::default();
+                while let Some((CidString(cid), value)) = access.next_entry()? {
+                    map.extend([(cid, value)]);
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
+
+/// A byte array, such as `[u8; 32]`, as a byte string of exactly that length.
+pub mod byte_array {
+    use super::*;
+
+    pub fn serialize<const N: usize, S: Serializer>(
+        bytes: &[u8; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serde_bytes::serialize(bytes, serializer)
+    }
+
+    pub fn deserialize<'de, const N: usize, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[u8; N], D::Error> {
+        serde_bytes::deserialize(deserializer)
+    }
+}
+
+/// Optional bytes, such as an `Option<Vec<u8>>` or `Option<[u8; N]>`, as a byte string or null.
+///
+/// Add `#[serde(default)]` to also accept a missing field.
+pub mod option_bytes {
+    use super::*;
+
+    pub fn serialize<T, S>(bytes: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: serde_bytes::Serialize,
+        S: Serializer,
+    {
+        serde_bytes::serialize(bytes, serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: serde_bytes::Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        serde_bytes::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,97 @@
+//! Adapters for [`bytes::Bytes`].
+//!
+//! `Bytes` is written as a byte string, directly from its buffer. When decoding with
+//! [`from_bytes`](crate::drisl::from_bytes) it shares the buffer of the input, otherwise the
+//! bytes are copied.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use bytes::Bytes;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::drisl::de::shared;
+
+/// A `Bytes` (de)serialized as a byte string.
+struct SharedBytes(Bytes);
+
+impl Serialize for SharedBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SharedBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SharedBytesVisitor;
+
+        impl<'de> de::Visitor<'de> for SharedBytesVisitor {
+            type Value = SharedBytes;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                write!(fmt, "a byte string")
+            }
+
+            fn visit_borrowed_bytes<E: de::Error>(
+                self,
+                value: &'de [u8],
+            ) -> Result<Self::Value, E> {
+                Ok(SharedBytes(shared(value)))
+            }
+
+            fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                Ok(SharedBytes(Bytes::copy_from_slice(value)))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(SharedBytes(Bytes::from(value)))
+            }
+
+            /// Some Serde data formats write byte strings as a sequence of bytes (e.g.
+            /// `serde_json`).
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(SharedBytes(Bytes::from(bytes)))
+            }
+        }
+
+        deserializer.deserialize_bytes(SharedBytesVisitor)
+    }
+}
+
+/// A [`Bytes`] as a byte string, sharing the input of
+/// [`from_bytes`](crate::drisl::from_bytes).
+pub mod shared_bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(bytes: &Bytes, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(bytes)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Bytes, D::Error> {
+        SharedBytes::deserialize(deserializer).map(|bytes| bytes.0)
+    }
+}
+
+/// An `Option<Bytes>` as a byte string or null, see [`shared_bytes`].
+///
+/// Add `#[serde(default)]` to also accept a missing field.
+pub mod option_shared_bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        bytes: &Option<Bytes>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        bytes.clone().map(SharedBytes).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Bytes>, D::Error> {
+        Option::<SharedBytes>::deserialize(deserializer).map(|bytes| bytes.map(|bytes| bytes.0))
+    }
+}
@@ -0,0 +1,189 @@
+//! Adapters for [`chrono`] timestamps.
+//!
+//! Timestamps are written as integers of Unix time in UTC, the seconds or milliseconds since
+//! 1970-01-01T00:00:00Z, negative before that. Anything more precise than the unit is rounded
+//! down. Times that don't fit into the range of `DateTime` fail to decode.
+
+use core::{fmt, marker::PhantomData};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+/// The unit a timestamp is written in.
+trait Unit {
+    const NAME: &str;
+
+    fn to_int(time: &DateTime<Utc>) -> i64;
+
+    fn from_int(value: i64) -> Option<DateTime<Utc>>;
+}
+
+struct Seconds;
+
+impl Unit for Seconds {
+    const NAME: &str = "seconds";
+
+    fn to_int(time: &DateTime<Utc>) -> i64 {
+        time.timestamp()
+    }
+
+    fn from_int(value: i64) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(value, 0)
+    }
+}
+
+struct Millis;
+
+impl Unit for Millis {
+    const NAME: &str = "milliseconds";
+
+    fn to_int(time: &DateTime<Utc>) -> i64 {
+        time.timestamp_millis()
+    }
+
+    fn from_int(value: i64) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp_millis(value)
+    }
+}
+
+/// A `DateTime` (de)serialized as an integer timestamp in the unit `U`.
+struct Timestamp<U>(DateTime<Utc>, PhantomData<U>);
+
+impl<U: Unit> Serialize for Timestamp<U> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(U::to_int(&self.0))
+    }
+}
+
+impl<'de, U: Unit> Deserialize<'de> for Timestamp<U> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TimestampVisitor<U>(PhantomData<U>);
+
+        impl<U: Unit> de::Visitor<'_> for TimestampVisitor<U> {
+            type Value = Timestamp<U>;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                write!(fmt, "a Unix timestamp in {}", U::NAME)
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                match U::from_int(value) {
+                    Some(time) => Ok(Timestamp(time, PhantomData)),
+                    None => Err(E::invalid_value(de::Unexpected::Signed(value), &self)),
+                }
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                match i64::try_from(value) {
+                    Ok(value) => self.visit_i64(value),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_i64(TimestampVisitor(PhantomData))
+    }
+}
+
+fn serialize<U: Unit, S: Serializer>(
+    time: &DateTime<Utc>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    Timestamp::<U>(*time, PhantomData).serialize(serializer)
+}
+
+fn deserialize<'de, U: Unit, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<DateTime<Utc>, D::Error> {
+    Timestamp::<U>::deserialize(deserializer).map(|time| time.0)
+}
+
+fn serialize_option<U: Unit, S: Serializer>(
+    time: &Option<DateTime<Utc>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    time.map(|time| Timestamp::<U>(time, PhantomData))
+        .serialize(serializer)
+}
+
+fn deserialize_option<'de, U: Unit, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<DateTime<Utc>>, D::Error> {
+    Option::<Timestamp<U>>::deserialize(deserializer).map(|time| time.map(|time| time.0))
+}
+
+/// A `DateTime<Utc>` as an integer of Unix time in seconds.
+pub mod timestamp_seconds {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        time: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize::<Seconds, _>(time, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        super::deserialize::<Seconds, _>(deserializer)
+    }
+}
+
+/// A `DateTime<Utc>` as an integer of Unix time in milliseconds.
+pub mod timestamp_millis {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        time: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize::<Millis, _>(time, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        super::deserialize::<Millis, _>(deserializer)
+    }
+}
+
+/// An `Option<DateTime<Utc>>` as an integer of Unix time in seconds or null.
+///
+/// Add `#[serde(default)]` to also accept a missing field.
+pub mod option_timestamp_seconds {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_option::<Seconds, _>(time, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        super::deserialize_option::<Seconds, _>(deserializer)
+    }
+}
+
+/// An `Option<DateTime<Utc>>` as an integer of Unix time in milliseconds or null.
+///
+/// Add `#[serde(default)]` to also accept a missing field.
+pub mod option_timestamp_millis {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        time: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_option::<Millis, _>(time, serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        super::deserialize_option::<Millis, _>(deserializer)
+    }
+}
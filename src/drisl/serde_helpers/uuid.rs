@@ -0,0 +1,86 @@
+//! Adapters for [`uuid::Uuid`].
+//!
+//! A `Uuid` is written as a byte string of its 16 bytes, instead of the 36 characters of its
+//! string form. Human-readable formats (e.g. JSON) get the hyphenated string form instead, like
+//! [`Cid`](crate::cid::Cid)s do.
+
+use core::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+use uuid::Uuid;
+
+/// A `Uuid` (de)serialized as its bytes.
+struct UuidBytes(Uuid);
+
+impl Serialize for UuidBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return serializer.collect_str(&self.0.hyphenated());
+        }
+        serializer.serialize_bytes(self.0.as_bytes())
+    }
+}
+
+impl<'de> Deserialize<'de> for UuidBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct UuidVisitor;
+
+        impl de::Visitor<'_> for UuidVisitor {
+            type Value = UuidBytes;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                write!(fmt, "a UUID as 16 bytes")
+            }
+
+            fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+                match Uuid::from_slice(value) {
+                    Ok(uuid) => Ok(UuidBytes(uuid)),
+                    Err(_) => Err(E::invalid_length(value.len(), &self)),
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                match Uuid::parse_str(value) {
+                    Ok(uuid) => Ok(UuidBytes(uuid)),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(UuidVisitor)
+        } else {
+            deserializer.deserialize_bytes(UuidVisitor)
+        }
+    }
+}
+
+/// A [`Uuid`] as a byte string of its 16 bytes.
+pub mod uuid_bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(uuid: &Uuid, serializer: S) -> Result<S::Ok, S::Error> {
+        UuidBytes(*uuid).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Uuid, D::Error> {
+        UuidBytes::deserialize(deserializer).map(|uuid| uuid.0)
+    }
+}
+
+/// An `Option<Uuid>` as a byte string of its 16 bytes or null.
+///
+/// Add `#[serde(default)]` to also accept a missing field.
+pub mod option_uuid_bytes {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(uuid: &Option<Uuid>, serializer: S) -> Result<S::Ok, S::Error> {
+        uuid.map(UuidBytes).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Uuid>, D::Error> {
+        Option::<UuidBytes>::deserialize(deserializer).map(|uuid| uuid.map(|uuid| uuid.0))
+    }
+}
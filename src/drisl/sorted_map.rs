@@ -0,0 +1,420 @@
+//! A map in the canonical order of DRISL, see [`SortedMap`].
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{cmp::Ordering, fmt, marker::PhantomData, mem};
+
+use serde::{Deserialize, de, ser};
+use thiserror::Error;
+
+use super::{cmp_map_keys, value::MAX_PREALLOC_LEN};
+
+/// A map backed by a vector of entries, sorted in the canonical order of DRISL.
+///
+/// Compared to a `BTreeMap`, it needs a single allocation and iterates over contiguous memory.
+/// Lookups are binary searches. Inserting in the middle moves the entries after it, so it's best
+/// suited for maps that are built in order, like decoded canonical data or with
+/// [`push`](Self::push).
+///
+/// The order is the canonical one of DRISL, shorter keys first, so entries are in the same order
+/// as in the encoded map.
+///
+/// ```
+/// # use dasl::{drisl, drisl::SortedMap};
+/// let bytes = drisl::to_vec(&drisl!({ "bb": 2, "a": 1, "ccc": 3 })).unwrap();
+/// let mut map: SortedMap<u8> = drisl::from_slice(&bytes).unwrap();
+/// assert_eq!(map.get("bb"), Some(&2));
+///
+/// *map.entry("d".to_string()).or_insert(0) += 4;
+/// let keys: Vec<&str> = map.keys().collect();
+/// assert_eq!(keys, ["a", "d", "bb", "ccc"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortedMap<V> {
+    entries: Vec<(String, V)>,
+}
+
+/// Returned by [`SortedMap::push`] and [`SortedMap::from_sorted`] for a key that isn't after the
+/// previous one.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Map key {key:?} isn't after the previous key in canonical order")]
+pub struct UnsortedKeyError {
+    /// The key that is out of order.
+    pub key: String,
+}
+
+impl<V> SortedMap<V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Creates an empty map with room for `capacity` entries.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Creates a map from entries that are already in canonical order, without duplicates.
+    pub fn from_sorted(entries: Vec<(String, V)>) -> Result<Self, UnsortedKeyError> {
+        if let Some(pair) = entries
+            .windows(2)
+            .find(|pair| cmp_map_keys(&pair[0].0, &pair[1].0) != Ordering::Less)
+        {
+            return Err(UnsortedKeyError {
+                key: pair[1].0.clone(),
+            });
+        }
+        Ok(Self { entries })
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the map has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entries in canonical order.
+    pub fn as_slice(&self) -> &[(String, V)] {
+        &self.entries
+    }
+
+    /// Returns the entries in canonical order.
+    pub fn into_vec(self) -> Vec<(String, V)> {
+        self.entries
+    }
+
+    fn search(&self, key: &str) -> Result<usize, usize> {
+        self.entries
+            .binary_search_by(|(entry, _)| cmp_map_keys(entry, key))
+    }
+
+    /// Returns the value of `key`.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        let index = self.search(key).ok()?;
+        Some(&self.entries[index].1)
+    }
+
+    /// Returns the value of `key` mutably.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        let index = self.search(key).ok()?;
+        Some(&mut self.entries[index].1)
+    }
+
+    /// Returns whether the map contains `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.search(key).is_ok()
+    }
+
+    /// Inserts an entry, returning the previous value of `key`.
+    ///
+    /// Keys that are after all others are appended without a search.
+    pub fn insert(&mut self, key: String, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Creates a map from entries in any order, for equal keys the last one wins.
+    ///
+    /// Sorted entries are taken as they are, others are sorted once instead of being inserted one
+    /// by one, which would take quadratic time.
+    fn from_entries(mut entries: Vec<(String, V)>) -> Self {
+        let sorted = entries
+            .windows(2)
+            .all(|pair| cmp_map_keys(&pair[0].0, &pair[1].0) == Ordering::Less);
+        if !sorted {
+            // The sort is stable, so of equal keys the last one is last.
+            entries.sort_by(|(a, _), (b, _)| cmp_map_keys(a, b));
+            entries.dedup_by(|(later_key, later), (key, value)| {
+                let equal = later_key == key;
+                if equal {
+                    mem::swap(later, value);
+                }
+                equal
+            });
+        }
+        Self { entries }
+    }
+
+    /// Appends an entry, failing if `key` isn't after all other keys.
+    ///
+    /// ```
+    /// # use dasl::drisl::SortedMap;
+    /// let mut map = SortedMap::new();
+    /// map.push("b".to_string(), 1).unwrap();
+    /// map.push("aa".to_string(), 2).unwrap();
+    /// assert_eq!(map.push("a".to_string(), 3).unwrap_err().key, "a");
+    /// assert_eq!(map.push("aa".to_string(), 3).unwrap_err().key, "aa");
+    /// ```
+    pub fn push(&mut self, key: String, value: V) -> Result<(), UnsortedKeyError> {
+        if let Some((last, _)) = self.entries.last() {
+            if cmp_map_keys(last, &key) != Ordering::Less {
+                return Err(UnsortedKeyError { key });
+            }
+        }
+        self.entries.push((key, value));
+        Ok(())
+    }
+
+    /// Removes the entry of `key`, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<V> {
+        let index = self.search(key).ok()?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Returns the entry of `key`, for inserting or updating it in place.
+    pub fn entry(&mut self, key: String) -> Entry<'_, V> {
+        // Appending is the common case when building a map in order.
+        let index = match self.entries.last() {
+            None => Err(0),
+            Some((last, _)) if cmp_map_keys(last, &key) == Ordering::Less => {
+                Err(self.entries.len())
+            }
+            Some(_) => self.search(&key),
+        };
+        match index {
+            Ok(index) => Entry::Occupied(OccupiedEntry { map: self, index }),
+            Err(index) => Entry::Vacant(VacantEntry {
+                map: self,
+                index,
+                key,
+            }),
+        }
+    }
+
+    /// Returns an iterator over the entries in canonical order.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&str, &V)> + ExactSizeIterator {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Returns an iterator over the entries in canonical order, with mutable values.
+    pub fn iter_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (&str, &mut V)> + ExactSizeIterator {
+        self.entries
+            .iter_mut()
+            .map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Returns an iterator over the keys in canonical order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator {
+        self.entries.iter().map(|(key, _)| key.as_str())
+    }
+
+    /// Returns an iterator over the values, in the canonical order of their keys.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &V> + ExactSizeIterator {
+        self.entries.iter().map(|(_, value)| value)
+    }
+}
+
+impl<V> Default for SortedMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for SortedMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An entry of a [`SortedMap`], see [`SortedMap::entry`].
+pub enum Entry<'a, V> {
+    /// The key exists.
+    Occupied(OccupiedEntry<'a, V>),
+    /// The key doesn't exist.
+    Vacant(VacantEntry<'a, V>),
+}
+
+impl<'a, V> Entry<'a, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &str {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the key doesn't exist, and returns the value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the key doesn't exist, and returns the value.
+    pub fn or_insert_with(self, default: impl FnOnce() -> V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Inserts the default value if the key doesn't exist, and returns the value.
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Modifies the value if the key exists.
+    pub fn and_modify(mut self, f: impl FnOnce(&mut V)) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+/// An entry of a [`SortedMap`] whose key exists.
+pub struct OccupiedEntry<'a, V> {
+    map: &'a mut SortedMap<V>,
+    index: usize,
+}
+
+impl<'a, V> OccupiedEntry<'a, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &str {
+        &self.map.entries[self.index].0
+    }
+
+    /// Returns the value.
+    pub fn get(&self) -> &V {
+        &self.map.entries[self.index].1
+    }
+
+    /// Returns the value mutably.
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.entries[self.index].1
+    }
+
+    /// Returns the value mutably, for the lifetime of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.entries[self.index].1
+    }
+
+    /// Replaces the value, returning the previous one.
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry, returning its value.
+    pub fn remove(self) -> V {
+        self.map.entries.remove(self.index).1
+    }
+}
+
+/// An entry of a [`SortedMap`] whose key doesn't exist.
+pub struct VacantEntry<'a, V> {
+    map: &'a mut SortedMap<V>,
+    index: usize,
+    key: String,
+}
+
+impl<'a, V> VacantEntry<'a, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Inserts the value at its position in canonical order, and returns it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.map.entries.insert(self.index, (self.key, value));
+        &mut self.map.entries[self.index].1
+    }
+}
+
+/// Entries are sorted, for equal keys the last one wins.
+impl<V> FromIterator<(String, V)> for SortedMap<V> {
+    fn from_iter<I: IntoIterator<Item = (String, V)>>(iter: I) -> Self {
+        Self::from_entries(iter.into_iter().collect())
+    }
+}
+
+/// Entries are sorted, for equal keys the last one wins.
+impl<V> Extend<(String, V)> for SortedMap<V> {
+    fn extend<I: IntoIterator<Item = (String, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<V> IntoIterator for SortedMap<V> {
+    type Item = (String, V);
+    type IntoIter = alloc::vec::IntoIter<(String, V)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<V> From<BTreeMap<String, V>> for SortedMap<V> {
+    fn from(map: BTreeMap<String, V>) -> Self {
+        let mut entries: Vec<_> = map.into_iter().collect();
+        entries.sort_by(|(a, _), (b, _)| cmp_map_keys(a, b));
+        Self { entries }
+    }
+}
+
+impl<V> From<SortedMap<V>> for BTreeMap<String, V> {
+    fn from(map: SortedMap<V>) -> Self {
+        map.entries.into_iter().collect()
+    }
+}
+
+impl<V: ser::Serialize> ser::Serialize for SortedMap<V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for SortedMap<V> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct SortedMapVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> de::Visitor<'de> for SortedMapVisitor<V> {
+            type Value = SortedMap<V>;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt.write_str("a map with string keys")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let capacity = access.size_hint().unwrap_or(0).min(MAX_PREALLOC_LEN);
+                let mut entries = Vec::with_capacity(capacity);
+                // Strict decoding only passes sorted keys on, lenient decoding and other formats
+                // may not, then the entries are sorted once at the end. The last one of equal keys
+                // wins, see `DuplicateKeys::LastWins`.
+                while let Some(entry) = access.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(SortedMap::from_entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(SortedMapVisitor(PhantomData))
+    }
+}
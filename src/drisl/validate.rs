@@ -0,0 +1,262 @@
+//! Checking that data is canonical DRISL.
+
+use alloc::vec;
+use core::cmp::Ordering;
+
+use cbor4ii::core::major;
+use thiserror::Error;
+
+use super::{CBOR_TAGS_CID, cbor4ii_nonpub::marker};
+use crate::cid::Cid;
+
+/// A rule of canonical DRISL, see [`CanonicityError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum CanonicityRule {
+    #[error("Data ends within an item")]
+    Truncated,
+    #[error("Data continues after the value")]
+    TrailingData,
+    #[error("Invalid initial byte")]
+    InvalidHead,
+    #[error("Integer or length isn't encoded in its shortest form")]
+    NonMinimal,
+    #[error("Indefinite length")]
+    IndefiniteLength,
+    #[error("Map key isn't a string")]
+    NonStringKey,
+    #[error("Map keys aren't sorted")]
+    UnsortedKeys,
+    #[error("Duplicate map key")]
+    DuplicateKey,
+    #[error("Invalid UTF-8")]
+    InvalidUtf8,
+    #[error("Unsupported tag")]
+    UnsupportedTag,
+    #[error("Invalid CID")]
+    InvalidCid,
+    #[error("Float isn't encoded with 64 bits")]
+    NonDoubleFloat,
+    #[error("Float isn't finite")]
+    NonFiniteFloat,
+    #[error("Unsupported simple value")]
+    UnsupportedSimple,
+}
+
+/// Returned by [`validate`] for data that isn't canonical DRISL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("{rule} at offset {offset}")]
+pub struct CanonicityError {
+    /// Position in the data where the rule is violated.
+    pub offset: usize,
+    /// The rule that is violated.
+    pub rule: CanonicityRule,
+}
+
+/// Checks that `bytes` are exactly one value of canonical DRISL.
+///
+/// This is cheaper than decoding, as no values are built. Integers and lengths have to be encoded
+/// in their shortest form, lengths have to be definite, map keys have to be unique strings sorted
+/// by length first and bytewise second, strings have to be valid UTF-8, floats have to be finite
+/// 64-bit floats and the only tag allowed is 42 for CIDs.
+///
+/// ```
+/// # use dasl::{drisl, drisl::CanonicityRule};
+/// let bytes = drisl::to_vec(&drisl!({ "a": 1, "bb": [true, null] })).unwrap();
+/// assert!(drisl::validate(&bytes).is_ok());
+///
+/// // {"bb": 1, "a": 1}
+/// let err = drisl::validate(&[0xa2, 0x62, 0x62, 0x62, 0x01, 0x61, 0x61, 0x01]).unwrap_err();
+/// assert_eq!(err.rule, CanonicityRule::UnsortedKeys);
+/// assert_eq!(err.offset, 5);
+/// ```
+pub fn validate(bytes: &[u8]) -> Result<(), CanonicityError> {
+    let mut validator = Validator { buf: bytes, pos: 0 };
+    // The value is validated as if it was the only item of an array.
+    let mut stack = vec![Frame::Array { remaining: 1 }];
+    while let Some(frame) = stack.last_mut() {
+        match frame {
+            Frame::Array { remaining: 0 } | Frame::Map { remaining: 0, .. } => {
+                stack.pop();
+            }
+            Frame::Array { remaining } => {
+                *remaining -= 1;
+                if let Some(frame) = validator.item()? {
+                    stack.push(frame);
+                }
+            }
+            Frame::Map {
+                remaining,
+                last_key,
+                expect_key: expect_key @ true,
+            } => {
+                *remaining -= 1;
+                *expect_key = false;
+                let key = validator.key(last_key.as_deref())?;
+                *last_key = Some(key);
+            }
+            Frame::Map {
+                remaining,
+                expect_key,
+                ..
+            } => {
+                *remaining -= 1;
+                *expect_key = true;
+                if let Some(frame) = validator.item()? {
+                    stack.push(frame);
+                }
+            }
+        }
+    }
+    if validator.pos != bytes.len() {
+        return Err(validator.error(validator.pos, CanonicityRule::TrailingData));
+    }
+    Ok(())
+}
+
+/// An array or map whose items are validated.
+enum Frame<'a> {
+    Array {
+        remaining: u64,
+    },
+    Map {
+        /// Number of keys and values left.
+        remaining: u64,
+        last_key: Option<&'a [u8]>,
+        expect_key: bool,
+    },
+}
+
+struct Validator<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&self, offset: usize, rule: CanonicityRule) -> CanonicityError {
+        CanonicityError { offset, rule }
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], CanonicityError> {
+        let rest = &self.buf[self.pos..];
+        match usize::try_from(len) {
+            Ok(len) if len <= rest.len() => {
+                self.pos += len;
+                Ok(&rest[..len])
+            }
+            _ => Err(self.error(self.buf.len(), CanonicityRule::Truncated)),
+        }
+    }
+
+    /// Reads the initial byte and argument of an item, lengths must be definite.
+    fn head(&mut self) -> Result<(u8, u64), CanonicityError> {
+        let start = self.pos;
+        let byte = self.take(1)?[0];
+        let (arg, min) = match byte & 0x1f {
+            info @ 0..=0x17 => return Ok((byte, u64::from(info))),
+            0x18 => (u64::from(self.take(1)?[0]), 0x18),
+            0x19 => (
+                u64::from(u16::from_be_bytes(self.take(2)?.try_into().unwrap())),
+                0x100,
+            ),
+            0x1a => (
+                u64::from(u32::from_be_bytes(self.take(4)?.try_into().unwrap())),
+                0x1_0000,
+            ),
+            0x1b => (
+                u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+                0x1_0000_0000,
+            ),
+            0x1f if (major::BYTES..=major::MAP).contains(&(byte >> 5)) => {
+                return Err(self.error(start, CanonicityRule::IndefiniteLength));
+            }
+            _ => return Err(self.error(start, CanonicityRule::InvalidHead)),
+        };
+        if arg < min {
+            return Err(self.error(start, CanonicityRule::NonMinimal));
+        }
+        Ok((byte, arg))
+    }
+
+    /// Validates a map key and returns its bytes.
+    fn key(&mut self, last_key: Option<&[u8]>) -> Result<&'a [u8], CanonicityError> {
+        let start = self.pos;
+        let (byte, len) = self.head()?;
+        if byte >> 5 != major::STRING {
+            return Err(self.error(start, CanonicityRule::NonStringKey));
+        }
+        let key = self.take(len)?;
+        if core::str::from_utf8(key).is_err() {
+            return Err(self.error(start, CanonicityRule::InvalidUtf8));
+        }
+        if let Some(last_key) = last_key {
+            match (key.len(), key).cmp(&(last_key.len(), last_key)) {
+                Ordering::Less => {
+                    return Err(self.error(start, CanonicityRule::UnsortedKeys));
+                }
+                Ordering::Equal => {
+                    return Err(self.error(start, CanonicityRule::DuplicateKey));
+                }
+                Ordering::Greater => {}
+            }
+        }
+        Ok(key)
+    }
+
+    /// Validates a single item, returns the frame for its items if it is an array or map.
+    fn item(&mut self) -> Result<Option<Frame<'a>>, CanonicityError> {
+        let start = self.pos;
+        let Some(&byte) = self.buf.get(start) else {
+            return Err(self.error(start, CanonicityRule::Truncated));
+        };
+        if byte >> 5 == major::SIMPLE {
+            self.pos += 1;
+            return match byte {
+                marker::FALSE | marker::TRUE | marker::NULL => Ok(None),
+                marker::F64 => {
+                    let bytes = self.take(8)?;
+                    if f64::from_be_bytes(bytes.try_into().unwrap()).is_finite() {
+                        Ok(None)
+                    } else {
+                        Err(self.error(start, CanonicityRule::NonFiniteFloat))
+                    }
+                }
+                marker::F16 | marker::F32 => Err(self.error(start, CanonicityRule::NonDoubleFloat)),
+                _ => Err(self.error(start, CanonicityRule::UnsupportedSimple)),
+            };
+        }
+
+        let (byte, arg) = self.head()?;
+        match byte >> 5 {
+            major::UNSIGNED | major::NEGATIVE => Ok(None),
+            major::BYTES => self.take(arg).map(|_| None),
+            major::STRING => {
+                if core::str::from_utf8(self.take(arg)?).is_err() {
+                    return Err(self.error(start, CanonicityRule::InvalidUtf8));
+                }
+                Ok(None)
+            }
+            major::ARRAY => Ok(Some(Frame::Array { remaining: arg })),
+            major::MAP => Ok(Some(Frame::Map {
+                remaining: arg.saturating_mul(2),
+                last_key: None,
+                expect_key: true,
+            })),
+            major::TAG => {
+                if arg != u64::from(CBOR_TAGS_CID) {
+                    return Err(self.error(start, CanonicityRule::UnsupportedTag));
+                }
+                let cid_start = self.pos;
+                let (byte, len) = self.head()?;
+                if byte >> 5 != major::BYTES {
+                    return Err(self.error(cid_start, CanonicityRule::InvalidCid));
+                }
+                if Cid::from_bytes(self.take(len)?).is_err() {
+                    return Err(self.error(cid_start, CanonicityRule::InvalidCid));
+                }
+                Ok(None)
+            }
+            _ => unreachable!("major types only have three bits"),
+        }
+    }
+}
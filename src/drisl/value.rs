@@ -1,11 +1,43 @@
-use std::{collections::BTreeMap, fmt};
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+    ops::Index,
+};
 
 use serde::{Deserialize, de, ser};
 
 use crate::cid::{BytesToCidVisitor, Cid};
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod deserializer;
+mod serializer;
+mod stats;
+
+pub use self::{deserializer::from_value, serializer::to_value, stats::ValueStats};
+
 /// A representation of a dynamic DRISL value that can be handled dynamically.
-#[derive(Clone, Debug, PartialEq, PartialOrd)]
+///
+/// Values have a total order, so they can be used as keys in a `BTreeMap` or a `HashSet`.
+/// Values of different kinds are ordered by the order of the variants below. Floats are
+/// compared with [`f64::total_cmp`], which matches how they are encoded: `-0.0` and `0.0` are
+/// distinct, and `NaN`s are equal to themselves (though they can't be encoded).
+///
+/// # Memory use
+///
+/// Every value takes 48 bytes, as CIDs are stored inline, plus a heap allocation for each string,
+/// byte string, array and map. For large trees of small nodes, [`ValueRef`](super::ValueRef)
+/// borrows strings and byte strings from the input instead, and `arena::ArenaValue` (with the
+/// `arena` feature) allocates all nodes in a single arena.
+#[derive(Clone, Debug)]
 pub enum Value {
     /// An integer
     Integer(i128),
@@ -27,6 +59,331 @@ pub enum Value {
     Map(BTreeMap<String, Value>),
 }
 
+/// Shared `Null` returned by the [`Index`] impls for missing entries.
+static NULL: Value = Value::Null;
+
+/// Maximum number of array elements allocated before decoding them, as lengths in the data can't
+/// be trusted.
+pub(crate) const MAX_PREALLOC_LEN: usize = 1024;
+
+/// Returned by [`Value::int`] for integers that can't be encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("Integer {0} is outside of the range [{min}, {max}]", min = Value::MIN_INTEGER, max = Value::MAX_INTEGER)]
+pub struct IntegerRangeError(pub i128);
+
+impl Value {
+    /// The smallest integer that can be encoded, `-2^64`.
+    pub const MIN_INTEGER: i128 = -(1 << 64);
+
+    /// The largest integer that can be encoded, `2^64 - 1`.
+    pub const MAX_INTEGER: i128 = u64::MAX as i128;
+
+    /// Creates an integer, if it is within [`Value::MIN_INTEGER`] and [`Value::MAX_INTEGER`].
+    ///
+    /// [`Value::Integer`] can hold any `i128`, but encoding one outside of this range fails.
+    ///
+    /// ```
+    /// # use dasl::drisl::Value;
+    /// assert_eq!(Value::int(-1), Ok(Value::Integer(-1)));
+    /// assert!(Value::int(Value::MAX_INTEGER + 1).is_err());
+    /// ```
+    pub fn int(value: i128) -> Result<Self, IntegerRangeError> {
+        if (Self::MIN_INTEGER..=Self::MAX_INTEGER).contains(&value) {
+            Ok(Self::Integer(value))
+        } else {
+            Err(IntegerRangeError(value))
+        }
+    }
+
+    /// Returns `true` if the value is [`Value::Null`].
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns the boolean, if it is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer, if it is one.
+    pub fn as_int(&self) -> Option<i128> {
+        match self {
+            Self::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the float, if it is one.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, if it is one.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes, if it is a byte string.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Bytes(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the CID, if it is one.
+    pub fn as_cid(&self) -> Option<&Cid> {
+        match self {
+            Self::Cid(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements, if it is an array.
+    pub fn as_array(&self) -> Option<&Vec<Value>> {
+        match self {
+            Self::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements mutably, if it is an array.
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match self {
+            Self::Array(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries, if it is a map.
+    pub fn as_map(&self) -> Option<&BTreeMap<String, Value>> {
+        match self {
+            Self::Map(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries mutably, if it is a map.
+    pub fn as_map_mut(&mut self) -> Option<&mut BTreeMap<String, Value>> {
+        match self {
+            Self::Map(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a map entry.
+    ///
+    /// Returns `None` if it isn't a map or the key doesn't exist.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.as_map()?.get(key)
+    }
+
+    /// Returns the value of a map entry mutably.
+    ///
+    /// Returns `None` if it isn't a map or the key doesn't exist.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> {
+        self.as_map_mut()?.get_mut(key)
+    }
+
+    /// Resolves a slash-separated path, such as `"posts/3/author"`.
+    ///
+    /// Segments are map keys for maps and indices for arrays. A leading slash is ignored, an
+    /// empty path resolves to the value itself. Returns `None` if any segment doesn't exist.
+    ///
+    /// ```
+    /// # use dasl::drisl;
+    /// let value = drisl!({ "posts": [{ "author": "alice" }] });
+    /// assert_eq!(value.at("posts/0/author").and_then(|v| v.as_str()), Some("alice"));
+    /// assert_eq!(value.at("posts/1/author"), None);
+    /// ```
+    pub fn at(&self, path: &str) -> Option<&Value> {
+        path_segments(path).try_fold(self, |value, segment| match value {
+            Self::Map(map) => map.get(segment),
+            Self::Array(array) => array.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+    }
+
+    /// Resolves a slash-separated path mutably, see [`Value::at`].
+    pub fn at_mut(&mut self, path: &str) -> Option<&mut Value> {
+        path_segments(path).try_fold(self, |value, segment| match value {
+            Self::Map(map) => map.get_mut(segment),
+            Self::Array(array) => array.get_mut(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+    }
+
+    /// Applies a merge patch, following [RFC 7386](https://www.rfc-editor.org/rfc/rfc7386).
+    ///
+    /// If the patch is a map, each of its entries is merged into the entry with the same key:
+    /// `null` removes the entry, maps are merged recursively and any other value replaces the
+    /// entry. If the value isn't a map, it is replaced with an empty map first. Any other patch
+    /// replaces the value as a whole, so arrays can't be patched element by element.
+    ///
+    /// ```
+    /// # use dasl::drisl;
+    /// let mut value = drisl!({ "title": "Hello", "author": { "name": "alice", "id": 1 } });
+    /// value.merge(&drisl!({ "title": "Hi", "author": { "id": null }, "tags": ["a"] }));
+    /// assert_eq!(
+    ///     value,
+    ///     drisl!({ "title": "Hi", "author": { "name": "alice" }, "tags": ["a"] })
+    /// );
+    /// ```
+    pub fn merge(&mut self, patch: &Value) {
+        let Self::Map(patch) = patch else {
+            *self = patch.clone();
+            return;
+        };
+        if !matches!(self, Self::Map(_)) {
+            *self = Self::Map(BTreeMap::new());
+        }
+        let Self::Map(map) = self else {
+            unreachable!("replaced with a map above");
+        };
+        for (key, value) in patch {
+            if value.is_null() {
+                map.remove(key);
+            } else {
+                map.entry(key.clone()).or_insert(Self::Null).merge(value);
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Position of the variant, used to order values of different kinds.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Integer(_) => 0,
+            Self::Bytes(_) => 1,
+            Self::Float(_) => 2,
+            Self::Text(_) => 3,
+            Self::Bool(_) => 4,
+            Self::Null => 5,
+            Self::Cid(_) => 6,
+            Self::Array(_) => 7,
+            Self::Map(_) => 8,
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => a.cmp(b),
+            (Self::Bytes(a), Self::Bytes(b)) => a.cmp(b),
+            (Self::Float(a), Self::Float(b)) => a.total_cmp(b),
+            (Self::Text(a), Self::Text(b)) => a.cmp(b),
+            (Self::Bool(a), Self::Bool(b)) => a.cmp(b),
+            (Self::Null, Self::Null) => Ordering::Equal,
+            (Self::Cid(a), Self::Cid(b)) => a.cmp(b),
+            (Self::Array(a), Self::Array(b)) => a.cmp(b),
+            (Self::Map(a), Self::Map(b)) => a.cmp(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        match self {
+            Self::Integer(value) => value.hash(state),
+            Self::Bytes(value) => value.hash(state),
+            // Consistent with `f64::total_cmp`, which only considers floats equal if their bits are.
+            Self::Float(value) => value.to_bits().hash(state),
+            Self::Text(value) => value.hash(state),
+            Self::Bool(value) => value.hash(state),
+            Self::Null => {}
+            Self::Cid(value) => Hash::hash(value, state),
+            Self::Array(value) => value.hash(state),
+            Self::Map(value) => value.hash(state),
+        }
+    }
+}
+
+/// Drops nested arrays and maps without recursion, so that deeply nested values, e.g. decoded
+/// with [`value_from_slice`](crate::drisl::de::value_from_slice), don't overflow the stack.
+///
+/// Because of this, fields can't be moved out of a `Value` by pattern matching, use
+/// [`mem::take`](core::mem::take) instead.
+impl Drop for Value {
+    fn drop(&mut self) {
+        let mut stack = match self {
+            Self::Array(items) if !items.is_empty() => mem::take(items),
+            Self::Map(entries) if !entries.is_empty() => mem::take(entries).into_values().collect(),
+            _ => return,
+        };
+        while let Some(mut value) = stack.pop() {
+            match &mut value {
+                Self::Array(items) => stack.append(items),
+                Self::Map(entries) => stack.extend(mem::take(entries).into_values()),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Splits a path into its segments, see [`Value::at`].
+fn path_segments(path: &str) -> impl Iterator<Item = &str> {
+    let path = path.strip_prefix('/').unwrap_or(path);
+    (!path.is_empty())
+        .then(|| path.split('/'))
+        .into_iter()
+        .flatten()
+}
+
+/// Looks up a map entry, returns [`Value::Null`] if it isn't a map or the key doesn't exist.
+///
+/// ```
+/// # use dasl::drisl;
+/// let value = drisl!({ "post": { "tags": ["a", "b"] } });
+/// assert_eq!(value["post"]["tags"][1].as_str(), Some("b"));
+/// assert!(value["missing"][0].is_null());
+/// ```
+impl Index<&str> for Value {
+    type Output = Value;
+
+    fn index(&self, key: &str) -> &Self::Output {
+        self.get(key).unwrap_or(&NULL)
+    }
+}
+
+/// Looks up an array element, returns [`Value::Null`] if it isn't an array or the index is out
+/// of bounds.
+impl Index<usize> for Value {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.as_array()
+            .and_then(|array| array.get(index))
+            .unwrap_or(&NULL)
+    }
+}
+
 impl From<String> for Value {
     fn from(value: String) -> Self {
         Self::Text(value)
@@ -69,6 +426,7 @@ impl From<i64> for Value {
     }
 }
 
+/// Doesn't check the range, see [`Value::int`].
 impl From<i128> for Value {
     fn from(value: i128) -> Self {
         Self::Integer(value)
@@ -87,6 +445,67 @@ impl From<f64> for Value {
     }
 }
 
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<u8> for Value {
+    fn from(value: u8) -> Self {
+        Self::Integer(i128::from(value))
+    }
+}
+
+impl From<u16> for Value {
+    fn from(value: u16) -> Self {
+        Self::Integer(i128::from(value))
+    }
+}
+
+impl From<i8> for Value {
+    fn from(value: i8) -> Self {
+        Self::Integer(i128::from(value))
+    }
+}
+
+impl From<i16> for Value {
+    fn from(value: i16) -> Self {
+        Self::Integer(i128::from(value))
+    }
+}
+
+impl From<&[u8]> for Value {
+    fn from(value: &[u8]) -> Self {
+        Self::Bytes(value.to_vec())
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Self::Float(f64::from(value))
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    fn from(value: Vec<Value>) -> Self {
+        Self::Array(value)
+    }
+}
+
+impl From<BTreeMap<String, Value>> for Value {
+    fn from(value: BTreeMap<String, Value>) -> Self {
+        Self::Map(value)
+    }
+}
+
+/// `None` is converted into [`Value::Null`].
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
 impl<'de> de::Deserialize<'de> for Value {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -194,7 +613,8 @@ impl<'de> de::Deserialize<'de> for Value {
             where
                 V: de::SeqAccess<'de>,
             {
-                let mut vec = Vec::with_capacity(visitor.size_hint().unwrap_or(0));
+                let mut vec =
+                    Vec::with_capacity(visitor.size_hint().unwrap_or(0).min(MAX_PREALLOC_LEN));
 
                 while let Some(elem) = visitor.next_element()? {
                     vec.push(elem);
@@ -210,11 +630,9 @@ impl<'de> de::Deserialize<'de> for Value {
             {
                 let mut values = BTreeMap::new();
 
+                // The last one of equal keys wins, see `DuplicateKeys::LastWins`.
                 while let Some((key, value)) = visitor.next_entry()? {
-                    let prev_value = values.insert(key, value);
-                    if prev_value.is_some() {
-                        return Err(de::Error::custom("Duplicate map key"));
-                    }
+                    values.insert(key, value);
                 }
 
                 Ok(Value::Map(values))
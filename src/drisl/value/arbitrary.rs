@@ -0,0 +1,54 @@
+//! Generating random [`Value`]s with [`arbitrary`].
+
+use alloc::{collections::BTreeMap, string::String};
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::Value;
+
+/// How deep arrays and maps are nested at most, to keep recursion bounded.
+const MAX_DEPTH: usize = 8;
+
+/// Generates values that can be encoded as DRISL: integers are within the range of CBOR
+/// integers, floats are finite and CIDs are valid.
+impl<'a> Arbitrary<'a> for Value {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        arbitrary_value(u, MAX_DEPTH)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, None)
+    }
+}
+
+fn arbitrary_value(u: &mut Unstructured<'_>, depth: usize) -> Result<Value> {
+    // Arrays and maps are the last two kinds.
+    let kinds = if depth == 0 { 7 } else { 9 };
+    Ok(match u.choose_index(kinds)? {
+        0 => Value::Null,
+        1 => Value::Bool(u.arbitrary()?),
+        2 => Value::Integer(u.int_in_range(Value::MIN_INTEGER..=Value::MAX_INTEGER)?),
+        3 => {
+            let float: f64 = u.arbitrary()?;
+            Value::Float(if float.is_finite() { float } else { 0.0 })
+        }
+        4 => Value::Text(u.arbitrary()?),
+        5 => Value::Bytes(u.arbitrary()?),
+        6 => Value::Cid(u.arbitrary()?),
+        7 => {
+            let len = u.arbitrary_len::<Value>()?;
+            let array = (0..len)
+                .map(|_| arbitrary_value(u, depth - 1))
+                .collect::<Result<_>>()?;
+            Value::Array(array)
+        }
+        _ => {
+            let len = u.arbitrary_len::<(String, Value)>()?;
+            let mut map = BTreeMap::new();
+            for _ in 0..len {
+                map.insert(u.arbitrary()?, arbitrary_value(u, depth - 1)?);
+            }
+            Value::Map(map)
+        }
+    })
+}
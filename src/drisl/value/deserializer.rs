@@ -0,0 +1,255 @@
+//! Deserializing from a [`Value`] without encoding to bytes.
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{convert::Infallible, mem};
+
+use serde::de::{
+    self, DeserializeOwned, IntoDeserializer, Visitor,
+    value::{BytesDeserializer, MapDeserializer, SeqDeserializer, StringDeserializer},
+};
+
+use super::Value;
+use crate::{cid::CID_SERDE_PRIVATE_IDENTIFIER, drisl::DecodeError};
+
+type Error = DecodeError<Infallible>;
+
+/// Converts a [`Value`] into a typed value, without encoding it to bytes first.
+///
+/// The result is the same as encoding the value and decoding it again.
+///
+/// ```
+/// # use dasl::{drisl, drisl::Value};
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// struct Post {
+///     title: String,
+///     tags: Vec<String>,
+/// }
+///
+/// let mut value = drisl!({ "title": "Hello", "tags": [] });
+/// if let Value::Map(map) = &mut value {
+///     map.insert("title".into(), "Bye".into());
+/// }
+/// let post: Post = drisl::from_value(value).unwrap();
+/// assert_eq!(post, Post { title: "Bye".into(), tags: vec![] });
+/// ```
+pub fn from_value<T>(value: Value) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(value)
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+        match &mut self {
+            // Match the byte decoder, which only uses 128-bit integers where it has to.
+            Value::Integer(int) => match (u64::try_from(*int), i64::try_from(*int)) {
+                (Ok(int), _) => visitor.visit_u64(int),
+                (_, Ok(int)) => visitor.visit_i64(int),
+                _ => visitor.visit_i128(*int),
+            },
+            Value::Bytes(bytes) => visitor.visit_byte_buf(mem::take(bytes)),
+            Value::Float(float) => visitor.visit_f64(*float),
+            Value::Text(text) => visitor.visit_string(mem::take(text)),
+            Value::Bool(bool) => visitor.visit_bool(*bool),
+            Value::Null => visitor.visit_none(),
+            Value::Cid(cid) => visitor.visit_newtype_struct(BytesDeserializer::new(cid.as_bytes())),
+            Value::Array(array) => visit_array(mem::take(array), visitor),
+            Value::Map(map) => visit_map(mem::take(map), visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            value => Err(value.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if name != CID_SERDE_PRIVATE_IDENTIFIER {
+            return visitor.visit_newtype_struct(self);
+        }
+        match self {
+            Value::Cid(cid) => visitor.visit_newtype_struct(BytesDeserializer::new(cid.as_bytes())),
+            value => Err(value.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        // Unit variants are strings, all others a map with the variant as its only key.
+        match &mut self {
+            Value::Text(variant) => visitor.visit_enum(EnumDeserializer {
+                variant: mem::take(variant),
+                value: None,
+            }),
+            Value::Map(map) if map.len() == 1 => {
+                let (variant, value) = mem::take(map)
+                    .into_iter()
+                    .next()
+                    .expect("map has one entry");
+                visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: Some(value),
+                })
+            }
+            _ => Err(self.invalid_type(&visitor)),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf seq
+        tuple tuple_struct map struct identifier
+    }
+}
+
+impl Value {
+    fn invalid_type(&self, expected: &dyn de::Expected) -> Error {
+        let unexpected = match self {
+            Value::Integer(int) => match (u64::try_from(*int), i64::try_from(*int)) {
+                (Ok(int), _) => de::Unexpected::Unsigned(int),
+                (_, Ok(int)) => de::Unexpected::Signed(int),
+                _ => de::Unexpected::Other("integer"),
+            },
+            Value::Bytes(bytes) => de::Unexpected::Bytes(bytes),
+            Value::Float(float) => de::Unexpected::Float(*float),
+            Value::Text(text) => de::Unexpected::Str(text),
+            Value::Bool(bool) => de::Unexpected::Bool(*bool),
+            Value::Null => de::Unexpected::Option,
+            Value::Cid(_) => de::Unexpected::NewtypeStruct,
+            Value::Array(_) => de::Unexpected::Seq,
+            Value::Map(_) => de::Unexpected::Map,
+        };
+        de::Error::invalid_type(unexpected, expected)
+    }
+}
+
+fn visit_array<'de, V: Visitor<'de>>(array: Vec<Value>, visitor: V) -> Result<V::Value, Error> {
+    let mut seq = SeqDeserializer::new(array.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+fn visit_map<'de, V: Visitor<'de>>(
+    map: BTreeMap<String, Value>,
+    visitor: V,
+) -> Result<V::Value, Error> {
+    let mut map = MapDeserializer::new(map.into_iter());
+    let value = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(value)
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: Option<Value>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = Error;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantDeserializer), Error> {
+        let variant = seed.deserialize(StringDeserializer::<Error>::new(self.variant))?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+struct VariantDeserializer(Option<Value>);
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0 {
+            None | Some(Value::Null) => Ok(()),
+            Some(value) => Err(value.invalid_type(&"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        match self.0 {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        mut self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(Value::Array(ref mut array)) => visit_array(mem::take(array), visitor),
+            Some(value) => Err(value.invalid_type(&visitor)),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &visitor,
+            )),
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        mut self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Some(Value::Map(ref mut map)) => visit_map(mem::take(map), visitor),
+            Some(value) => Err(value.invalid_type(&visitor)),
+            None => Err(de::Error::invalid_type(
+                de::Unexpected::UnitVariant,
+                &visitor,
+            )),
+        }
+    }
+}
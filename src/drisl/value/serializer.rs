@@ -0,0 +1,395 @@
+//! Serializing into a [`Value`] without encoding to bytes.
+
+use alloc::{
+    borrow::ToOwned,
+    collections::{BTreeMap, btree_map::Entry},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::Infallible, mem};
+
+use serde::{Serialize, ser};
+
+use super::Value;
+use crate::{
+    cid::{CID_SERDE_PRIVATE_IDENTIFIER, Cid},
+    drisl::EncodeError,
+};
+
+type Error = EncodeError<Infallible>;
+
+/// Converts a value into a [`Value`], without encoding it to bytes first.
+///
+/// The result is the same as encoding and decoding it again, e.g. structs become maps and floats
+/// have to be finite.
+///
+/// ```
+/// # use dasl::drisl;
+/// #[derive(serde::Serialize)]
+/// struct Post {
+///     title: String,
+///     tags: Vec<String>,
+/// }
+///
+/// let post = Post { title: "Hello".into(), tags: vec!["a".into()] };
+/// let value = drisl::to_value(&post).unwrap();
+/// assert_eq!(value, drisl!({ "title": "Hello", "tags": ["a"] }));
+/// ```
+pub fn to_value<T>(value: &T) -> Result<Value, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(Serializer)
+}
+
+/// A serializer that produces a [`Value`].
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeMap;
+    type SerializeStructVariant = SerializeVariant<SerializeMap>;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, Error> {
+        Ok(Value::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value, Error> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value, Error> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value, Error> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value, Error> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value, Error> {
+        if !(Value::MIN_INTEGER..=Value::MAX_INTEGER).contains(&v) {
+            return Err(EncodeError::IntegerOutOfRange);
+        }
+        Ok(Value::Integer(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value, Error> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value, Error> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value, Error> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value, Error> {
+        Ok(Value::Integer(v.into()))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value, Error> {
+        if (u64::MAX as u128) < v {
+            return Err(EncodeError::IntegerOutOfRange);
+        }
+        Ok(Value::Integer(v as i128))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value, Error> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value, Error> {
+        if !v.is_finite() {
+            return Err(EncodeError::Msg(
+                "Float must be a finite number, not Infinity or NaN".into(),
+            ));
+        }
+        Ok(Value::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value, Error> {
+        Ok(Value::Text(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value, Error> {
+        Ok(Value::Text(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, Error> {
+        Ok(Value::Bytes(v.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, Error> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value, Error> {
+        Ok(Value::Text(variant.to_owned()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        if name != CID_SERDE_PRIVATE_IDENTIFIER {
+            return value.serialize(self);
+        }
+        // CIDs are serialized as their raw bytes.
+        match &value.serialize(self)? {
+            Value::Bytes(bytes) => Cid::from_bytes_raw(bytes)
+                .map(Value::Cid)
+                .map_err(|err| EncodeError::Msg(format!("Invalid CID: {err}"))),
+            _ => Err(EncodeError::Msg("Invalid CID".into())),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, Error> {
+        let value = value.serialize(self)?;
+        Ok(Value::Map(BTreeMap::from([(variant.to_owned(), value)])))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, Error> {
+        Ok(SerializeArray(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeVariant<SerializeArray>, Error> {
+        Ok(SerializeVariant {
+            variant,
+            inner: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap::default())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<SerializeMap, Error> {
+        Ok(SerializeMap::default())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeVariant<SerializeMap>, Error> {
+        Ok(SerializeVariant {
+            variant,
+            inner: SerializeMap::default(),
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct SerializeArray(Vec<Value>);
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.0.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Array(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+#[derive(Default)]
+struct SerializeMap {
+    map: BTreeMap<String, Value>,
+    next_key: Option<String>,
+}
+
+impl SerializeMap {
+    fn insert(&mut self, key: String, value: Value) -> Result<(), Error> {
+        match self.map.entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+            Entry::Occupied(entry) => Err(EncodeError::Msg(format!(
+                "Duplicate map key: {}",
+                entry.key()
+            ))),
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        match &mut key.serialize(Serializer)? {
+            Value::Text(key) => {
+                self.next_key = Some(mem::take(key));
+                Ok(())
+            }
+            _ => Err(EncodeError::Msg("Map key must be a string".into())),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| EncodeError::Msg("Map value without a key".into()))?;
+        let value = value.serialize(Serializer)?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Map(self.map))
+    }
+}
+
+impl ser::SerializeStruct for SerializeMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        let value = value.serialize(Serializer)?;
+        self.insert(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        Ok(Value::Map(self.map))
+    }
+}
+
+/// Wraps the value of a tuple or struct variant into a map with the variant as its only key.
+struct SerializeVariant<T> {
+    variant: &'static str,
+    inner: T,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        ser::SerializeSeq::serialize_element(&mut self.inner, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        let value = ser::SerializeSeq::end(self.inner)?;
+        Ok(Value::Map(BTreeMap::from([(
+            self.variant.to_owned(),
+            value,
+        )])))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeMap> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        ser::SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<Value, Error> {
+        let value = ser::SerializeStruct::end(self.inner)?;
+        Ok(Value::Map(BTreeMap::from([(
+            self.variant.to_owned(),
+            value,
+        )])))
+    }
+}
@@ -0,0 +1,159 @@
+//! Statistics about the size and shape of a value.
+
+use alloc::vec::Vec;
+
+use super::Value;
+
+/// Statistics about a [`Value`], see [`Value::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ValueStats {
+    /// Number of integers.
+    pub integers: usize,
+    /// Number of byte strings.
+    pub bytes: usize,
+    /// Number of floats.
+    pub floats: usize,
+    /// Number of strings, not counting map keys.
+    pub texts: usize,
+    /// Number of booleans.
+    pub bools: usize,
+    /// Number of nulls.
+    pub nulls: usize,
+    /// Number of CIDs, i.e. links to other documents.
+    pub cids: usize,
+    /// Number of arrays.
+    pub arrays: usize,
+    /// Number of maps.
+    pub maps: usize,
+    /// Number of map entries, over all maps.
+    pub map_entries: usize,
+    /// Number of arrays and maps nested inside each other at the deepest point, `0` for scalars.
+    ///
+    /// This is the smallest [`DecodeOptions::max_depth`](crate::drisl::de::DecodeOptions::max_depth)
+    /// that accepts the value.
+    pub max_depth: usize,
+    /// Total length of all strings in bytes, including map keys.
+    pub text_len: usize,
+    /// Total length of all byte strings.
+    pub bytes_len: usize,
+    /// Length of the canonical encoding, the same as [`encoded_len`](crate::drisl::encoded_len).
+    ///
+    /// `None` if the value can't be encoded, because it contains a non-finite float or an integer
+    /// that is out of range.
+    pub encoded_len: Option<usize>,
+}
+
+impl ValueStats {
+    /// Returns the total number of values, including arrays and maps but not map keys.
+    pub fn nodes(&self) -> usize {
+        self.integers
+            + self.bytes
+            + self.floats
+            + self.texts
+            + self.bools
+            + self.nulls
+            + self.cids
+            + self.arrays
+            + self.maps
+    }
+}
+
+impl Value {
+    /// Collects statistics about the value, e.g. to check it against quotas before storing it.
+    ///
+    /// The value is walked once, without encoding it and without recursion.
+    ///
+    /// ```
+    /// # use dasl::{cid::{Cid, Codec}, drisl};
+    /// let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    /// let value = drisl!({ "name": "foo", "links": [cid, cid] });
+    /// let stats = value.stats();
+    /// assert_eq!(stats.cids, 2);
+    /// assert_eq!(stats.nodes(), 5);
+    /// assert_eq!(stats.max_depth, 2);
+    /// assert_eq!(stats.encoded_len, Some(drisl::to_vec(&value).unwrap().len()));
+    /// ```
+    pub fn stats(&self) -> ValueStats {
+        let mut stats = ValueStats::default();
+        let mut encoded_len = Some(0usize);
+        let mut add_len = |len: Option<usize>| {
+            encoded_len = encoded_len.zip(len).and_then(|(a, b)| a.checked_add(b));
+        };
+
+        // Values with their depth, counting the arrays and maps they are in.
+        let mut stack = Vec::from([(self, 0)]);
+        while let Some((value, depth)) = stack.pop() {
+            match value {
+                Self::Integer(value) => {
+                    stats.integers += 1;
+                    let arg = if *value < 0 { -1 - *value } else { *value };
+                    add_len(u64::try_from(arg).ok().map(header_len));
+                }
+                Self::Bytes(value) => {
+                    stats.bytes += 1;
+                    stats.bytes_len += value.len();
+                    add_len(Some(str_len(value.len())));
+                }
+                Self::Float(value) => {
+                    stats.floats += 1;
+                    // Floats are always encoded with 64 bits.
+                    add_len(value.is_finite().then_some(9));
+                }
+                Self::Text(value) => {
+                    stats.texts += 1;
+                    stats.text_len += value.len();
+                    add_len(Some(str_len(value.len())));
+                }
+                Self::Bool(_) => {
+                    stats.bools += 1;
+                    add_len(Some(1));
+                }
+                Self::Null => {
+                    stats.nulls += 1;
+                    add_len(Some(1));
+                }
+                Self::Cid(cid) => {
+                    stats.cids += 1;
+                    // Tag 42 and a byte string of the CID with a leading zero byte.
+                    add_len(Some(2 + str_len(1 + cid.as_bytes().len())));
+                }
+                Self::Array(items) => {
+                    stats.arrays += 1;
+                    stats.max_depth = stats.max_depth.max(depth + 1);
+                    add_len(Some(header_len(items.len() as u64)));
+                    stack.extend(items.iter().map(|item| (item, depth + 1)));
+                }
+                Self::Map(entries) => {
+                    stats.maps += 1;
+                    stats.map_entries += entries.len();
+                    stats.max_depth = stats.max_depth.max(depth + 1);
+                    add_len(Some(header_len(entries.len() as u64)));
+                    for (key, value) in entries {
+                        stats.text_len += key.len();
+                        add_len(Some(str_len(key.len())));
+                        stack.push((value, depth + 1));
+                    }
+                }
+            }
+        }
+        stats.encoded_len = encoded_len;
+        stats
+    }
+}
+
+/// Length of the header of a data item with the given argument.
+fn header_len(arg: u64) -> usize {
+    match arg {
+        0..24 => 1,
+        24..0x100 => 2,
+        0x100..0x1_0000 => 3,
+        0x1_0000..0x1_0000_0000 => 5,
+        _ => 9,
+    }
+}
+
+/// Length of a string or byte string of `len` bytes, including its header.
+fn str_len(len: usize) -> usize {
+    header_len(len as u64) + len
+}
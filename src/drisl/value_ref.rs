@@ -0,0 +1,242 @@
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use core::fmt;
+
+use serde::{Deserialize, de, ser};
+
+use super::{Value, value::MAX_PREALLOC_LEN};
+use crate::cid::{BytesToCidVisitor, Cid};
+
+/// A dynamic DRISL value that borrows strings and byte strings from the input.
+///
+/// Use [`from_slice_ref`](super::from_slice_ref) to decode it without copying any strings or
+/// byte strings. It can only be deserialized from data that can be borrowed, i.e. slices.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum ValueRef<'a> {
+    /// An integer
+    Integer(i128),
+    /// Bytes
+    Bytes(&'a [u8]),
+    /// A float
+    Float(f64),
+    /// A string
+    Text(&'a str),
+    /// A boolean
+    Bool(bool),
+    /// Null
+    Null,
+    /// CID
+    Cid(Cid),
+    /// An array
+    Array(Vec<ValueRef<'a>>),
+    /// A map
+    Map(BTreeMap<&'a str, ValueRef<'a>>),
+}
+
+impl ValueRef<'_> {
+    /// Converts it into an owned [`Value`], copying all strings and byte strings.
+    #[allow(clippy::should_implement_trait)]
+    pub fn to_owned(&self) -> Value {
+        match self {
+            Self::Integer(value) => Value::Integer(*value),
+            Self::Bytes(value) => Value::Bytes(value.to_vec()),
+            Self::Float(value) => Value::Float(*value),
+            Self::Text(value) => Value::Text(value.to_string()),
+            Self::Bool(value) => Value::Bool(*value),
+            Self::Null => Value::Null,
+            Self::Cid(value) => Value::Cid(*value),
+            Self::Array(value) => Value::Array(value.iter().map(Self::to_owned).collect()),
+            Self::Map(value) => Value::Map(
+                value
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<ValueRef<'_>> for Value {
+    fn from(value: ValueRef<'_>) -> Self {
+        value.to_owned()
+    }
+}
+
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    fn from(value: &'a Value) -> Self {
+        match value {
+            Value::Integer(value) => Self::Integer(*value),
+            Value::Bytes(value) => Self::Bytes(value),
+            Value::Float(value) => Self::Float(*value),
+            Value::Text(value) => Self::Text(value),
+            Value::Bool(value) => Self::Bool(*value),
+            Value::Null => Self::Null,
+            Value::Cid(value) => Self::Cid(*value),
+            Value::Array(value) => Self::Array(value.iter().map(Self::from).collect()),
+            Value::Map(value) => Self::Map(
+                value
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), Self::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<'de> de::Deserialize<'de> for ValueRef<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        struct ValueRefVisitor;
+
+        impl<'de> de::Visitor<'de> for ValueRefVisitor {
+            type Value = ValueRef<'de>;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt.write_str("any valid DRISL kind, borrowed from the input")
+            }
+
+            #[inline]
+            fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Text(value))
+            }
+
+            #[inline]
+            fn visit_borrowed_bytes<E>(self, value: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Bytes(value))
+            }
+
+            #[inline]
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Integer(v.into()))
+            }
+
+            #[inline]
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Integer(v.into()))
+            }
+
+            #[inline]
+            fn visit_i128<E>(self, v: i128) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Integer(v))
+            }
+
+            #[inline]
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Float(v))
+            }
+
+            #[inline]
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Bool(v))
+            }
+
+            #[inline]
+            fn visit_none<E>(self) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Null)
+            }
+
+            #[inline]
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                Deserialize::deserialize(deserializer)
+            }
+
+            #[inline]
+            fn visit_unit<E>(self) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                Ok(ValueRef::Null)
+            }
+
+            #[inline]
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::SeqAccess<'de>,
+            {
+                let mut vec =
+                    Vec::with_capacity(visitor.size_hint().unwrap_or(0).min(MAX_PREALLOC_LEN));
+
+                while let Some(elem) = visitor.next_element()? {
+                    vec.push(elem);
+                }
+
+                Ok(ValueRef::Array(vec))
+            }
+
+            #[inline]
+            fn visit_map<V>(self, mut visitor: V) -> Result<Self::Value, V::Error>
+            where
+                V: de::MapAccess<'de>,
+            {
+                let mut values = BTreeMap::new();
+
+                // The last one of equal keys wins, see `DuplicateKeys::LastWins`.
+                while let Some((key, value)) = visitor.next_entry()? {
+                    values.insert(key, value);
+                }
+
+                Ok(ValueRef::Map(values))
+            }
+
+            /// Newtype structs are only used to deserialize CIDs.
+            #[inline]
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                deserializer
+                    .deserialize_bytes(BytesToCidVisitor)
+                    .map(ValueRef::Cid)
+            }
+        }
+
+        deserializer.deserialize_any(ValueRefVisitor)
+    }
+}
+
+impl ser::Serialize for ValueRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        match &self {
+            Self::Null => serializer.serialize_none(),
+            Self::Bool(value) => serializer.serialize_bool(*value),
+            Self::Integer(value) => serializer.serialize_i128(*value),
+            Self::Float(value) => serializer.serialize_f64(*value),
+            Self::Text(value) => serializer.serialize_str(value),
+            Self::Bytes(value) => serializer.serialize_bytes(value),
+            Self::Array(value) => serializer.collect_seq(value),
+            Self::Map(value) => serializer.collect_map(value),
+            Self::Cid(value) => value.serialize(serializer),
+        }
+    }
+}
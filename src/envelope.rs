@@ -0,0 +1,183 @@
+//! Signed DRISL envelopes.
+//!
+//! A [`Signed`] envelope wraps a payload together with an ed25519 signature over its canonical
+//! DRISL encoding. It is encoded as a DRISL map:
+//!
+//! ```text
+//! {
+//!   "payload": bytes,    // the canonical DRISL encoding of the payload
+//!   "publicKey": bytes,  // the 32 byte ed25519 public key of the signer
+//!   "signature": bytes,  // the 64 byte ed25519 signature over `payload`
+//! }
+//! ```
+//!
+//! The signature is verified whenever an envelope is decoded, hence a successfully decoded
+//! [`Signed`] value always carries a valid signature.
+
+use std::{collections::TryReserveError, convert::Infallible, fmt};
+
+pub use ed25519_dalek;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize, de, de::DeserializeOwned, ser};
+use serde_bytes::{ByteBuf, Bytes};
+use thiserror::Error;
+
+use crate::{
+    cid::{Cid, Codec},
+    drisl::{DecodeError, EncodeError},
+};
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum EnvelopeError {
+    #[error("Failed to encode payload: {_0}")]
+    Encode(#[from] EncodeError<TryReserveError>),
+    #[error("Failed to decode payload: {_0}")]
+    Decode(#[from] DecodeError<Infallible>),
+    #[error("Invalid public key")]
+    InvalidPublicKey,
+    #[error("Invalid signature")]
+    InvalidSignature,
+}
+
+/// A payload signed with an ed25519 key.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::{drisl, envelope::{Signed, ed25519_dalek::SigningKey}};
+///
+/// let key = SigningKey::from_bytes(&[7u8; 32]);
+/// let signed = Signed::sign("hello".to_string(), &key).unwrap();
+/// let encoded = drisl::to_vec(&signed).unwrap();
+///
+/// // Decoding verifies the signature.
+/// let decoded: Signed<String> = drisl::from_slice(&encoded).unwrap();
+/// assert_eq!(decoded.public_key(), &key.verifying_key());
+/// let (payload, cid) = decoded.into_parts();
+/// assert_eq!(payload, "hello");
+/// assert_eq!(cid, signed.cid());
+/// ```
+#[derive(Clone)]
+pub struct Signed<T> {
+    payload: T,
+    payload_bytes: Vec<u8>,
+    public_key: VerifyingKey,
+    signature: Signature,
+}
+
+/// The encoded form of an envelope.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEnvelope<B> {
+    payload: B,
+    public_key: B,
+    signature: B,
+}
+
+impl<T: Serialize> Signed<T> {
+    /// Signs the canonical encoding of `payload` with `key`.
+    pub fn sign(payload: T, key: &SigningKey) -> Result<Self, EnvelopeError> {
+        let payload_bytes = crate::drisl::ser::to_vec(&payload)?;
+        let signature = key.sign(&payload_bytes);
+        Ok(Self {
+            payload,
+            payload_bytes,
+            public_key: key.verifying_key(),
+            signature,
+        })
+    }
+}
+
+impl<T: DeserializeOwned> Signed<T> {
+    /// Verifies a signature over an encoded payload and decodes it.
+    pub fn verify(
+        payload_bytes: Vec<u8>,
+        public_key: VerifyingKey,
+        signature: Signature,
+    ) -> Result<Self, EnvelopeError> {
+        public_key
+            .verify(&payload_bytes, &signature)
+            .map_err(|_| EnvelopeError::InvalidSignature)?;
+        let payload = crate::drisl::de::from_slice(&payload_bytes)?;
+        Ok(Self {
+            payload,
+            payload_bytes,
+            public_key,
+            signature,
+        })
+    }
+}
+
+impl<T> Signed<T> {
+    /// Returns the payload.
+    pub fn payload(&self) -> &T {
+        &self.payload
+    }
+
+    /// Returns the canonical encoding of the payload, which the signature is over.
+    pub fn payload_bytes(&self) -> &[u8] {
+        &self.payload_bytes
+    }
+
+    /// Returns the public key of the signer.
+    pub fn public_key(&self) -> &VerifyingKey {
+        &self.public_key
+    }
+
+    /// Returns the signature.
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Returns the SHA2-256 CID of the encoded payload.
+    pub fn cid(&self) -> Cid {
+        Cid::digest_sha2(Codec::Drisl, &self.payload_bytes)
+    }
+
+    /// Returns the payload and its CID.
+    pub fn into_parts(self) -> (T, Cid) {
+        let cid = self.cid();
+        (self.payload, cid)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Signed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signed")
+            .field("payload", &self.payload)
+            .field("public_key", &self.public_key)
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl<T> ser::Serialize for Signed<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        RawEnvelope {
+            payload: Bytes::new(&self.payload_bytes),
+            public_key: Bytes::new(self.public_key.as_bytes()),
+            signature: Bytes::new(&self.signature.to_bytes()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: DeserializeOwned> de::Deserialize<'de> for Signed<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let raw = RawEnvelope::<ByteBuf>::deserialize(deserializer)?;
+        let public_key = <[u8; 32]>::try_from(raw.public_key.as_slice())
+            .ok()
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| de::Error::custom(EnvelopeError::InvalidPublicKey))?;
+        let signature = Signature::from_slice(&raw.signature)
+            .map_err(|_| de::Error::custom(EnvelopeError::InvalidSignature))?;
+        Self::verify(raw.payload.into_vec(), public_key, signature).map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,255 @@
+//! Bindings for Swift, Kotlin and Python, built with [UniFFI](https://mozilla.github.io/uniffi-rs/).
+//!
+//! The bindings are generated from the compiled library with the `uniffi-bindgen` binary:
+//!
+//! ```text
+//! cargo rustc --lib --release --features uniffi --crate-type cdylib
+//! cargo run --features uniffi-bindgen --bin uniffi-bindgen -- generate \
+//!     --library target/release/libdasl.so --language swift --out-dir out
+//! ```
+//!
+//! DRISL values are exposed as [`DrislValue`], CIDs as the [`Cid`] object.
+
+use std::{
+    collections::HashMap, collections::TryReserveError, convert::Infallible, mem, sync::Arc,
+};
+
+use thiserror::Error;
+
+use crate::{
+    cid::{self, CidBuilder, CidParseError, Codec, Multibase, Multihash},
+    drisl::{self, CanonicityError, DecodeError, EncodeError, Value},
+};
+
+/// An error returned by the bindings.
+#[derive(Debug, Error, uniffi::Error)]
+#[uniffi(flat_error)]
+#[non_exhaustive]
+pub enum DaslError {
+    #[error("Failed to encode: {_0}")]
+    Encode(#[from] EncodeError<TryReserveError>),
+    #[error("Failed to decode: {_0}")]
+    Decode(#[from] DecodeError<Infallible>),
+    #[error("Not canonical: {_0}")]
+    NotCanonical(#[from] CanonicityError),
+    #[error("Invalid CID: {_0}")]
+    InvalidCid(#[from] CidParseError),
+    #[error("Unknown multibase: {_0}")]
+    UnknownMultibase(String),
+}
+
+/// A dynamic DRISL value.
+///
+/// Integers are split like in CBOR: `Integer` holds everything that fits into an `i64`,
+/// `Unsigned` larger positive integers and `Negative` smaller negative integers, which are
+/// `-1 - value`.
+#[derive(Debug, Clone, PartialEq, uniffi::Enum)]
+pub enum DrislValue {
+    Integer { value: i64 },
+    Unsigned { value: u64 },
+    Negative { value: u64 },
+    Float { value: f64 },
+    Bytes { value: Vec<u8> },
+    Text { value: String },
+    Bool { value: bool },
+    Null,
+    Cid { value: Arc<Cid> },
+    Array { value: Vec<DrislValue> },
+    Map { value: HashMap<String, DrislValue> },
+}
+
+impl DrislValue {
+    /// Converts a decoded value, whose integers are always within the range of DRISL.
+    fn from_value(mut value: Value) -> Self {
+        match &mut value {
+            Value::Integer(int) => match i64::try_from(*int) {
+                Ok(value) => Self::Integer { value },
+                Err(_) if *int > 0 => Self::Unsigned { value: *int as u64 },
+                Err(_) => Self::Negative {
+                    value: (-1 - *int) as u64,
+                },
+            },
+            Value::Bytes(value) => Self::Bytes {
+                value: mem::take(value),
+            },
+            Value::Float(value) => Self::Float { value: *value },
+            Value::Text(value) => Self::Text {
+                value: mem::take(value),
+            },
+            Value::Bool(value) => Self::Bool { value: *value },
+            Value::Null => Self::Null,
+            Value::Cid(cid) => Self::Cid {
+                value: Arc::new(Cid(*cid)),
+            },
+            Value::Array(array) => Self::Array {
+                value: mem::take(array).into_iter().map(Self::from_value).collect(),
+            },
+            Value::Map(map) => Self::Map {
+                value: mem::take(map)
+                    .into_iter()
+                    .map(|(key, value)| (key, Self::from_value(value)))
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl From<DrislValue> for Value {
+    fn from(value: DrislValue) -> Self {
+        match value {
+            DrislValue::Integer { value } => Self::Integer(value.into()),
+            DrislValue::Unsigned { value } => Self::Integer(value.into()),
+            DrislValue::Negative { value } => Self::Integer(-1 - i128::from(value)),
+            DrislValue::Float { value } => Self::Float(value),
+            DrislValue::Bytes { value } => Self::Bytes(value),
+            DrislValue::Text { value } => Self::Text(value),
+            DrislValue::Bool { value } => Self::Bool(value),
+            DrislValue::Null => Self::Null,
+            DrislValue::Cid { value } => Self::Cid(value.0),
+            DrislValue::Array { value } => Self::Array(value.into_iter().map(Self::from).collect()),
+            DrislValue::Map { value } => Self::Map(
+                value
+                    .into_iter()
+                    .map(|(key, value)| (key, Self::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Encodes a value as DRISL.
+#[uniffi::export]
+pub fn encode(value: DrislValue) -> Result<Vec<u8>, DaslError> {
+    Ok(drisl::ser::to_vec(&Value::from(value))?)
+}
+
+/// Decodes a DRISL value.
+#[uniffi::export]
+pub fn decode(bytes: &[u8]) -> Result<DrislValue, DaslError> {
+    Ok(DrislValue::from_value(drisl::de::from_slice(bytes)?))
+}
+
+/// Checks that the bytes are canonical DRISL.
+#[uniffi::export]
+pub fn validate(bytes: &[u8]) -> Result<(), DaslError> {
+    Ok(drisl::validate(bytes)?)
+}
+
+/// Computes the CID of a value encoded as DRISL.
+#[uniffi::export]
+pub fn cid_of(value: DrislValue, hash: CidHash) -> Result<Arc<Cid>, DaslError> {
+    let cid = drisl::ser::cid_of(&Value::from(value), hash.into()).map_err(|err| match err {
+        EncodeError::Msg(msg) => EncodeError::Msg(msg),
+        EncodeError::IntegerOutOfRange => EncodeError::IntegerOutOfRange,
+        EncodeError::Write(never) => match never {},
+    })?;
+    Ok(Arc::new(Cid(cid)))
+}
+
+/// The codec of a [`Cid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum CidCodec {
+    Raw,
+    Drisl,
+}
+
+impl From<CidCodec> for Codec {
+    fn from(codec: CidCodec) -> Self {
+        match codec {
+            CidCodec::Raw => Codec::Raw,
+            CidCodec::Drisl => Codec::Drisl,
+        }
+    }
+}
+
+/// The hash function of a [`Cid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum CidHash {
+    Sha2256,
+    Blake3,
+}
+
+impl From<CidHash> for Multihash {
+    fn from(hash: CidHash) -> Self {
+        match hash {
+            CidHash::Sha2256 => Multihash::Sha2256,
+            CidHash::Blake3 => Multihash::Blake3,
+        }
+    }
+}
+
+/// A content identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, uniffi::Object)]
+#[uniffi::export(Debug, Display, Eq, Hash)]
+pub struct Cid(cid::Cid);
+
+impl std::fmt::Display for Cid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[uniffi::export]
+impl Cid {
+    /// Parses a CID from its string form, in any supported multibase.
+    #[uniffi::constructor]
+    pub fn parse(cid: &str) -> Result<Arc<Self>, DaslError> {
+        Ok(Arc::new(Self(cid.parse()?)))
+    }
+
+    /// Decodes a CID from its binary form.
+    #[uniffi::constructor]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Arc<Self>, DaslError> {
+        Ok(Arc::new(Self(cid::Cid::from_bytes_raw(bytes)?)))
+    }
+
+    /// Computes the CID of `data`.
+    #[uniffi::constructor]
+    pub fn digest(data: &[u8], codec: CidCodec, hash: CidHash) -> Arc<Self> {
+        let cid = CidBuilder::new()
+            .codec(codec.into())
+            .hash(hash.into())
+            .digest(data);
+        Arc::new(Self(cid))
+    }
+
+    /// Returns the string form with the multibase of the given prefix, e.g. `"z"` for base58.
+    pub fn to_string_with_base(&self, prefix: &str) -> Result<String, DaslError> {
+        let mut chars = prefix.chars();
+        match (chars.next().and_then(Multibase::from_prefix), chars.next()) {
+            (Some(base), None) => Ok(self.0.to_string_with_base(base)),
+            _ => Err(DaslError::UnknownMultibase(prefix.to_string())),
+        }
+    }
+
+    /// Returns the binary form.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.as_bytes().to_vec()
+    }
+
+    /// Returns the codec.
+    pub fn codec(&self) -> CidCodec {
+        match self.0.codec() {
+            Codec::Raw => CidCodec::Raw,
+            Codec::Drisl => CidCodec::Drisl,
+        }
+    }
+
+    /// Returns the hash function.
+    pub fn hash_type(&self) -> CidHash {
+        match self.0.multihash_type() {
+            Multihash::Sha2256 => CidHash::Sha2256,
+            Multihash::Blake3 => CidHash::Blake3,
+        }
+    }
+
+    /// Returns the hash digest.
+    pub fn hash(&self) -> Vec<u8> {
+        self.0.hash().to_vec()
+    }
+
+    /// Returns whether `data` hashes to this CID.
+    pub fn verify(&self, data: &[u8]) -> bool {
+        self.0.verify(data).is_ok()
+    }
+}
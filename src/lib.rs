@@ -1,6 +1,39 @@
 #![deny(rustdoc::broken_intra_doc_links)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-mod base32;
+extern crate alloc;
 
+mod varint;
+
+#[cfg(feature = "atproto")]
+pub mod atproto;
+#[cfg(feature = "std")]
+pub mod blockstore;
+#[cfg(feature = "std")]
+pub mod car;
 pub mod cid;
 pub mod drisl;
+#[cfg(feature = "ed25519")]
+pub mod envelope;
+#[cfg(feature = "uniffi")]
+pub mod ffi;
+pub mod multibase;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(any(feature = "rasl", feature = "rasl-blocking", feature = "rasl-server"))]
+pub mod rasl;
+#[cfg(feature = "test-utils")]
+pub mod testing;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
+/// Items used by the macros, which can't rely on `std` being available.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{collections::BTreeMap, vec};
+}
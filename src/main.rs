@@ -1,39 +1,66 @@
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let source = std::env::args().nth(1).expect("missing source");
-    println!("Parsing data from {} ...", source);
+use clap::{Parser, Subcommand};
 
-    let file = std::fs::File::open(&source)?;
-    let mut file = std::io::BufReader::new(file);
+mod cli;
 
-    let iter = dasl::drisl::de::iter_from_reader::<dasl::drisl::Value, _>(&mut file);
-    let now = std::time::Instant::now();
-    let mut count = 0;
-    for (i, el) in iter.enumerate() {
-        let el = el?;
-        if i == 0 {
-            println!("{:?}", el);
-        }
-        if i % 100 == 0 {
-            print!(".");
-        }
-        count += 1;
-    }
-    println!("\n");
-
-    let done = now.elapsed();
+/// Tools for working with DASL data.
+#[derive(Debug, Parser)]
+#[command(name = "dasl", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
 
-    let meta = std::fs::metadata(&source)?;
-    let mbs = meta.len() as f64 / done.as_secs_f64() / 1024. / 1024.;
-    let values_per_sec = count as f64 / done.as_secs_f64();
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Measure how fast a file of concatenated DRISL values can be decoded.
+    Bench(cli::bench::Args),
+    /// Pack, unpack and list CAR files.
+    Car(cli::car::Args),
+    /// Show the components and encodings of a CID.
+    Cid(cli::cid::Args),
+    /// Convert a value between DRISL, JSON and diagnostic notation.
+    Convert(cli::convert::Args),
+    /// Show the structural differences between two DRISL values.
+    Diff(cli::diff::Args),
+    /// Print an annotated hexdump of CBOR data, item by item.
+    Dump(cli::dump::Args),
+    /// Fetch content from a RASL endpoint and verify it against its CID.
+    Get(cli::get::Args),
+    /// Print the results of a query against each value of a DRISL file.
+    Grep(cli::grep::Args),
+    /// Compute the CID of a file.
+    Hash(cli::hash::Args),
+    /// Print DRISL values in diagnostic notation.
+    Inspect(cli::inspect::Args),
+    /// Serve a directory blockstore or a CAR file over HTTP, per the RASL spec.
+    Serve(cli::serve::Args),
+    /// Report value counts, key frequencies and record sizes of a DRISL or CAR file.
+    Stats(cli::stats::Args),
+    /// Print the last values of a file, and follow it as values are appended.
+    Tail(cli::tail::Args),
+    /// Check that a file is a single value of canonical DRISL.
+    Validate(cli::validate::Args),
+}
 
-    println!(
-        "File '{}' ({:.01}MiB)\nParsed {} values in {}ms\n{:.02} Values/s\n{:.02} MiB/s",
-        source,
-        meta.len() as f64 / 1024. / 1024.,
-        count,
-        done.as_millis(),
-        values_per_sec,
-        mbs,
-    );
-    Ok(())
+fn main() {
+    let result = match Cli::parse().command {
+        Command::Bench(args) => cli::bench::run(args),
+        Command::Car(args) => cli::car::run(args),
+        Command::Cid(args) => cli::cid::run(args),
+        Command::Convert(args) => cli::convert::run(args),
+        Command::Diff(args) => cli::diff::run(args),
+        Command::Dump(args) => cli::dump::run(args),
+        Command::Get(args) => cli::get::run(args),
+        Command::Grep(args) => cli::grep::run(args),
+        Command::Hash(args) => cli::hash::run(args),
+        Command::Inspect(args) => cli::inspect::run(args),
+        Command::Serve(args) => cli::serve::run(args),
+        Command::Stats(args) => cli::stats::run(args),
+        Command::Tail(args) => cli::tail::run(args),
+        Command::Validate(args) => cli::validate::run(args),
+    };
+    if let Err(err) = result {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
 }
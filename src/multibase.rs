@@ -0,0 +1,125 @@
+//! [Multibase](https://github.com/multiformats/multibase) encodings, as used for the string form
+//! of `CID`s.
+//!
+//! A multibase string starts with a prefix character that identifies its encoding:
+//!
+//! ```
+//! # use dasl::multibase::{self, Multibase};
+//! let encoded = multibase::encode(Multibase::Base32Lower, b"hello");
+//! assert_eq!(encoded, "bnbswy3dp");
+//! assert_eq!(
+//!     multibase::decode(&encoded).unwrap(),
+//!     (Multibase::Base32Lower, b"hello".to_vec())
+//! );
+//! ```
+//!
+//! The [`base32`] module has allocation-free slice APIs and streaming encoders and decoders.
+
+use alloc::{string::String, vec::Vec};
+
+use thiserror::Error;
+
+pub mod base32;
+mod base58;
+
+/// The supported multibase encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[non_exhaustive]
+pub enum Multibase {
+    /// `b`, the default.
+    #[default]
+    Base32Lower,
+    /// `z`
+    Base58Btc,
+    /// `f`
+    Base16Lower,
+    /// `u`, without padding.
+    Base64Url,
+}
+
+impl Multibase {
+    /// Returns the prefix character identifying this encoding.
+    pub fn prefix(&self) -> char {
+        match self {
+            Self::Base32Lower => 'b',
+            Self::Base58Btc => 'z',
+            Self::Base16Lower => 'f',
+            Self::Base64Url => 'u',
+        }
+    }
+
+    /// Looks up the encoding for a prefix character.
+    pub fn from_prefix(prefix: char) -> Option<Self> {
+        match prefix {
+            'b' => Some(Self::Base32Lower),
+            'z' => Some(Self::Base58Btc),
+            'f' => Some(Self::Base16Lower),
+            'u' => Some(Self::Base64Url),
+            _ => None,
+        }
+    }
+
+    /// Encodes `data` in this encoding, without the prefix.
+    pub fn encode(&self, data: &[u8]) -> String {
+        match self {
+            Self::Base32Lower => base32::encode(data),
+            Self::Base58Btc => base58::encode(data),
+            Self::Base16Lower => data_encoding::HEXLOWER.encode(data),
+            Self::Base64Url => data_encoding::BASE64URL_NOPAD.encode(data),
+        }
+    }
+
+    /// Decodes `data` in this encoding, which doesn't start with the prefix.
+    pub fn decode(&self, data: &str) -> Result<Vec<u8>, MultibaseError> {
+        match self {
+            Self::Base32Lower => base32::decode(data.as_bytes()),
+            Self::Base58Btc => base58::decode(data).ok_or(MultibaseError::InvalidEncoding),
+            Self::Base16Lower => data_encoding::HEXLOWER
+                .decode(data.as_bytes())
+                .map_err(MultibaseError::from_data_encoding),
+            Self::Base64Url => data_encoding::BASE64URL_NOPAD
+                .decode(data.as_bytes())
+                .map_err(MultibaseError::from_data_encoding),
+        }
+    }
+}
+
+/// Errors from decoding multibase strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum MultibaseError {
+    #[error("Missing multibase prefix")]
+    MissingPrefix,
+    #[error("Unknown multibase prefix: {0:?}")]
+    UnknownPrefix(char),
+    #[error("Invalid length")]
+    InvalidLength,
+    #[error("Invalid encoding")]
+    InvalidEncoding,
+    #[error("Output buffer too small, {needed} bytes needed")]
+    BufferTooSmall { needed: usize },
+}
+
+impl MultibaseError {
+    fn from_data_encoding(err: data_encoding::DecodeError) -> Self {
+        match err.kind {
+            data_encoding::DecodeKind::Length => Self::InvalidLength,
+            _ => Self::InvalidEncoding,
+        }
+    }
+}
+
+/// Encodes `data` in the given encoding, starting with its prefix.
+pub fn encode(base: Multibase, data: &[u8]) -> String {
+    let mut out = String::from(base.prefix());
+    out.push_str(&base.encode(data));
+    out
+}
+
+/// Decodes a string in any of the supported encodings, identified by its prefix.
+pub fn decode(data: &str) -> Result<(Multibase, Vec<u8>), MultibaseError> {
+    let mut chars = data.chars();
+    let prefix = chars.next().ok_or(MultibaseError::MissingPrefix)?;
+    let base = Multibase::from_prefix(prefix).ok_or(MultibaseError::UnknownPrefix(prefix))?;
+    Ok((base, base.decode(chars.as_str())?))
+}
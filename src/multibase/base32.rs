@@ -0,0 +1,361 @@
+//! Lowercase base32 without padding, as used by the `b` multibase.
+//!
+//! With the `fast-base32` feature, a table-driven implementation that encodes and decodes whole
+//! 5 byte blocks without branching is used. Otherwise the scalar implementation of
+//! `data-encoding` is used. Both produce identical outputs.
+//!
+//! Besides encoding to and decoding from `String`s and `Vec`s, there are functions working on
+//! slices that don't allocate, and with the `std` feature an `Encoder` and `Decoder` for
+//! streaming.
+//!
+//! ```
+//! # use dasl::multibase::base32;
+//! let mut out = [0; base32::encoded_len(5)];
+//! let len = base32::encode_to_slice(b"hello", &mut out).unwrap();
+//! assert_eq!(&out[..len], b"nbswy3dp");
+//!
+//! let mut bytes = [0; 5];
+//! let len = base32::decode_to_slice(b"nbswy3dp", &mut bytes).unwrap();
+//! assert_eq!(&bytes[..len], b"hello");
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use super::MultibaseError;
+
+#[cfg(feature = "std")]
+mod io;
+
+#[cfg(feature = "std")]
+pub use self::io::{Decoder, Encoder};
+
+#[cfg(any(not(feature = "fast-base32"), test))]
+const BASE32_LOWER: data_encoding::Encoding = data_encoding_macro::new_encoding! {
+    symbols: "abcdefghijklmnopqrstuvwxyz234567",
+};
+
+/// Encodes `input`.
+pub fn encode(input: &[u8]) -> String {
+    #[cfg(feature = "fast-base32")]
+    {
+        let mut out = alloc::vec![0; encoded_len(input.len())];
+        encode_into(input, &mut out);
+        String::from_utf8(out).expect("base32 is ASCII")
+    }
+    #[cfg(not(feature = "fast-base32"))]
+    BASE32_LOWER.encode(input)
+}
+
+/// Encodes `input` into a formatter.
+pub fn encode_write(input: &[u8], f: &mut impl fmt::Write) -> fmt::Result {
+    #[cfg(feature = "fast-base32")]
+    {
+        // Batch the blocks, so that the formatter is only called once for a CID.
+        const BATCH_LEN: usize = 8 * BLOCK_LEN;
+        let mut chars = [0; encoded_len(BATCH_LEN)];
+        for batch in input.chunks(BATCH_LEN) {
+            let chars = &mut chars[..encoded_len(batch.len())];
+            encode_into(batch, chars);
+            f.write_str(core::str::from_utf8(chars).map_err(|_| fmt::Error)?)?;
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "fast-base32"))]
+    BASE32_LOWER.encode_write(input, f)
+}
+
+/// Encodes `input` into the start of `out`, returns the number of characters written.
+///
+/// `out` has to be at least [`encoded_len`] long.
+pub fn encode_to_slice(input: &[u8], out: &mut [u8]) -> Result<usize, MultibaseError> {
+    let len = encoded_len(input.len());
+    let out = out
+        .get_mut(..len)
+        .ok_or(MultibaseError::BufferTooSmall { needed: len })?;
+    #[cfg(feature = "fast-base32")]
+    encode_into(input, out);
+    #[cfg(not(feature = "fast-base32"))]
+    BASE32_LOWER.encode_mut(input, out);
+    Ok(len)
+}
+
+/// Decodes `input`.
+pub fn decode(input: &[u8]) -> Result<Vec<u8>, MultibaseError> {
+    #[cfg(feature = "fast-base32")]
+    {
+        let mut out = alloc::vec![0; decoded_len(input.len())?];
+        decode_to_slice(input, &mut out)?;
+        Ok(out)
+    }
+    #[cfg(not(feature = "fast-base32"))]
+    BASE32_LOWER
+        .decode(input)
+        .map_err(MultibaseError::from_data_encoding)
+}
+
+/// Decodes `input` into the start of `out`, returns the number of bytes written.
+///
+/// `out` has to be at least [`decoded_len`] long.
+pub fn decode_to_slice(input: &[u8], out: &mut [u8]) -> Result<usize, MultibaseError> {
+    let len = decoded_len(input.len())?;
+    let out = out
+        .get_mut(..len)
+        .ok_or(MultibaseError::BufferTooSmall { needed: len })?;
+    #[cfg(feature = "fast-base32")]
+    decode_into(input, out).ok_or(MultibaseError::InvalidEncoding)?;
+    #[cfg(not(feature = "fast-base32"))]
+    BASE32_LOWER
+        .decode_mut(input, out)
+        .map_err(|partial| MultibaseError::from_data_encoding(partial.error))?;
+    Ok(len)
+}
+
+/// Decodes `input` into an array in a const context, returns it along with the number of bytes
+/// written.
+///
+/// This decodes one character at a time, it's meant for inputs that are known at compile time.
+/// Returns `None` if the input is invalid or decodes to more than `N` bytes.
+pub(crate) const fn decode_array<const N: usize>(input: &[u8]) -> Option<([u8; N], usize)> {
+    let mut out = [0; N];
+    let mut len = 0;
+    // Bits that haven't been written yet, and how many.
+    let mut bits: u16 = 0;
+    let mut bits_len = 0;
+    let mut i = 0;
+    while i < input.len() {
+        let value = match input[i] {
+            char @ b'a'..=b'z' => char - b'a',
+            char @ b'2'..=b'7' => char - b'2' + 26,
+            _ => return None,
+        };
+        bits = bits << 5 | value as u16;
+        bits_len += 5;
+        if bits_len >= 8 {
+            if len == N {
+                return None;
+            }
+            bits_len -= 8;
+            out[len] = (bits >> bits_len) as u8;
+            bits &= (1 << bits_len) - 1;
+            len += 1;
+        }
+        i += 1;
+    }
+    // A whole character left over is an invalid length, see `decoded_len`. Like `decode`, the
+    // unused trailing bits must be zero.
+    if bits_len >= 5 || bits != 0 {
+        return None;
+    }
+    Some((out, len))
+}
+
+/// Returns the number of characters `len` bytes are encoded to.
+pub const fn encoded_len(len: usize) -> usize {
+    (len * 8).div_ceil(5)
+}
+
+/// Returns the number of bytes `len` characters decode to.
+///
+/// Fails if no number of bytes is encoded to that many characters.
+pub fn decoded_len(len: usize) -> Result<usize, MultibaseError> {
+    let tail = match len % CHARS_LEN {
+        0 => 0,
+        2 => 1,
+        4 => 2,
+        5 => 3,
+        7 => 4,
+        _ => return Err(MultibaseError::InvalidLength),
+    };
+    Ok(len / CHARS_LEN * BLOCK_LEN + tail)
+}
+
+/// Number of bytes encoded by a block of characters.
+const BLOCK_LEN: usize = 5;
+
+/// Number of characters of a block.
+const CHARS_LEN: usize = 8;
+
+#[cfg(any(feature = "fast-base32", test))]
+const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Maps 10 bits to their two characters.
+#[cfg(any(feature = "fast-base32", test))]
+const ENCODE_PAIRS: [[u8; 2]; 1024] = {
+    let mut table = [[0; 2]; 1024];
+    let mut i = 0;
+    while i < table.len() {
+        table[i] = [ALPHABET[i >> 5], ALPHABET[i & 0x1f]];
+        i += 1;
+    }
+    table
+};
+
+/// Maps characters to their value, or to `INVALID` if they aren't part of the alphabet.
+#[cfg(any(feature = "fast-base32", test))]
+const DECODE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut i = 0;
+    while i < ALPHABET.len() {
+        table[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+#[cfg(any(feature = "fast-base32", test))]
+const INVALID: u8 = 0xff;
+
+/// Encodes `input` into `out`, which has to be exactly [`encoded_len`] long.
+#[cfg(any(feature = "fast-base32", test))]
+fn encode_into(input: &[u8], out: &mut [u8]) {
+    let blocks = input.chunks_exact(BLOCK_LEN);
+    let tail = blocks.remainder();
+    let mut chars = out.chunks_exact_mut(CHARS_LEN);
+    for (block, chars) in blocks.zip(&mut chars) {
+        let bits = u64::from(block[0]) << 32
+            | u64::from(block[1]) << 24
+            | u64::from(block[2]) << 16
+            | u64::from(block[3]) << 8
+            | u64::from(block[4]);
+        encode_block(bits, chars.try_into().expect("block of chars"));
+    }
+    if !tail.is_empty() {
+        let mut bytes = [0; 8];
+        bytes[3..3 + tail.len()].copy_from_slice(tail);
+        let mut block = [0; CHARS_LEN];
+        encode_block(u64::from_be_bytes(bytes), &mut block);
+        let rest = chars.into_remainder();
+        rest.copy_from_slice(&block[..rest.len()]);
+    }
+}
+
+/// Encodes the lower 40 bits of `bits`.
+#[cfg(any(feature = "fast-base32", test))]
+#[inline(always)]
+fn encode_block(bits: u64, out: &mut [u8; CHARS_LEN]) {
+    let [a, b] = ENCODE_PAIRS[(bits >> 30) as usize & 0x3ff];
+    let [c, d] = ENCODE_PAIRS[(bits >> 20) as usize & 0x3ff];
+    let [e, f] = ENCODE_PAIRS[(bits >> 10) as usize & 0x3ff];
+    let [g, h] = ENCODE_PAIRS[bits as usize & 0x3ff];
+    *out = [a, b, c, d, e, f, g, h];
+}
+
+/// Decodes `input` into `out`, which has to be exactly [`decoded_len`] long.
+///
+/// Like `data-encoding`, the unused trailing bits of an incomplete block must be zero. Invalid
+/// characters are only checked for once at the end.
+#[cfg(any(feature = "fast-base32", test))]
+fn decode_into(input: &[u8], out: &mut [u8]) -> Option<()> {
+    let blocks = input.chunks_exact(CHARS_LEN);
+    let tail = blocks.remainder();
+    let mut bytes = out.chunks_exact_mut(BLOCK_LEN);
+    let mut invalid = 0;
+    for (block, bytes) in blocks.zip(&mut bytes) {
+        let (bits, block_invalid) = decode_block(block.try_into().expect("block of chars"));
+        invalid |= block_invalid;
+        bytes.copy_from_slice(&bits.to_be_bytes()[3..]);
+    }
+    if !tail.is_empty() {
+        // Characters with the value zero don't change the bits.
+        let mut block = [ALPHABET[0]; CHARS_LEN];
+        block[..tail.len()].copy_from_slice(tail);
+        let (bits, block_invalid) = decode_block(&block);
+        invalid |= block_invalid;
+        let rest = bytes.into_remainder();
+        let trailing = bits & ((1 << (40 - rest.len() * 8)) - 1);
+        if trailing != 0 {
+            return None;
+        }
+        rest.copy_from_slice(&bits.to_be_bytes()[3..3 + rest.len()]);
+    }
+    (invalid & !0x1f == 0).then_some(())
+}
+
+/// Decodes a block into 40 bits, and returns the values ORed together to check them.
+#[cfg(any(feature = "fast-base32", test))]
+#[inline(always)]
+fn decode_block(block: &[u8; CHARS_LEN]) -> (u64, u8) {
+    let mut bits = 0;
+    let mut invalid = 0;
+    for &char in block {
+        let value = DECODE[usize::from(char)];
+        invalid |= value;
+        bits = bits << 5 | u64::from(value & 0x1f);
+    }
+    (bits, invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random bytes.
+    fn bytes(len: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(2654435761) | 1;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
+    fn fast_encode(input: &[u8]) -> String {
+        let mut out = vec![0; encoded_len(input.len())];
+        encode_into(input, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    fn fast_decode(input: &[u8]) -> Result<Vec<u8>, MultibaseError> {
+        let mut out = vec![0; decoded_len(input.len())?];
+        decode_into(input, &mut out).ok_or(MultibaseError::InvalidEncoding)?;
+        Ok(out)
+    }
+
+    #[test]
+    fn matches_data_encoding() {
+        for len in 0..100 {
+            for seed in 0..10 {
+                let input = bytes(len, seed);
+                let encoded = BASE32_LOWER.encode(&input);
+                assert_eq!(fast_encode(&input), encoded);
+                assert_eq!(encode(&input), encoded);
+
+                let mut formatted = String::new();
+                encode_write(&input, &mut formatted).unwrap();
+                assert_eq!(formatted, encoded);
+
+                assert_eq!(fast_decode(encoded.as_bytes()).as_ref(), Ok(&input));
+                assert_eq!(decode(encoded.as_bytes()).as_ref(), Ok(&input));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_like_data_encoding() {
+        let mut cases: Vec<Vec<u8>> = ["a", "abc", "abcdef", "ab", "ac", "A", "ab=", "aaaaaaaa1"]
+            .iter()
+            .map(|case| case.as_bytes().to_vec())
+            .collect();
+        // Every character in every position of a short and a long input.
+        for byte in 0..=255 {
+            for len in [2, 7, 8, 12] {
+                for position in 0..len {
+                    let mut input = vec![b'a'; len];
+                    input[position] = byte;
+                    cases.push(input);
+                }
+            }
+        }
+        for case in cases {
+            let expected = BASE32_LOWER
+                .decode(&case)
+                .map_err(MultibaseError::from_data_encoding);
+            assert_eq!(fast_decode(&case), expected, "for input {case:?}");
+            assert_eq!(decode(&case), expected, "for input {case:?}");
+        }
+    }
+}
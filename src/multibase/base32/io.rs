@@ -0,0 +1,171 @@
+//! Streaming base32 over `std::io`.
+
+use std::io::{self, Read, Write};
+
+use super::{BLOCK_LEN, CHARS_LEN, decode_to_slice, encode_to_slice, encoded_len};
+
+/// Number of blocks encoded or decoded at once.
+const BATCH_BLOCKS: usize = 128;
+
+/// Encodes everything written to it as base32 into the wrapped writer.
+///
+/// Bytes that don't fill a whole block of 5 bytes are kept until more are written, call
+/// [`finish`](Self::finish) to write the rest.
+///
+/// ```
+/// # use std::io::Write;
+/// # use dasl::multibase::base32::Encoder;
+/// let mut encoder = Encoder::new(Vec::new());
+/// encoder.write_all(b"hel").unwrap();
+/// encoder.write_all(b"lo").unwrap();
+/// assert_eq!(encoder.finish().unwrap(), b"nbswy3dp");
+/// ```
+#[derive(Debug)]
+pub struct Encoder<W> {
+    writer: W,
+    pending: [u8; BLOCK_LEN],
+    pending_len: usize,
+}
+
+impl<W: Write> Encoder<W> {
+    /// Creates an encoder that writes to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            pending: [0; BLOCK_LEN],
+            pending_len: 0,
+        }
+    }
+
+    /// Writes the encoding of the remaining bytes and returns the wrapped writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let mut chars = [0; CHARS_LEN];
+        let len = encode_to_slice(&self.pending[..self.pending_len], &mut chars)
+            .expect("buffer fits a block");
+        self.writer.write_all(&chars[..len])?;
+        Ok(self.writer)
+    }
+
+    fn write_blocks(&mut self, blocks: &[u8]) -> io::Result<()> {
+        let mut chars = [0; encoded_len(BATCH_BLOCKS * BLOCK_LEN)];
+        for batch in blocks.chunks(BATCH_BLOCKS * BLOCK_LEN) {
+            let len = encode_to_slice(batch, &mut chars).expect("buffer fits a batch");
+            self.writer.write_all(&chars[..len])?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut input = buf;
+        if self.pending_len > 0 {
+            let len = input.len().min(BLOCK_LEN - self.pending_len);
+            self.pending[self.pending_len..self.pending_len + len].copy_from_slice(&input[..len]);
+            self.pending_len += len;
+            input = &input[len..];
+            if self.pending_len < BLOCK_LEN {
+                return Ok(buf.len());
+            }
+            let block = self.pending;
+            self.write_blocks(&block)?;
+            self.pending_len = 0;
+        }
+
+        let (blocks, rest) = input.split_at(input.len() - input.len() % BLOCK_LEN);
+        self.write_blocks(blocks)?;
+        self.pending[..rest.len()].copy_from_slice(rest);
+        self.pending_len = rest.len();
+        Ok(buf.len())
+    }
+
+    /// Flushes the wrapped writer, bytes that don't fill a whole block are kept.
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Decodes base32 read from the wrapped reader.
+///
+/// Invalid input is reported as an error of kind [`io::ErrorKind::InvalidData`].
+///
+/// ```
+/// # use std::io::Read;
+/// # use dasl::multibase::base32::Decoder;
+/// let mut decoded = Vec::new();
+/// Decoder::new(&b"nbswy3dp"[..]).read_to_end(&mut decoded).unwrap();
+/// assert_eq!(decoded, b"hello");
+/// ```
+#[derive(Debug)]
+pub struct Decoder<R> {
+    reader: R,
+    chars: [u8; BATCH_BLOCKS * CHARS_LEN],
+    chars_len: usize,
+    bytes: [u8; BATCH_BLOCKS * BLOCK_LEN],
+    bytes_pos: usize,
+    bytes_len: usize,
+    eof: bool,
+}
+
+impl<R: Read> Decoder<R> {
+    /// Creates a decoder that reads from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            chars: [0; BATCH_BLOCKS * CHARS_LEN],
+            chars_len: 0,
+            bytes: [0; BATCH_BLOCKS * BLOCK_LEN],
+            bytes_pos: 0,
+            bytes_len: 0,
+            eof: false,
+        }
+    }
+
+    /// Returns the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Reads and decodes at least one whole block, or the rest of the input.
+    fn fill(&mut self) -> io::Result<()> {
+        while self.chars_len < CHARS_LEN {
+            match self.reader.read(&mut self.chars[self.chars_len..]) {
+                Ok(0) => {
+                    self.eof = true;
+                    break;
+                }
+                Ok(len) => self.chars_len += len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        // Incomplete blocks can only be decoded at the end.
+        let len = if self.eof {
+            self.chars_len
+        } else {
+            self.chars_len - self.chars_len % CHARS_LEN
+        };
+        self.bytes_len = decode_to_slice(&self.chars[..len], &mut self.bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        self.bytes_pos = 0;
+        self.chars.copy_within(len..self.chars_len, 0);
+        self.chars_len -= len;
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.bytes_pos == self.bytes_len {
+            if self.eof || buf.is_empty() {
+                return Ok(0);
+            }
+            self.fill()?;
+        }
+        let len = buf.len().min(self.bytes_len - self.bytes_pos);
+        buf[..len].copy_from_slice(&self.bytes[self.bytes_pos..self.bytes_pos + len]);
+        self.bytes_pos += len;
+        Ok(len)
+    }
+}
@@ -0,0 +1,85 @@
+//! Base58 with the bitcoin alphabet, as used by the `base58btc` multibase.
+
+use alloc::{string::String, vec, vec::Vec};
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Encodes `input`, leading zero bytes become leading `1`s.
+pub(crate) fn encode(input: &[u8]) -> String {
+    let zeros = input.iter().take_while(|&&byte| byte == 0).count();
+
+    // Little-endian base58 digits of the remaining input.
+    let mut digits: Vec<u8> = Vec::with_capacity(input.len() * 138 / 100 + 1);
+    for &byte in &input[zeros..] {
+        let mut carry = u32::from(byte);
+        for digit in digits.iter_mut() {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut out = String::with_capacity(zeros + digits.len());
+    out.extend(core::iter::repeat_n('1', zeros));
+    out.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|&digit| char::from(ALPHABET[usize::from(digit)])),
+    );
+    out
+}
+
+/// Decodes `input`, returns `None` if it contains characters outside the alphabet.
+pub(crate) fn decode(input: &str) -> Option<Vec<u8>> {
+    let zeros = input.bytes().take_while(|&byte| byte == b'1').count();
+
+    // Little-endian bytes of the remaining input.
+    let mut bytes: Vec<u8> = Vec::with_capacity(input.len());
+    for char in input.bytes().skip(zeros) {
+        let mut carry = ALPHABET.iter().position(|&c| c == char)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut out = vec![0; zeros];
+    out.extend(bytes.iter().rev());
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        for (bytes, encoded) in [
+            (&b""[..], ""),
+            (&b"Hello World!"[..], "2NEpo7TZRRrLZSi2U"),
+            (&[0, 0, 1][..], "112"),
+            (&[0][..], "1"),
+            (&[0xff, 0xff][..], "LUv"),
+        ] {
+            assert_eq!(encode(bytes), encoded);
+            assert_eq!(decode(encoded).as_deref(), Some(bytes));
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(decode("0"), None);
+        assert_eq!(decode("abcl"), None);
+        assert_eq!(decode("é"), None);
+    }
+}
@@ -0,0 +1,65 @@
+//! [Proptest](https://docs.rs/proptest) strategies for generating valid [`Cid`]s and
+//! [`Value`]s.
+//!
+//! ```
+//! use dasl::drisl;
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(value in dasl::proptest::value())| {
+//!     let bytes = drisl::to_vec(&value).unwrap();
+//!     prop_assert_eq!(drisl::from_slice::<drisl::Value>(&bytes).unwrap(), value);
+//! });
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use ::proptest::{collection, num, option, prelude::*};
+
+use crate::{
+    cid::{Cid, Codec, Multihash},
+    drisl::Value,
+};
+
+/// Generates `CID`s with any of the supported codecs and multihash types, with a random digest or
+/// occasionally an empty hash.
+pub fn cid() -> impl Strategy<Value = Cid> {
+    (
+        prop_oneof![Just(Codec::Raw), Just(Codec::Drisl)],
+        prop_oneof![Just(Multihash::Sha2256), Just(Multihash::Blake3)],
+        option::weighted(0.9, any::<[u8; 32]>()),
+    )
+        .prop_map(|(codec, multihash, digest)| {
+            let mut bytes = Vec::from([1, codec as u8, multihash as u8, 0]);
+            if let Some(digest) = digest {
+                bytes[3] = digest.len() as u8;
+                bytes.extend_from_slice(&digest);
+            }
+            Cid::from_bytes_raw(&bytes).expect("valid CID")
+        })
+}
+
+/// Generates values that can be encoded as DRISL, nested up to 4 levels deep.
+///
+/// Integers are within the range of CBOR integers, floats are finite and map keys are strings.
+pub fn value() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        (Value::MIN_INTEGER..=Value::MAX_INTEGER).prop_map(Value::Integer),
+        (num::f64::POSITIVE
+            | num::f64::NEGATIVE
+            | num::f64::NORMAL
+            | num::f64::SUBNORMAL
+            | num::f64::ZERO)
+            .prop_map(Value::Float),
+        any::<String>().prop_map(Value::Text),
+        collection::vec(any::<u8>(), 0..64).prop_map(Value::Bytes),
+        cid().prop_map(Value::Cid),
+    ];
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            collection::vec(inner.clone(), 0..8).prop_map(Value::Array),
+            collection::btree_map(any::<String>(), inner, 0..8).prop_map(Value::Map),
+        ]
+    })
+}
@@ -0,0 +1,346 @@
+//! Python bindings, built with [PyO3](https://pyo3.rs).
+//!
+//! The extension module is built with [maturin](https://www.maturin.rs), e.g. `maturin develop`
+//! in the repository root installs it into the active virtualenv. DRISL values are converted to
+//! and from plain Python objects:
+//!
+//! | DRISL       | Python                                  |
+//! |-------------|-----------------------------------------|
+//! | integer     | `int`                                   |
+//! | float       | `float`                                 |
+//! | byte string | `bytes`, `bytearray` is accepted as well |
+//! | string      | `str`                                   |
+//! | bool        | `bool`                                  |
+//! | null        | `None`                                  |
+//! | CID         | `dasl.Cid`                              |
+//! | array       | `list`, `tuple` is accepted as well     |
+//! | map         | `dict` with `str` keys                  |
+//!
+//! ```python
+//! import dasl
+//!
+//! data = dasl.dumps({"hello": "world", "data": b"\x01\x02"})
+//! cid = dasl.cid_digest(data, codec="drisl")
+//! print(cid, dasl.loads(data))
+//!
+//! with open("blocks.car", "rb") as f:
+//!     for cid, block in dasl.CarReader(f.read()):
+//!         print(cid, len(block))
+//! ```
+
+use std::{
+    fs::File,
+    io::{BufReader, Cursor, Read},
+};
+
+use pyo3::{
+    create_exception,
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyBool, PyByteArray, PyBytes, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple},
+};
+
+use crate::{
+    car,
+    cid::{self, CidBuilder, Codec, Multibase, Multihash},
+    drisl::{self, Value},
+};
+
+/// The maximum number of nested lists and dicts [`dumps`] accepts, which catches cycles before
+/// they overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+create_exception!(dasl, DaslError, PyValueError, "An error raised by dasl.");
+
+fn error(err: impl std::fmt::Display) -> PyErr {
+    DaslError::new_err(err.to_string())
+}
+
+/// Encodes a Python object as DRISL.
+#[pyfunction]
+fn dumps<'py>(py: Python<'py>, obj: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyBytes>> {
+    let value = from_py(obj, 0)?;
+    let bytes = drisl::to_vec(&value).map_err(error)?;
+    Ok(PyBytes::new(py, &bytes))
+}
+
+/// Decodes DRISL into a Python object.
+#[pyfunction]
+fn loads<'py>(py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+    let value: Value = drisl::from_slice(data).map_err(error)?;
+    to_py(py, &value)
+}
+
+/// Checks that the bytes are canonical DRISL, raises an error describing the first violation
+/// otherwise.
+#[pyfunction]
+fn validate(data: &[u8]) -> PyResult<()> {
+    drisl::validate(data).map_err(error)
+}
+
+/// Computes the CID of `data`.
+///
+/// `codec` is `"raw"` (the default) or `"drisl"`, `hash` is `"sha2-256"` (the default) or
+/// `"blake3"`.
+#[pyfunction]
+#[pyo3(signature = (data, codec = "raw", hash = "sha2-256"))]
+fn cid_digest(data: &[u8], codec: &str, hash: &str) -> PyResult<Cid> {
+    Ok(Cid(CidBuilder::new()
+        .codec(parse_codec(codec)?)
+        .hash(parse_multihash(hash)?)
+        .digest(data)))
+}
+
+/// A content identifier.
+#[pyclass(module = "dasl", frozen, eq, hash, from_py_object)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cid(cid::Cid);
+
+#[pymethods]
+impl Cid {
+    /// Parses a CID from its string form, in any supported multibase.
+    #[new]
+    fn parse(cid: &str) -> PyResult<Self> {
+        Ok(Self(cid.parse().map_err(error)?))
+    }
+
+    /// Decodes a CID from its binary form.
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Self(cid::Cid::from_bytes_raw(data).map_err(error)?))
+    }
+
+    /// Returns the string form, in base32 unless another multibase prefix is given.
+    #[pyo3(signature = (base = None))]
+    fn encode(&self, base: Option<&str>) -> PyResult<String> {
+        let base = match base {
+            None => Multibase::default(),
+            Some(prefix) => {
+                let mut chars = prefix.chars();
+                match (chars.next().and_then(Multibase::from_prefix), chars.next()) {
+                    (Some(base), None) => base,
+                    _ => return Err(error(format!("Unknown multibase: {prefix}"))),
+                }
+            }
+        };
+        Ok(self.0.to_string_with_base(base))
+    }
+
+    /// The codec, `"raw"` or `"drisl"`.
+    #[getter]
+    fn codec(&self) -> &'static str {
+        match self.0.codec() {
+            Codec::Raw => "raw",
+            Codec::Drisl => "drisl",
+        }
+    }
+
+    /// The hash function, `"sha2-256"` or `"blake3"`.
+    #[getter]
+    fn hash_type(&self) -> &'static str {
+        match self.0.multihash_type() {
+            Multihash::Sha2256 => "sha2-256",
+            Multihash::Blake3 => "blake3",
+        }
+    }
+
+    /// The hash digest.
+    #[getter]
+    fn digest<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, self.0.hash())
+    }
+
+    /// Returns whether `data` hashes to this CID.
+    fn verify(&self, data: &[u8]) -> bool {
+        self.0.verify(data).is_ok()
+    }
+
+    fn __bytes__<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, self.0.as_bytes())
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Cid('{}')", self.0)
+    }
+}
+
+impl From<cid::Cid> for Cid {
+    fn from(cid: cid::Cid) -> Self {
+        Self(cid)
+    }
+}
+
+impl From<Cid> for cid::Cid {
+    fn from(cid: Cid) -> Self {
+        cid.0
+    }
+}
+
+/// Reads the blocks of a CAR file, iterating yields `(Cid, bytes)` tuples.
+#[pyclass(module = "dasl")]
+pub struct CarReader(car::CarReader<Box<dyn Read + Send + Sync>>);
+
+#[pymethods]
+impl CarReader {
+    /// Reads a CAR file from memory.
+    #[new]
+    fn new(data: Vec<u8>) -> PyResult<Self> {
+        Self::from_reader(Box::new(Cursor::new(data)))
+    }
+
+    /// Opens a CAR file, blocks are read from disk while iterating.
+    #[staticmethod]
+    fn open(path: std::path::PathBuf) -> PyResult<Self> {
+        let file = File::open(path).map_err(error)?;
+        Self::from_reader(Box::new(BufReader::new(file)))
+    }
+
+    /// The root CIDs of the archive.
+    #[getter]
+    fn roots(&self) -> Vec<Cid> {
+        self.0.roots().iter().copied().map(Cid).collect()
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__<'py>(&mut self, py: Python<'py>) -> PyResult<Option<(Cid, Bound<'py, PyBytes>)>> {
+        let block = self.0.next_block().map_err(error)?;
+        Ok(block.map(|(cid, data)| (Cid(cid), PyBytes::new(py, &data))))
+    }
+}
+
+impl CarReader {
+    fn from_reader(reader: Box<dyn Read + Send + Sync>) -> PyResult<Self> {
+        Ok(Self(car::CarReader::new(reader).map_err(error)?))
+    }
+}
+
+/// The `dasl` Python module.
+#[pymodule]
+pub fn dasl(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(dumps, m)?)?;
+    m.add_function(wrap_pyfunction!(loads, m)?)?;
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    m.add_function(wrap_pyfunction!(cid_digest, m)?)?;
+    m.add_class::<Cid>()?;
+    m.add_class::<CarReader>()?;
+    m.add("DaslError", m.py().get_type::<DaslError>())?;
+    Ok(())
+}
+
+fn parse_codec(codec: &str) -> PyResult<Codec> {
+    match codec {
+        "raw" => Ok(Codec::Raw),
+        "drisl" => Ok(Codec::Drisl),
+        codec => Err(error(format!("Unknown codec: {codec}"))),
+    }
+}
+
+fn parse_multihash(hash: &str) -> PyResult<Multihash> {
+    match hash {
+        "sha2-256" => Ok(Multihash::Sha2256),
+        "blake3" => Ok(Multihash::Blake3),
+        hash => Err(error(format!("Unknown hash: {hash}"))),
+    }
+}
+
+/// Converts a DRISL value into a Python object.
+fn to_py<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        Value::Integer(int) => int.into_pyobject(py)?.into_any(),
+        Value::Bytes(bytes) => PyBytes::new(py, bytes).into_any(),
+        Value::Float(float) => PyFloat::new(py, *float).into_any(),
+        Value::Text(text) => PyString::new(py, text).into_any(),
+        Value::Bool(bool) => PyBool::new(py, *bool).to_owned().into_any(),
+        Value::Null => py.None().into_bound(py),
+        Value::Cid(cid) => Bound::new(py, Cid(*cid))?.into_any(),
+        Value::Array(array) => PyList::new(
+            py,
+            array
+                .iter()
+                .map(|item| to_py(py, item))
+                .collect::<PyResult<Vec<_>>>()?,
+        )?
+        .into_any(),
+        Value::Map(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, to_py(py, value)?)?;
+            }
+            dict.into_any()
+        }
+    })
+}
+
+/// Converts a Python object into a DRISL value.
+fn from_py(obj: &Bound<'_, PyAny>, depth: usize) -> PyResult<Value> {
+    if obj.is_none() {
+        return Ok(Value::Null);
+    }
+    // `bool` is a subclass of `int`, so it has to be checked first.
+    if let Ok(bool) = obj.cast::<PyBool>() {
+        return Ok(Value::Bool(bool.is_true()));
+    }
+    if obj.is_instance_of::<PyInt>() {
+        let int = obj
+            .extract()
+            .map_err(|_| error("Integer is out of range"))?;
+        return Ok(Value::Integer(int));
+    }
+    if let Ok(float) = obj.cast::<PyFloat>() {
+        return Ok(Value::Float(float.value()));
+    }
+    if let Ok(text) = obj.cast::<PyString>() {
+        return Ok(Value::Text(text.to_str()?.to_string()));
+    }
+    if let Ok(bytes) = obj.cast::<PyBytes>() {
+        return Ok(Value::Bytes(bytes.as_bytes().to_vec()));
+    }
+    if let Ok(bytes) = obj.cast::<PyByteArray>() {
+        return Ok(Value::Bytes(bytes.to_vec()));
+    }
+    if let Ok(cid) = obj.cast::<Cid>() {
+        return Ok(Value::Cid(cid.get().0));
+    }
+    if depth >= MAX_DEPTH {
+        return Err(error(format!("Nested deeper than {MAX_DEPTH} levels")));
+    }
+    if let Ok(list) = obj.cast::<PyList>() {
+        return list
+            .iter()
+            .map(|item| from_py(&item, depth + 1))
+            .collect::<PyResult<_>>()
+            .map(Value::Array);
+    }
+    if let Ok(tuple) = obj.cast::<PyTuple>() {
+        return tuple
+            .iter()
+            .map(|item| from_py(&item, depth + 1))
+            .collect::<PyResult<_>>()
+            .map(Value::Array);
+    }
+    if let Ok(dict) = obj.cast::<PyDict>() {
+        return dict
+            .iter()
+            .map(|(key, value)| {
+                let key = key
+                    .cast::<PyString>()
+                    .map_err(|_| PyTypeError::new_err("Map key isn't a str"))?
+                    .to_str()?
+                    .to_string();
+                Ok((key, from_py(&value, depth + 1)?))
+            })
+            .collect::<PyResult<_>>()
+            .map(Value::Map);
+    }
+    Err(PyTypeError::new_err(format!(
+        "Unsupported type: {}",
+        obj.get_type().name()?
+    )))
+}
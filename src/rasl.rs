@@ -0,0 +1,125 @@
+//! RASL (Retrieval of Arbitrary Structures & Links) over HTTP.
+//!
+//! Content is retrieved by CID from `{base_url}/.well-known/rasl/{cid}`. As the CID commits to
+//! the content, any server can be used, the retrieved bytes are always verified against the CID
+//! before they are handed out.
+//!
+//! [Spec](https://dasl.ing/rasl.html)
+
+use std::convert::Infallible;
+
+use thiserror::Error;
+
+use crate::{
+    cid::{Cid, Codec},
+    drisl::{DecodeError, Value},
+};
+
+#[cfg(feature = "rasl-blocking")]
+pub mod blocking;
+#[cfg(feature = "rasl")]
+mod client;
+#[cfg(feature = "rasl-server")]
+pub mod server;
+
+#[cfg(feature = "rasl")]
+pub use self::client::Client;
+
+/// The path prefix under which content is served.
+pub const WELL_KNOWN_PATH: &str = "/.well-known/rasl/";
+
+/// The default maximum size of retrieved content.
+pub const DEFAULT_MAX_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RaslError {
+    #[error("HTTP error: {_0}")]
+    Http(Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("IO error: {_0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unexpected HTTP status: {_0}")]
+    Status(u16),
+    #[error("Content exceeds the maximum size of {_0} bytes")]
+    TooLarge(usize),
+    #[error("Content doesn't match CID: expected {expected}, got {actual}")]
+    HashMismatch { expected: Cid, actual: Cid },
+    #[error("Failed to decode content: {_0}")]
+    Decode(#[from] DecodeError<Infallible>),
+}
+
+/// Verified content retrieved by CID.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Content {
+    /// Raw bytes, for CIDs with the raw codec.
+    Raw(Vec<u8>),
+    /// A decoded value, for CIDs with the DRISL codec.
+    Drisl(Value),
+}
+
+impl Content {
+    /// Decodes bytes that were verified against a CID, according to the codec of the CID.
+    pub fn decode(codec: Codec, bytes: Vec<u8>) -> Result<Self, RaslError> {
+        match codec {
+            Codec::Raw => Ok(Self::Raw(bytes)),
+            Codec::Drisl => Ok(Self::Drisl(crate::drisl::de::from_slice(&bytes)?)),
+        }
+    }
+}
+
+/// Returns the URL under which the content with the given CID is served.
+///
+/// # Examples
+///
+/// ```
+/// # use dasl::{cid, rasl};
+/// let cid = cid!("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy");
+/// assert_eq!(
+///     rasl::url("https://example.com/", &cid),
+///     "https://example.com/.well-known/rasl/bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy",
+/// );
+/// ```
+pub fn url(base_url: &str, cid: &Cid) -> String {
+    format!("{}{WELL_KNOWN_PATH}{cid}", base_url.trim_end_matches('/'))
+}
+
+/// Buffers content while hashing it, enforcing a maximum size.
+#[cfg(any(feature = "rasl", feature = "rasl-blocking"))]
+struct Verifier {
+    expected: Cid,
+    hasher: crate::cid::Hasher,
+    buffer: Vec<u8>,
+    max_size: usize,
+}
+
+#[cfg(any(feature = "rasl", feature = "rasl-blocking"))]
+impl Verifier {
+    fn new(expected: Cid, max_size: usize) -> Self {
+        Self {
+            expected,
+            hasher: crate::cid::Hasher::new(expected.multihash_type()),
+            buffer: Vec::new(),
+            max_size,
+        }
+    }
+
+    fn update(&mut self, chunk: &[u8]) -> Result<(), RaslError> {
+        if self.buffer.len() + chunk.len() > self.max_size {
+            return Err(RaslError::TooLarge(self.max_size));
+        }
+        self.hasher.update(chunk);
+        self.buffer.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Vec<u8>, RaslError> {
+        let actual = self.hasher.finalize(self.expected.codec());
+        if actual != self.expected {
+            return Err(RaslError::HashMismatch {
+                expected: self.expected,
+                actual,
+            });
+        }
+        Ok(self.buffer)
+    }
+}
@@ -0,0 +1,82 @@
+//! Blocking RASL client.
+
+use std::io::Read;
+
+use super::{Content, DEFAULT_MAX_SIZE, RaslError, Verifier};
+use crate::cid::Cid;
+
+/// A blocking client for retrieving content via RASL.
+///
+/// # Examples
+///
+/// ```no_run
+/// use dasl::{cid, rasl::blocking::Client};
+///
+/// let cid = cid!("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy");
+/// let content = Client::new().fetch("https://example.com", &cid).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Client {
+    agent: ureq::Agent,
+    max_size: usize,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    /// Creates a new client with default settings.
+    pub fn new() -> Self {
+        Self::with_agent(ureq::Agent::new_with_defaults())
+    }
+
+    /// Creates a new client using the given agent.
+    pub fn with_agent(agent: ureq::Agent) -> Self {
+        Self {
+            agent,
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+
+    /// Sets the maximum size of content that is retrieved.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Fetches the content with the given CID and verifies it.
+    ///
+    /// Content with the DRISL codec is decoded into a [`Value`](crate::drisl::Value).
+    pub fn fetch(&self, base_url: &str, cid: &Cid) -> Result<Content, RaslError> {
+        Content::decode(cid.codec(), self.fetch_bytes(base_url, cid)?)
+    }
+
+    /// Fetches the bytes of the content with the given CID and verifies them.
+    pub fn fetch_bytes(&self, base_url: &str, cid: &Cid) -> Result<Vec<u8>, RaslError> {
+        let response = match self.agent.get(super::url(base_url, cid)).call() {
+            Ok(response) => response,
+            Err(ureq::Error::StatusCode(status)) => return Err(RaslError::Status(status)),
+            Err(err) => return Err(RaslError::Http(err.into())),
+        };
+        let status = response.status();
+        if !status.is_success() {
+            return Err(RaslError::Status(status.as_u16()));
+        }
+
+        let mut verifier = Verifier::new(*cid, self.max_size);
+        let mut reader = response.into_body().into_reader();
+        let mut buf = [0u8; 16 * 1024];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => verifier.update(&buf[..n])?,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        verifier.finish()
+    }
+}
@@ -0,0 +1,81 @@
+//! Async RASL client.
+
+use super::{Content, DEFAULT_MAX_SIZE, RaslError, Verifier};
+use crate::cid::Cid;
+
+/// An async client for retrieving content via RASL.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn run() -> Result<(), dasl::rasl::RaslError> {
+/// use dasl::{cid, rasl::Client};
+///
+/// let cid = cid!("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy");
+/// let content = Client::new().fetch("https://example.com", &cid).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    max_size: usize,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    /// Creates a new client with default settings.
+    pub fn new() -> Self {
+        Self::with_http_client(reqwest::Client::new())
+    }
+
+    /// Creates a new client using the given HTTP client.
+    pub fn with_http_client(http: reqwest::Client) -> Self {
+        Self {
+            http,
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+
+    /// Sets the maximum size of content that is retrieved.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Fetches the content with the given CID and verifies it.
+    ///
+    /// Content with the DRISL codec is decoded into a [`Value`](crate::drisl::Value).
+    pub async fn fetch(&self, base_url: &str, cid: &Cid) -> Result<Content, RaslError> {
+        Content::decode(cid.codec(), self.fetch_bytes(base_url, cid).await?)
+    }
+
+    /// Fetches the bytes of the content with the given CID and verifies them.
+    pub async fn fetch_bytes(&self, base_url: &str, cid: &Cid) -> Result<Vec<u8>, RaslError> {
+        let mut response = self
+            .http
+            .get(super::url(base_url, cid))
+            .send()
+            .await
+            .map_err(|err| RaslError::Http(err.into()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(RaslError::Status(status.as_u16()));
+        }
+
+        let mut verifier = Verifier::new(*cid, self.max_size);
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| RaslError::Http(err.into()))?
+        {
+            verifier.update(&chunk)?;
+        }
+        verifier.finish()
+    }
+}
@@ -0,0 +1,185 @@
+//! Serving content via RASL with [`axum`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn run() {
+//! use std::sync::Arc;
+//!
+//! use dasl::{blockstore::MemoryBlockStore, rasl::server};
+//!
+//! let store = Arc::new(MemoryBlockStore::new());
+//! let app = axum::Router::new().merge(server::router(store));
+//! # }
+//! ```
+
+use std::{ops::Range, sync::Arc};
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+
+use crate::{
+    blockstore::BlockStore,
+    cid::{Cid, Codec},
+};
+
+/// Content type of blocks with the raw codec.
+pub const CONTENT_TYPE_RAW: &str = "application/octet-stream";
+
+/// Content type of blocks with the DRISL codec.
+pub const CONTENT_TYPE_DRISL: &str = "application/vnd.ipld.dag-cbor";
+
+/// Content never changes for a given CID, so it can be cached forever.
+const CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Returns a router that serves the blocks of `store` under `/.well-known/rasl/{cid}`.
+///
+/// `HEAD` requests are supported for all blocks, single range requests only for raw blocks.
+/// Requests for CIDs that can't be verified, as they don't carry a digest, are rejected.
+pub fn router<S>(store: Arc<S>) -> Router
+where
+    S: BlockStore + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/.well-known/rasl/{cid}", get(handler::<S>))
+        .with_state(store)
+}
+
+/// Handles a `GET` (or `HEAD`) request for a single block.
+pub async fn handler<S>(
+    State(store): State<Arc<S>>,
+    Path(cid): Path<String>,
+    headers: HeaderMap,
+) -> Response
+where
+    S: BlockStore + Send + Sync + 'static,
+{
+    let Ok(cid) = cid.parse::<Cid>() else {
+        return (StatusCode::BAD_REQUEST, "invalid CID").into_response();
+    };
+    if cid.hash().is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            "CID without digest can't be verified",
+        )
+            .into_response();
+    }
+
+    let data = match store.get(&cid) {
+        Ok(Some(data)) => data,
+        Ok(None) => return StatusCode::NOT_FOUND.into_response(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    if cid.verify(&data).is_err() {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "stored block is corrupted",
+        )
+            .into_response();
+    }
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(CACHE_CONTROL),
+    );
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{cid}\"")) {
+        response_headers.insert(header::ETAG, etag);
+    }
+
+    match cid.codec() {
+        Codec::Drisl => {
+            response_headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(CONTENT_TYPE_DRISL),
+            );
+            (response_headers, data).into_response()
+        }
+        Codec::Raw => {
+            response_headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(CONTENT_TYPE_RAW),
+            );
+            response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+
+            let Some(range) = headers.get(header::RANGE) else {
+                return (response_headers, data).into_response();
+            };
+            // Malformed ranges are ignored, as in RFC 9110, section 14.2.
+            let Some(range) = range.to_str().ok().and_then(|r| parse_range(r, data.len())) else {
+                return (response_headers, data).into_response();
+            };
+            if range.is_empty() {
+                if let Ok(content_range) = HeaderValue::from_str(&format!("bytes */{}", data.len()))
+                {
+                    response_headers.insert(header::CONTENT_RANGE, content_range);
+                }
+                return (StatusCode::RANGE_NOT_SATISFIABLE, response_headers).into_response();
+            }
+            let content_range = format!("bytes {}-{}/{}", range.start, range.end - 1, data.len());
+            if let Ok(content_range) = HeaderValue::from_str(&content_range) {
+                response_headers.insert(header::CONTENT_RANGE, content_range);
+            }
+            (
+                StatusCode::PARTIAL_CONTENT,
+                response_headers,
+                data[range].to_vec(),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Parses a single `bytes` range, returns `None` if it is malformed or has several ranges, as those
+/// are ignored. The range is empty if it can't be satisfied.
+fn parse_range(value: &str, len: usize) -> Option<Range<usize>> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    let (start, end) = spec.split_once('-')?;
+    match (start.trim(), end.trim()) {
+        // Suffix range, the last `n` bytes.
+        ("", suffix) => {
+            let suffix: usize = suffix.parse().ok()?;
+            Some(len.saturating_sub(suffix)..len)
+        }
+        (start, "") => {
+            let start: usize = start.parse().ok()?;
+            Some(start.min(len)..len)
+        }
+        (start, end) => {
+            let start: usize = start.parse().ok()?;
+            let end: usize = end.parse().ok()?;
+            if end < start {
+                return None;
+            }
+            Some(start.min(len)..end.saturating_add(1).min(len))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_range;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("bytes=0-3", 10), Some(0..4));
+        assert_eq!(parse_range("bytes=5-", 10), Some(5..10));
+        assert_eq!(parse_range("bytes=-3", 10), Some(7..10));
+        assert_eq!(parse_range("bytes=8-100", 10), Some(8..10));
+        // Valid, but not satisfiable.
+        assert_eq!(parse_range("bytes=10-", 10), Some(10..10));
+        assert_eq!(parse_range("bytes=12-15", 10), Some(10..10));
+        assert_eq!(parse_range("bytes=-0", 10), Some(10..10));
+        assert_eq!(parse_range("bytes=-3", 0), Some(0..0));
+        // Malformed or several ranges.
+        assert_eq!(parse_range("bytes=4-2", 10), None);
+        assert_eq!(parse_range("bytes=0-1,4-5", 10), None);
+        assert_eq!(parse_range("bytes=a-", 10), None);
+        assert_eq!(parse_range("items=0-1", 10), None);
+    }
+}
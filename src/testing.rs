@@ -0,0 +1,247 @@
+//! Deterministic generation of [`Value`]s for tests and benchmarks.
+//!
+//! A [`Generator`] produces the same values for the same seed and shape on every platform, so
+//! tests and benchmarks can share realistic data without checking it in.
+//!
+//! ```
+//! use dasl::{drisl, testing::Generator};
+//!
+//! let mut generator = Generator::new(42).depth(3).width(4).link_density(0.2);
+//! let (value, bytes) = generator.encoded();
+//! assert_eq!(drisl::from_slice::<drisl::Value>(&bytes).unwrap(), value);
+//!
+//! // The same seed and shape generate the same values.
+//! let again = Generator::new(42).depth(3).width(4).link_density(0.2).value();
+//! assert_eq!(again, value);
+//!
+//! // A generator is an endless iterator of values.
+//! let values: Vec<_> = generator.take(10).collect();
+//! assert_eq!(values.len(), 10);
+//! ```
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{
+    cid::{Cid, Codec},
+    drisl::{self, Value},
+};
+
+/// Relative weights of the kinds of scalars a [`Generator`] produces.
+///
+/// A kind with weight 0 is never generated. If all weights are 0, scalars are [`Value::Null`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScalarMix {
+    /// Weight of [`Value::Null`].
+    pub null: u32,
+    /// Weight of [`Value::Bool`].
+    pub bool: u32,
+    /// Weight of [`Value::Integer`], mostly small ones but also some up to the 64-bit limits.
+    pub integer: u32,
+    /// Weight of [`Value::Float`], always finite.
+    pub float: u32,
+    /// Weight of [`Value::Text`], ASCII words of up to 32 characters.
+    pub text: u32,
+    /// Weight of [`Value::Bytes`], up to 64 random bytes.
+    pub bytes: u32,
+}
+
+impl Default for ScalarMix {
+    /// Mostly integers and strings, like typical records.
+    fn default() -> Self {
+        Self {
+            null: 1,
+            bool: 1,
+            integer: 4,
+            float: 1,
+            text: 4,
+            bytes: 1,
+        }
+    }
+}
+
+/// Generates [`Value`] trees of a configurable shape from a seed.
+///
+/// The root value is a map, unless the depth is 0. Below it, each value above the maximum depth
+/// is a map, an array or a scalar with equal chance, and values at the maximum depth are scalars.
+/// Map keys are short lowercase words.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    rng: SplitMix64,
+    depth: usize,
+    width: usize,
+    scalars: ScalarMix,
+    link_density: f64,
+}
+
+impl Generator {
+    /// Creates a generator with the default shape: a depth of 4, a width of 8, the default
+    /// [`ScalarMix`] and no links.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SplitMix64(seed),
+            depth: 4,
+            width: 8,
+            scalars: ScalarMix::default(),
+            link_density: 0.0,
+        }
+    }
+
+    /// Sets how many levels of arrays and maps are nested at most, 0 generates only scalars.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Sets the maximum number of items in an array or entries in a map.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Sets the mix of scalars.
+    pub fn scalars(mut self, scalars: ScalarMix) -> Self {
+        self.scalars = scalars;
+        self
+    }
+
+    /// Sets the fraction of scalars that are replaced by [`Value::Cid`]s, from 0.0 to 1.0.
+    pub fn link_density(mut self, density: f64) -> Self {
+        self.link_density = density;
+        self
+    }
+
+    /// Generates the next value.
+    pub fn value(&mut self) -> Value {
+        self.generate(0)
+    }
+
+    /// Generates the next value together with its canonical encoding.
+    pub fn encoded(&mut self) -> (Value, Vec<u8>) {
+        let value = self.value();
+        let bytes = drisl::to_vec(&value).expect("generated values can be encoded");
+        (value, bytes)
+    }
+
+    fn generate(&mut self, depth: usize) -> Value {
+        if depth >= self.depth {
+            return self.scalar();
+        }
+        let len = self.rng.below(self.width as u64 + 1) as usize;
+        if depth == 0 {
+            return self.map(depth, len);
+        }
+        match self.rng.below(3) {
+            0 => self.map(depth, len),
+            1 => Value::Array((0..len).map(|_| self.generate(depth + 1)).collect()),
+            _ => self.scalar(),
+        }
+    }
+
+    fn map(&mut self, depth: usize, len: usize) -> Value {
+        let mut map = BTreeMap::new();
+        for _ in 0..len {
+            let key_len = 1 + self.rng.below(10) as usize;
+            map.insert(self.word(key_len), self.generate(depth + 1));
+        }
+        Value::Map(map)
+    }
+
+    fn scalar(&mut self) -> Value {
+        if self.rng.chance(self.link_density) {
+            let mut digest = [0; 32];
+            self.rng.fill(&mut digest);
+            return Value::Cid(Cid::digest_sha2(Codec::Drisl, digest));
+        }
+
+        let ScalarMix {
+            null,
+            bool,
+            integer,
+            float,
+            text,
+            bytes,
+        } = self.scalars;
+        let weights = [null, bool, integer, float, text, bytes].map(u64::from);
+        let mut pick = self.rng.below(weights.iter().sum());
+        let kind = weights
+            .iter()
+            .position(|&weight| {
+                let found = pick < weight;
+                pick = pick.saturating_sub(weight);
+                found
+            })
+            .unwrap_or(0);
+        match kind {
+            0 => Value::Null,
+            1 => Value::Bool(self.rng.below(2) == 1),
+            2 => Value::Integer(self.integer()),
+            3 => {
+                // Uniform in [-1000, 1000), with the 53 bits of precision of a float.
+                let unit = (self.rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+                Value::Float(unit * 2000.0 - 1000.0)
+            }
+            4 => {
+                let len = self.rng.below(33) as usize;
+                Value::Text(self.word(len))
+            }
+            _ => {
+                let mut bytes = alloc::vec![0; self.rng.below(65) as usize];
+                self.rng.fill(&mut bytes);
+                Value::Bytes(bytes)
+            }
+        }
+    }
+
+    fn integer(&mut self) -> i128 {
+        match self.rng.below(4) {
+            0 => i128::from(self.rng.next_u64()),
+            1 => -1 - i128::from(self.rng.next_u64()),
+            _ => self.rng.below(2000) as i128 - 1000,
+        }
+    }
+
+    fn word(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| char::from(b'a' + self.rng.below(26) as u8))
+            .collect()
+    }
+}
+
+/// Generates values endlessly, see [`Generator::value`].
+impl Iterator for Generator {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        Some(self.value())
+    }
+}
+
+/// The SplitMix64 random number generator, which is small and has a fixed output for a seed.
+#[derive(Debug, Clone)]
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number below `n`, or 0 if `n` is 0.
+    fn below(&mut self, n: u64) -> u64 {
+        if n == 0 { 0 } else { self.next_u64() % n }
+    }
+
+    /// Returns `true` with the given probability.
+    fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    fn fill(&mut self, bytes: &mut [u8]) {
+        for chunk in bytes.chunks_mut(8) {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes()[..chunk.len()]);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Unsigned LEB128 varints, as used by multiformats and CAR framing.
+
+/// Maximum number of bytes a `u64` varint can occupy.
+pub(crate) const MAX_LEN_U64: usize = 10;
+
+/// Decodes a varint from the front of `buf`.
+///
+/// Returns the value and the number of bytes consumed, or `None` if the buffer ends before the
+/// varint does or the value doesn't fit into a `u64`.
+pub(crate) const fn decode_u64(buf: &[u8]) -> Option<(u64, usize)> {
+    let mut value: u64 = 0;
+    // Iterators can't be used in const contexts.
+    let mut i = 0;
+    while i < buf.len() && i < MAX_LEN_U64 {
+        let byte = buf[i];
+        let low = (byte & 0x7f) as u64;
+        if i == MAX_LEN_U64 - 1 && low > 1 {
+            return None;
+        }
+        value |= low << (i * 7);
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Encodes `value` as a varint into `buf`, returning the encoded slice.
+#[cfg(feature = "std")]
+pub(crate) fn encode_u64(mut value: u64, buf: &mut [u8; MAX_LEN_U64]) -> &[u8] {
+    let mut i = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[i] = byte;
+            return &buf[..=i];
+        }
+        buf[i] = byte | 0x80;
+        i += 1;
+    }
+}
+
+/// Reads a varint from `reader`, one byte at a time.
+///
+/// Returns `Ok(None)` if the reader is at EOF before the first byte.
+#[cfg(feature = "std")]
+pub(crate) fn read_u64<R: std::io::Read>(mut reader: R) -> std::io::Result<Option<u64>> {
+    let mut buf = [0u8; MAX_LEN_U64];
+    for i in 0..MAX_LEN_U64 {
+        let mut byte = [0u8; 1];
+        if reader.read(&mut byte)? == 0 {
+            if i == 0 {
+                return Ok(None);
+            }
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        buf[i] = byte[0];
+        if byte[0] & 0x80 == 0 {
+            return decode_u64(&buf[..=i])
+                .map(|(value, _)| Some(value))
+                .ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid varint")
+                });
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "varint too long",
+    ))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        for value in [0, 1, 0x7f, 0x80, 0x3fff, 0x4000, u32::MAX as u64, u64::MAX] {
+            let mut buf = [0u8; MAX_LEN_U64];
+            let encoded = encode_u64(value, &mut buf).to_vec();
+            assert_eq!(decode_u64(&encoded), Some((value, encoded.len())));
+            assert_eq!(read_u64(&encoded[..]).unwrap(), Some(value));
+        }
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(decode_u64(&[0x80]), None);
+        assert_eq!(decode_u64(&[]), None);
+        assert!(read_u64(&[0x80][..]).is_err());
+        assert_eq!(read_u64(&[][..]).unwrap(), None);
+    }
+}
@@ -0,0 +1,293 @@
+//! JavaScript bindings for use in browsers and Node.js, built with `wasm-bindgen`.
+//!
+//! DRISL values are converted to and from plain JavaScript values:
+//!
+//! | DRISL       | JavaScript                                                         |
+//! |-------------|--------------------------------------------------------------------|
+//! | integer     | `number` if it is a safe integer, `bigint` otherwise               |
+//! | float       | `number`                                                           |
+//! | byte string | `Uint8Array`                                                       |
+//! | string      | `string`                                                           |
+//! | bool        | `boolean`                                                          |
+//! | null        | `null`, `undefined` is accepted as well                            |
+//! | CID         | `{ "/": "bafy…" }`, as in DAG-JSON                                 |
+//! | array       | `Array`                                                            |
+//! | map         | plain object                                                       |
+//!
+//! JavaScript doesn't distinguish integers from floats, so every integral `number` is encoded as
+//! an integer, e.g. `1.0` becomes `1`. Non-finite numbers can't be encoded.
+//!
+//! ```js
+//! import { encode, decode, Cid } from "dasl";
+//!
+//! const bytes = encode({ hello: "world", data: new Uint8Array([1, 2]) });
+//! const cid = Cid.digest(bytes, "drisl");
+//! console.log(cid.toString(), decode(bytes));
+//! ```
+
+use js_sys::{Array, Object, Uint8Array};
+use wasm_bindgen::{JsCast, prelude::*};
+
+use crate::{
+    cid::{self, Codec, Multibase, Multihash},
+    drisl::{self, Value},
+};
+
+/// Key of the object that represents a CID.
+const CID_KEY: &str = "/";
+
+/// The largest integer a `number` represents exactly.
+const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+/// The maximum number of nested arrays and objects [`encode`] accepts, which catches cycles before
+/// they overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+/// Encodes a JavaScript value as DRISL.
+#[wasm_bindgen]
+pub fn encode(value: JsValue) -> Result<Vec<u8>, JsError> {
+    let value = from_js(&value, 0)?;
+    Ok(drisl::to_vec(&value)?)
+}
+
+/// Decodes DRISL into a JavaScript value.
+#[wasm_bindgen]
+pub fn decode(bytes: &[u8]) -> Result<JsValue, JsError> {
+    let value: Value = drisl::from_slice(bytes)?;
+    to_js(&value)
+}
+
+/// Checks that the bytes are canonical DRISL, throws an error describing the first violation
+/// otherwise.
+#[wasm_bindgen]
+pub fn validate(bytes: &[u8]) -> Result<(), JsError> {
+    Ok(drisl::validate(bytes)?)
+}
+
+/// Computes the CID of a JavaScript value encoded as DRISL.
+#[wasm_bindgen(js_name = cidOf)]
+pub fn cid_of(value: JsValue, hash: Option<String>) -> Result<Cid, JsError> {
+    let value = from_js(&value, 0)?;
+    let multihash = parse_multihash(hash.as_deref())?;
+    Ok(Cid(drisl::cid_of(&value, multihash)?))
+}
+
+/// A content identifier.
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy)]
+pub struct Cid(cid::Cid);
+
+#[wasm_bindgen]
+impl Cid {
+    /// Parses a CID from its string form, in any supported multibase.
+    pub fn parse(cid: &str) -> Result<Cid, JsError> {
+        Ok(Self(cid.parse()?))
+    }
+
+    /// Decodes a CID from its binary form.
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Cid, JsError> {
+        Ok(Self(cid::Cid::from_bytes_raw(bytes)?))
+    }
+
+    /// Computes the CID of `data`.
+    ///
+    /// `codec` is `"raw"` (the default) or `"drisl"`, `hash` is `"sha2-256"` (the default) or
+    /// `"blake3"`.
+    pub fn digest(
+        data: &[u8],
+        codec: Option<String>,
+        hash: Option<String>,
+    ) -> Result<Cid, JsError> {
+        let codec = parse_codec(codec.as_deref())?;
+        let multihash = parse_multihash(hash.as_deref())?;
+        Ok(Self(
+            cid::CidBuilder::new()
+                .codec(codec)
+                .hash(multihash)
+                .digest(data),
+        ))
+    }
+
+    /// Returns the string form, in base32 unless another multibase prefix is given.
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string_js(&self, base: Option<String>) -> Result<String, JsError> {
+        let base = match base.as_deref() {
+            None => Multibase::default(),
+            Some(prefix) => prefix
+                .chars()
+                .next()
+                .and_then(Multibase::from_prefix)
+                .ok_or_else(|| JsError::new(&format!("Unknown multibase: {prefix}")))?,
+        };
+        Ok(self.0.to_string_with_base(base))
+    }
+
+    /// Returns the binary form.
+    #[wasm_bindgen(js_name = toBytes)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.as_bytes().to_vec()
+    }
+
+    /// The codec, `"raw"` or `"drisl"`.
+    #[wasm_bindgen(getter)]
+    pub fn codec(&self) -> String {
+        match self.0.codec() {
+            Codec::Raw => "raw",
+            Codec::Drisl => "drisl",
+        }
+        .to_string()
+    }
+
+    /// The hash function, `"sha2-256"` or `"blake3"`.
+    #[wasm_bindgen(getter, js_name = hashType)]
+    pub fn hash_type(&self) -> String {
+        match self.0.multihash_type() {
+            Multihash::Sha2256 => "sha2-256",
+            Multihash::Blake3 => "blake3",
+        }
+        .to_string()
+    }
+
+    /// The hash digest.
+    #[wasm_bindgen(getter)]
+    pub fn hash(&self) -> Vec<u8> {
+        self.0.hash().to_vec()
+    }
+
+    /// Returns whether `data` hashes to this CID.
+    pub fn verify(&self, data: &[u8]) -> bool {
+        self.0.verify(data).is_ok()
+    }
+
+    /// Returns whether both CIDs are the same.
+    pub fn equals(&self, other: &Cid) -> bool {
+        self.0 == other.0
+    }
+}
+
+fn parse_codec(codec: Option<&str>) -> Result<Codec, JsError> {
+    match codec {
+        None | Some("raw") => Ok(Codec::Raw),
+        Some("drisl") => Ok(Codec::Drisl),
+        Some(codec) => Err(JsError::new(&format!("Unknown codec: {codec}"))),
+    }
+}
+
+fn parse_multihash(hash: Option<&str>) -> Result<Multihash, JsError> {
+    match hash {
+        None | Some("sha2-256") => Ok(Multihash::Sha2256),
+        Some("blake3") => Ok(Multihash::Blake3),
+        Some(hash) => Err(JsError::new(&format!("Unknown hash: {hash}"))),
+    }
+}
+
+/// Converts a DRISL value into a JavaScript value.
+fn to_js(value: &Value) -> Result<JsValue, JsError> {
+    Ok(match value {
+        Value::Integer(int) if int.abs() <= MAX_SAFE_INTEGER => JsValue::from_f64(*int as f64),
+        Value::Integer(int) => JsValue::from(*int),
+        Value::Bytes(bytes) => Uint8Array::from(&bytes[..]).into(),
+        Value::Float(float) => JsValue::from_f64(*float),
+        Value::Text(text) => JsValue::from_str(text),
+        Value::Bool(bool) => JsValue::from_bool(*bool),
+        Value::Null => JsValue::NULL,
+        Value::Cid(cid) => object([(CID_KEY, JsValue::from_str(&cid.to_string()))])?,
+        Value::Array(array) => array
+            .iter()
+            .map(to_js)
+            .collect::<Result<Array, _>>()?
+            .into(),
+        Value::Map(map) => object(
+            map.iter()
+                .map(|(key, value)| Ok((key.as_str(), to_js(value)?)))
+                .collect::<Result<Vec<_>, JsError>>()?,
+        )?,
+    })
+}
+
+/// Creates a plain object, keys are defined as own properties, even `__proto__`.
+fn object<'a>(entries: impl IntoIterator<Item = (&'a str, JsValue)>) -> Result<JsValue, JsError> {
+    let entries: Array = entries
+        .into_iter()
+        .map(|(key, value)| Array::of2(&JsValue::from_str(key), &value))
+        .collect();
+    Ok(Object::from_entries(&entries)
+        .map_err(|_| JsError::new("Failed to create object"))?
+        .into())
+}
+
+/// Converts a JavaScript value into a DRISL value.
+fn from_js(value: &JsValue, depth: usize) -> Result<Value, JsError> {
+    if value.is_null() || value.is_undefined() {
+        return Ok(Value::Null);
+    }
+    if let Some(bool) = value.as_bool() {
+        return Ok(Value::Bool(bool));
+    }
+    if let Some(number) = value.as_f64() {
+        if !number.is_finite() {
+            return Err(JsError::new(&format!("Number {number} isn't finite")));
+        }
+        if number.fract() == 0.0 && number.abs() <= MAX_SAFE_INTEGER as f64 {
+            return Ok(Value::Integer(number as i128));
+        }
+        return Ok(Value::Float(number));
+    }
+    if value.is_bigint() {
+        let int =
+            i128::try_from(value.clone()).map_err(|_| JsError::new("BigInt is out of range"))?;
+        return Ok(Value::Integer(int));
+    }
+    if let Some(text) = value.as_string() {
+        return Ok(Value::Text(text));
+    }
+    if let Some(bytes) = value.dyn_ref::<Uint8Array>() {
+        return Ok(Value::Bytes(bytes.to_vec()));
+    }
+    if let Some(array) = value.dyn_ref::<Array>() {
+        check_depth(depth)?;
+        return Ok(Value::Array(
+            array
+                .iter()
+                .map(|item| from_js(&item, depth + 1))
+                .collect::<Result<_, _>>()?,
+        ));
+    }
+    if let Some(object) = value.dyn_ref::<Object>() {
+        let entries = Object::entries(object);
+        if entries.length() == 1 {
+            let entry = Array::from(&entries.get(0));
+            if let (Some(CID_KEY), Some(cid)) = (
+                entry.get(0).as_string().as_deref(),
+                entry.get(1).as_string(),
+            ) {
+                return Ok(Value::Cid(cid.parse()?));
+            }
+        }
+        check_depth(depth)?;
+        return entries
+            .iter()
+            .map(|entry| {
+                let entry = Array::from(&entry);
+                let key = entry
+                    .get(0)
+                    .as_string()
+                    .ok_or_else(|| JsError::new("Map key isn't a string"))?;
+                Ok((key, from_js(&entry.get(1), depth + 1)?))
+            })
+            .collect::<Result<_, JsError>>()
+            .map(Value::Map);
+    }
+    Err(JsError::new("Unsupported JavaScript value"))
+}
+
+/// Fails if an array or object at `depth` would be nested too deep, see [`MAX_DEPTH`].
+fn check_depth(depth: usize) -> Result<(), JsError> {
+    if depth >= MAX_DEPTH {
+        return Err(JsError::new(&format!(
+            "Nested deeper than {MAX_DEPTH} levels"
+        )));
+    }
+    Ok(())
+}
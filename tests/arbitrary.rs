@@ -0,0 +1,64 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use dasl::{
+    cid::Cid,
+    drisl::{self, Value},
+};
+
+/// Deterministic pseudo-random bytes to generate values from.
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_arbitrary_value_roundtrip() {
+    for seed in 1..200 {
+        let bytes = random_bytes(seed, 1024);
+        let mut u = Unstructured::new(&bytes);
+        let value = Value::arbitrary(&mut u).unwrap();
+        let encoded = drisl::to_vec(&value).unwrap();
+        assert_eq!(drisl::from_slice::<Value>(&encoded).unwrap(), value);
+    }
+}
+
+#[test]
+fn test_arbitrary_cid() {
+    for seed in 1..200 {
+        let bytes = random_bytes(seed, 64);
+        let cid = Cid::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert_eq!(Cid::from_bytes_raw(cid.as_bytes()).unwrap(), cid);
+        assert_eq!(cid.to_string().parse::<Cid>().unwrap(), cid);
+    }
+}
+
+#[cfg(feature = "proptest")]
+mod strategies {
+    use dasl::{
+        cid::Cid,
+        drisl::{self, Value},
+    };
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_value_roundtrip(value in dasl::proptest::value()) {
+            let encoded = drisl::to_vec(&value).unwrap();
+            prop_assert_eq!(drisl::from_slice::<Value>(&encoded).unwrap(), value);
+        }
+
+        #[test]
+        fn test_cid_roundtrip(cid in dasl::proptest::cid()) {
+            prop_assert_eq!(cid.to_string().parse::<Cid>().unwrap(), cid);
+        }
+    }
+}
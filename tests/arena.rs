@@ -0,0 +1,78 @@
+#![cfg(feature = "arena")]
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{
+        Value,
+        arena::{self, ArenaSeed, ArenaValue, Bump},
+        de::{DecodeOptions, Deserializer, DuplicateKeys},
+    },
+};
+use serde::de::DeserializeSeed;
+
+#[test]
+fn test_arena_value() {
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let value = drisl!({
+        "int": -18446744073709551616i128,
+        "float": 1.5,
+        "bytes": vec![1u8, 2, 3],
+        "text": "hello",
+        "bool": true,
+        "null": null,
+        "link": cid,
+        "list": [1, [cid, { "nested": "map" }]],
+    });
+    let bytes = drisl::to_vec(&value).unwrap();
+
+    let bump = Bump::new();
+    let decoded = arena::from_slice_in(&bytes, &bump).unwrap();
+    assert_eq!(decoded.to_owned(), value);
+    assert_eq!(Value::from(decoded), value);
+    assert_eq!(drisl::to_vec(&decoded).unwrap(), bytes);
+
+    // Entries are in canonical order and strings are borrowed from the input.
+    let ArenaValue::Map(entries) = decoded else {
+        panic!("expected a map");
+    };
+    let keys: Vec<_> = entries.iter().map(|(key, _)| *key).collect();
+    assert_eq!(
+        keys,
+        [
+            "int", "bool", "link", "list", "null", "text", "bytes", "float"
+        ]
+    );
+    assert_eq!(decoded.get("link"), Some(&ArenaValue::Cid(cid)));
+    assert_eq!(decoded.get("missing"), None);
+    let Some(ArenaValue::Text(text)) = decoded.get("text") else {
+        panic!("expected a string");
+    };
+    assert!(bytes.as_ptr_range().contains(&text.as_ptr()));
+
+    // From a reader everything is copied into the arena.
+    #[cfg(feature = "std")]
+    {
+        let reader_bump = Bump::new();
+        let decoded = arena::from_reader_in(&bytes[..], &reader_bump).unwrap();
+        assert_eq!(decoded.to_owned(), value);
+        assert!(reader_bump.allocated_bytes() > 0);
+    }
+
+    assert!(arena::from_slice_in(&[0x01, 0x02], &bump).is_err());
+}
+
+#[test]
+fn test_arena_duplicate_keys() {
+    // {"a": 1, "a": 2}
+    let bytes = [0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02];
+    let bump = Bump::new();
+    assert!(arena::from_slice_in(&bytes, &bump).is_err());
+
+    let options = DecodeOptions::new()
+        .lenient(true)
+        .duplicate_keys(DuplicateKeys::LastWins);
+    let mut deserializer = Deserializer::from_slice(&bytes).with_options(options);
+    let value = ArenaSeed(&bump).deserialize(&mut deserializer).unwrap();
+    assert_eq!(value, ArenaValue::Map(&[("a", ArenaValue::Integer(2))]));
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "tokio")]
+
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{DecodeError, Value, de::from_async_reader, ser::to_async_writer, to_vec},
+};
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Hands out the data one byte at a time, returning `Pending` in between.
+struct Trickle<'a> {
+    data: &'a [u8],
+    pending: bool,
+}
+
+impl AsyncRead for Trickle<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        if let Some((first, rest)) = self.data.split_first() {
+            buf.put_slice(&[*first]);
+            self.data = rest;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn trickle(data: &[u8]) -> Trickle<'_> {
+    Trickle {
+        data,
+        pending: false,
+    }
+}
+
+fn sample() -> Value {
+    drisl!({
+        "cid": Cid::digest_sha2(Codec::Raw, b"foo"),
+        "list": [1, -2, 1.5, null, true],
+        "text": "x".repeat(20_000),
+    })
+}
+
+#[tokio::test]
+async fn test_async_roundtrip() {
+    let value = sample();
+
+    let mut encoded = Vec::new();
+    to_async_writer(&mut encoded, &value).await.unwrap();
+    assert_eq!(encoded, to_vec(&value).unwrap());
+
+    let decoded: Value = from_async_reader(&encoded[..]).await.unwrap();
+    assert_eq!(decoded, value);
+
+    let decoded: Value = from_async_reader(trickle(&encoded)).await.unwrap();
+    assert_eq!(decoded, value);
+}
+
+#[tokio::test]
+async fn test_async_reader_errors() {
+    let encoded = to_vec(&sample()).unwrap();
+
+    let result = from_async_reader::<Value, _>(trickle(&encoded[..encoded.len() - 1])).await;
+    assert!(matches!(result, Err(DecodeError::Eof { .. })));
+
+    let mut trailing = encoded.clone();
+    trailing.push(0x01);
+    let result = from_async_reader::<Value, _>(&trailing[..]).await;
+    assert!(matches!(result, Err(DecodeError::TrailingData)));
+
+    let result = from_async_reader::<Value, _>(&[][..]).await;
+    assert!(matches!(result, Err(DecodeError::Eof { .. })));
+
+    let result = from_async_reader::<Value, _>(&[0x9f, 0xff][..]).await;
+    assert!(matches!(result, Err(DecodeError::IndefiniteSize)));
+
+    let result = to_async_writer(Vec::new(), &f64::NAN).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_async_stream() {
+    use dasl::drisl::de::stream_from_async_reader;
+    use futures_util::StreamExt;
+
+    let values = [sample(), drisl!(1), drisl!("two"), drisl!([])];
+    let encoded: Vec<u8> = values
+        .iter()
+        .flat_map(|value| to_vec(value).unwrap())
+        .collect();
+
+    let decoded: Vec<Value> = stream_from_async_reader(trickle(&encoded))
+        .map(Result::unwrap)
+        .collect()
+        .await;
+    assert_eq!(decoded, values);
+
+    let mut stream = stream_from_async_reader::<Value, _>(&[][..]);
+    assert!(stream.next().await.is_none());
+
+    // Values of the wrong type don't end the stream, malformed data does.
+    let mut encoded = to_vec(&(1, "one")).unwrap();
+    encoded.extend(to_vec("two").unwrap());
+    encoded.extend(to_vec(&(3, "three")).unwrap());
+    encoded.extend([0x9f, 0xff]);
+    let results: Vec<_> = stream_from_async_reader::<(i32, String), _>(&encoded[..])
+        .collect()
+        .await;
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0].as_ref().unwrap(), &(1, "one".to_string()));
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap(), &(3, "three".to_string()));
+    assert!(matches!(results[3], Err(DecodeError::IndefiniteSize)));
+}
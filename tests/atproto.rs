@@ -0,0 +1,155 @@
+#![cfg(feature = "atproto")]
+
+use dasl::{
+    atproto::{Repo, RepoError},
+    car::CarWriter,
+    cid::{Cid, Codec},
+    drisl::{Value, to_vec},
+};
+use serde::Serialize;
+use serde_bytes::ByteBuf;
+
+#[derive(Serialize)]
+struct Commit {
+    did: String,
+    version: u64,
+    data: Cid,
+    rev: String,
+    prev: Option<Cid>,
+    sig: ByteBuf,
+}
+
+#[derive(Serialize)]
+struct Node {
+    l: Option<Cid>,
+    e: Vec<TreeEntry>,
+}
+
+#[derive(Serialize)]
+struct TreeEntry {
+    p: usize,
+    k: ByteBuf,
+    v: Cid,
+    t: Option<Cid>,
+}
+
+fn entry(p: usize, k: &str, v: Cid, t: Option<Cid>) -> TreeEntry {
+    TreeEntry {
+        p,
+        k: ByteBuf::from(k.as_bytes()),
+        v,
+        t,
+    }
+}
+
+struct Blocks(Vec<(Cid, Vec<u8>)>);
+
+impl Blocks {
+    fn put<T: Serialize>(&mut self, value: &T) -> Cid {
+        let data = to_vec(value).unwrap();
+        let cid = Cid::digest_sha2(Codec::Drisl, &data);
+        self.0.push((cid, data));
+        cid
+    }
+
+    fn into_car(self, root: Cid) -> Vec<u8> {
+        let mut writer = CarWriter::new(Vec::new(), vec![root]).unwrap();
+        for (cid, data) in self.0 {
+            writer.write_block(&cid, &data).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+}
+
+fn record(text: &str) -> Value {
+    Value::Map([("text".to_string(), Value::Text(text.to_string()))].into())
+}
+
+#[test]
+fn test_repo_entries() {
+    let mut blocks = Blocks(Vec::new());
+    let profile = blocks.put(&record("profile"));
+    let post_a = blocks.put(&record("a"));
+    let post_b = blocks.put(&record("b"));
+    let post_c = blocks.put(&record("c"));
+
+    let left = blocks.put(&Node {
+        l: None,
+        e: vec![entry(0, "app.bsky.actor.profile/self", profile, None)],
+    });
+    let right = blocks.put(&Node {
+        l: None,
+        e: vec![entry(0, "app.bsky.feed.post/c", post_c, None)],
+    });
+    let root = blocks.put(&Node {
+        l: Some(left),
+        e: vec![
+            entry(0, "app.bsky.feed.post/a", post_a, None),
+            entry(19, "b", post_b, Some(right)),
+        ],
+    });
+    let commit = blocks.put(&Commit {
+        did: "did:plc:test".to_string(),
+        version: 3,
+        data: root,
+        rev: "3jzfcijpj2z2a".to_string(),
+        prev: None,
+        sig: ByteBuf::from(vec![0u8; 64]),
+    });
+
+    let repo = Repo::from_car(&blocks.into_car(commit)[..]).unwrap();
+    assert_eq!(repo.root(), &commit);
+    assert_eq!(repo.commit().did, "did:plc:test");
+    assert_eq!(repo.commit().data, root);
+
+    let entries = repo.entries().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            (
+                "app.bsky.actor.profile/self".to_string(),
+                profile,
+                record("profile")
+            ),
+            ("app.bsky.feed.post/a".to_string(), post_a, record("a")),
+            ("app.bsky.feed.post/b".to_string(), post_b, record("b")),
+            ("app.bsky.feed.post/c".to_string(), post_c, record("c")),
+        ]
+    );
+}
+
+#[test]
+fn test_repo_missing_block() {
+    let mut blocks = Blocks(Vec::new());
+    let missing = Cid::digest_sha2(Codec::Drisl, b"missing");
+    let root = blocks.put(&Node {
+        l: None,
+        e: vec![entry(0, "app.bsky.feed.post/a", missing, None)],
+    });
+    let commit = blocks.put(&Commit {
+        did: "did:plc:test".to_string(),
+        version: 3,
+        data: root,
+        rev: "3jzfcijpj2z2a".to_string(),
+        prev: None,
+        sig: ByteBuf::new(),
+    });
+
+    let repo = Repo::from_car(&blocks.into_car(commit)[..]).unwrap();
+    let mut entries = repo.entries();
+    assert!(matches!(entries.next(), Some(Err(RepoError::MissingBlock(cid))) if cid == missing));
+    assert!(entries.next().is_none());
+}
+
+#[test]
+fn test_repo_hash_mismatch() {
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let mut writer = CarWriter::new(Vec::new(), vec![cid]).unwrap();
+    writer.write_block(&cid, b"bar").unwrap();
+    let car = writer.finish().unwrap();
+
+    assert!(matches!(
+        Repo::from_car(&car[..]),
+        Err(RepoError::HashMismatch(_))
+    ));
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "std")]
+
+use dasl::{
+    blockstore::{BlockStore, DirBlockStore},
+    cid::{Cid, Codec},
+};
+
+#[test]
+fn test_dir_block_store_concurrent_put() {
+    let dir = std::env::temp_dir().join(format!("dasl-blockstore-{}", std::process::id()));
+    let store = DirBlockStore::open(&dir).unwrap();
+    let data = vec![7; 1000];
+    let cid = Cid::digest_sha2(Codec::Raw, &data);
+
+    // Writers of the same block don't share a temporary file.
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..200 {
+                    store.put(&cid, &data).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(store.get(&cid).unwrap(), Some(data));
+    assert!(store.has(&cid).unwrap());
+    // No temporary files are left behind.
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
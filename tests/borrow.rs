@@ -0,0 +1,83 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use dasl::drisl::{
+    de::{Deserializer, from_slice},
+    to_vec,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Kind<'a> {
+    Named { name: &'a str },
+    Tagged(&'a str),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Borrowed<'a> {
+    text: &'a str,
+    #[serde(with = "serde_bytes")]
+    bytes: &'a [u8],
+    #[serde(borrow)]
+    cow: Cow<'a, str>,
+    #[serde(borrow)]
+    optional: Option<&'a str>,
+    #[serde(borrow)]
+    list: Vec<&'a str>,
+    #[serde(borrow)]
+    map: BTreeMap<&'a str, &'a str>,
+    #[serde(borrow)]
+    kind: Kind<'a>,
+}
+
+/// Returns true if `part` points into `whole`.
+fn borrows_from(whole: &[u8], part: &[u8]) -> bool {
+    let range = whole.as_ptr_range();
+    range.contains(&part.as_ptr()) && part.as_ptr_range().end <= range.end
+}
+
+#[test]
+fn test_borrowed_struct() {
+    let value = Borrowed {
+        text: "text",
+        bytes: b"bytes",
+        cow: Cow::Borrowed("cow"),
+        optional: Some("optional"),
+        list: vec!["one", "two"],
+        map: [("key", "value")].into(),
+        kind: Kind::Named { name: "name" },
+    };
+    let encoded = to_vec(&value).unwrap();
+    let decoded: Borrowed = from_slice(&encoded).unwrap();
+    assert_eq!(decoded, value);
+
+    assert!(borrows_from(&encoded, decoded.text.as_bytes()));
+    assert!(borrows_from(&encoded, decoded.bytes));
+    assert!(matches!(decoded.cow, Cow::Borrowed(cow) if borrows_from(&encoded, cow.as_bytes())));
+    assert!(borrows_from(&encoded, decoded.optional.unwrap().as_bytes()));
+    for item in &decoded.list {
+        assert!(borrows_from(&encoded, item.as_bytes()));
+    }
+    for (key, value) in &decoded.map {
+        assert!(borrows_from(&encoded, key.as_bytes()));
+        assert!(borrows_from(&encoded, value.as_bytes()));
+    }
+    let Kind::Named { name } = decoded.kind else {
+        panic!("unexpected variant");
+    };
+    assert!(borrows_from(&encoded, name.as_bytes()));
+}
+
+#[test]
+fn test_borrowed_stream() {
+    let mut encoded = to_vec("first").unwrap();
+    encoded.extend(to_vec("second").unwrap());
+
+    let values = Deserializer::from_slice(&encoded)
+        .into_iter::<&str>()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(values, ["first", "second"]);
+    for value in values {
+        assert!(borrows_from(&encoded, value.as_bytes()));
+    }
+}
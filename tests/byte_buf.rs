@@ -0,0 +1,64 @@
+use dasl::drisl::{ByteBuf, Value, from_slice, from_value, to_value, to_vec};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Blob {
+    data: ByteBuf,
+    items: Vec<ByteBuf>,
+    signature: Option<ByteBuf>,
+}
+
+#[test]
+fn test_byte_buf_encodes_as_byte_string() {
+    assert_eq!(to_vec(&ByteBuf::from([1, 2, 3])).unwrap(), [0x43, 1, 2, 3]);
+    assert_eq!(to_vec(&ByteBuf::new()).unwrap(), [0x40]);
+    // Without the newtype, Serde encodes the bytes as an array of integers.
+    assert_eq!(to_vec(&vec![1u8, 2, 3]).unwrap(), [0x83, 1, 2, 3]);
+}
+
+#[test]
+fn test_byte_buf_roundtrip() {
+    let blob = Blob {
+        data: ByteBuf::from(vec![0; 300]),
+        items: vec![ByteBuf::from(&b"foo"[..]), ByteBuf::new()],
+        signature: Some(ByteBuf::from([0xff; 64])),
+    };
+    let bytes = to_vec(&blob).unwrap();
+    assert_eq!(from_slice::<Blob>(&bytes).unwrap(), blob);
+
+    let value = to_value(&blob).unwrap();
+    assert_eq!(value.get("data"), Some(&Value::Bytes(vec![0; 300])));
+    assert_eq!(from_value::<Blob>(value).unwrap(), blob);
+}
+
+#[test]
+fn test_byte_buf_rejects_arrays() {
+    // An array of integers isn't accepted in place of a byte string.
+    assert!(from_slice::<ByteBuf>(&[0x83, 1, 2, 3]).is_err());
+    assert!(from_slice::<ByteBuf>(&[0x63, b'f', b'o', b'o']).is_err());
+}
+
+#[test]
+fn test_byte_buf_json() {
+    let buf = ByteBuf::from([1, 2, 3]);
+    let json = serde_json::to_string(&buf).unwrap();
+    assert_eq!(json, "[1,2,3]");
+    assert_eq!(serde_json::from_str::<ByteBuf>(&json).unwrap(), buf);
+}
+
+#[test]
+fn test_byte_buf_conversions() {
+    let mut buf: ByteBuf = b"foo".iter().copied().collect();
+    buf.push(0);
+    assert_eq!(buf.as_slice(), b"foo\0");
+    assert_eq!(format!("{buf:?}"), r#"b"foo\x00""#);
+    assert_eq!(Value::from(buf.clone()), Value::Bytes(b"foo\0".to_vec()));
+    assert_eq!(Vec::from(buf.clone()), buf.into_vec());
+
+    let other = serde_bytes::ByteBuf::from(vec![1, 2]);
+    assert_eq!(Value::from(other.clone()), Value::Bytes(vec![1, 2]));
+    assert_eq!(
+        serde_bytes::ByteBuf::from(ByteBuf::from(other.clone())),
+        other
+    );
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "bytes")]
+
+use bytes::Bytes;
+use dasl::drisl::{self, Value, de::DecodeOptions, from_slice, serde_helpers, to_vec};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Packet {
+    id: u32,
+    #[serde(with = "serde_helpers::shared_bytes")]
+    payload: Bytes,
+    #[serde(with = "serde_helpers::option_shared_bytes", default)]
+    signature: Option<Bytes>,
+}
+
+fn packet(id: u32) -> Packet {
+    Packet {
+        id,
+        payload: Bytes::from(vec![id as u8; 100]),
+        signature: Some(Bytes::from_static(b"sig")),
+    }
+}
+
+fn shares(input: &Bytes, bytes: &Bytes) -> bool {
+    input.as_ptr_range().contains(&bytes.as_ptr())
+}
+
+#[test]
+fn test_from_bytes_shares_input() {
+    let packets = vec![packet(1), packet(2)];
+    let input = drisl::to_bytes(&packets).unwrap();
+    assert_eq!(input, to_vec(&packets).unwrap());
+
+    let decoded: Vec<Packet> = drisl::from_bytes(&input).unwrap();
+    assert_eq!(decoded, packets);
+    for packet in &decoded {
+        assert!(shares(&input, &packet.payload));
+        assert!(shares(&input, packet.signature.as_ref().unwrap()));
+    }
+
+    // The decoded bytes keep the buffer alive.
+    let payload = decoded[1].payload.clone();
+    drop((input, decoded));
+    assert_eq!(payload, [2; 100][..]);
+
+    let options = DecodeOptions::new().max_str_len(10);
+    let input = drisl::to_bytes(&packet(3)).unwrap();
+    assert!(drisl::de::from_bytes_with::<Packet>(&input, options).is_err());
+}
+
+#[test]
+fn test_bytes_copied_elsewhere() {
+    let input = drisl::to_bytes(&packet(1)).unwrap();
+
+    // Outside of `from_bytes`, the bytes are copied.
+    let decoded: Packet = from_slice(&input).unwrap();
+    assert_eq!(decoded, packet(1));
+    assert!(!shares(&input, &decoded.payload));
+    let _: Packet = drisl::from_bytes(&input).unwrap();
+    let decoded: Packet = from_slice(&input).unwrap();
+    assert!(!shares(&input, &decoded.payload));
+
+    let value: Value = from_slice(&input).unwrap();
+    assert_eq!(drisl::from_value::<Packet>(value).unwrap(), packet(1));
+    let json = serde_json::to_string(&packet(1)).unwrap();
+    assert_eq!(serde_json::from_str::<Packet>(&json).unwrap(), packet(1));
+
+    // Indefinite length byte strings aren't contiguous in the input.
+    let input = Bytes::from_static(&[
+        0xa3, 0x62, b'i', b'd', 0x01, 0x67, b'p', b'a', b'y', b'l', b'o', b'a', b'd', 0x5f, 0x41,
+        b'a', 0x41, b'b', 0xff, 0x69, b's', b'i', b'g', b'n', b'a', b't', b'u', b'r', b'e', 0xf6,
+    ]);
+    let options = DecodeOptions::new().lenient(true);
+    let decoded: Packet = drisl::de::from_bytes_with(&input, options).unwrap();
+    assert_eq!(decoded.payload, b"ab"[..]);
+    assert_eq!(decoded.signature, None);
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "std")]
+
+use dasl::{
+    car::{CarError, CarReader, CarWriter},
+    cid::{Cid, Codec},
+    drisl::{Value, to_vec},
+};
+
+#[test]
+fn test_car_roundtrip() {
+    let raw = b"raw block".to_vec();
+    let raw_cid = Cid::digest_sha2(Codec::Raw, &raw);
+    let drisl = to_vec(&Value::Text("drisl block".into())).unwrap();
+    let drisl_cid = Cid::digest_blake3(Codec::Drisl, &drisl);
+
+    let mut writer = CarWriter::new(Vec::new(), vec![drisl_cid]).unwrap();
+    writer.write_block(&drisl_cid, &drisl).unwrap();
+    writer.write_block(&raw_cid, &raw).unwrap();
+    let car = writer.finish().unwrap();
+
+    let reader = CarReader::new(&car[..]).unwrap();
+    assert_eq!(reader.header().version, 1);
+    assert_eq!(reader.roots(), &[drisl_cid]);
+    let blocks = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(blocks, vec![(drisl_cid, drisl), (raw_cid, raw)]);
+}
+
+#[test]
+fn test_car_truncated() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let mut writer = CarWriter::new(Vec::new(), vec![cid]).unwrap();
+    writer.write_block(&cid, b"foo").unwrap();
+    let car = writer.finish().unwrap();
+
+    let mut reader = CarReader::new(&car[..car.len() - 1]).unwrap();
+    assert!(matches!(reader.next_block(), Err(CarError::UnexpectedEof)));
+}
+
+#[test]
+fn test_car_unsupported_version() {
+    // {"roots": [], "version": 2}
+    let header = [
+        0xa2, 0x65, 0x72, 0x6f, 0x6f, 0x74, 0x73, 0x80, 0x67, 0x76, 0x65, 0x72, 0x73, 0x69, 0x6f,
+        0x6e, 0x02,
+    ];
+    let mut car = vec![header.len() as u8];
+    car.extend_from_slice(&header);
+    assert!(matches!(
+        CarReader::new(&car[..]),
+        Err(CarError::UnsupportedVersion(2))
+    ));
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{DateTime, TimeZone, Utc};
+use dasl::drisl::{self, Value, from_slice, serde_helpers, to_vec};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Post {
+    #[serde(with = "serde_helpers::timestamp_seconds")]
+    created: DateTime<Utc>,
+    #[serde(with = "serde_helpers::timestamp_millis")]
+    indexed: DateTime<Utc>,
+    #[serde(with = "serde_helpers::option_timestamp_seconds", default)]
+    edited: Option<DateTime<Utc>>,
+    #[serde(with = "serde_helpers::option_timestamp_millis", default)]
+    deleted: Option<DateTime<Utc>>,
+}
+
+#[test]
+fn test_timestamps() {
+    let created = Utc.with_ymd_and_hms(2024, 5, 17, 12, 30, 0).unwrap();
+    let post = Post {
+        created,
+        indexed: DateTime::from_timestamp_millis(1_715_949_000_123).unwrap(),
+        edited: Some(DateTime::from_timestamp(-86_400, 0).unwrap()),
+        deleted: None,
+    };
+    let bytes = to_vec(&post).unwrap();
+    assert_eq!(
+        from_slice::<Value>(&bytes).unwrap(),
+        dasl::drisl!({
+            "created": 1_715_949_000,
+            "indexed": 1_715_949_000_123_i64,
+            "edited": (-86_400),
+            "deleted": null,
+        })
+    );
+    assert_eq!(from_slice::<Post>(&bytes).unwrap(), post);
+
+    let json = serde_json::to_string(&post).unwrap();
+    assert_eq!(serde_json::from_str::<Post>(&json).unwrap(), post);
+
+    // Precision beyond the unit is rounded down, missing optional fields are `None`.
+    let precise = Post {
+        created: DateTime::from_timestamp(-1, 999_999_999).unwrap(),
+        indexed: DateTime::from_timestamp(0, 1_500_000).unwrap(),
+        edited: None,
+        deleted: None,
+    };
+    let value = drisl::to_value(&precise).unwrap();
+    assert_eq!(value.get("created"), Some(&Value::Integer(-1)));
+    assert_eq!(value.get("indexed"), Some(&Value::Integer(1)));
+    let decoded: Post = drisl::from_value(dasl::drisl!({ "created": (-1), "indexed": 1 })).unwrap();
+    assert_eq!(decoded.created, DateTime::from_timestamp(-1, 0).unwrap());
+    assert_eq!(decoded.indexed, DateTime::from_timestamp_millis(1).unwrap());
+    assert_eq!(decoded.edited, None);
+}
+
+#[test]
+fn test_invalid_timestamps() {
+    for value in [
+        dasl::drisl!({ "created": (i64::MAX), "indexed": 0 }),
+        dasl::drisl!({ "created": (u64::MAX), "indexed": 0 }),
+        dasl::drisl!({ "created": "2024-05-17T12:30:00Z", "indexed": 0 }),
+        dasl::drisl!({ "created": 1.5, "indexed": 0 }),
+    ] {
+        let err = drisl::from_value::<Post>(value).unwrap_err();
+        assert!(
+            err.to_string().contains("Unix timestamp in seconds"),
+            "{err}"
+        );
+    }
+}
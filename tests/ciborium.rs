@@ -0,0 +1,104 @@
+#![cfg(feature = "ciborium")]
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{IntegerRangeError, Value, ciborium::CiboriumError},
+};
+
+#[test]
+fn test_ciborium_roundtrip() {
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let value = drisl!({
+        "int": -18446744073709551616i128,
+        "max": 18446744073709551615u64,
+        "float": 1.5,
+        "bytes": vec![1u8, 2, 3],
+        "text": "hello",
+        "bool": true,
+        "null": null,
+        "cid": cid,
+        "array": [1, [2, { "nested": cid }]],
+    });
+
+    let cbor = ciborium::Value::try_from(&value).unwrap();
+    assert_eq!(Value::try_from(cbor.clone()).unwrap(), value);
+
+    // Entries are in canonical order.
+    let ciborium::Value::Map(entries) = &cbor else {
+        panic!("expected a map");
+    };
+    let keys: Vec<_> = entries
+        .iter()
+        .map(|(key, _)| key.as_text().unwrap())
+        .collect();
+    assert_eq!(
+        keys,
+        [
+            "cid", "int", "max", "bool", "null", "text", "array", "bytes", "float"
+        ]
+    );
+
+    let mut bytes = Vec::new();
+    ciborium::into_writer(&cbor, &mut bytes).unwrap();
+    let decoded: ciborium::Value = ciborium::from_reader(&bytes[..]).unwrap();
+    assert_eq!(Value::try_from(decoded).unwrap(), value);
+}
+
+#[test]
+fn test_ciborium_errors() {
+    let tagged = ciborium::Value::Tag(1, Box::new(ciborium::Value::Integer(0.into())));
+    assert!(matches!(
+        Value::try_from(tagged),
+        Err(CiboriumError::Tag(1))
+    ));
+
+    let text_cid = ciborium::Value::Tag(42, Box::new(ciborium::Value::Text("cid".into())));
+    assert!(matches!(
+        Value::try_from(text_cid),
+        Err(CiboriumError::CidNotBytes)
+    ));
+
+    let bad_cid = ciborium::Value::Tag(42, Box::new(ciborium::Value::Bytes(vec![0, 1, 2])));
+    assert!(matches!(
+        Value::try_from(bad_cid),
+        Err(CiboriumError::Cid(_))
+    ));
+
+    let int_key = ciborium::Value::Map(vec![(
+        ciborium::Value::Integer(1.into()),
+        ciborium::Value::Null,
+    )]);
+    let err = Value::try_from(int_key).unwrap_err();
+    assert!(
+        matches!(&err, CiboriumError::NonStringKey(key) if **key == ciborium::Value::Integer(1.into()))
+    );
+
+    let duplicate = ciborium::Value::Map(vec![
+        (ciborium::Value::Text("a".into()), ciborium::Value::Null),
+        (
+            ciborium::Value::Text("a".into()),
+            ciborium::Value::Bool(true),
+        ),
+    ]);
+    assert!(matches!(
+        Value::try_from(duplicate),
+        Err(CiboriumError::DuplicateKey(key)) if key == "a"
+    ));
+
+    // Errors in nested items are passed on.
+    let nested = ciborium::Value::Array(vec![ciborium::Value::Tag(
+        2,
+        Box::new(ciborium::Value::Null),
+    )]);
+    assert!(matches!(
+        Value::try_from(nested),
+        Err(CiboriumError::Tag(2))
+    ));
+
+    let too_large = Value::Array(vec![Value::Integer(Value::MAX_INTEGER + 1)]);
+    assert_eq!(
+        ciborium::Value::try_from(too_large),
+        Err(IntegerRangeError(Value::MAX_INTEGER + 1))
+    );
+}
@@ -1,10 +1,11 @@
 use std::{
     convert::{TryFrom, TryInto},
+    mem,
     str::FromStr,
 };
 
 use dasl::{
-    cid::Cid,
+    cid::{Cid, CidParseError},
     drisl::{Value, from_slice, to_vec},
 };
 use serde::{Deserialize, Serialize, de};
@@ -135,10 +136,10 @@ fn test_cid_in_kinded_enum() {
     impl TryFrom<Value> for Kinded {
         type Error = ();
 
-        fn try_from(drisl: Value) -> Result<Self, Self::Error> {
-            match drisl {
-                Value::Bytes(bytes) => Ok(Self::Bytes(ByteBuf::from(bytes))),
-                Value::Cid(cid) => Ok(Self::Link(cid)),
+        fn try_from(mut drisl: Value) -> Result<Self, Self::Error> {
+            match &mut drisl {
+                Value::Bytes(bytes) => Ok(Self::Bytes(ByteBuf::from(mem::take(bytes)))),
+                Value::Cid(cid) => Ok(Self::Link(*cid)),
                 _ => Err(()),
             }
         }
@@ -197,10 +198,10 @@ fn test_cid_in_kinded_enum_with_newtype() {
     impl TryFrom<Value> for Kinded {
         type Error = ();
 
-        fn try_from(drisl: Value) -> Result<Self, Self::Error> {
-            match drisl {
-                Value::Bytes(bytes) => Ok(Self::MyBytes(Foo(bytes))),
-                Value::Cid(cid) => Ok(Self::Link(cid)),
+        fn try_from(mut drisl: Value) -> Result<Self, Self::Error> {
+            match &mut drisl {
+                Value::Bytes(bytes) => Ok(Self::MyBytes(Foo(mem::take(bytes)))),
+                Value::Cid(cid) => Ok(Self::Link(*cid)),
                 _ => Err(()),
             }
         }
@@ -268,10 +269,9 @@ fn test_cid_in_tagged_enum() {
 
     // {"cid": cid, "type": "Cid"}
     let cbor_map2 = [
-        vec![
-            0xa2, 0x64, 0x74, 0x79, 0x70, 0x65, 0x63, 0x43, 0x69, 0x64, 0x63, 0x63, 0x69, 0x64,
-        ],
+        vec![0xa2, 0x63, 0x63, 0x69, 0x64],
         Vec::from(cbor_cid),
+        vec![0x64, 0x74, 0x79, 0x70, 0x65, 0x63, 0x43, 0x69, 0x64],
     ]
     .concat();
 
@@ -306,3 +306,477 @@ fn test_cid_decode_from_reader() {
     let cid_decoded: Cid = from_slice(&cid_encoded).unwrap();
     assert_eq!(&cid_encoded[5..], cid_decoded.as_bytes());
 }
+
+#[test]
+fn test_cid_as_collection_key() {
+    use std::collections::{BTreeMap, HashMap};
+
+    let foo = Cid::from_str("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy").unwrap();
+    let bar = Cid::from_str("bafkr4iae4c5tt4yldi76xcpvg3etxykqkvec352im5fqbutolj2xo5yc5e").unwrap();
+
+    let mut blocks = HashMap::new();
+    blocks.insert(foo, "foo");
+    blocks.insert(bar, "bar");
+    blocks.insert(foo, "foo again");
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[&foo], "foo again");
+
+    let sorted: BTreeMap<_, _> = blocks.into_iter().collect();
+    let keys: Vec<_> = sorted.keys().map(|cid| cid.as_bytes()).collect();
+    assert!(keys.is_sorted());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_cid_hashing_io() {
+    use std::io::{Read, Write};
+
+    use dasl::cid::{Codec, HashingReader, HashingWriter, Multihash};
+
+    let data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+
+    for multihash in [Multihash::Sha2256, Multihash::Blake3] {
+        let expected = match multihash {
+            Multihash::Sha2256 => Cid::digest_sha2(Codec::Drisl, &data),
+            _ => Cid::digest_blake3(Codec::Drisl, &data),
+        };
+
+        let mut reader = HashingReader::new(&data[..], multihash);
+        let mut chunk = [0u8; 1000];
+        while reader.read(&mut chunk).unwrap() > 0 {}
+        assert_eq!(reader.finalize(Codec::Drisl), expected);
+
+        let mut writer = HashingWriter::new(std::io::sink(), multihash);
+        for chunk in data.chunks(777) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(writer.finalize(Codec::Drisl), expected);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_cid_verifying_reader() {
+    use std::io::{ErrorKind, Read};
+
+    use dasl::cid::{CidVerifyError, Codec, VerifyingReader};
+
+    let data = vec![7u8; 10_000];
+    let cid = Cid::digest_blake3(Codec::Raw, &data);
+
+    let mut out = Vec::new();
+    VerifyingReader::new(&data[..], cid)
+        .read_to_end(&mut out)
+        .unwrap();
+    assert_eq!(out, data);
+
+    let err = VerifyingReader::new(&data[1..], cid)
+        .read_to_end(&mut Vec::new())
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = err
+        .into_inner()
+        .unwrap()
+        .downcast::<CidVerifyError>()
+        .unwrap();
+    assert_eq!(err.expected, cid);
+    assert_eq!(err.actual, Cid::digest_blake3(Codec::Raw, &data[1..]));
+
+    // Stopping early only fails on `finish`.
+    let mut reader = VerifyingReader::new(&data[..], cid);
+    reader.read_exact(&mut [0u8; 100]).unwrap();
+    assert!(reader.finish().is_err());
+
+    let mut reader = VerifyingReader::new(&data[..], cid);
+    std::io::copy(&mut reader, &mut std::io::sink()).unwrap();
+    reader.finish().unwrap();
+}
+
+#[test]
+fn test_cid_builder() {
+    use dasl::cid::{CidBuilder, Codec, Multihash};
+
+    let data = vec![3u8; 50_000];
+
+    let cid = CidBuilder::new().digest(&data);
+    assert_eq!(cid, Cid::digest_sha2(Codec::Raw, &data));
+    #[cfg(feature = "std")]
+    assert_eq!(
+        CidBuilder::new().digest_from_reader(&data[..]).unwrap(),
+        cid
+    );
+
+    let builder = CidBuilder::new()
+        .codec(Codec::Drisl)
+        .hash(Multihash::Blake3);
+    assert_eq!(
+        builder.digest(&data),
+        Cid::digest_blake3(Codec::Drisl, &data)
+    );
+    #[cfg(feature = "std")]
+    assert_eq!(
+        builder.digest_from_reader(&data[..]).unwrap(),
+        Cid::digest_blake3(Codec::Drisl, &data)
+    );
+
+    let empty = builder.empty();
+    assert!(empty.hash().is_empty());
+    assert_eq!(empty.codec(), Codec::Drisl);
+    assert!(matches!(empty.multihash_type(), Multihash::Blake3));
+    assert_eq!(Cid::from_bytes_raw(empty.as_bytes()).unwrap(), empty);
+}
+
+#[test]
+fn test_cid_too_short() {
+    // Version, codec and hash type, but no digest length.
+    assert!(matches!(
+        Cid::from_bytes_raw(&[0x01, 0x71, 0x12]),
+        Err(CidParseError::TooShort)
+    ));
+    assert!(Cid::from_str("bafkre").is_err());
+}
+
+#[test]
+fn test_cid_human_readable() {
+    #[derive(Debug, PartialEq, Deserialize, Serialize)]
+    struct MyStruct {
+        cid: Cid,
+    }
+
+    let cid_str = "bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy";
+    let mystruct = MyStruct {
+        cid: Cid::from_str(cid_str).unwrap(),
+    };
+
+    let json = serde_json::to_string(&mystruct).unwrap();
+    assert_eq!(json, format!(r#"{{"cid":"{cid_str}"}}"#));
+    let decoded: MyStruct = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, mystruct);
+
+    // Other multibase encodings and the raw bytes are accepted as well.
+    let base58 = r#"{"cid":"zb2rhZcdMzBSGEjx2xbFsY9pTpMgegGQLEWsNpPnUzbPwCx1T"}"#;
+    assert_eq!(serde_json::from_str::<MyStruct>(base58).unwrap(), mystruct);
+    let bytes = format!(r#"{{"cid":{:?}}}"#, mystruct.cid.as_bytes());
+    assert_eq!(serde_json::from_str::<MyStruct>(&bytes).unwrap(), mystruct);
+    assert!(serde_json::from_str::<MyStruct>(r#"{"cid":"bnotacid"}"#).is_err());
+
+    // DRISL keeps using the tagged byte form.
+    let encoded = to_vec(&mystruct).unwrap();
+    assert_eq!(from_slice::<MyStruct>(&encoded).unwrap(), mystruct);
+    assert!(encoded.windows(2).any(|window| window == [0xd8, 0x2a]));
+}
+
+#[test]
+#[cfg(feature = "cid")]
+fn test_cid_crate_conversion() {
+    use ::cid::multihash::Multihash as OtherMultihash;
+    use dasl::cid::{Codec, Multihash, UnsupportedCidError};
+
+    for cid in [
+        Cid::digest_sha2(Codec::Drisl, b"foo"),
+        Cid::digest_blake3(Codec::Raw, b"foo"),
+        Cid::empty_sha2_256(Codec::Drisl),
+    ] {
+        let other = ::cid::Cid::from(cid);
+        assert_eq!(other.to_bytes(), cid.as_bytes());
+        assert_eq!(other.to_string(), cid.to_string());
+        assert_eq!(Cid::try_from(other), Ok(cid));
+
+        let multihash = OtherMultihash::<64>::from(cid);
+        assert_eq!(multihash.code(), cid.multihash_type() as u64);
+        assert_eq!(multihash.digest(), cid.hash());
+    }
+
+    let sha256 = OtherMultihash::wrap(Multihash::Sha2256 as u64, &[0; 32]).unwrap();
+    assert_eq!(
+        Cid::try_from(::cid::Cid::new_v0(sha256).unwrap()),
+        Err(UnsupportedCidError::Version0)
+    );
+    assert_eq!(
+        Cid::try_from(::cid::Cid::new_v1(0x0129, sha256)),
+        Err(UnsupportedCidError::Codec(0x0129))
+    );
+    let sha512 = OtherMultihash::wrap(0x13, &[0; 64]).unwrap();
+    assert_eq!(
+        Cid::try_from(::cid::Cid::new_v1(0x71, sha512)),
+        Err(UnsupportedCidError::Multihash(0x13))
+    );
+    let truncated = OtherMultihash::wrap(Multihash::Sha2256 as u64, &[0; 20]).unwrap();
+    assert_eq!(
+        Cid::try_from(::cid::Cid::new_v1(0x71, truncated)),
+        Err(UnsupportedCidError::DigestLength(20))
+    );
+}
+
+#[test]
+fn test_cid_blake3_hash() {
+    use dasl::cid::{Blake3HashError, Codec, Multihash};
+
+    let hash = blake3::hash(b"foo");
+    let cid = Cid::from(hash);
+    assert_eq!(cid, Cid::digest_blake3(Codec::Raw, b"foo"));
+    assert_eq!(blake3::Hash::try_from(cid), Ok(hash));
+
+    // The codec doesn't change the hash of the bytes.
+    let drisl = Cid::digest_blake3(Codec::Drisl, b"foo");
+    assert_eq!(blake3::Hash::try_from(&drisl), Ok(hash));
+
+    assert_eq!(
+        blake3::Hash::try_from(Cid::digest_sha2(Codec::Raw, b"foo")),
+        Err(Blake3HashError::Multihash(Multihash::Sha2256))
+    );
+    assert_eq!(
+        blake3::Hash::try_from(Cid::empty_blake3(Codec::Raw)),
+        Err(Blake3HashError::Empty)
+    );
+}
+
+#[test]
+fn test_cid_parse_base32() {
+    use dasl::cid::{Codec, Multibase};
+
+    const FOO: Cid = dasl::cid!("bafkreibme22gw2h7y2h7tg2fhqotaqjucnbc24deqo72b6mkl2egezxhvy");
+    assert_eq!(FOO, Cid::digest_sha2(Codec::Raw, b"foo"));
+
+    for cid in [
+        Cid::digest_sha2(Codec::Drisl, b"bar"),
+        Cid::digest_blake3(Codec::Raw, b"bar"),
+        Cid::empty_sha2_256(Codec::Drisl),
+        Cid::empty_blake3(Codec::Raw),
+    ] {
+        assert_eq!(Cid::parse_base32(&cid.to_string()).unwrap(), cid);
+    }
+
+    // Invalid CIDs fail like with `FromStr`.
+    let valid = FOO.to_string();
+    let longer = dasl::multibase::encode(Multibase::Base32Lower, &[FOO.as_bytes(), &[0]].concat());
+    for invalid in [
+        "",
+        "b",
+        "bafkre",
+        &valid[..valid.len() - 1],
+        &valid[..valid.len() - 2],
+        &valid.to_uppercase()[1..],
+        &valid.replace('b', "z"),
+        &valid.replace('2', "1"),
+        &longer,
+        &format!("{valid}aaaaaaaa"),
+    ] {
+        assert!(Cid::parse_base32(invalid).is_err(), "{invalid}");
+        assert!(Cid::from_str(invalid).is_err(), "{invalid}");
+    }
+    assert!(matches!(
+        Cid::parse_base32(&longer),
+        Err(CidParseError::InvalidMultihash(_))
+    ));
+
+    // Unused trailing bits must be zero, otherwise "aa" and "ab" would both decode to 0x00.
+    assert!(Cid::parse_base32("bab").is_err());
+    assert!(dasl::multibase::base32::decode(b"ab").is_err());
+
+    // Other multibases are only supported by `FromStr`.
+    let base58 = FOO.to_string_with_base(Multibase::Base58Btc);
+    assert!(Cid::parse_base32(&base58).is_err());
+    assert_eq!(Cid::from_str(&base58).unwrap(), FOO);
+}
+
+#[test]
+fn test_cid_parse_malformed() {
+    use dasl::cid::Codec;
+
+    // Every truncation and every value of each prefix byte either parses into a `CID` whose
+    // accessors agree with its encoding, or fails.
+    for cid in [
+        Cid::digest_sha2(Codec::Drisl, b"foo"),
+        Cid::empty_blake3(Codec::Raw),
+    ] {
+        let bytes = cid.as_bytes();
+        let mut inputs: Vec<Vec<u8>> = (0..bytes.len()).map(|len| bytes[..len].to_vec()).collect();
+        for i in 0..4 {
+            for byte in 0..=u8::MAX {
+                let mut input = bytes.to_vec();
+                input[i] = byte;
+                inputs.push(input);
+            }
+        }
+        for input in inputs {
+            let Ok(parsed) = Cid::from_bytes_raw(&input) else {
+                continue;
+            };
+            assert_eq!(parsed.as_bytes(), input);
+            assert_eq!(parsed.codec() as u8, input[1]);
+            assert_eq!(parsed.multihash_type() as u8, input[2]);
+            assert_eq!(parsed.hash(), &input[4..]);
+        }
+    }
+    assert!(matches!(
+        Cid::from_bytes_raw(&[0x01, 0x71, 0x12, 0x20, 0x00]),
+        Err(CidParseError::InvalidMultihash(_))
+    ));
+    assert!(matches!(
+        Cid::from_bytes_raw(&[0x01, 0x71, 0x12, 0x00, 0x00]),
+        Err(CidParseError::InvalidMultihash(_))
+    ));
+}
+
+#[test]
+fn test_opaque_cid() {
+    use dasl::cid::{Codec, Multibase, OpaqueCid};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Link {
+        link: OpaqueCid,
+    }
+
+    // DAG-JSON with SHA2-512, neither is supported by `Cid`.
+    let mut bytes = vec![0x01, 0xa9, 0x02, 0x13, 0x40];
+    bytes.extend_from_slice(&[0xab; 64]);
+    let opaque = OpaqueCid::from_bytes_raw(&bytes).unwrap();
+    assert_eq!(opaque.codec(), 0x0129);
+    assert_eq!(opaque.multihash_code(), 0x13);
+    assert_eq!(opaque.hash(), &[0xab; 64]);
+    assert_eq!(opaque.as_bytes(), bytes);
+    assert!(Cid::try_from(&opaque).is_err());
+
+    // It round-trips in every form.
+    let base58 = opaque.to_string_with_base(Multibase::Base58Btc);
+    assert_eq!(base58.parse::<OpaqueCid>().unwrap(), opaque);
+    assert_eq!(opaque.to_string().parse::<OpaqueCid>().unwrap(), opaque);
+    let value = Link {
+        link: opaque.clone(),
+    };
+    let encoded = to_vec(&value).unwrap();
+    assert_eq!(from_slice::<Link>(&encoded).unwrap(), value);
+    assert_eq!(
+        OpaqueCid::from_bytes(&encoded[encoded.len() - bytes.len() - 1..]).unwrap(),
+        opaque
+    );
+    let json = serde_json::to_string(&value).unwrap();
+    assert_eq!(serde_json::from_str::<Link>(&json).unwrap(), value);
+
+    // Supported CIDs convert both ways, and are encoded the same.
+    for cid in [
+        Cid::digest_sha2(Codec::Drisl, b"foo"),
+        Cid::empty_blake3(Codec::Raw),
+    ] {
+        let opaque = OpaqueCid::from(cid);
+        assert_eq!(opaque.as_bytes(), cid.as_bytes());
+        assert_eq!(opaque.codec(), cid.codec() as u64);
+        assert_eq!(opaque.multihash_code(), cid.multihash_type() as u64);
+        assert_eq!(opaque.hash(), cid.hash());
+        assert_eq!(opaque.to_string(), cid.to_string());
+        assert_eq!(OpaqueCid::from_bytes_raw(cid.as_bytes()).unwrap(), opaque);
+        assert_eq!(Cid::try_from(opaque).unwrap(), cid);
+        assert_eq!(
+            to_vec(&OpaqueCid::from(cid)).unwrap(),
+            to_vec(&cid).unwrap()
+        );
+    }
+
+    // Malformed ones still fail.
+    for (invalid, expected) in [
+        (&[][..], "Too short"),
+        (&[0x01, 0x71, 0x12], "Too short"),
+        (&[0x01, 0x80], "Too short"),
+        (&[0x00, 0x71, 0x12, 0x00], "Invalid CID version: 0"),
+        (&[0x01, 0xf1, 0x00, 0x12, 0x00], "Invalid encoding"),
+        (
+            &[0x01, 0x71, 0x12, 0x01],
+            "Invalid multihash: Invalid length: 0",
+        ),
+        (
+            &[0x01, 0x71, 0x12, 0x00, 0x00],
+            "Invalid multihash: Invalid length: 1",
+        ),
+    ] {
+        let err = OpaqueCid::from_bytes_raw(invalid).unwrap_err();
+        assert_eq!(err.to_string(), expected, "{invalid:02x?}");
+    }
+}
+
+#[test]
+fn test_cid_varint_codes() {
+    use dasl::cid::{MultihashParseError, OpaqueCid, ParseCodecError};
+
+    // DAG-JSON (0x0129) and BLAKE2b-256 (0xb220) take two and three bytes.
+    let digest = [0xcd; 32];
+    let dag_json = [&[0x01, 0xa9, 0x02, 0x12, 0x20][..], &digest].concat();
+    let blake2b = [&[0x01, 0x71, 0xa0, 0xe4, 0x02, 0x20][..], &digest].concat();
+    assert!(matches!(
+        Cid::from_bytes_raw(&dag_json),
+        Err(CidParseError::InvalidCodec(ParseCodecError::UnknownCodec(
+            0x0129
+        )))
+    ));
+    assert!(matches!(
+        Cid::from_bytes_raw(&blake2b),
+        Err(CidParseError::InvalidMultihash(
+            MultihashParseError::UnknownHash(0xb220)
+        ))
+    ));
+    for (bytes, codec, multihash) in [(&dag_json, 0x0129, 0x12), (&blake2b, 0x71, 0xb220)] {
+        let opaque = OpaqueCid::from_bytes_raw(bytes).unwrap();
+        assert_eq!(opaque.codec(), codec);
+        assert_eq!(opaque.multihash_code(), multihash);
+        assert_eq!(opaque.hash(), digest);
+        assert_eq!(opaque.as_bytes(), &bytes[..]);
+    }
+
+    // Codes must be in their shortest form, otherwise a CID would have several encodings.
+    for invalid in [
+        &[0x01, 0xd5, 0x00, 0x12, 0x00][..],
+        &[0x01, 0x55, 0x92, 0x00, 0x00],
+    ] {
+        assert!(matches!(
+            Cid::from_bytes_raw(invalid),
+            Err(CidParseError::InvalidEncoding)
+        ));
+        assert!(matches!(
+            OpaqueCid::from_bytes_raw(invalid),
+            Err(CidParseError::InvalidEncoding)
+        ));
+    }
+    assert!(matches!(
+        Cid::from_bytes_raw(&[0x01, 0xa9]),
+        Err(CidParseError::TooShort)
+    ));
+}
+
+#[test]
+fn test_cid_std_conversions() {
+    use std::collections::{BTreeMap, HashMap};
+
+    use dasl::cid::Codec;
+
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let empty = Cid::empty_blake3(Codec::Raw);
+    for cid in [cid, empty] {
+        let bytes = cid.as_bytes();
+        assert_eq!(Cid::try_from(bytes).unwrap(), cid);
+        assert_eq!(Cid::try_from(bytes.to_vec()).unwrap(), cid);
+        assert_eq!(AsRef::<[u8]>::as_ref(&cid), bytes);
+    }
+    assert!(Cid::try_from(&[0x01, 0x71][..]).is_err());
+    assert!(Cid::try_from(vec![0; 36]).is_err());
+
+    assert_eq!(<[u8; 36]>::from(cid), cid.as_bytes());
+    let array = <[u8; 36]>::from(empty);
+    assert_eq!(&array[..4], empty.as_bytes());
+    assert_eq!(array[4..], [0; 32]);
+
+    // Keys can be looked up by their bytes.
+    let hash_map = HashMap::from([(cid, 1), (empty, 2)]);
+    let btree_map = BTreeMap::from([(cid, 1), (empty, 2)]);
+    assert_eq!(hash_map.get(cid.as_bytes()), Some(&1));
+    assert_eq!(hash_map.get(empty.as_bytes()), Some(&2));
+    assert_eq!(btree_map.get(cid.as_bytes()), Some(&1));
+    assert_eq!(btree_map.get(empty.as_bytes()), Some(&2));
+    assert_eq!(hash_map.get(&b"foo"[..]), None);
+}
+
+#[test]
+fn test_cid_size() {
+    // The encoding plus the codec and hash type, which are kept for the accessors.
+    assert_eq!(std::mem::size_of::<Cid>(), 38);
+    assert_eq!(std::mem::align_of::<Cid>(), 1);
+}
@@ -0,0 +1,320 @@
+#![cfg(feature = "cli")]
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{Value, de::iter_from_reader, to_vec},
+    multibase::Multibase,
+};
+
+/// Runs the `dasl` binary with `args` and `input` on stdin, and returns its stdout.
+fn dasl(args: &[&str], input: &[u8]) -> Vec<u8> {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dasl"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "dasl {args:?} failed");
+    output.stdout
+}
+
+#[test]
+fn test_convert_ndjson_records() {
+    let ndjson = "{\"a\":1,\"b\":[true,null]}\n{\"c\":\"x\"}\n{\"d\":-2.5}\n";
+    let encoded = dasl(
+        &["convert", "--from", "ndjson", "--to", "drisl"],
+        ndjson.as_bytes(),
+    );
+
+    let records: Vec<Value> = iter_from_reader(&encoded[..])
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        records,
+        [
+            drisl!({ "a": 1, "b": [true, null] }),
+            drisl!({ "c": "x" }),
+            drisl!({ "d": -2.5 }),
+        ]
+    );
+
+    let back = dasl(&["convert", "--from", "drisl", "--to", "ndjson"], &encoded);
+    assert_eq!(String::from_utf8(back).unwrap(), ndjson);
+}
+
+#[test]
+fn test_convert_bytes_and_cids() {
+    let value = drisl!({ "cid": Cid::digest_sha2(Codec::Raw, b"hi"), "data": vec![1_u8, 2] });
+    let encoded = to_vec(&value).unwrap();
+    let json = dasl(&["convert", "--to", "ndjson", "--bytes", "array"], &encoded);
+    let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(json["data"], serde_json::json!([1, 2]));
+    assert_eq!(
+        json["cid"].as_str().unwrap(),
+        value["cid"].as_cid().unwrap().to_string()
+    );
+}
+
+#[test]
+fn test_car_pack_unpack() {
+    let dir = std::env::temp_dir().join(format!("dasl-cli-car-{}", std::process::id()));
+    let files = dir.join("files");
+    std::fs::create_dir_all(files.join("sub")).unwrap();
+    std::fs::write(files.join("a"), b"hello").unwrap();
+    std::fs::write(files.join("sub/b"), b"world").unwrap();
+    let a = Cid::digest_sha2(Codec::Raw, b"hello");
+    let b = Cid::digest_sha2(Codec::Raw, b"world");
+
+    let files = files.to_str().unwrap();
+    let root = a.to_string();
+    let car = dasl(&["car", "pack", files, "--root", &root], b"");
+    let listing = dasl(&["car", "ls"], &car);
+    assert_eq!(
+        String::from_utf8(listing).unwrap(),
+        format!("{a} 5\n{b} 5\n")
+    );
+    let roots = dasl(&["car", "ls", "--roots"], &car);
+    assert_eq!(String::from_utf8(roots).unwrap(), format!("{a}\n"));
+
+    // Unpacking gives a directory blockstore, which packs into the same blocks.
+    let store = dir.join("store");
+    let store = store.to_str().unwrap();
+    dasl(&["car", "unpack", "-o", store], &car);
+    assert_eq!(
+        std::fs::read(dir.join("store").join(b.to_string())).unwrap(),
+        b"world"
+    );
+    let repacked = dasl(&["car", "pack", store, "--root", &root], b"");
+    assert_eq!(repacked, car);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_cid_inspect() {
+    let cid = Cid::digest_sha2(Codec::Drisl, b"hello");
+    let expected = dasl(&["cid", &cid.to_string()], b"");
+    let output = String::from_utf8(expected.clone()).unwrap();
+    assert!(output.contains("codec:     drisl (0x71)\n"), "{output}");
+    assert!(output.contains(&format!("digest:    {}\n", hex::encode(cid.hash()))));
+    let base58 = cid.to_string_with_base(Multibase::Base58Btc);
+    assert!(output.contains(&format!("base58btc: {base58}\n")));
+
+    // The same CID as other multibases, hex, and binary on stdin, with and without the zero byte.
+    let hex = hex::encode(cid.as_bytes());
+    assert_eq!(dasl(&["cid", &base58], b""), expected);
+    assert_eq!(dasl(&["cid", &hex], b""), expected);
+    assert_eq!(dasl(&["cid"], cid.as_bytes()), expected);
+    assert_eq!(
+        dasl(&["cid", "-"], &[&[0], cid.as_bytes()].concat()),
+        expected
+    );
+
+    // Unsupported codecs and hashes are shown by their code.
+    let dag_json = "baguqeeraiqjw7i2vwntyuekgvulpp2det2kpwt6cd7tx5ayqybqpmhfk76fa";
+    let output = String::from_utf8(dasl(&["cid", dag_json], b"")).unwrap();
+    assert!(output.contains("codec:     unknown (0x129)\n"), "{output}");
+    assert!(output.contains(&format!("base32:    {dag_json}\n")));
+}
+
+#[test]
+fn test_diff() {
+    let dir = std::env::temp_dir().join(format!("dasl-cli-diff-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let old = dir.join("old");
+    let new = dir.join("new");
+    let old_value = drisl!({ "a": 1, "list": [1, 2, 3], "nested": { "x": "y" } });
+    let new_value = drisl!({ "a": 2, "list": [1, 2], "nested": { "x": "y", "z": null } });
+    std::fs::write(&old, to_vec(&old_value).unwrap()).unwrap();
+    std::fs::write(&new, to_vec(&new_value).unwrap()).unwrap();
+    let (old, new) = (old.to_str().unwrap(), new.to_str().unwrap());
+
+    let output = dasl(&["diff", old, new], b"");
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "~ a: 1 -> 2\n- list[2]: 3\n+ nested.z: null\n"
+    );
+
+    let status = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_dasl"))
+            .args(args)
+            .output()
+            .unwrap()
+            .status
+            .code()
+    };
+    assert_eq!(status(&["diff", "--exit-code", old, new]), Some(1));
+    assert_eq!(status(&["diff", "--exit-code", old, old]), Some(0));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_stats() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let mut input = Vec::new();
+    for value in [
+        drisl!({ "name": "a", "link": cid }),
+        drisl!({ "name": "b", "tags": ["x", "y"] }),
+        drisl!([1, { "name": null }]),
+    ] {
+        input.extend(to_vec(&value).unwrap());
+    }
+
+    let output = dasl(&["stats", "--format", "json"], &input);
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["records"], 3);
+    assert_eq!(report["bytes"], input.len());
+    assert_eq!(report["links"], 1);
+    assert_eq!(report["max_depth"], 2);
+    assert_eq!(report["kinds"]["map"], 3);
+    assert_eq!(report["kinds"]["text"], 4);
+    assert_eq!(report["distinct_keys"], 3);
+    assert_eq!(report["keys"][0]["key"], "name");
+    assert_eq!(report["keys"][0]["count"], 3);
+
+    let output = String::from_utf8(dasl(&["stats", "--top-keys", "1"], &input)).unwrap();
+    assert!(output.contains("records:     3\n"));
+    assert!(output.contains("keys (1 of 3 distinct):\n  3 \"name\"\n"));
+}
+
+#[test]
+fn test_grep() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let records = [
+        drisl!({ "name": "alice", "likes": 3 }),
+        drisl!({ "name": "bob", "likes": 12, "link": cid }),
+        drisl!({ "name": "carol", "likes": 30 }),
+    ];
+    let mut input = Vec::new();
+    for record in &records {
+        input.extend(to_vec(record).unwrap());
+    }
+
+    let output = dasl(&["grep", "select(.likes > 10) | .name"], &input);
+    assert_eq!(String::from_utf8(output).unwrap(), "\"bob\"\n\"carol\"\n");
+    let output = dasl(
+        &["grep", "select(.name | contains(\"ro\"))", "--offsets"],
+        &input,
+    );
+    let start = to_vec(&records[0]).unwrap().len() + to_vec(&records[1]).unwrap().len();
+    let len = to_vec(&records[2]).unwrap().len();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        format!("{start} {len}\n")
+    );
+    let query = format!("select(.link == cid(\"{cid}\")) | .name");
+    assert_eq!(dasl(&["grep", &query], &input), b"\"bob\"\n");
+
+    // Like grep, nothing found is an error.
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dasl"))
+        .args(["grep", "select(has(\"missing\"))"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&input).unwrap();
+    assert_eq!(child.wait().unwrap().code(), Some(1));
+}
+
+#[test]
+fn test_dump() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let input = to_vec(&drisl!({ "a": [1, -2], "cid": cid })).unwrap();
+    let output = String::from_utf8(dasl(&["dump"], &input)).unwrap();
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines.len(), 13);
+    // Nested items are indented, and the annotations are aligned.
+    let column = lines[0].find("map(2)").unwrap();
+    assert!(lines[0].starts_with("00000000  a2 "));
+    assert!(lines[3].starts_with("00000003    82 "));
+    assert_eq!(lines[3].find("array(2)"), Some(column));
+    assert!(lines[5].starts_with("00000005      21 "));
+    assert_eq!(lines[5].find("negative(-2)"), Some(column));
+    assert!(lines[8].starts_with("0000000a    d8 2a "));
+    assert_eq!(lines[8].find("tag(42) CID"), Some(column));
+    assert!(lines[10].ends_with(&cid.to_string()));
+
+    // Anything that isn't canonical is marked, e.g. unsorted keys and non-minimal integers.
+    let input = [0xa2, 0x61, 0x62, 0x18, 0x01, 0x61, 0x61, 0xf6];
+    let output = String::from_utf8(dasl(&["dump"], &input)).unwrap();
+    assert!(
+        output.contains("unsigned(1) ! Integer or length isn't encoded in its shortest form\n")
+    );
+    assert!(output.contains("text(1) ! Map keys aren't sorted\n"));
+}
+
+#[test]
+fn test_tail() {
+    use std::io::{BufRead, BufReader};
+
+    let dir = std::env::temp_dir().join(format!("dasl-cli-tail-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("log");
+    let record = |i: i32| to_vec(&drisl!({ "i": i })).unwrap();
+    let mut data: Vec<u8> = (0..3).flat_map(record).collect();
+    // A value that is still being written isn't printed.
+    let next = record(3);
+    data.extend(&next[..2]);
+    std::fs::write(&path, &data).unwrap();
+    let file = path.to_str().unwrap();
+
+    let output = dasl(&["tail", "-n", "2", file], b"");
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "{\"i\": 1}\n{\"i\": 2}\n"
+    );
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dasl"))
+        .args(["tail", "-f", "-n", "1", "--interval", "10", file])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    assert_eq!(lines.next().unwrap().unwrap(), "{\"i\": 2}");
+
+    let mut log = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap();
+    log.write_all(&next[2..]).unwrap();
+    assert_eq!(lines.next().unwrap().unwrap(), "{\"i\": 3}");
+    log.write_all(&record(4)).unwrap();
+    assert_eq!(lines.next().unwrap().unwrap(), "{\"i\": 4}");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_error() {
+    // Errors are printed with their message, not their debug representation.
+    let mut child = Command::new(env!("CARGO_BIN_EXE_dasl"))
+        .arg("validate")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(&[0x18, 0x01])
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(
+        stderr,
+        format!("Error: {}\n", drisl::validate(&[0x18, 0x01]).unwrap_err())
+    );
+}
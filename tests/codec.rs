@@ -0,0 +1,91 @@
+#![cfg(feature = "codec")]
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{Value, codec::DrislCodec, error::CodecError, to_vec},
+};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio_util::bytes::BytesMut;
+use tokio_util::codec::{Decoder, Framed, FramedRead};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Message {
+    id: u32,
+    body: String,
+}
+
+#[tokio::test]
+async fn test_codec_framed() {
+    let (client, server) = tokio::io::duplex(16);
+    let mut client = Framed::new(client, DrislCodec::<Message>::new());
+    let mut server = Framed::new(server, DrislCodec::<Message>::new());
+
+    let messages: Vec<Message> = (0..10)
+        .map(|id| Message {
+            id,
+            body: "x".repeat(id as usize * 10),
+        })
+        .collect();
+    let send = async {
+        for message in &messages {
+            client.send(message.clone()).await.unwrap();
+        }
+        client.close().await.unwrap();
+    };
+    let receive = async {
+        let mut received = Vec::new();
+        while let Some(message) = server.next().await {
+            received.push(message.unwrap());
+        }
+        received
+    };
+    let ((), received) = tokio::join!(send, receive);
+    assert_eq!(received, messages);
+}
+
+#[test]
+fn test_codec_partial() {
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let value = drisl!({ "a": [1, "two", cid], "b": { "c": null } });
+    let mut bytes = to_vec(&value).unwrap();
+    bytes.extend(to_vec(&drisl!(true)).unwrap());
+
+    let mut codec = DrislCodec::<Value>::new();
+    let mut src = BytesMut::new();
+    let mut decoded = Vec::new();
+    for &byte in &bytes {
+        src.extend_from_slice(&[byte]);
+        if let Some(value) = codec.decode(&mut src).unwrap() {
+            decoded.push(value);
+        }
+    }
+    assert_eq!(decoded, [value, drisl!(true)]);
+    assert!(src.is_empty());
+    assert!(codec.decode_eof(&mut src).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_codec_errors() {
+    // Truncated value at the end of the stream.
+    let data: &[u8] = &[0x82, 0x01];
+    let mut reader = FramedRead::new(data, DrislCodec::<Value>::new());
+    assert!(matches!(reader.next().await, Some(Err(CodecError::Io(_)))));
+
+    // Malformed data.
+    let data: &[u8] = &[0x9f, 0x01, 0xff];
+    let mut reader = FramedRead::new(data, DrislCodec::<Value>::new());
+    assert!(matches!(
+        reader.next().await,
+        Some(Err(CodecError::Decode(_)))
+    ));
+
+    // Complete value of the wrong type.
+    let data: &[u8] = &[0x01];
+    let mut reader = FramedRead::new(data, DrislCodec::<String>::new());
+    assert!(matches!(
+        reader.next().await,
+        Some(Err(CodecError::Decode(_)))
+    ));
+}
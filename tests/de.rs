@@ -4,7 +4,9 @@ use std::{
     net::{IpAddr, Ipv4Addr},
 };
 
-use dasl::drisl::{DecodeError, Value, de, de::from_slice, error::Len, to_vec};
+use dasl::drisl::{
+    DecodeError, Value, de, de::from_slice, error::Len, ser::NonFiniteFloats, to_vec,
+};
 use serde::{Deserialize, Serialize};
 use serde_tuple::{Deserialize_tuple, Serialize_tuple};
 
@@ -162,6 +164,139 @@ fn test_float() {
     assert_eq!(drisl.unwrap(), Value::Float(100000.0));
 }
 
+#[test]
+fn test_non_finite_float() {
+    for input in [
+        "fb7ff8000000000000",
+        "fbfff0000000000000",
+        "fbffff000000000000",
+    ] {
+        let input = hex::decode(input).unwrap();
+        assert!(matches!(
+            de::from_slice::<Value>(&input),
+            Err(DecodeError::NonFiniteFloat)
+        ));
+        assert!(matches!(
+            de::from_slice::<f64>(&input),
+            Err(DecodeError::NonFiniteFloat)
+        ));
+        let lenient = de::DecodeOptions::new().lenient(true);
+        assert!(matches!(
+            de::from_slice_with::<Value>(&input, lenient),
+            Err(DecodeError::NonFiniteFloat)
+        ));
+
+        // The policy only applies to lenient decoding.
+        let null = lenient.non_finite_floats(NonFiniteFloats::Null);
+        let value: Value = de::from_slice_with(&input, null).unwrap();
+        assert_eq!(value, Value::Null);
+        assert!(de::from_slice_with::<f64>(&input, null).is_err());
+        assert!(matches!(
+            de::from_slice_with::<Value>(&input, null.lenient(false)),
+            Err(DecodeError::NonFiniteFloat)
+        ));
+
+        let canonical = lenient.non_finite_floats(NonFiniteFloats::Canonicalize);
+        let value: f64 = de::from_slice_with(&input, canonical).unwrap();
+        if value.is_nan() {
+            assert_eq!(value.to_bits(), 0x7ff8_0000_0000_0000);
+        } else {
+            assert_eq!(
+                value,
+                f64::from_bits(u64::from_be_bytes(input[1..].try_into().unwrap()))
+            );
+        }
+    }
+}
+
+#[test]
+fn test_duplicate_keys() {
+    use de::DuplicateKeys;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Entry {
+        a: u8,
+        b: Option<u8>,
+    }
+
+    // [{"a": 1, "b": 0, "b": 2}], sorted apart from the duplicate
+    let sorted = hex::decode("81a3616101616200616202").unwrap();
+    let err = de::from_slice::<Vec<Value>>(&sorted).unwrap_err();
+    assert!(matches!(err, DecodeError::DuplicateKey { key, offset: 8 } if key == "b"));
+    #[cfg(feature = "std")]
+    {
+        let err = de::from_reader::<Vec<Entry>, _>(&sorted[..]).unwrap_err();
+        assert!(matches!(err, DecodeError::DuplicateKey { key, offset: 8 } if key == "b"));
+    }
+    let path = de::DecodeOptions::new().track_path(true);
+    let err = de::from_slice_with::<Vec<Entry>>(&sorted, path).unwrap_err();
+    assert_eq!(err.path(), Some("[0]"));
+    assert!(matches!(
+        err.without_path(),
+        DecodeError::DuplicateKey { offset: 8, .. }
+    ));
+
+    // [{"b": 0, "a": 1, "b": 2, "a": 3}]
+    let input = hex::decode("81a4616200616101616202616103").unwrap();
+    let err = de::from_slice::<Vec<Value>>(&input).unwrap_err();
+    assert!(matches!(err, DecodeError::UnsortedKey { key, offset: 5 } if key == "a"));
+
+    // The policy only applies to lenient decoding.
+    let lenient = de::DecodeOptions::new().lenient(true);
+    let first = lenient.duplicate_keys(DuplicateKeys::FirstWins);
+    assert!(matches!(
+        de::from_slice_with::<Vec<Value>>(&sorted, first.lenient(false)),
+        Err(DecodeError::DuplicateKey { .. })
+    ));
+    assert!(matches!(
+        de::from_slice_with::<Vec<Value>>(&input, lenient),
+        Err(DecodeError::DuplicateKey { .. })
+    ));
+
+    let value: Vec<Value> = de::from_slice_with(&input, first).unwrap();
+    assert_eq!(value, [dasl::drisl!({"a": 1, "b": 0})]);
+    let entries: Vec<Entry> = de::from_slice_with(&input, first).unwrap();
+    assert_eq!(entries, [Entry { a: 1, b: Some(0) }]);
+
+    let last = lenient.duplicate_keys(DuplicateKeys::LastWins);
+    let value: Vec<Value> = de::from_slice_with(&input, last).unwrap();
+    assert_eq!(value, [dasl::drisl!({"a": 3, "b": 2})]);
+    let map: Vec<BTreeMap<String, u8>> = de::from_slice_with(&input, last).unwrap();
+    assert_eq!(map[0]["b"], 2);
+    // Derived structs don't accept duplicate fields.
+    assert!(de::from_slice_with::<Vec<Entry>>(&input, last).is_err());
+
+    // Keys that only differ in length or content aren't duplicates.
+    // {"a": 1, "b": 2, "aa": 3}
+    let input = hex::decode("a361610161620262616103").unwrap();
+    let value: Value = de::from_slice(&input).unwrap();
+    assert_eq!(value, dasl::drisl!({"a": 1, "b": 2, "aa": 3}));
+}
+
+#[test]
+fn test_duplicate_keys_large_map() {
+    // 200k keys in reverse order, finding duplicates used to take quadratic time for them.
+    const LEN: u32 = 200_000;
+    let mut input = vec![0xba];
+    input.extend_from_slice(&LEN.to_be_bytes());
+    for i in (0..LEN).rev() {
+        input.push(0x66);
+        input.extend_from_slice(format!("{i:06}").as_bytes());
+        input.push(0x00);
+    }
+
+    // The keys aren't sorted, only lenient decoding accepts them.
+    assert!(matches!(
+        de::from_slice::<Value>(&input),
+        Err(DecodeError::UnsortedKey { offset: 13, .. })
+    ));
+    let lenient = de::DecodeOptions::new().lenient(true);
+    let value: BTreeMap<String, u8> = de::from_slice_with(&input, lenient).unwrap();
+    assert_eq!(value.len(), LEN as usize);
+    let value: Value = de::from_slice_with(&input, lenient).unwrap();
+    assert_eq!(value.get("000000"), Some(&Value::Integer(0)));
+}
+
 #[test]
 fn test_rejected_tag() {
     let drisl: Result<Value, _> =
@@ -207,8 +342,14 @@ fn test_nan() {
 // but it parses to a cbor value.
 fn test_kietaub_file() {
     let file = include_bytes!("kietaub.cbor");
+    // Its map keys aren't sorted, so it's only accepted when decoding is lenient.
     let value_result: Result<Value, _> = de::from_slice(file);
-    value_result.unwrap();
+    assert!(matches!(
+        value_result,
+        Err(DecodeError::UnsortedKey { key, offset: 63 }) if key == "flag"
+    ));
+    let lenient = de::DecodeOptions::new().lenient(true);
+    de::from_slice_with::<Value>(file, lenient).unwrap();
 }
 
 #[test]
@@ -264,6 +405,7 @@ fn test_object_determinism_roundtrip() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn test_from_reader_once() {
     let v: &[u8] = &[0x66, 0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72, 0x0a];
     let mut reader = std::io::Cursor::new(v);
@@ -275,12 +417,13 @@ fn test_from_reader_once() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn test_stream_deserializer() {
     let v: &[u8] = &[
         0x66, 0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72, 0x63, 0x62, 0x61, 0x7A,
     ];
     let reader = std::io::Cursor::new(v);
-    let reader = cbor4ii::core::utils::IoReader::new(reader);
+    let reader = de::IoReader::new(reader);
     let mut i = de::Deserializer::from_reader(reader).into_iter();
     let value_1: String = i.next().unwrap().unwrap();
     assert_eq!(value_1, "foobar");
@@ -290,11 +433,12 @@ fn test_stream_deserializer() {
 }
 
 #[test]
+#[cfg(feature = "std")]
 fn test_stream_deserializer_trailing_data() {
     // one byte missing on the end
     let v: &[u8] = &[0x66, 0x66, 0x6f, 0x6f, 0x62, 0x61, 0x72, 0x63, 0x62, 0x61];
     let reader = std::io::Cursor::new(v);
-    let reader = cbor4ii::core::utils::IoReader::new(reader);
+    let reader = de::IoReader::new(reader);
     let mut i = de::Deserializer::from_reader(reader).into_iter();
     let value_1: String = i.next().unwrap().unwrap();
     assert_eq!(value_1, "foobar");
@@ -303,6 +447,251 @@ fn test_stream_deserializer_trailing_data() {
     assert!(matches!(i.next(), Some(Err(DecodeError::Eof { .. }))));
 }
 
+#[test]
+fn test_custom_reader() {
+    /// Hands out one byte at a time, which can't be borrowed.
+    struct ByteByByte<'a>(&'a [u8]);
+
+    impl<'de> de::Read<'de> for ByteByByte<'_> {
+        type Error = Infallible;
+
+        fn fill<'short>(
+            &'short mut self,
+            _want: usize,
+        ) -> Result<de::Reference<'de, 'short>, Infallible> {
+            Ok(de::Reference::Short(&self.0[..self.0.len().min(1)]))
+        }
+
+        fn advance(&mut self, n: usize) {
+            self.0 = &self.0[n..];
+        }
+    }
+
+    let input = to_vec(&("foobar", [1, 2], serde_bytes::Bytes::new(&[0, 1]))).unwrap();
+    let mut deserializer = de::Deserializer::from_reader(ByteByByte(&input));
+    let value: (String, Vec<u8>, serde_bytes::ByteBuf) =
+        Deserialize::deserialize(&mut deserializer).unwrap();
+    assert_eq!(value, ("foobar".into(), vec![1, 2], vec![0, 1].into()));
+    deserializer.end().unwrap();
+}
+
+#[test]
+fn test_iter_from_slice() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Record<'a> {
+        name: &'a str,
+        #[serde(with = "serde_bytes")]
+        data: &'a [u8],
+    }
+
+    let records = [
+        to_vec(&dasl::drisl!({ "name": "a", "data": vec![1_u8, 2] })).unwrap(),
+        to_vec(&dasl::drisl!({ "name": "bcdef", "data": Vec::<u8>::new() })).unwrap(),
+    ];
+    let buf = records.concat();
+
+    let items: Vec<(Record<'_>, usize)> =
+        de::iter_from_slice(&buf).collect::<Result<_, _>>().unwrap();
+    assert_eq!(
+        items,
+        [
+            (
+                Record {
+                    name: "a",
+                    data: &[1, 2]
+                },
+                records[0].len()
+            ),
+            (
+                Record {
+                    name: "bcdef",
+                    data: &[]
+                },
+                records[1].len()
+            ),
+        ]
+    );
+    // The strings are borrowed from the buffer.
+    assert!(buf.as_ptr_range().contains(&items[1].0.name.as_ptr()));
+
+    assert!(de::iter_from_slice::<Value>(&[]).next().is_none());
+
+    // Errors end the iteration.
+    let mut truncated = de::iter_from_slice::<Record<'_>>(&buf[..buf.len() - 1]);
+    assert_eq!(truncated.next().unwrap().unwrap().1, records[0].len());
+    assert!(matches!(
+        truncated.next(),
+        Some(Err(DecodeError::Eof { .. }))
+    ));
+    assert!(truncated.next().is_none());
+
+    // The limits apply to each value.
+    let options = de::DecodeOptions::new().max_size(records[0].len());
+    let mut limited = de::iter_from_slice_with::<Value>(&buf, options);
+    assert!(limited.next().unwrap().is_ok());
+    assert!(matches!(
+        limited.next(),
+        Some(Err(DecodeError::SizeLimit { .. }))
+    ));
+}
+
+#[test]
+fn test_from_slice_partial() {
+    let first = to_vec(&dasl::drisl!({ "id": 1, "tags": ["a", "b"] })).unwrap();
+    let second = to_vec(&dasl::drisl!("end")).unwrap();
+    let buf = [&first[..], &second[..]].concat();
+
+    let (value, rest): (Value, _) = de::from_slice_partial(&buf).unwrap();
+    assert_eq!(value, dasl::drisl!({ "id": 1, "tags": ["a", "b"] }));
+    assert_eq!(rest, second);
+    let (value, rest): (&str, _) = dasl::drisl::from_slice_partial(rest).unwrap();
+    assert_eq!(value, "end");
+    assert!(rest.is_empty());
+
+    // Trailing data is only an error for `from_slice`.
+    assert!(matches!(
+        from_slice::<Value>(&buf),
+        Err(DecodeError::TrailingData)
+    ));
+    assert!(matches!(
+        de::from_slice_partial::<Value>(&first[..first.len() - 1]),
+        Err(DecodeError::Eof { .. })
+    ));
+    assert!(matches!(
+        de::from_slice_partial::<Value>(&[]),
+        Err(DecodeError::Eof { .. })
+    ));
+
+    let options = de::DecodeOptions::new().max_collection_len(1);
+    assert!(matches!(
+        de::from_slice_partial_with::<Value>(&buf, options),
+        Err(DecodeError::CollectionLenLimit { .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_iter_from_reader_indexed() {
+    use std::io::{BufReader, Cursor, Seek, SeekFrom};
+
+    let long = "x".repeat(100);
+    let records = [
+        dasl::drisl!({ "name": long, "n": 1 }),
+        dasl::drisl!([1, 2, 3]),
+        dasl::drisl!({ "bytes": vec![7_u8; 50] }),
+    ];
+    let encoded: Vec<Vec<u8>> = records.iter().map(|r| to_vec(r).unwrap()).collect();
+    let buf = encoded.concat();
+
+    // A small buffer makes values span several refills.
+    let reader = BufReader::with_capacity(7, Cursor::new(&buf));
+    let mut iter = de::iter_from_reader_indexed::<Value, _>(reader);
+    let mut index = Vec::new();
+    for (record, encoded) in records.iter().zip(&encoded) {
+        let (value, range) = iter.next().unwrap().unwrap();
+        assert_eq!(&value, record);
+        assert_eq!(&buf[range.start as usize..range.end as usize], &encoded[..]);
+        assert_eq!(iter.position(), range.end);
+        index.push(range);
+    }
+    assert!(iter.next().is_none());
+
+    // Seek back to a record with the index.
+    let mut file = Cursor::new(&buf);
+    file.seek(SeekFrom::Start(index[1].start)).unwrap();
+    let value: Value = de::from_reader_once(&mut file).unwrap();
+    assert_eq!(value, records[1]);
+
+    // Errors end the iteration.
+    let truncated = &buf[..buf.len() - 1];
+    let mut iter = de::iter_from_reader_indexed::<Value, _>(truncated);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_ok());
+    assert!(matches!(iter.next(), Some(Err(DecodeError::Eof { .. }))));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_iter_from_reader_indexed_recover() {
+    use std::io::BufReader;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        name: String,
+        n: u64,
+    }
+
+    let records: Vec<Record> = (0..4)
+        .map(|n| Record {
+            name: "x".repeat(20),
+            n,
+        })
+        .collect();
+    let encoded: Vec<Vec<u8>> = records.iter().map(|r| to_vec(r).unwrap()).collect();
+    let mut corrupted = encoded.clone();
+    // Break the second record in the middle of its name and cut off the last one.
+    corrupted[1][5] = 0xff;
+    corrupted[3].pop();
+    let buf = corrupted.concat();
+    let offsets: Vec<u64> = encoded
+        .iter()
+        .scan(0, |offset, e| {
+            let start = *offset;
+            *offset += e.len() as u64;
+            Some(start)
+        })
+        .collect();
+
+    let reader = BufReader::with_capacity(7, &buf[..]);
+    let mut iter = de::iter_from_reader_indexed::<Record, _>(reader).recover(true);
+    let (value, range) = iter.next().unwrap().unwrap();
+    assert_eq!(value, records[0]);
+    assert_eq!(range, 0..offsets[1]);
+    match iter.next().unwrap() {
+        Err(DecodeError::Skipped { range, error }) => {
+            assert_eq!(range, offsets[1]..offsets[2]);
+            assert!(matches!(*error, DecodeError::RequireUtf8 { .. }));
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+    let (value, range) = iter.next().unwrap().unwrap();
+    assert_eq!(value, records[2]);
+    assert_eq!(range, offsets[2]..offsets[3]);
+    match iter.next().unwrap() {
+        Err(DecodeError::Skipped { range, error }) => {
+            assert_eq!(range, offsets[3]..buf.len() as u64);
+            assert!(matches!(*error, DecodeError::Eof { .. }));
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+    assert!(iter.next().is_none());
+
+    // Without recovery the first error ends the iteration.
+    let mut iter = de::iter_from_reader_indexed::<Record, _>(&buf[..]);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_huge_array_len() {
+    // An array claiming u64::MAX elements, and one claiming 0xdededede, without the elements.
+    for bytes in [
+        &[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff][..],
+        &[0x9a, 0xde, 0xde, 0xde, 0xde],
+    ] {
+        assert!(matches!(
+            de::from_slice::<Value>(bytes),
+            Err(DecodeError::Eof { .. })
+        ));
+        assert!(matches!(
+            de::from_slice_ref(bytes),
+            Err(DecodeError::Eof { .. })
+        ));
+    }
+}
+
 #[test]
 fn crash() {
     let file = include_bytes!("crash.cbor");
@@ -348,7 +737,7 @@ fn truncated_object() {
 fn invalid_string() {
     // Non UTF-8 byte sequence, but using major type 3 (text string)
     let input = [0x63, 0xc5, 0x01, 0x02];
-    let result = dasl::drisl::from_slice::<Value>(&input);
+    let result = dasl::drisl::de::from_slice::<Value>(&input);
     let err = result.unwrap_err();
     assert!(matches!(err, DecodeError::RequireUtf8 { .. }), "{err:?}");
 }
@@ -357,7 +746,7 @@ fn invalid_string() {
 fn error_on_undefined() {
     // CBOR smple type `undefined`
     let input = [0xf7];
-    let result = dasl::drisl::from_slice::<Value>(&input);
+    let result = dasl::drisl::de::from_slice::<Value>(&input);
     assert!(matches!(
         result.unwrap_err(),
         DecodeError::Unsupported { .. }
@@ -670,3 +1059,483 @@ fn test_default_values() {
         }
     }
 }
+
+#[test]
+fn test_decode_options_limits() {
+    use de::{DecodeOptions, from_slice_with};
+
+    let value = dasl::drisl!({ "a": [[1, 2], "xyz"], "b": { "c": &b"bytes"[..] } });
+    let bytes = to_vec(&value).unwrap();
+
+    // Limits that aren't hit don't change the result.
+    let options = DecodeOptions::new()
+        .max_depth(3)
+        .max_str_len(5)
+        .max_collection_len(2)
+        .max_size(21);
+    assert_eq!(from_slice_with::<Value>(&bytes, options).unwrap(), value);
+    #[cfg(feature = "std")]
+    assert_eq!(
+        de::from_reader_with::<Value, _>(&bytes[..], options).unwrap(),
+        value
+    );
+
+    let result = from_slice_with::<Value>(&bytes, options.max_depth(2));
+    assert!(matches!(result, Err(DecodeError::DepthLimit { limit: 2 })));
+
+    let result = from_slice_with::<Value>(&bytes, options.max_str_len(4));
+    assert!(matches!(
+        result,
+        Err(DecodeError::StrLenLimit {
+            name: "bytes",
+            len: 5,
+            limit: 4
+        })
+    ));
+
+    // [1, 2, 3]
+    let result = from_slice_with::<Vec<u8>>(&[0x83, 0x01, 0x02, 0x03], options);
+    assert!(matches!(
+        result,
+        Err(DecodeError::CollectionLenLimit {
+            name: "array",
+            len: 3,
+            limit: 2
+        })
+    ));
+
+    let result = from_slice_with::<Value>(&bytes, options.max_size(20));
+    assert!(matches!(result, Err(DecodeError::SizeLimit { limit: 20 })));
+    #[cfg(feature = "std")]
+    {
+        let result = de::from_reader_with::<Value, _>(&bytes[..], options.max_size(20));
+        assert!(matches!(result, Err(DecodeError::SizeLimit { limit: 20 })));
+    }
+
+    // Ignored fields are limited as well.
+    #[derive(Debug, Deserialize)]
+    struct OnlyB {
+        #[allow(dead_code)]
+        b: Value,
+    }
+    let result = from_slice_with::<OnlyB>(&bytes, options.max_str_len(2));
+    assert!(matches!(
+        result,
+        Err(DecodeError::StrLenLimit { name: "str", .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_decode_options_huge_string() {
+    // A byte string that claims to be 4 GiB long is rejected before it is read.
+    let input = [0x5a, 0xff, 0xff, 0xff, 0xff, 0x00];
+    let options = de::DecodeOptions::new().max_str_len(1024);
+    let result = de::from_reader_with::<Value, _>(&input[..], options);
+    assert!(matches!(
+        result,
+        Err(DecodeError::StrLenLimit {
+            len: 0xffff_ffff,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_decode_error_path() {
+    use de::{DecodeOptions, from_slice_with};
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Entry {
+        name: String,
+        cid: dasl::cid::Cid,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Header {
+        version: u8,
+        entries: Vec<Entry>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Document {
+        header: Header,
+    }
+
+    let cid = dasl::cid::Cid::digest_sha2(dasl::cid::Codec::Raw, b"foo");
+    let entry = dasl::drisl!({ "name": "a", "cid": cid });
+    let bad_entry = dasl::drisl!({ "name": "b", "cid": "not a cid" });
+    let document = dasl::drisl!({
+        "header": {
+            "version": 1,
+            "entries": [entry.clone(), entry.clone(), entry, bad_entry],
+        },
+    });
+    let bytes = to_vec(&document).unwrap();
+
+    let options = DecodeOptions::new().track_path(true);
+    let err = from_slice_with::<Document>(&bytes, options).unwrap_err();
+    assert_eq!(err.path(), Some("header.entries[3].cid"));
+    assert!(matches!(err.without_path(), DecodeError::Mismatch { .. }));
+
+    // Errors of the deserialized type point to the value they occurred in.
+    let document = dasl::drisl!({ "header": { "version": 1, "entries": [{ "name": "a" }] } });
+    let bytes = to_vec(&document).unwrap();
+    let err = from_slice_with::<Document>(&bytes, options).unwrap_err();
+    assert_eq!(err.path(), Some("header.entries[0]"));
+    assert!(matches!(err.without_path(), DecodeError::Msg(msg) if msg.contains("cid")));
+
+    // Without tracking, the error is returned as is.
+    let err = from_slice::<Document>(&bytes).unwrap_err();
+    assert_eq!(err.path(), None);
+    assert!(matches!(err, DecodeError::Msg(_)));
+
+    // Errors at the top level have no path.
+    let err = from_slice_with::<Document>(&[0x01], options).unwrap_err();
+    assert_eq!(err.path(), None);
+}
+
+#[test]
+fn test_lenient() {
+    use de::{DecodeOptions, from_slice_with};
+
+    let lenient = DecodeOptions::new().lenient(true);
+    let cases: &[(&str, Value)] = &[
+        // 1 as one-byte unsigned integer
+        ("1801", dasl::drisl!(1)),
+        // -1 as two-byte negative integer
+        ("390000", dasl::drisl!(-1)),
+        // [1] with a one-byte length
+        ("980101", dasl::drisl!([1])),
+        // "a" with a four-byte length
+        ("7a0000000161", dasl::drisl!("a")),
+        // [_ 1, [_ ]]
+        ("9f019fffff", dasl::drisl!([1, []])),
+        // {_ "a": 1}
+        ("bf616101ff", dasl::drisl!({ "a": 1 })),
+        // (_ "ab", "c")
+        ("7f6261626163ff", dasl::drisl!("abc")),
+        // (_ h'01', h'0203')
+        ("5f4101420203ff", Value::Bytes(vec![1, 2, 3])),
+    ];
+    for (hex, expected) in cases {
+        let input = hex::decode(hex).unwrap();
+        assert!(from_slice::<Value>(&input).is_err(), "for input {hex}");
+        assert_eq!(
+            &from_slice_with::<Value>(&input, lenient).unwrap(),
+            expected,
+            "for input {hex}"
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(
+            &de::from_reader_with::<Value, _>(&input[..], lenient).unwrap(),
+            expected,
+            "for input {hex}"
+        );
+    }
+
+    let result = from_slice::<u8>(&[0x18, 0x01]);
+    assert!(matches!(result, Err(DecodeError::NonMinimal { .. })));
+    let result = from_slice::<Vec<u8>>(&[0x9f, 0x01, 0xff]);
+    assert!(matches!(result, Err(DecodeError::IndefiniteSize)));
+
+    // Chunks of indefinite length strings must have a definite length.
+    let result = from_slice_with::<String>(&hex::decode("7f7f6161ffff").unwrap(), lenient);
+    assert!(matches!(result, Err(DecodeError::IndefiniteSize)));
+
+    // Structs and tuples can be indefinite length maps and arrays.
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Point {
+        x: u8,
+        y: u8,
+    }
+    // {_ "x": 1, "y": 2}
+    let input = hex::decode("bf617801617902ff").unwrap();
+    let point: Point = from_slice_with(&input, lenient).unwrap();
+    assert_eq!(point, Point { x: 1, y: 2 });
+    // [_ 1, 2]
+    let tuple: (u8, u8) = from_slice_with(&[0x9f, 0x01, 0x02, 0xff], lenient).unwrap();
+    assert_eq!(tuple, (1, 2));
+    let result = from_slice_with::<(u8, u8)>(&[0x9f, 0x01, 0x02, 0x03, 0xff], lenient);
+    assert!(matches!(result, Err(DecodeError::Mismatch { .. })));
+
+    // Limits still apply to indefinite lengths.
+    let options = lenient.max_collection_len(2);
+    let result = from_slice_with::<Value>(&[0x9f, 0x01, 0x02, 0x03, 0xff], options);
+    assert!(matches!(
+        result,
+        Err(DecodeError::CollectionLenLimit { len: 3, .. })
+    ));
+    let options = lenient.max_str_len(3);
+    let result = from_slice_with::<Value>(&hex::decode("7f626162626364ff").unwrap(), options);
+    assert!(matches!(result, Err(DecodeError::StrLenLimit { .. })));
+}
+
+#[test]
+fn test_skip_ignored_fields() {
+    use de::{DecodeOptions, from_slice_with};
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Sparse {
+        id: u8,
+    }
+
+    let cid = dasl::cid::Cid::digest_sha2(dasl::cid::Codec::Raw, b"foo");
+    let input = to_vec(&dasl::drisl!({
+        "id": 1,
+        "text": "a longer string",
+        "bytes": Value::Bytes(vec![1, 2, 3]),
+        "nested": { "list": [1, -1, 1.5, true, false, null, cid], "empty": {} },
+        "big": -18446744073709551616i128,
+    }))
+    .unwrap();
+    assert_eq!(from_slice::<Sparse>(&input).unwrap(), Sparse { id: 1 });
+    #[cfg(feature = "std")]
+    assert_eq!(
+        de::from_reader::<Sparse, _>(&input[..]).unwrap(),
+        Sparse { id: 1 }
+    );
+
+    // {"x": <invalid UTF-8>, "id": 1}, ignored strings aren't validated.
+    let input = hex::decode("a2617861ff 62696401".replace(' ', "")).unwrap();
+    assert_eq!(from_slice::<Sparse>(&input).unwrap(), Sparse { id: 1 });
+    assert!(from_slice::<Value>(&input).is_err());
+
+    // The structure is still checked.
+    let cases = [
+        // {"x": <truncated string>}
+        "a16178 62ff",
+        // {"x": {1: 2}, "id": 1}
+        "a26178 a10102 62696401",
+        // {"x": 1(0), "id": 1}, only tag 42 is supported
+        "a26178 c100 62696401",
+        // {"x": <one-byte length 1>, "id": 1}
+        "a26178 1801 62696401",
+        // {"x": [_ ], "id": 1}
+        "a26178 9fff 62696401",
+    ];
+    for hex in cases {
+        let input = hex::decode(hex.replace(' ', "")).unwrap();
+        assert!(from_slice::<Sparse>(&input).is_err(), "for input {hex}");
+    }
+    // {"x": {"b": 1, "a": 2}, "id": 1}, the keys of ignored maps are checked as well.
+    let input = hex::decode("a26178 a2616201616102 62696401".replace(' ', "")).unwrap();
+    assert!(matches!(
+        de::from_slice::<Sparse>(&input),
+        Err(DecodeError::UnsortedKey { key, offset: 7 }) if key == "a"
+    ));
+    // {"x": {"a": 1, "a": 2}, "id": 1}
+    let input = hex::decode("a26178 a2616101616102 62696401".replace(' ', "")).unwrap();
+    assert!(matches!(
+        de::from_slice::<Sparse>(&input),
+        Err(DecodeError::DuplicateKey { key, offset: 7 }) if key == "a"
+    ));
+
+    // {"id": 1, "x": [_ (_ "a")]} is fine when lenient.
+    let input = hex::decode("a2626964016178 9f7f6161ffff".replace(' ', "")).unwrap();
+    let lenient = DecodeOptions::new().lenient(true);
+    assert_eq!(
+        from_slice_with::<Sparse>(&input, lenient).unwrap(),
+        Sparse { id: 1 }
+    );
+
+    // Limits apply to ignored values as well.
+    let input = to_vec(&dasl::drisl!({ "id": 1, "x": [[["abc"]]], "y": [1, 2, 3] })).unwrap();
+    let options = DecodeOptions::new().max_depth(3);
+    let result = from_slice_with::<Sparse>(&input, options);
+    assert!(matches!(result, Err(DecodeError::DepthLimit { limit: 3 })));
+    let options = DecodeOptions::new().max_str_len(2);
+    let result = from_slice_with::<Sparse>(&input, options);
+    assert!(matches!(
+        result,
+        Err(DecodeError::StrLenLimit { len: 3, .. })
+    ));
+    let options = DecodeOptions::new().max_collection_len(2);
+    let result = from_slice_with::<Sparse>(&input, options);
+    assert!(matches!(
+        result,
+        Err(DecodeError::CollectionLenLimit { len: 3, .. })
+    ));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_lazy_bytes() {
+    use std::io::{Cursor, Read};
+
+    use de::{DecodeOptions, LazyBytes};
+
+    #[derive(Serialize)]
+    struct Original {
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+        name: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Record {
+        data: LazyBytes,
+        name: String,
+    }
+
+    let data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+    let original = Original {
+        data: data.clone(),
+        name: "blob".to_string(),
+    };
+    let bytes = to_vec(&original).unwrap();
+
+    let mut source = Cursor::new(&bytes);
+    let record: Record = dasl::drisl::from_reader(&mut source).unwrap();
+    assert_eq!(record.name, "blob");
+    assert_eq!(record.data.len(), data.len() as u64);
+    // The map header, the key "data" and the byte string header.
+    assert_eq!(record.data.offset(), 1 + 5 + 5);
+    assert_eq!(record.data.slice(&bytes), Some(&data[..]));
+
+    let mut read = Vec::new();
+    record
+        .data
+        .reader(&mut source)
+        .unwrap()
+        .read_to_end(&mut read)
+        .unwrap();
+    assert_eq!(read, data);
+
+    // The limits apply as for other byte strings.
+    let options = DecodeOptions::new().max_str_len(1000);
+    assert!(matches!(
+        de::from_slice_with::<Record>(&bytes, options),
+        Err(DecodeError::StrLenLimit { len: 100_000, .. })
+    ));
+
+    // Indefinite length byte strings aren't contiguous.
+    let options = DecodeOptions::new().lenient(true);
+    assert!(matches!(
+        de::from_slice_with::<LazyBytes>(&[0x5f, 0x41, 0x01, 0xff], options),
+        Err(DecodeError::IndefiniteSize)
+    ));
+
+    assert!(matches!(
+        de::from_slice::<LazyBytes>(&[0x01]),
+        Err(DecodeError::Mismatch { .. })
+    ));
+    assert!(dasl::drisl::from_value::<LazyBytes>(Value::Bytes(vec![1])).is_err());
+}
+
+#[test]
+fn test_value_from_slice() {
+    use de::{DecodeOptions, DuplicateKeys, value_from_slice, value_from_slice_with};
+
+    // The same as decoding with Serde.
+    let value = dasl::drisl!({
+        "a": [[1, 2], "xyz", null, 1.5, true],
+        "b": { "c": &b"bytes"[..], "": [] },
+        "cid": dasl::cid::Cid::digest_sha2(dasl::cid::Codec::Raw, b"foo"),
+    });
+    let bytes = to_vec(&value).unwrap();
+    assert_eq!(value_from_slice(&bytes).unwrap(), value);
+    assert!(matches!(
+        value_from_slice(&[0x01, 0x02]),
+        Err(DecodeError::TrailingData)
+    ));
+
+    // Nesting isn't limited by the stack, neither when decoding nor when dropping the value.
+    let depth = 1_000_000;
+    let mut nested = vec![0x81; depth];
+    nested.push(0xa0);
+    assert!(matches!(
+        from_slice::<Value>(&nested),
+        Err(DecodeError::DepthOverflow { .. })
+    ));
+    let value = value_from_slice(&nested).unwrap();
+    let mut levels = 0;
+    let mut inner = &value;
+    while let Value::Array(items) = inner {
+        assert_eq!(items.len(), 1);
+        inner = &items[0];
+        levels += 1;
+    }
+    assert_eq!(levels, depth);
+    assert_eq!(*inner, Value::Map(BTreeMap::new()));
+    drop(value);
+
+    // The options apply the same as with Serde.
+    let options = DecodeOptions::new().max_depth(3);
+    assert!(value_from_slice_with(&bytes, options).is_ok());
+    assert!(matches!(
+        value_from_slice_with(&bytes, options.max_depth(2)),
+        Err(DecodeError::DepthLimit { limit: 2 })
+    ));
+    assert!(matches!(
+        value_from_slice_with(&nested, options),
+        Err(DecodeError::DepthLimit { limit: 3 })
+    ));
+    assert!(matches!(
+        value_from_slice_with(&bytes, options.max_collection_len(4)),
+        Err(DecodeError::CollectionLenLimit { .. })
+    ));
+
+    // [_ {_ "a": 1}, 2]
+    let indefinite = hex::decode("9fbf616101ff02ff").unwrap();
+    assert!(matches!(
+        value_from_slice(&indefinite),
+        Err(DecodeError::IndefiniteSize)
+    ));
+    let lenient = DecodeOptions::new().lenient(true);
+    assert_eq!(
+        value_from_slice_with(&indefinite, lenient).unwrap(),
+        dasl::drisl!([{ "a": 1 }, 2])
+    );
+
+    // [{"a": 1, "b": 0, "b": 2}]
+    let sorted = hex::decode("81a3616101616200616202").unwrap();
+    assert!(matches!(
+        value_from_slice(&sorted),
+        Err(DecodeError::DuplicateKey { key, offset: 8 }) if key == "b"
+    ));
+    // [{"b": 0, "a": 1, "b": 2, "a": 3}]
+    let duplicates = hex::decode("81a4616200616101616202616103").unwrap();
+    assert!(matches!(
+        value_from_slice(&duplicates),
+        Err(DecodeError::UnsortedKey { key, offset: 5 }) if key == "a"
+    ));
+    let first = lenient.duplicate_keys(DuplicateKeys::FirstWins);
+    assert_eq!(
+        value_from_slice_with(&duplicates, first).unwrap(),
+        dasl::drisl!([{ "a": 1, "b": 0 }])
+    );
+    let last = lenient.duplicate_keys(DuplicateKeys::LastWins);
+    assert_eq!(
+        value_from_slice_with(&duplicates, last).unwrap(),
+        dasl::drisl!([{ "a": 3, "b": 2 }])
+    );
+
+    // Errors point to the value they occurred in.
+    let options = DecodeOptions::new().track_path(true).max_str_len(3);
+    let err = value_from_slice_with(&bytes, options).unwrap_err();
+    assert_eq!(err.path(), Some("b.c"));
+    assert!(matches!(
+        err.without_path(),
+        DecodeError::StrLenLimit { .. }
+    ));
+    let err = value_from_slice_with(&duplicates, options).unwrap_err();
+    assert_eq!(err.path(), Some("[0]"));
+    let err = value_from_slice_with(&[0x7f], options).unwrap_err();
+    assert_eq!(err.path(), None);
+}
+
+#[test]
+fn test_eof_len() {
+    assert_eq!(Len::new(3), Len::Small(3));
+    assert_eq!(Len::new(usize::from(u16::MAX) + 1), Len::Big);
+    // "abc" that ends after the first byte of the string.
+    let err = from_slice::<String>(&[0x63, 0x61]).unwrap_err();
+    assert!(
+        matches!(err, DecodeError::Eof { expect, .. } if expect == Len::Small(2)),
+        "{err:?}"
+    );
+}
@@ -0,0 +1,152 @@
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{
+        Value,
+        diag::{self, DiagError, DiagErrorKind, DiagOptions},
+    },
+};
+
+#[test]
+fn test_to_string() {
+    let cases = [
+        (drisl!(0), "0"),
+        (drisl!(-18446744073709551616i128), "-18446744073709551616"),
+        (drisl!(1.0), "1.0"),
+        (drisl!(-0.5), "-0.5"),
+        (drisl!(1e100), "1e100"),
+        (Value::Float(f64::NAN), "NaN"),
+        (Value::Float(f64::NEG_INFINITY), "-Infinity"),
+        (drisl!(true), "true"),
+        (drisl!(null), "null"),
+        (drisl!(""), r#""""#),
+        (drisl!("a\"\\\n\u{1}ü"), r#""a\"\\\n\u0001ü""#),
+        (drisl!(Vec::<u8>::new()), "h''"),
+        (drisl!(vec![0xde_u8, 0xad]), "h'dead'"),
+        (drisl!([]), "[]"),
+        (drisl!([1, [2, 3]]), "[1, [2, 3]]"),
+        (drisl!({}), "{}"),
+        (
+            drisl!({ "aa": 1, "b": { "c": [] } }),
+            r#"{"b": {"c": []}, "aa": 1}"#,
+        ),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(diag::to_string(&value), expected);
+    }
+}
+
+#[test]
+fn test_cid() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let value = drisl!({ "link": cid });
+    assert_eq!(
+        diag::to_string(&value),
+        format!("{{\"link\": 42(h'00{}')}}", hex::encode(cid.as_bytes()))
+    );
+    let options = DiagOptions::new().cid_strings(true);
+    assert_eq!(
+        diag::to_string_with(&value, options),
+        format!("{{\"link\": 42(\"{cid}\")}}")
+    );
+}
+
+#[test]
+fn test_slice_to_string() {
+    // {"a": [1, h'ff']}
+    let bytes = hex::decode("a16161820141ff").unwrap();
+    assert_eq!(
+        diag::slice_to_string(&bytes).unwrap(),
+        r#"{"a": [1, h'ff']}"#
+    );
+    assert!(diag::slice_to_string(&[0x82, 0x01]).is_err());
+}
+
+#[test]
+fn test_from_str() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let cases = [
+        ("0", drisl!(0)),
+        ("-18446744073709551616", drisl!(-18446744073709551616i128)),
+        ("1.0", drisl!(1.0)),
+        ("-2.5e-3", drisl!(-0.0025)),
+        ("1E3", drisl!(1000.0)),
+        ("Infinity", Value::Float(f64::INFINITY)),
+        ("-Infinity", Value::Float(f64::NEG_INFINITY)),
+        ("true", drisl!(true)),
+        ("false", drisl!(false)),
+        ("null", drisl!(null)),
+        (r#""a\"\\\/\n\u0001ü😀""#, drisl!("a\"\\/\n\u{1}ü😀")),
+        ("h''", drisl!(Vec::<u8>::new())),
+        ("h'DE ad'", drisl!(vec![0xde_u8, 0xad])),
+        (" [ 1 , [ ] ] ", drisl!([1, []])),
+        ("{}", drisl!({})),
+        (
+            r#"{"b": {"c": [h'ff']}, "aa": 1}"#,
+            drisl!({ "aa": 1, "b": { "c": [vec![0xff_u8]] } }),
+        ),
+        (
+            &format!("42(h'00{}')", hex::encode(cid.as_bytes())),
+            drisl!(cid),
+        ),
+        (&format!("42( \"{cid}\" )"), drisl!(cid)),
+    ];
+    for (input, expected) in cases {
+        assert_eq!(diag::from_str(input).unwrap(), expected, "{input}");
+    }
+    assert!(diag::from_str("NaN").unwrap().as_float().unwrap().is_nan());
+}
+
+#[test]
+fn test_from_str_roundtrip() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let value = drisl!({
+        "int": -3,
+        "float": 1e100,
+        "text": "line\nbreak \"quoted\"",
+        "bytes": vec![0u8, 1, 255],
+        "cid": cid,
+        "array": [null, true, false, {}],
+    });
+    assert_eq!(diag::from_str(&diag::to_string(&value)).unwrap(), value);
+    let options = DiagOptions::new().cid_strings(true);
+    assert_eq!(
+        diag::from_str(&diag::to_string_with(&value, options)).unwrap(),
+        value
+    );
+}
+
+#[test]
+fn test_from_str_errors() {
+    let cases = [
+        ("", 0, DiagErrorKind::UnexpectedEnd),
+        ("[1, 2", 5, DiagErrorKind::UnexpectedEnd),
+        ("[1 2]", 3, DiagErrorKind::UnexpectedChar('2')),
+        ("nul", 0, DiagErrorKind::UnexpectedChar('n')),
+        ("-", 1, DiagErrorKind::InvalidNumber),
+        ("1.", 2, DiagErrorKind::InvalidNumber),
+        ("1e", 2, DiagErrorKind::InvalidNumber),
+        (
+            "1000000000000000000000000000000000000000",
+            0,
+            DiagErrorKind::InvalidNumber,
+        ),
+        (r#""\x""#, 2, DiagErrorKind::InvalidEscape),
+        (r#""\ud800""#, 7, DiagErrorKind::InvalidEscape),
+        ("h'0g'", 3, DiagErrorKind::InvalidHex),
+        ("h'0'", 3, DiagErrorKind::InvalidHex),
+        ("1(2)", 0, DiagErrorKind::UnsupportedTag(1)),
+        ("42(h'01')", 3, DiagErrorKind::InvalidCid),
+        ("42(1)", 3, DiagErrorKind::InvalidCid),
+        ("{1: 2}", 1, DiagErrorKind::NonStringKey),
+        (r#"{"a": 1, "a": 2}"#, 9, DiagErrorKind::DuplicateKey),
+        ("1 2", 2, DiagErrorKind::TrailingData),
+    ];
+    for (input, offset, kind) in cases {
+        assert_eq!(
+            diag::from_str(input),
+            Err(DiagError { offset, kind }),
+            "{input}"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{ApplyError, Change, ChangeKind, PathSegment, Value, apply_changes, diff},
+};
+
+fn key(key: &str) -> PathSegment {
+    PathSegment::Key(key.into())
+}
+
+#[test]
+fn test_diff() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"a");
+    let old = drisl!({
+        "same": [1, 2],
+        "int": 1,
+        "kind": 1,
+        "gone": cid,
+        "list": [{ "a": 1 }, "b", "c"],
+    });
+    let new = drisl!({
+        "same": [1, 2],
+        "int": 2,
+        "kind": 1.0,
+        "list": [{ "a": 1, "b": null }, "b"],
+        "new": true,
+    });
+
+    let changes = diff(&old, &new);
+    assert_eq!(
+        changes,
+        [
+            Change {
+                path: vec![key("gone")],
+                kind: ChangeKind::Removed(Value::Cid(cid)),
+            },
+            Change {
+                path: vec![key("int")],
+                kind: ChangeKind::Replaced {
+                    old: drisl!(1),
+                    new: drisl!(2),
+                },
+            },
+            Change {
+                path: vec![key("kind")],
+                kind: ChangeKind::Replaced {
+                    old: drisl!(1),
+                    new: drisl!(1.0),
+                },
+            },
+            Change {
+                path: vec![key("list"), PathSegment::Index(0), key("b")],
+                kind: ChangeKind::Added(Value::Null),
+            },
+            Change {
+                path: vec![key("list"), PathSegment::Index(2)],
+                kind: ChangeKind::Removed(drisl!("c")),
+            },
+            Change {
+                path: vec![key("new")],
+                kind: ChangeKind::Added(drisl!(true)),
+            },
+        ]
+    );
+    assert_eq!(changes[3].to_string(), "+ list[0].b: null");
+
+    assert_eq!(diff(&old, &old), []);
+    assert_eq!(diff(&new, &old).len(), changes.len());
+}
+
+#[test]
+fn test_diff_root() {
+    let changes = diff(&drisl!([1]), &drisl!({ "a": 1 }));
+    assert_eq!(
+        changes,
+        [Change {
+            path: vec![],
+            kind: ChangeKind::Replaced {
+                old: drisl!([1]),
+                new: drisl!({ "a": 1 }),
+            },
+        }]
+    );
+    assert_eq!(changes[0].to_string(), r#"~ .: [1] -> {"a": 1}"#);
+}
+
+#[test]
+fn test_apply_changes() {
+    let values = [
+        drisl!(null),
+        drisl!([]),
+        drisl!([1, 2, 3, 4]),
+        drisl!([1, [2, 3], 5]),
+        drisl!({}),
+        drisl!({ "a": 1, "b": [1, 2, 3], "c": { "d": [{ "e": 1 }] } }),
+        drisl!({ "a": 1.0, "b": [0], "c": { "d": [{ "e": 2, "f": 3 }, 4] }, "g": "h" }),
+    ];
+    for old in &values {
+        for new in &values {
+            let mut value = old.clone();
+            apply_changes(&mut value, diff(old, new)).unwrap();
+            assert_eq!(&value, new, "from {old:?}");
+        }
+    }
+}
+
+#[test]
+fn test_apply_changes_errors() {
+    let changes = diff(
+        &drisl!({ "a": 1, "b": [1, 2] }),
+        &drisl!({ "a": 2, "b": [1], "c": true }),
+    );
+
+    let mut value = drisl!({ "a": 3, "b": [1, 2] });
+    assert_eq!(
+        apply_changes(&mut value, changes.clone()),
+        Err(ApplyError::Conflict { path: "a".into() })
+    );
+
+    let mut value = drisl!({ "a": 1, "b": [1] });
+    assert_eq!(
+        apply_changes(&mut value, changes.clone()),
+        Err(ApplyError::Missing {
+            path: "b[1]".into()
+        })
+    );
+    // The changes before the failing one are applied.
+    assert_eq!(value, drisl!({ "a": 2, "b": [1] }));
+
+    let mut value = drisl!({ "a": 1, "b": [1, 2], "c": false });
+    assert_eq!(
+        apply_changes(&mut value, changes.clone()),
+        Err(ApplyError::Conflict { path: "c".into() })
+    );
+
+    let mut value = drisl!({ "a": 1, "b": "x" });
+    assert_eq!(
+        apply_changes(&mut value, changes),
+        Err(ApplyError::Missing {
+            path: "b[1]".into()
+        })
+    );
+
+    let removed = Change {
+        path: vec![],
+        kind: ChangeKind::Removed(drisl!(1)),
+    };
+    assert_eq!(
+        apply_changes(&mut drisl!(1), vec![removed]),
+        Err(ApplyError::Conflict { path: ".".into() })
+    );
+}
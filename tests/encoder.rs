@@ -0,0 +1,129 @@
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{
+        EncodeError,
+        ser::{BufWriter, Encoder},
+        to_vec,
+    },
+};
+
+fn new_encoder() -> Encoder<BufWriter> {
+    Encoder::new(BufWriter::new(Vec::new()))
+}
+
+#[test]
+fn test_encoder() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+
+    let mut encoder = new_encoder();
+    encoder
+        .map_start(7)
+        .unwrap()
+        .key("a")
+        .unwrap()
+        .int(-1)
+        .unwrap()
+        .key("b")
+        .unwrap()
+        .float(1.5)
+        .unwrap()
+        .key("aa")
+        .unwrap()
+        .array_start(4)
+        .unwrap()
+        .bool(true)
+        .unwrap()
+        .null()
+        .unwrap()
+        .bytes(&[1, 2])
+        .unwrap()
+        .link(&cid)
+        .unwrap()
+        .end()
+        .unwrap()
+        .key("ab")
+        .unwrap()
+        .text("text")
+        .unwrap()
+        .key("big")
+        .unwrap()
+        .int(u64::MAX)
+        .unwrap()
+        .key("empty")
+        .unwrap()
+        .map_start(0)
+        .unwrap()
+        .end()
+        .unwrap()
+        .key("nested")
+        .unwrap()
+        .value(&drisl!({ "z": 1, "y": [2] }))
+        .unwrap()
+        .end()
+        .unwrap();
+    let bytes = encoder.finish().unwrap().into_inner();
+
+    let expected = drisl!({
+        "a": -1,
+        "b": 1.5,
+        "aa": [true, null, vec![1u8, 2], cid],
+        "ab": "text",
+        "big": u64::MAX,
+        "empty": {},
+        "nested": { "z": 1, "y": [2] },
+    });
+    assert_eq!(bytes, to_vec(&expected).unwrap());
+}
+
+#[test]
+fn test_encoder_lengths() {
+    let mut encoder = new_encoder();
+    encoder.array_start(1).unwrap().int(1).unwrap();
+    assert!(encoder.int(2).is_err());
+
+    let mut encoder = new_encoder();
+    encoder.array_start(2).unwrap().int(1).unwrap();
+    assert!(encoder.end().is_err());
+
+    let mut encoder = new_encoder();
+    encoder.map_start(1).unwrap().key("a").unwrap();
+    assert!(encoder.end().is_err());
+    encoder.int(1).unwrap();
+    assert!(encoder.key("b").is_err());
+    encoder.end().unwrap();
+    assert!(encoder.end().is_err());
+    encoder.finish().unwrap();
+
+    let mut encoder = new_encoder();
+    encoder.array_start(1).unwrap();
+    assert!(matches!(encoder.finish(), Err(EncodeError::Msg(_))));
+}
+
+#[test]
+fn test_encoder_keys() {
+    let mut encoder = new_encoder();
+    assert!(encoder.key("a").is_err());
+    encoder.array_start(1).unwrap();
+    assert!(encoder.key("a").is_err());
+
+    let mut encoder = new_encoder();
+    encoder.map_start(3).unwrap();
+    assert!(encoder.int(1).is_err());
+    encoder.key("b").unwrap();
+    assert!(encoder.key("c").is_err());
+    encoder.int(1).unwrap();
+    // Keys are sorted by length first, duplicates aren't allowed.
+    assert!(encoder.key("a").is_err());
+    assert!(encoder.key("b").is_err());
+    encoder.key("c").unwrap().int(2).unwrap();
+    encoder.key("aa").unwrap().int(3).unwrap();
+    encoder.end().unwrap();
+}
+
+#[test]
+fn test_encoder_invalid_values() {
+    let mut encoder = new_encoder();
+    assert!(encoder.float(f64::NAN).is_err());
+    assert!(encoder.int(i128::MAX).is_err());
+}
@@ -1,5 +1,12 @@
-use dasl::drisl::{DecodeError, from_slice, to_vec};
+use std::collections::BTreeMap;
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{DecodeError, Value, from_slice, to_vec},
+};
 use serde::{Deserialize, Serialize};
+use serde_bytes::ByteBuf;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 enum Enum {
@@ -81,7 +88,7 @@ enum Foo {
 #[test]
 fn test_variable_length_array_error() {
     let slice = hex::decode("9F6772657175697265FF").unwrap();
-    let value: Result<Vec<Foo>, _> = from_slice(&slice);
+    let value: Result<Vec<Foo>, _> = dasl::drisl::de::from_slice(&slice);
     let err = value.unwrap_err();
     assert!(matches!(err, DecodeError::IndefiniteSize), "{err:?}");
 }
@@ -93,3 +100,78 @@ enum Bar {
     Flag(String, bool),
     Point { x: i32, y: i32 },
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Untagged {
+    Link(Cid),
+    Bytes(ByteBuf),
+    Map(BTreeMap<String, Untagged>),
+    Number(i64),
+}
+
+#[test]
+fn test_untagged_enum() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let values = [
+        Untagged::Link(cid),
+        Untagged::Bytes(ByteBuf::from(vec![1, 2])),
+        Untagged::Map(BTreeMap::from([
+            ("link".to_string(), Untagged::Link(cid)),
+            ("number".to_string(), Untagged::Number(-1)),
+        ])),
+        Untagged::Number(1),
+    ];
+    for value in values {
+        let bytes = to_vec(&value).unwrap();
+        assert_eq!(from_slice::<Untagged>(&bytes).unwrap(), value);
+    }
+
+    // Bytes that happen to be a valid CID stay bytes, as they lack the CID tag.
+    let value = Untagged::Bytes(ByteBuf::from(cid.as_bytes()));
+    let bytes = to_vec(&value).unwrap();
+    assert_eq!(from_slice::<Untagged>(&bytes).unwrap(), value);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum InternallyTagged {
+    Block {
+        cid: Cid,
+        data: ByteBuf,
+        meta: Value,
+    },
+    Empty,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "content")]
+enum AdjacentlyTagged {
+    Link(Cid),
+    Data(ByteBuf),
+}
+
+#[test]
+fn test_tagged_enum() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let block = InternallyTagged::Block {
+        cid,
+        data: ByteBuf::from(b"hello".to_vec()),
+        meta: drisl!({ "links": [cid], "size": 5 }),
+    };
+    for value in [block, InternallyTagged::Empty] {
+        let bytes = to_vec(&value).unwrap();
+        assert_eq!(from_slice::<InternallyTagged>(&bytes).unwrap(), value);
+    }
+    for value in [
+        AdjacentlyTagged::Link(cid),
+        AdjacentlyTagged::Data(ByteBuf::from(vec![1])),
+    ] {
+        let bytes = to_vec(&value).unwrap();
+        assert_eq!(from_slice::<AdjacentlyTagged>(&bytes).unwrap(), value);
+    }
+
+    // A CID field doesn't accept untagged bytes.
+    let bytes = to_vec(&drisl!({ "type": "Block", "cid": cid.as_bytes().to_vec(), "data": vec![1u8], "meta": null })).unwrap();
+    assert!(from_slice::<InternallyTagged>(&bytes).is_err());
+}
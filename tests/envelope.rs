@@ -0,0 +1,74 @@
+#![cfg(feature = "ed25519")]
+
+use std::collections::BTreeMap;
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl::{Value, from_slice, to_vec},
+    envelope::{Signed, ed25519_dalek::SigningKey},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Message {
+    from: String,
+    seq: u64,
+}
+
+fn key() -> SigningKey {
+    SigningKey::from_bytes(&[42u8; 32])
+}
+
+#[test]
+fn test_signed_roundtrip() {
+    let message = Message {
+        from: "alice".to_string(),
+        seq: 7,
+    };
+    let signed = Signed::sign(message.clone(), &key()).unwrap();
+    let encoded = to_vec(&signed).unwrap();
+
+    let decoded: Signed<Message> = from_slice(&encoded).unwrap();
+    assert_eq!(decoded.payload(), &message);
+    assert_eq!(decoded.public_key(), &key().verifying_key());
+    assert_eq!(decoded.payload_bytes(), to_vec(&message).unwrap());
+
+    let (payload, cid) = decoded.into_parts();
+    assert_eq!(payload, message);
+    assert_eq!(
+        cid,
+        Cid::digest_sha2(Codec::Drisl, to_vec(&message).unwrap())
+    );
+}
+
+#[test]
+fn test_signed_encoding() {
+    let signed = Signed::sign(1u8, &key()).unwrap();
+    let value: Value = from_slice(&to_vec(&signed).unwrap()).unwrap();
+    let Value::Map(map) = &value else {
+        panic!("envelope must be a map");
+    };
+    assert_eq!(
+        map.keys().collect::<Vec<_>>(),
+        ["payload", "publicKey", "signature"]
+    );
+    assert_eq!(map["payload"], Value::Bytes(vec![0x01]));
+}
+
+#[test]
+fn test_signed_tampered() {
+    let signed = Signed::sign("hello".to_string(), &key()).unwrap();
+    let mut value: Value = from_slice(&to_vec(&signed).unwrap()).unwrap();
+    let Value::Map(map) = &mut value else {
+        panic!("envelope must be a map");
+    };
+    map.insert(
+        "payload".to_string(),
+        Value::Bytes(to_vec("world").unwrap()),
+    );
+    let tampered = to_vec(&value).unwrap();
+    assert!(from_slice::<Signed<String>>(&tampered).is_err());
+
+    let empty = to_vec(&Value::Map(BTreeMap::new())).unwrap();
+    assert!(from_slice::<Signed<String>>(&empty).is_err());
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "std")]
+
+use dasl::{
+    drisl,
+    drisl::{
+        DecodeError, EncodeError, Error, ErrorKind, Value,
+        de::{self, DecodeOptions},
+    },
+};
+
+fn decode(bytes: &[u8], options: DecodeOptions) -> Result<Value, Error> {
+    Ok(de::from_slice_with(bytes, options)?)
+}
+
+#[test]
+fn test_error_kind() {
+    let options = DecodeOptions::default;
+    assert_eq!(
+        decode(&[0x82, 0x01], options()).unwrap_err().kind(),
+        ErrorKind::Eof
+    );
+    // Non-minimal integer, trailing data and a reserved initial byte.
+    assert_eq!(
+        decode(&[0x18, 0x01], options()).unwrap_err().kind(),
+        ErrorKind::Syntax
+    );
+    assert_eq!(
+        decode(&[0x01, 0x01], options()).unwrap_err().kind(),
+        ErrorKind::Syntax
+    );
+    assert_eq!(
+        decode(&[0x1c], options()).unwrap_err().kind(),
+        ErrorKind::Syntax
+    );
+    let err = decode(&[0x81, 0x80], options().max_depth(1)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Limit);
+
+    let err = drisl::from_slice::<String>(&[0x01]).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Data);
+    let err = drisl::to_vec(&u128::MAX).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Data);
+    assert!(matches!(
+        drisl::ser::to_vec(&u128::MAX),
+        Err(EncodeError::IntegerOutOfRange)
+    ));
+}
+
+#[test]
+fn test_error_path_and_offset() {
+    // {"a": [1, "x"]} decoded into a map of integer lists.
+    let bytes = drisl::to_vec(&drisl!({ "a": [1, "x"] })).unwrap();
+    let err: Error = de::from_slice_with::<std::collections::BTreeMap<String, Vec<u8>>>(
+        &bytes,
+        DecodeOptions::default().track_path(true),
+    )
+    .unwrap_err()
+    .into();
+    assert_eq!(err.kind(), ErrorKind::Data);
+    assert_eq!(err.path(), Some("a[1]"));
+    assert_eq!(err.offset(), None);
+
+    // {"a": 1, "a": 2}
+    let err = decode(
+        &[0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02],
+        DecodeOptions::default(),
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+    assert_eq!(err.offset(), Some(4));
+    assert!(matches!(
+        DecodeError::<std::convert::Infallible>::TrailingData.kind(),
+        ErrorKind::Syntax
+    ));
+}
+
+#[test]
+fn test_error_io() {
+    use std::io::{self, Read};
+
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+
+    let err = drisl::from_reader::<Value, _>(io::BufReader::new(Failing)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Io);
+    assert_eq!(err.io_error().unwrap().to_string(), "broken");
+    assert!(std::error::Error::source(&err).is_some());
+
+    let err = Error::from(io::Error::other("closed"));
+    assert_eq!(err.kind(), ErrorKind::Io);
+    assert_eq!(err.to_string(), "closed");
+}
+
+#[test]
+fn test_error_display() {
+    let message = |bytes: &[u8]| drisl::from_slice::<Value>(bytes).unwrap_err().to_string();
+    assert_eq!(
+        message(&[0x63, 0x61]),
+        "Unexpected end of data in str, expected 2 more bytes"
+    );
+    assert_eq!(message(&[0x01, 0x02]), "Trailing data after the value");
+    assert_eq!(
+        message(&[0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02]),
+        "Duplicate map key \"a\" at offset 4"
+    );
+    assert_eq!(
+        drisl::from_slice::<String>(&[0x01])
+            .unwrap_err()
+            .to_string(),
+        "Unexpected byte 0x01 for str"
+    );
+
+    let err = de::from_slice_with::<Value>(
+        &[0x81, 0x81, 0x80],
+        DecodeOptions::default().max_depth(2).track_path(true),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Nested deeper than the limit of 2 at [0][0]"
+    );
+
+    assert_eq!(
+        drisl::to_vec(&u128::MAX).unwrap_err().to_string(),
+        "Integer is out of the range of CBOR"
+    );
+}
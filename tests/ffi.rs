@@ -0,0 +1,95 @@
+#![cfg(feature = "uniffi")]
+
+use std::collections::HashMap;
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    ffi::{self, CidCodec, CidHash, DrislValue},
+};
+
+#[test]
+fn test_roundtrip() {
+    let value = DrislValue::Map {
+        value: HashMap::from([
+            (
+                "a".to_string(),
+                DrislValue::Array {
+                    value: vec![
+                        DrislValue::Integer { value: 1 },
+                        DrislValue::Float { value: 2.5 },
+                        DrislValue::Text {
+                            value: "x".to_string(),
+                        },
+                        DrislValue::Null,
+                        DrislValue::Bool { value: true },
+                    ],
+                },
+            ),
+            ("b".to_string(), DrislValue::Bytes { value: vec![1, 2] }),
+        ]),
+    };
+    let bytes = ffi::encode(value.clone()).unwrap();
+    assert_eq!(
+        bytes,
+        drisl::to_vec(&drisl!({ "a": [1, 2.5, "x", null, true], "b": vec![1u8, 2] })).unwrap()
+    );
+    assert_eq!(ffi::decode(&bytes).unwrap(), value);
+}
+
+#[test]
+fn test_integers() {
+    let bytes = drisl::to_vec(&drisl!([u64::MAX, -1 - i128::from(u64::MAX), i64::MIN])).unwrap();
+    let value = ffi::decode(&bytes).unwrap();
+    assert_eq!(
+        value,
+        DrislValue::Array {
+            value: vec![
+                DrislValue::Unsigned { value: u64::MAX },
+                DrislValue::Negative { value: u64::MAX },
+                DrislValue::Integer { value: i64::MIN },
+            ],
+        }
+    );
+    assert_eq!(ffi::encode(value).unwrap(), bytes);
+}
+
+#[test]
+fn test_errors() {
+    assert!(ffi::encode(DrislValue::Float { value: f64::NAN }).is_err());
+    assert!(ffi::decode(&[0x82, 0x01]).is_err());
+    // 1 as one-byte unsigned integer
+    assert!(ffi::validate(&[0x18, 0x01]).is_err());
+    assert!(ffi::validate(&[0x01]).is_ok());
+}
+
+#[test]
+fn test_cid() {
+    let expected = Cid::digest_blake3(Codec::Drisl, b"hello");
+    let cid = ffi::Cid::digest(b"hello", CidCodec::Drisl, CidHash::Blake3);
+    assert_eq!(cid.to_bytes(), expected.as_bytes());
+    assert_eq!(cid.codec(), CidCodec::Drisl);
+    assert_eq!(cid.hash_type(), CidHash::Blake3);
+    assert_eq!(cid.hash(), expected.hash());
+    assert!(cid.verify(b"hello"));
+    assert!(!cid.verify(b"world"));
+
+    assert_eq!(cid.to_string(), expected.to_string());
+    assert_eq!(ffi::Cid::parse(&cid.to_string()).unwrap(), cid);
+    let base58 = cid.to_string_with_base("z").unwrap();
+    assert_eq!(ffi::Cid::parse(&base58).unwrap(), cid);
+    assert!(cid.to_string_with_base("?").is_err());
+    assert_eq!(ffi::Cid::from_bytes(&cid.to_bytes()).unwrap(), cid);
+    assert!(ffi::Cid::parse("nope").is_err());
+
+    let value = DrislValue::Map {
+        value: HashMap::from([("link".to_string(), DrislValue::Cid { value: cid.clone() })]),
+    };
+    let bytes = ffi::encode(value.clone()).unwrap();
+    assert_eq!(ffi::decode(&bytes).unwrap(), value);
+    let cid = ffi::cid_of(value, CidHash::Sha2256).unwrap();
+    assert_eq!(
+        cid.to_bytes(),
+        Cid::digest_sha2(Codec::Drisl, bytes).as_bytes()
+    );
+}
@@ -0,0 +1,152 @@
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{CanonicityRule, NodeKind, inspect},
+};
+
+#[test]
+fn test_inspect_structure() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let bytes = drisl::to_vec(&drisl!({ "a": [1.5, null], "cid": cid })).unwrap();
+    let nodes = inspect(&bytes);
+    let kinds: Vec<_> = nodes.iter().map(|node| node.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            NodeKind::Map { len: Some(2) },
+            NodeKind::Text { len: Some(1) },
+            NodeKind::Array { len: Some(2) },
+            NodeKind::Float(1.5),
+            NodeKind::Null,
+            NodeKind::Text { len: Some(3) },
+            NodeKind::Tag(42),
+            NodeKind::Bytes { len: Some(37) },
+        ]
+    );
+    let depths: Vec<_> = nodes.iter().map(|node| node.depth).collect();
+    assert_eq!(depths, [0, 1, 1, 2, 2, 1, 1, 2]);
+    let parents: Vec<_> = nodes.iter().map(|node| node.parent).collect();
+    assert_eq!(
+        parents,
+        [
+            None,
+            Some(0),
+            Some(0),
+            Some(2),
+            Some(2),
+            Some(0),
+            Some(0),
+            Some(6)
+        ]
+    );
+    assert!(nodes.iter().all(|node| node.issues.is_empty()));
+
+    // Ranges map the nodes back to their bytes.
+    assert_eq!(nodes[0].range, 0..bytes.len());
+    assert_eq!(nodes[2].range, 3..14);
+    assert_eq!(nodes[3].header, 4..13);
+    assert_eq!(nodes[6].range, 18..bytes.len());
+    let content = nodes[7].content();
+    assert_eq!(Cid::from_bytes(&bytes[content]).unwrap(), cid);
+}
+
+#[test]
+fn test_inspect_concatenated() {
+    let mut bytes = drisl::to_vec(&[1]).unwrap();
+    bytes.extend(drisl::to_vec("foo").unwrap());
+    let nodes = inspect(&bytes);
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(nodes[2].range, 2..6);
+    assert_eq!(nodes[2].depth, 0);
+    assert_eq!(nodes[2].parent, None);
+}
+
+#[test]
+fn test_inspect_issues() {
+    let issues = |bytes: &[u8]| -> Vec<Vec<CanonicityRule>> {
+        inspect(bytes).into_iter().map(|node| node.issues).collect()
+    };
+    // {"b": 1, "a": 1, "a": 1}
+    assert_eq!(
+        issues(&[0xa3, 0x61, 0x62, 0x01, 0x61, 0x61, 0x01, 0x61, 0x61, 0x01]),
+        [
+            vec![],
+            vec![],
+            vec![],
+            vec![CanonicityRule::UnsortedKeys],
+            vec![],
+            vec![CanonicityRule::DuplicateKey],
+            vec![],
+        ]
+    );
+    // {1: 2.5 as float32}
+    assert_eq!(
+        issues(&[0xa1, 0x01, 0xfa, 0x40, 0x20, 0x00, 0x00]),
+        [
+            vec![],
+            vec![CanonicityRule::NonStringKey],
+            vec![CanonicityRule::NonDoubleFloat],
+        ]
+    );
+    // Tag 1 of undefined.
+    assert_eq!(
+        issues(&[0xc1, 0xf7]),
+        [
+            vec![CanonicityRule::UnsupportedTag],
+            vec![CanonicityRule::UnsupportedSimple],
+        ]
+    );
+
+    // Indefinite lengths end with a break.
+    let nodes = inspect(&[0x9f, 0x01, 0x5f, 0x41, 0x00, 0xff, 0xff]);
+    let kinds: Vec<_> = nodes.iter().map(|node| node.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            NodeKind::Array { len: None },
+            NodeKind::Unsigned(1),
+            NodeKind::Bytes { len: None },
+            NodeKind::Bytes { len: Some(1) },
+            NodeKind::Break,
+            NodeKind::Break,
+        ]
+    );
+    assert_eq!(nodes[0].range, 0..7);
+    assert_eq!(nodes[2].range, 2..6);
+    assert_eq!(nodes[0].issues, [CanonicityRule::IndefiniteLength]);
+}
+
+#[test]
+fn test_inspect_invalid() {
+    // An array of two items that ends after the first.
+    let nodes = inspect(&[0x82, 0x01]);
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(nodes[0].range, 0..2);
+    assert_eq!(nodes[2].kind, NodeKind::Invalid);
+    assert_eq!(nodes[2].range, 2..2);
+    assert_eq!(nodes[2].issues, [CanonicityRule::Truncated]);
+
+    // A string that is longer than the data.
+    let nodes = inspect(&[0x01, 0x63, 0x61]);
+    assert_eq!(nodes[1].kind, NodeKind::Invalid);
+    assert_eq!(nodes[1].range, 1..3);
+
+    // A break outside of an indefinite length item.
+    let nodes = inspect(&[0x81, 0xff]);
+    assert_eq!(nodes[1].kind, NodeKind::Invalid);
+    assert_eq!(nodes[1].issues, [CanonicityRule::InvalidHead]);
+}
+
+#[test]
+fn test_inspect_half_floats() {
+    let float = |bytes: [u8; 2]| inspect(&[0xf9, bytes[0], bytes[1]])[0].kind;
+    assert_eq!(float([0x3c, 0x00]), NodeKind::Float(1.0));
+    assert_eq!(float([0xc4, 0x00]), NodeKind::Float(-4.0));
+    assert_eq!(float([0x7b, 0xff]), NodeKind::Float(65504.0));
+    assert_eq!(float([0x00, 0x01]), NodeKind::Float(5.960464477539063e-8));
+    assert_eq!(float([0x7c, 0x00]), NodeKind::Float(f64::INFINITY));
+    let NodeKind::Float(nan) = float([0x7e, 0x00]) else {
+        panic!("not a float");
+    };
+    assert!(nan.is_nan());
+}
@@ -76,12 +76,7 @@ fn test_integer_range() {
 fn test_map_keys() {
     let results = run_test_group("map_keys.json");
 
-    process_results(
-        results,
-        &[
-            "map keys in incorrect order", // TODO: needs fixing
-        ],
-    );
+    process_results(results, &[]);
 }
 
 #[test]
@@ -305,7 +300,11 @@ fn invalid_encode(b: &[u8]) -> (bool, String) {
     let obj: ciborium::Value = ciborium::from_reader(std::io::Cursor::new(b))
         .expect("general CBOR library failed to decode test input");
     dbg!(&obj);
-    let drisl_obj = match cbor_value_to_drisl(obj) {
+    #[cfg(feature = "ciborium")]
+    let drisl_obj = DrislValue::try_from(obj).map_err(|e| e.to_string());
+    #[cfg(not(feature = "ciborium"))]
+    let drisl_obj = cbor_value_to_drisl(obj);
+    let drisl_obj = match drisl_obj {
         Ok(obj) => obj,
         Err(e) => return (true, e),
     };
@@ -321,8 +320,9 @@ fn invalid_encode(b: &[u8]) -> (bool, String) {
     }
 }
 
+/// Without the `ciborium` feature, there's no `TryFrom<ciborium::Value>` for `Value`.
+#[cfg(not(feature = "ciborium"))]
 fn cbor_value_to_drisl(value: ciborium::Value) -> Result<DrislValue, String> {
-    dbg!(&value);
     match value {
         ciborium::Value::Integer(i) => Ok(DrislValue::Integer(i.into())),
         ciborium::Value::Bytes(b) => Ok(DrislValue::Bytes(b)),
@@ -0,0 +1,78 @@
+#![cfg(feature = "ipld")]
+
+use std::collections::BTreeMap;
+
+use dasl::{
+    cid::{Cid, Codec, UnsupportedCidError},
+    drisl,
+    drisl::Value,
+};
+use ipld_core::{cid::Cid as IpldCid, cid::multihash::Multihash, ipld::Ipld};
+
+fn sample() -> (Value, Ipld) {
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let value = drisl!({
+        "int": -18446744073709551616i128,
+        "float": 1.5,
+        "bytes": vec![1u8, 2, 3],
+        "text": "hello",
+        "bool": true,
+        "null": null,
+        "link": cid,
+        "list": [1, [cid]],
+    });
+    let ipld = Ipld::Map(BTreeMap::from([
+        ("int".to_string(), Ipld::Integer(-18446744073709551616)),
+        ("float".to_string(), Ipld::Float(1.5)),
+        ("bytes".to_string(), Ipld::Bytes(vec![1, 2, 3])),
+        ("text".to_string(), Ipld::String("hello".to_string())),
+        ("bool".to_string(), Ipld::Bool(true)),
+        ("null".to_string(), Ipld::Null),
+        ("link".to_string(), Ipld::Link(cid.into())),
+        (
+            "list".to_string(),
+            Ipld::List(vec![
+                Ipld::Integer(1),
+                Ipld::List(vec![Ipld::Link(cid.into())]),
+            ]),
+        ),
+    ]));
+    (value, ipld)
+}
+
+#[test]
+fn test_ipld_conversion() {
+    let (value, ipld) = sample();
+    assert_eq!(Ipld::from(value.clone()), ipld);
+    assert_eq!(Value::try_from(ipld).unwrap(), value);
+}
+
+#[test]
+fn test_ipld_unsupported_cid() {
+    // A SHA2-512 hash isn't supported by this crate.
+    let sha512 = IpldCid::new_v1(0x71, Multihash::wrap(0x13, &[0; 64]).unwrap());
+    assert_eq!(
+        Value::try_from(Ipld::List(vec![Ipld::Link(sha512)])),
+        Err(UnsupportedCidError::Multihash(0x13))
+    );
+}
+
+#[test]
+fn test_ipld_serde() {
+    let (value, ipld) = sample();
+    let bytes = drisl::to_vec(&value).unwrap();
+
+    // `Ipld` and the CIDs of the `cid` crate use the same Serde representation of CIDs.
+    assert_eq!(drisl::to_vec(&ipld).unwrap(), bytes);
+    assert_eq!(drisl::from_slice::<Ipld>(&bytes).unwrap(), ipld);
+    assert_eq!(drisl::to_value(&ipld).unwrap(), value);
+    assert_eq!(drisl::from_value::<Ipld>(value.clone()).unwrap(), ipld);
+
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let encoded = drisl::to_vec(&cid).unwrap();
+    assert_eq!(drisl::to_vec(&IpldCid::from(cid)).unwrap(), encoded);
+    assert_eq!(
+        drisl::from_slice::<IpldCid>(&encoded).unwrap(),
+        IpldCid::from(cid)
+    );
+}
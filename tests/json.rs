@@ -0,0 +1,110 @@
+#![cfg(feature = "json")]
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{
+        Value,
+        json::{BytesPolicy, IntegerPolicy, JsonError, JsonOptions},
+    },
+};
+use serde_json::json;
+
+#[test]
+fn test_to_json() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let value = drisl!({
+        "int": -3,
+        "big": u64::MAX,
+        "float": 1.5,
+        "text": "hi",
+        "bool": true,
+        "null": null,
+        "cid": cid,
+        "array": [1, [2]],
+        "bytes": vec![0u8, 255],
+    });
+    let json = serde_json::Value::try_from(&value).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "int": -3,
+            "big": u64::MAX,
+            "float": 1.5,
+            "text": "hi",
+            "bool": true,
+            "null": null,
+            "cid": cid.to_string(),
+            "array": [1, [2]],
+            "bytes": "AP8=",
+        })
+    );
+}
+
+#[test]
+fn test_to_json_policies() {
+    let bytes = Value::Bytes(vec![0, 255]);
+    let options = JsonOptions::new().bytes(BytesPolicy::Array);
+    assert_eq!(
+        drisl::json::to_json_with(&bytes, options).unwrap(),
+        json!([0, 255])
+    );
+    let options = JsonOptions::new().bytes(BytesPolicy::Reject);
+    assert_eq!(
+        drisl::json::to_json_with(&bytes, options),
+        Err(JsonError::Bytes)
+    );
+
+    let int = Value::Integer(-(1 << 64));
+    assert_eq!(
+        serde_json::Value::try_from(int.clone()),
+        Err(JsonError::IntegerRange(-(1 << 64)))
+    );
+    let options = JsonOptions::new().integers(IntegerPolicy::String);
+    assert_eq!(
+        drisl::json::to_json_with(&int, options).unwrap(),
+        json!("-18446744073709551616")
+    );
+    let options = JsonOptions::new().integers(IntegerPolicy::Float);
+    assert_eq!(
+        drisl::json::to_json_with(&int, options).unwrap(),
+        json!(-18446744073709551616.0)
+    );
+
+    assert!(matches!(
+        drisl::json::to_json(&Value::Float(f64::NAN)),
+        Err(JsonError::NonFiniteFloat(_))
+    ));
+}
+
+#[test]
+fn test_from_json() {
+    let json = json!({
+        "int": -3,
+        "big": u64::MAX,
+        "float": 1.0,
+        "text": "hi",
+        "bool": false,
+        "null": null,
+        "array": [1, {"a": []}],
+    });
+    assert_eq!(
+        Value::from(json),
+        drisl!({
+            "int": -3,
+            "big": u64::MAX,
+            "float": 1.0,
+            "text": "hi",
+            "bool": false,
+            "null": null,
+            "array": [1, {"a": []}],
+        })
+    );
+}
+
+#[test]
+fn test_from_json_duplicate_keys() {
+    // Other formats leave duplicate keys to the map, the last one wins.
+    let value: Value = serde_json::from_str(r#"{"a": 1, "b": 2, "a": 3}"#).unwrap();
+    assert_eq!(value, drisl!({"a": 3, "b": 2}));
+}
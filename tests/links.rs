@@ -0,0 +1,76 @@
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{DecodeError, links, to_vec},
+};
+
+fn collect(bytes: &[u8]) -> Vec<Cid> {
+    links(bytes).collect::<Result<_, _>>().unwrap()
+}
+
+#[test]
+fn test_links() {
+    let foo = Cid::digest_sha2(Codec::Raw, b"foo");
+    let bar = Cid::digest_blake3(Codec::Drisl, b"bar");
+    let empty = Cid::empty_sha2_256(Codec::Raw);
+
+    let value = drisl!({
+        "a": foo,
+        "nested": { "list": [1, -2, 1.5, null, true, bar, { "deep": [[empty]] }] },
+        // Looks like a tagged CID, but is just bytes.
+        "bytes": vec![0xd8u8, 0x2a, 0x58, 0x25],
+        "text": "a somewhat longer string that needs a one byte length",
+    });
+    let bytes = to_vec(&value).unwrap();
+    assert_eq!(collect(&bytes), [foo, bar, empty]);
+
+    assert_eq!(collect(&to_vec(&foo).unwrap()), [foo]);
+    assert_eq!(collect(&to_vec(&drisl!([])).unwrap()), []);
+    assert_eq!(collect(&to_vec(&drisl!("no links")).unwrap()), []);
+}
+
+#[test]
+fn test_links_errors() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let bytes = to_vec(&drisl!([cid, cid])).unwrap();
+
+    // Links before the error are still returned.
+    let mut truncated = links(&bytes[..bytes.len() - 1]);
+    assert_eq!(truncated.next().unwrap().unwrap(), cid);
+    assert!(matches!(
+        truncated.next(),
+        Some(Err(DecodeError::Eof { .. }))
+    ));
+    assert!(truncated.next().is_none());
+
+    let mut trailing = bytes.clone();
+    trailing.push(0x00);
+    assert!(matches!(
+        links(&trailing).last(),
+        Some(Err(DecodeError::TrailingData))
+    ));
+
+    // Indefinite length array.
+    assert!(matches!(
+        links(&[0x9f, 0xff]).next(),
+        Some(Err(DecodeError::IndefiniteSize))
+    ));
+    // Tag other than 42.
+    assert!(matches!(
+        links(&[0xc1, 0x00]).next(),
+        Some(Err(DecodeError::Mismatch { .. }))
+    ));
+    // Tag 42 around something other than bytes.
+    assert!(links(&[0xd8, 0x2a, 0x60]).next().unwrap().is_err());
+    // Tag 42 around bytes that are no CID.
+    assert!(links(&[0xd8, 0x2a, 0x41, 0x00]).next().unwrap().is_err());
+    // Array claiming more items than there are bytes.
+    assert!(matches!(
+        links(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]).next(),
+        Some(Err(DecodeError::Eof { .. }))
+    ));
+    assert!(matches!(
+        links(&[]).next(),
+        Some(Err(DecodeError::Eof { .. }))
+    ));
+}
@@ -0,0 +1,83 @@
+use std::collections::BTreeMap;
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{Value, from_slice, to_vec},
+};
+
+#[test]
+fn test_drisl_scalars() {
+    assert_eq!(drisl!(null), Value::Null);
+    assert_eq!(drisl!(true), Value::Bool(true));
+    assert_eq!(drisl!(false), Value::Bool(false));
+    assert_eq!(drisl!(42), Value::Integer(42));
+    assert_eq!(drisl!(-7i8), Value::Integer(-7));
+    assert_eq!(drisl!(u64::MAX), Value::Integer(u64::MAX.into()));
+    assert_eq!(drisl!(1.5), Value::Float(1.5));
+    assert_eq!(drisl!("hello"), Value::Text("hello".to_string()));
+    assert_eq!(drisl!(b"hello".as_slice()), Value::Bytes(b"hello".to_vec()));
+}
+
+#[test]
+fn test_drisl_empty() {
+    assert_eq!(drisl!([]), Value::Array(Vec::new()));
+    assert_eq!(drisl!({}), Value::Map(BTreeMap::new()));
+}
+
+#[test]
+fn test_drisl_array() {
+    assert_eq!(
+        drisl!([1, "two", null, [true], {}]),
+        Value::Array(vec![
+            Value::Integer(1),
+            Value::Text("two".to_string()),
+            Value::Null,
+            Value::Array(vec![Value::Bool(true)]),
+            Value::Map(BTreeMap::new()),
+        ])
+    );
+    // Trailing commas are accepted.
+    assert_eq!(drisl!([1,]), Value::Array(vec![Value::Integer(1)]));
+}
+
+#[test]
+fn test_drisl_map() {
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let key = "dynamic";
+    let inner = drisl!({ "x": 1 });
+
+    let value = drisl!({
+        "cid": cid,
+        "nested": { "list": [1, 2], "none": None::<u32> },
+        key: "value",
+        "inner": inner.clone(),
+        "sum": 1 + 2,
+    });
+
+    let mut nested = BTreeMap::new();
+    nested.insert(
+        "list".to_string(),
+        Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
+    );
+    nested.insert("none".to_string(), Value::Null);
+    let mut expected = BTreeMap::new();
+    expected.insert("cid".to_string(), Value::Cid(cid));
+    expected.insert("nested".to_string(), Value::Map(nested));
+    expected.insert("dynamic".to_string(), Value::Text("value".to_string()));
+    expected.insert("inner".to_string(), inner);
+    expected.insert("sum".to_string(), Value::Integer(3));
+    assert_eq!(value, Value::Map(expected));
+}
+
+#[test]
+fn test_drisl_roundtrip() {
+    let value = drisl!({
+        "link": Cid::digest_blake3(Codec::Raw, b"bar"),
+        "bytes": vec![0u8, 1, 2],
+        "float": 0.25,
+    });
+    let encoded = to_vec(&value).unwrap();
+    let decoded: Value = from_slice(&encoded).unwrap();
+    assert_eq!(decoded, value);
+}
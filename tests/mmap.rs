@@ -0,0 +1,81 @@
+#![cfg(feature = "mmap")]
+
+use std::path::PathBuf;
+
+use dasl::drisl::{self, DecodeError, de::MmapFile};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record<'a> {
+    name: &'a str,
+    #[serde(with = "serde_bytes")]
+    data: &'a [u8],
+}
+
+fn write_file(name: &str, contents: &[u8]) -> PathBuf {
+    let dir = std::env::temp_dir().join("dasl-tests-mmap");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_mmap_decode() {
+    let record = Record {
+        name: "foo",
+        data: &[1, 2, 3],
+    };
+    let path = write_file("decode.drisl", &drisl::to_vec(&record).unwrap());
+
+    // SAFETY: The test files aren't modified after they're written.
+    let file = unsafe { MmapFile::open(&path) }.unwrap();
+    let decoded: Record = file.decode().unwrap();
+    assert_eq!(decoded, record);
+    // Strings and byte strings are borrowed from the mapping.
+    assert!(
+        file.as_bytes()
+            .as_ptr_range()
+            .contains(&decoded.data.as_ptr())
+    );
+
+    let value: drisl::Value = unsafe { drisl::from_file_mmap(&path) }.unwrap();
+    assert_eq!(value, drisl::to_value(&record).unwrap());
+
+    let trailing = write_file("trailing.drisl", &[0x01, 0x02]);
+    assert!(matches!(
+        unsafe { drisl::de::from_file_mmap::<u8>(&trailing) },
+        Err(DecodeError::TrailingData)
+    ));
+    assert!(matches!(
+        unsafe { drisl::de::from_file_mmap::<u8>(trailing.with_extension("missing")) },
+        Err(DecodeError::Read(_))
+    ));
+}
+
+#[test]
+fn test_mmap_iter() {
+    let mut contents = Vec::new();
+    for i in 0..10u8 {
+        contents.extend(
+            drisl::to_vec(&Record {
+                name: "record",
+                data: &[i],
+            })
+            .unwrap(),
+        );
+    }
+    let path = write_file("iter.drisl", &contents);
+
+    // SAFETY: The test files aren't modified after they're written.
+    let file = unsafe { MmapFile::open(&path) }.unwrap();
+    let records: Vec<(Record, usize)> = file.iter().collect::<Result<_, _>>().unwrap();
+    assert_eq!(records.len(), 10);
+    for (i, (record, len)) in records.iter().enumerate() {
+        assert_eq!(record.data, [i as u8]);
+        assert_eq!(*len, contents.len() / 10);
+    }
+
+    let empty = unsafe { MmapFile::open(write_file("empty.drisl", &[])) }.unwrap();
+    assert_eq!(empty.iter::<Record>().count(), 0);
+}
@@ -0,0 +1,128 @@
+use dasl::multibase::{self, Multibase, MultibaseError, base32};
+
+#[test]
+fn test_multibase_roundtrip() {
+    for (base, data, encoded) in [
+        (Multibase::Base32Lower, &b"hello"[..], "bnbswy3dp"),
+        (Multibase::Base58Btc, b"Hello World!", "z2NEpo7TZRRrLZSi2U"),
+        (Multibase::Base16Lower, b"\x01\xab", "f01ab"),
+        (Multibase::Base64Url, b"hello?", "uaGVsbG8_"),
+        (Multibase::Base32Lower, b"", "b"),
+    ] {
+        assert_eq!(multibase::encode(base, data), encoded);
+        assert_eq!(base.encode(data), encoded[1..]);
+        assert_eq!(multibase::decode(encoded).unwrap(), (base, data.to_vec()));
+        assert_eq!(base.decode(&encoded[1..]).unwrap(), data);
+    }
+}
+
+#[test]
+fn test_multibase_errors() {
+    assert_eq!(multibase::decode(""), Err(MultibaseError::MissingPrefix));
+    assert_eq!(
+        multibase::decode("xabc"),
+        Err(MultibaseError::UnknownPrefix('x'))
+    );
+    assert_eq!(
+        multibase::decode("babc"),
+        Err(MultibaseError::InvalidLength)
+    );
+    assert_eq!(
+        multibase::decode("bab1a"),
+        Err(MultibaseError::InvalidEncoding)
+    );
+    assert_eq!(
+        multibase::decode("z0"),
+        Err(MultibaseError::InvalidEncoding)
+    );
+    assert_eq!(
+        multibase::decode("fabc"),
+        Err(MultibaseError::InvalidLength)
+    );
+    assert_eq!(
+        multibase::decode("fxx"),
+        Err(MultibaseError::InvalidEncoding)
+    );
+}
+
+#[test]
+fn test_base32_slices() {
+    let data = b"hello world";
+    let mut chars = [0; 64];
+    let len = base32::encode_to_slice(data, &mut chars).unwrap();
+    assert_eq!(len, base32::encoded_len(data.len()));
+    assert_eq!(&chars[..len], base32::encode(data).as_bytes());
+
+    let mut bytes = [0; 64];
+    let decoded = base32::decode_to_slice(&chars[..len], &mut bytes).unwrap();
+    assert_eq!(decoded, base32::decoded_len(len).unwrap());
+    assert_eq!(&bytes[..decoded], data);
+
+    assert_eq!(
+        base32::encode_to_slice(data, &mut chars[..10]),
+        Err(MultibaseError::BufferTooSmall { needed: 18 })
+    );
+    assert_eq!(
+        base32::decode_to_slice(&chars[..len], &mut bytes[..10]),
+        Err(MultibaseError::BufferTooSmall { needed: 11 })
+    );
+    assert_eq!(base32::decoded_len(3), Err(MultibaseError::InvalidLength));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_base32_streaming() {
+    use std::io::{self, Read, Write};
+
+    /// Returns the data one byte at a time.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some((&first, rest)) = self.0.split_first() else {
+                return Ok(0);
+            };
+            if buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = first;
+            self.0 = rest;
+            Ok(1)
+        }
+    }
+
+    let data: Vec<u8> = (0..5000u32).map(|i| (i * 7 % 251) as u8).collect();
+    for split in [0, 1, 3, 7, 640, 4999] {
+        let mut encoder = base32::Encoder::new(Vec::new());
+        encoder.write_all(&data[..split]).unwrap();
+        for chunk in data[split..].chunks(13) {
+            encoder.write_all(chunk).unwrap();
+        }
+        let encoded = encoder.finish().unwrap();
+        assert_eq!(encoded, base32::encode(&data).as_bytes());
+
+        let mut decoded = Vec::new();
+        base32::Decoder::new(&encoded[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+
+        let mut decoded = Vec::new();
+        base32::Decoder::new(Trickle(&encoded))
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    // Errors are reported as invalid data, also for an incomplete block at the end.
+    for input in [&b"nbswy3dp1"[..], b"nbswy3dpabc", &[b'a'; 2049][..]] {
+        let err = base32::Decoder::new(input)
+            .read_to_end(&mut Vec::new())
+            .unwrap_err();
+        assert_eq!(
+            err.kind(),
+            io::ErrorKind::InvalidData,
+            "for input {input:?}"
+        );
+    }
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "rayon")]
+
+use dasl::drisl::{DecodeError, Value, de, to_vec};
+use rayon::prelude::*;
+
+fn records(count: u64) -> (Vec<Value>, Vec<u8>) {
+    let values: Vec<Value> = (0..count)
+        .map(|i| dasl::drisl!({ "id": i, "name": format!("record {i}"), "tags": [i % 3] }))
+        .collect();
+    let bytes = values.iter().flat_map(|v| to_vec(v).unwrap()).collect();
+    (values, bytes)
+}
+
+#[test]
+fn test_par_iter_from_slice() {
+    let (values, bytes) = records(1000);
+    let decoded: Vec<Value> = de::par_iter_from_slice(&bytes)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(decoded, values);
+
+    // The same as decoding sequentially.
+    let sequential: Vec<Value> = de::iter_from_slice(&bytes)
+        .map(|value| value.map(|(value, _)| value))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(decoded, sequential);
+
+    let empty = de::par_iter_from_slice::<Value>(&[]).unwrap();
+    assert_eq!(empty.count(), 0);
+
+    // Borrowing from the slice.
+    let mut bytes = to_vec("foo").unwrap();
+    bytes.extend(to_vec("bar").unwrap());
+    let strings: Vec<&str> = de::par_iter_from_slice(&bytes)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(strings, ["foo", "bar"]);
+}
+
+#[test]
+fn test_par_iter_errors() {
+    let (_, mut bytes) = records(10);
+
+    // Values that fail to deserialize only fail their own item.
+    let results: Vec<Result<u8, _>> = de::par_iter_from_slice(&bytes).unwrap().collect();
+    assert_eq!(results.len(), 10);
+    assert!(results.iter().all(Result::is_err));
+
+    // The options apply to each value.
+    let options = de::DecodeOptions::new().max_depth(1);
+    let results: Vec<Result<Value, _>> = de::par_iter_from_slice_with(&bytes, options)
+        .unwrap()
+        .collect();
+    assert!(matches!(
+        results[0],
+        Err(DecodeError::DepthLimit { limit: 1 })
+    ));
+
+    // Incomplete data can't be split.
+    bytes.pop();
+    assert!(matches!(
+        de::par_iter_from_slice::<Value>(&bytes),
+        Err(DecodeError::Eof { .. })
+    ));
+    bytes.push(0x1f);
+    assert!(de::par_iter_from_slice::<Value>(&bytes).is_err());
+}
+
+#[test]
+fn test_par_iter_from_offsets() {
+    let (values, bytes) = records(100);
+    let offsets = de::value_offsets(&bytes).unwrap();
+    assert_eq!(offsets.len(), 100);
+    assert_eq!(offsets[0], 0);
+
+    let decoded: Vec<Value> =
+        de::par_iter_from_offsets(&bytes, &offsets, de::DecodeOptions::default())
+            .collect::<Result<_, _>>()
+            .unwrap();
+    assert_eq!(decoded, values);
+
+    // Offsets that aren't value boundaries fail the affected values.
+    let offsets = [offsets[0], offsets[1] + 1, bytes.len() + 1];
+    let results: Vec<Result<Value, _>> =
+        de::par_iter_from_offsets(&bytes, offsets, de::DecodeOptions::default()).collect();
+    assert!(matches!(results[0], Err(DecodeError::TrailingData)));
+    assert!(results[1].is_err());
+    assert!(matches!(&results[2], Err(DecodeError::Msg(msg)) if msg.contains("offsets")));
+}
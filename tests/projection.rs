@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{
+        DecodeError, Value,
+        de::{DecodeOptions, Projection},
+        to_vec,
+    },
+};
+use serde::Deserialize;
+
+fn post() -> Vec<u8> {
+    to_vec(&drisl!({
+        "title": "Hello",
+        "body": "A long text",
+        "author": { "name": "alice", "bio": "…", "links": [1, 2, 3] },
+        "tags": [{ "name": "a", "id": 1 }, { "name": "b", "id": 2 }],
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_projection_value() {
+    let bytes = post();
+    let value: Value = Projection::new(["title", "author/name", "tags/*/id"])
+        .from_slice(&bytes)
+        .unwrap();
+    assert_eq!(
+        value,
+        drisl!({
+            "title": "Hello",
+            "author": { "name": "alice" },
+            "tags": [{ "id": 1 }, { "id": 2 }],
+        })
+    );
+}
+
+#[test]
+fn test_projection_whole_subtree() {
+    let bytes = post();
+    let value: Value = Projection::new(["author"]).from_slice(&bytes).unwrap();
+    assert_eq!(
+        value,
+        drisl!({ "author": { "name": "alice", "bio": "…", "links": [1, 2, 3] } })
+    );
+
+    let value: Value = Projection::new([""]).from_slice(&bytes).unwrap();
+    assert_eq!(value, drisl::from_slice::<Value>(&bytes).unwrap());
+}
+
+#[test]
+fn test_projection_array_index() {
+    let bytes = post();
+    let value: Value = Projection::new(["tags/1/name", "author/links/0"])
+        .from_slice(&bytes)
+        .unwrap();
+    assert_eq!(
+        value,
+        drisl!({
+            "author": { "links": [1] },
+            "tags": [{ "name": "b" }],
+        })
+    );
+}
+
+#[test]
+fn test_projection_missing_path() {
+    let bytes = post();
+    let value: Value = Projection::new(["nope", "title/deeper"])
+        .from_slice(&bytes)
+        .unwrap();
+    assert_eq!(value, drisl!({ "title": "Hello" }));
+
+    let value: Value = Projection::new(["nope"]).from_slice(&bytes).unwrap();
+    assert_eq!(value, Value::Map(BTreeMap::new()));
+}
+
+#[test]
+fn test_projection_struct() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Author<'a> {
+        name: &'a str,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Post<'a> {
+        title: &'a str,
+        #[serde(borrow)]
+        author: Author<'a>,
+        #[serde(default)]
+        body: Option<String>,
+    }
+
+    let bytes = post();
+    let post: Post = Projection::new(["title", "author/name"])
+        .from_slice(&bytes)
+        .unwrap();
+    assert_eq!(
+        post,
+        Post {
+            title: "Hello",
+            author: Author { name: "alice" },
+            body: None,
+        }
+    );
+}
+
+#[test]
+fn test_projection_cid() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let bytes = to_vec(&drisl!({ "link": cid, "other": cid })).unwrap();
+    let value: Value = Projection::new(["link"]).from_slice(&bytes).unwrap();
+    assert_eq!(value, drisl!({ "link": cid }));
+}
+
+#[test]
+fn test_projection_errors() {
+    let mut bytes = post();
+    bytes.push(0x00);
+    let result: Result<Value, _> = Projection::new(["title"]).from_slice(&bytes);
+    assert!(matches!(result, Err(DecodeError::TrailingData)));
+
+    // Limits apply to skipped values as well.
+    let bytes = to_vec(&drisl!({ "a": 1, "b": [[[[1]]]] })).unwrap();
+    let result: Result<Value, _> =
+        Projection::new(["a"]).from_slice_with(&bytes, DecodeOptions::default().max_depth(3));
+    assert!(matches!(result, Err(DecodeError::DepthLimit { .. })));
+}
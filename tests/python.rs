@@ -0,0 +1,144 @@
+#![cfg(feature = "python")]
+
+use std::ffi::CStr;
+
+use dasl::{
+    car::CarWriter,
+    cid::{Cid, Codec},
+    drisl,
+};
+use pyo3::{prelude::*, types::PyDict, wrap_pymodule};
+
+/// Runs `script` with the `dasl` module and `car`, the bytes of a CAR file, in scope.
+fn run(script: &CStr) {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let mut writer = CarWriter::new(Vec::new(), vec![cid]).unwrap();
+    writer.write_block(&cid, b"hello").unwrap();
+    let car = writer.finish().unwrap();
+
+    Python::initialize();
+    Python::attach(|py| {
+        let globals = PyDict::new(py);
+        globals
+            .set_item("dasl", wrap_pymodule!(dasl::python::dasl)(py))
+            .unwrap();
+        globals.set_item("car", car).unwrap();
+        if let Err(err) = py.run(script, Some(&globals), None) {
+            err.display(py);
+            panic!("{err}");
+        }
+    });
+}
+
+#[test]
+fn test_roundtrip() {
+    let expected =
+        drisl::to_vec(&drisl!({ "a": [1, 2.5, "x", null, true], "b": vec![1u8, 2] })).unwrap();
+    Python::initialize();
+    Python::attach(|py| {
+        let module = wrap_pymodule!(dasl::python::dasl)(py);
+        let globals = PyDict::new(py);
+        globals.set_item("dasl", module).unwrap();
+        let bytes: Vec<u8> = py
+            .eval(
+                c"dasl.dumps({'b': b'\\x01\\x02', 'a': (1, 2.5, 'x', None, True)})",
+                Some(&globals),
+                None,
+            )
+            .unwrap()
+            .extract()
+            .unwrap();
+        assert_eq!(bytes, expected);
+    });
+    run(c"
+value = {'a': [1, 2.5, 'x', None, True], 'b': b'\\x01\\x02', 'big': 2**64 - 1, 'neg': -2**64}
+assert dasl.loads(dasl.dumps(value)) == value
+assert type(dasl.loads(dasl.dumps(True))) is bool
+assert dasl.loads(dasl.dumps(bytearray(b'ab'))) == b'ab'
+");
+}
+
+#[test]
+fn test_errors() {
+    run(c"
+def raises(f, error):
+    try:
+        f()
+    except error:
+        return True
+    return False
+
+assert issubclass(dasl.DaslError, ValueError)
+assert raises(lambda: dasl.dumps(float('nan')), dasl.DaslError)
+assert raises(lambda: dasl.dumps(2**64), dasl.DaslError)
+assert raises(lambda: dasl.dumps(2**200), dasl.DaslError)
+assert raises(lambda: dasl.dumps({1: 2}), TypeError)
+assert raises(lambda: dasl.dumps(object()), TypeError)
+cycle = []
+cycle.append(cycle)
+assert raises(lambda: dasl.dumps(cycle), dasl.DaslError)
+assert raises(lambda: dasl.loads(b'\\x82\\x01'), dasl.DaslError)
+# 1 as one-byte unsigned integer
+assert raises(lambda: dasl.validate(b'\\x18\\x01'), dasl.DaslError)
+dasl.validate(b'\\x01')
+");
+}
+
+#[test]
+fn test_cid() {
+    let expected = Cid::digest_blake3(Codec::Drisl, b"hello");
+    let script = format!(
+        "
+cid = dasl.cid_digest(b'hello', codec='drisl', hash='blake3')
+assert str(cid) == '{expected}'
+assert bytes(cid) == bytes.fromhex('{bytes}')
+assert cid.codec == 'drisl'
+assert cid.hash_type == 'blake3'
+assert cid.digest == bytes.fromhex('{hash}')
+assert cid.verify(b'hello')
+assert not cid.verify(b'world')
+assert dasl.Cid(str(cid)) == cid
+assert dasl.Cid(cid.encode('z')) == cid
+assert dasl.Cid.from_bytes(bytes(cid)) == cid
+assert len({{cid, dasl.Cid(str(cid))}}) == 1
+assert repr(cid) == \"Cid('{expected}')\"
+assert dasl.cid_digest(b'hello') == dasl.Cid('{sha2}')
+assert dasl.loads(dasl.dumps({{'link': cid}})) == {{'link': cid}}
+try:
+    dasl.Cid('nope')
+    assert False
+except dasl.DaslError:
+    pass
+\0",
+        bytes = hex::encode(expected.as_bytes()),
+        hash = hex::encode(expected.hash()),
+        sha2 = Cid::digest_sha2(Codec::Raw, b"hello"),
+    );
+    run(CStr::from_bytes_with_nul(script.as_bytes()).unwrap());
+}
+
+#[test]
+fn test_car() {
+    let path = std::env::temp_dir().join(format!("dasl-python-{}.car", std::process::id()));
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let mut writer = CarWriter::new(Vec::new(), vec![cid]).unwrap();
+    writer.write_block(&cid, b"hello").unwrap();
+    std::fs::write(&path, writer.finish().unwrap()).unwrap();
+
+    let script = format!(
+        "
+reader = dasl.CarReader(car)
+assert [str(root) for root in reader.roots] == ['{cid}']
+assert [(str(cid), data) for cid, data in reader] == [('{cid}', b'hello')]
+assert [(str(cid), data) for cid, data in dasl.CarReader.open({path:?})] == [('{cid}', b'hello')]
+try:
+    dasl.CarReader(b'nope')
+    assert False
+except dasl.DaslError:
+    pass
+\0",
+        path = path.to_str().unwrap(),
+    );
+    run(CStr::from_bytes_with_nul(script.as_bytes()).unwrap());
+    std::fs::remove_file(path).unwrap();
+}
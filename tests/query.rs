@@ -0,0 +1,207 @@
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{
+        Value,
+        query::{Query, QueryError},
+    },
+};
+
+fn posts() -> Value {
+    drisl!({
+        "posts": [
+            { "author": "alice", "likes": 3, "score": 0.5, "draft": true },
+            { "author": "bob", "likes": 12, "score": 2.5, "draft": false },
+            { "author": "carol", "likes": 7 },
+        ],
+        "meta": { "$type": "feed", "my key": "spaced" },
+    })
+}
+
+fn eval(query: &str, value: &Value) -> Vec<Value> {
+    query
+        .parse::<Query>()
+        .unwrap()
+        .eval(value)
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn test_query_paths() {
+    let value = posts();
+    assert_eq!(eval(".", &value), std::slice::from_ref(&value));
+    assert_eq!(eval(".posts[0].author", &value), [drisl!("alice")]);
+    assert_eq!(eval(".posts[-1].author", &value), [drisl!("carol")]);
+    assert_eq!(eval(".posts.[1].likes", &value), [drisl!(12)]);
+    assert_eq!(eval(".meta.$type", &value), [drisl!("feed")]);
+    assert_eq!(eval(".meta.\"my key\"", &value), [drisl!("spaced")]);
+    assert_eq!(eval(".meta[\"my key\"]", &value), [drisl!("spaced")]);
+    assert_eq!(eval(".posts[3]", &value), Vec::<Value>::new());
+    assert_eq!(eval(".posts[-4]", &value), Vec::<Value>::new());
+    assert_eq!(eval(".missing.deeper", &value), Vec::<Value>::new());
+}
+
+#[test]
+fn test_query_iterate() {
+    let value = posts();
+    assert_eq!(
+        eval(".posts[].author", &value),
+        [drisl!("alice"), drisl!("bob"), drisl!("carol")]
+    );
+    // Map values are yielded in key order.
+    assert_eq!(eval(".meta[]", &value), [drisl!("feed"), drisl!("spaced")]);
+    assert_eq!(eval(".posts[0].author[]", &value), Vec::<Value>::new());
+}
+
+#[test]
+fn test_query_select() {
+    let value = posts();
+    assert_eq!(
+        eval(".posts[] | select(.likes > 5) | .author", &value),
+        [drisl!("bob"), drisl!("carol")]
+    );
+    assert_eq!(
+        eval(".posts[] | select(.likes <= 3) | .author", &value),
+        [drisl!("alice")]
+    );
+    assert_eq!(
+        eval(".posts[] | select(.author == \"bob\") | .likes", &value),
+        [drisl!(12)]
+    );
+    assert_eq!(
+        eval(".posts[] | select(.author != \"bob\") | .likes", &value),
+        [drisl!(3), drisl!(7)]
+    );
+    assert_eq!(
+        eval(".posts[] | select(.draft == true) | .author", &value),
+        [drisl!("alice")]
+    );
+    // Integers and floats compare numerically.
+    assert_eq!(
+        eval(".posts[] | select(.score >= 1) | .author", &value),
+        [drisl!("bob")]
+    );
+    assert_eq!(
+        eval(".posts[] | select(.likes < 7.5) | .author", &value),
+        [drisl!("alice"), drisl!("carol")]
+    );
+    // Missing fields and mismatched kinds never match.
+    assert_eq!(
+        eval(".posts[] | select(.score > -1) | .author", &value),
+        [drisl!("alice"), drisl!("bob")]
+    );
+    assert_eq!(
+        eval(".posts[] | select(.author > 1)", &value),
+        Vec::<Value>::new()
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_query_reader() {
+    let mut stream = Vec::new();
+    for likes in [1, 20, 30] {
+        stream.extend(drisl::to_vec(&drisl!({ "likes": likes })).unwrap());
+    }
+    let query = Query::parse("select(.likes > 10) | .likes").unwrap();
+    let results = query
+        .eval_reader(&stream[..])
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(results, [drisl!(20), drisl!(30)]);
+
+    // Decoding errors are yielded.
+    let mut results = query.eval_reader(&[0xa1][..]);
+    assert!(results.next().unwrap().is_err());
+}
+
+#[test]
+fn test_query_parse_errors() {
+    assert_eq!(Query::parse(""), Err(QueryError::UnexpectedEnd));
+    assert_eq!(
+        Query::parse("posts"),
+        Err(QueryError::Unexpected {
+            position: 0,
+            found: 'p'
+        })
+    );
+    assert_eq!(Query::parse(".posts["), Err(QueryError::UnexpectedEnd));
+    assert_eq!(
+        Query::parse(".posts[1.5]"),
+        Err(QueryError::InvalidNumber { position: 7 })
+    );
+    assert_eq!(
+        Query::parse("select(.a ~ 1)"),
+        Err(QueryError::Unexpected {
+            position: 10,
+            found: '~'
+        })
+    );
+    assert_eq!(Query::parse(".a |"), Err(QueryError::UnexpectedEnd));
+    assert_eq!(
+        Query::parse("select(.a == \"x)"),
+        Err(QueryError::UnexpectedEnd)
+    );
+    assert_eq!(
+        Query::parse("select(.a | len(\"x\"))"),
+        Err(QueryError::Unexpected {
+            position: 12,
+            found: 'l'
+        })
+    );
+    assert_eq!(
+        Query::parse("select(has(x))"),
+        Err(QueryError::Unexpected {
+            position: 11,
+            found: 'x'
+        })
+    );
+}
+
+#[test]
+fn test_query_select_tests() {
+    let value = posts();
+    assert_eq!(
+        eval(".posts[] | select(has(\"draft\")) | .author", &value),
+        [drisl!("alice"), drisl!("bob")]
+    );
+    assert_eq!(
+        eval("select(.meta | has(\"$type\")) | .meta.$type", &value),
+        [drisl!("feed")]
+    );
+    assert_eq!(
+        eval(
+            ".posts[] | select(.author | contains(\"ro\")) | .likes",
+            &value
+        ),
+        [drisl!(7)]
+    );
+    // Only strings contain strings.
+    assert_eq!(
+        eval(".posts[] | select(.likes | contains(\"1\"))", &value),
+        Vec::<Value>::new()
+    );
+    assert_eq!(
+        eval("select(.posts[] | has(\"missing\"))", &value),
+        Vec::<Value>::new()
+    );
+}
+
+#[test]
+fn test_query_cid() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let other = Cid::digest_sha2(Codec::Raw, b"bar");
+    let value = drisl!([{ "link": cid }, { "link": other }, { "link": cid.to_string() }]);
+    let query = format!(".[] | select(.link == cid(\"{cid}\"))");
+    assert_eq!(eval(&query, &value), [drisl!({ "link": cid })]);
+    let query = format!(".[] | select(.link != cid(\"{cid}\")) | .link");
+    assert_eq!(
+        eval(&query, &value),
+        [drisl!(other), drisl!(cid.to_string())]
+    );
+    assert_eq!(
+        Query::parse("select(.link == cid(\"bafy\"))"),
+        Err(QueryError::InvalidCid { position: 16 })
+    );
+}
@@ -0,0 +1,159 @@
+#![cfg(any(feature = "rasl", feature = "rasl-blocking"))]
+
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+};
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl::{Value, to_vec},
+    rasl::{self, Content, RaslError},
+};
+
+/// Serves the given bodies by path, responds with 404 for unknown paths.
+fn serve(bodies: HashMap<String, Vec<u8>>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let path = request_line.split(' ').nth(1).unwrap_or_default();
+            let response = match bodies.get(path) {
+                Some(body) => {
+                    let mut response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    )
+                    .into_bytes();
+                    response.extend_from_slice(body);
+                    response
+                }
+                None => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_vec(),
+            };
+            stream.write_all(&response).unwrap();
+        }
+    });
+    format!("http://{addr}")
+}
+
+struct Fixture {
+    base_url: String,
+    raw: Cid,
+    drisl: Cid,
+    tampered: Cid,
+    missing: Cid,
+}
+
+fn fixture() -> Fixture {
+    let raw = Cid::digest_sha2(Codec::Raw, b"hello");
+    let drisl_bytes = to_vec(&Value::Text("hello".into())).unwrap();
+    let drisl = Cid::digest_blake3(Codec::Drisl, &drisl_bytes);
+    let tampered = Cid::digest_sha2(Codec::Raw, b"original");
+    let missing = Cid::digest_sha2(Codec::Raw, b"missing");
+
+    let mut bodies = HashMap::new();
+    bodies.insert(format!("/.well-known/rasl/{raw}"), b"hello".to_vec());
+    bodies.insert(format!("/.well-known/rasl/{drisl}"), drisl_bytes);
+    bodies.insert(
+        format!("/.well-known/rasl/{tampered}"),
+        b"tampered".to_vec(),
+    );
+
+    Fixture {
+        base_url: serve(bodies),
+        raw,
+        drisl,
+        tampered,
+        missing,
+    }
+}
+
+#[test]
+fn test_url() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    assert_eq!(
+        rasl::url("http://localhost:8080", &cid),
+        format!("http://localhost:8080/.well-known/rasl/{cid}")
+    );
+    assert_eq!(
+        rasl::url("http://localhost:8080/", &cid),
+        rasl::url("http://localhost:8080", &cid)
+    );
+}
+
+#[cfg(feature = "rasl-blocking")]
+#[test]
+fn test_blocking_fetch() {
+    use dasl::rasl::blocking::Client;
+
+    let fixture = fixture();
+    let client = Client::new();
+    assert_eq!(
+        client.fetch(&fixture.base_url, &fixture.raw).unwrap(),
+        Content::Raw(b"hello".to_vec())
+    );
+    assert_eq!(
+        client.fetch(&fixture.base_url, &fixture.drisl).unwrap(),
+        Content::Drisl(Value::Text("hello".into()))
+    );
+    assert!(matches!(
+        client.fetch(&fixture.base_url, &fixture.tampered),
+        Err(RaslError::HashMismatch { expected, .. }) if expected == fixture.tampered
+    ));
+    assert!(matches!(
+        client.fetch(&fixture.base_url, &fixture.missing),
+        Err(RaslError::Status(404))
+    ));
+    assert!(matches!(
+        client.max_size(4).fetch(&fixture.base_url, &fixture.raw),
+        Err(RaslError::TooLarge(4))
+    ));
+}
+
+#[cfg(feature = "rasl")]
+#[tokio::test]
+async fn test_async_fetch() {
+    use dasl::rasl::Client;
+
+    let fixture = fixture();
+    let client = Client::new();
+    assert_eq!(
+        client.fetch(&fixture.base_url, &fixture.raw).await.unwrap(),
+        Content::Raw(b"hello".to_vec())
+    );
+    assert_eq!(
+        client
+            .fetch(&fixture.base_url, &fixture.drisl)
+            .await
+            .unwrap(),
+        Content::Drisl(Value::Text("hello".into()))
+    );
+    assert!(matches!(
+        client.fetch(&fixture.base_url, &fixture.tampered).await,
+        Err(RaslError::HashMismatch { expected, .. }) if expected == fixture.tampered
+    ));
+    assert!(matches!(
+        client.fetch(&fixture.base_url, &fixture.missing).await,
+        Err(RaslError::Status(404))
+    ));
+    assert!(matches!(
+        client
+            .max_size(4)
+            .fetch(&fixture.base_url, &fixture.raw)
+            .await,
+        Err(RaslError::TooLarge(4))
+    ));
+}
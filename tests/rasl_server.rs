@@ -0,0 +1,163 @@
+#![cfg(feature = "rasl-server")]
+
+use std::sync::Arc;
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{Method, Request, StatusCode, header},
+};
+use dasl::{
+    blockstore::{BlockStore, MemoryBlockStore},
+    cid::{Cid, Codec},
+    drisl::{Value, to_vec},
+    rasl::server,
+};
+use tower::ServiceExt;
+
+async fn request(
+    store: &Arc<MemoryBlockStore>,
+    method: Method,
+    path: String,
+    range: Option<&str>,
+) -> (StatusCode, axum::http::HeaderMap, Vec<u8>) {
+    let mut request = Request::builder().method(method).uri(path);
+    if let Some(range) = range {
+        request = request.header(header::RANGE, range);
+    }
+    let response = server::router(store.clone())
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, headers, body.to_vec())
+}
+
+fn store() -> (Arc<MemoryBlockStore>, Cid, Cid) {
+    let store = MemoryBlockStore::new();
+    let raw = Cid::digest_sha2(Codec::Raw, b"hello world");
+    store.put(&raw, b"hello world").unwrap();
+    let drisl_bytes = to_vec(&Value::Bool(true)).unwrap();
+    let drisl = Cid::digest_blake3(Codec::Drisl, &drisl_bytes);
+    store.put(&drisl, &drisl_bytes).unwrap();
+    (Arc::new(store), raw, drisl)
+}
+
+#[tokio::test]
+async fn test_get() {
+    let (store, raw, drisl) = store();
+
+    let (status, headers, body) = request(
+        &store,
+        Method::GET,
+        format!("/.well-known/rasl/{raw}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], server::CONTENT_TYPE_RAW);
+    assert_eq!(headers[header::ACCEPT_RANGES], "bytes");
+    assert_eq!(body, b"hello world");
+
+    let (status, headers, body) = request(
+        &store,
+        Method::GET,
+        format!("/.well-known/rasl/{drisl}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_TYPE], server::CONTENT_TYPE_DRISL);
+    assert_eq!(body, [0xf5]);
+}
+
+#[tokio::test]
+async fn test_head() {
+    let (store, raw, _) = store();
+    let (status, headers, body) = request(
+        &store,
+        Method::HEAD,
+        format!("/.well-known/rasl/{raw}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(headers[header::CONTENT_LENGTH], "11");
+    assert!(body.is_empty());
+}
+
+#[tokio::test]
+async fn test_range() {
+    let (store, raw, drisl) = store();
+
+    let path = format!("/.well-known/rasl/{raw}");
+    let (status, headers, body) =
+        request(&store, Method::GET, path.clone(), Some("bytes=6-")).await;
+    assert_eq!(status, StatusCode::PARTIAL_CONTENT);
+    assert_eq!(headers[header::CONTENT_RANGE], "bytes 6-10/11");
+    assert_eq!(body, b"world");
+
+    let (status, headers, _) = request(&store, Method::GET, path.clone(), Some("bytes=20-")).await;
+    assert_eq!(status, StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(headers[header::CONTENT_RANGE], "bytes */11");
+
+    // Malformed ranges and several ranges are ignored.
+    for range in ["bytes=6-2", "bytes=0-1,6-", "bytes=x-"] {
+        let (status, headers, body) = request(&store, Method::GET, path.clone(), Some(range)).await;
+        assert_eq!(status, StatusCode::OK, "{range}");
+        assert!(!headers.contains_key(header::CONTENT_RANGE), "{range}");
+        assert_eq!(body, b"hello world", "{range}");
+    }
+
+    // Range requests are ignored for DRISL blocks.
+    let path = format!("/.well-known/rasl/{drisl}");
+    let (status, _, body) = request(&store, Method::GET, path, Some("bytes=1-")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, [0xf5]);
+}
+
+#[tokio::test]
+async fn test_rejected() {
+    let (store, _, _) = store();
+
+    let missing = Cid::digest_sha2(Codec::Raw, b"missing");
+    let (status, _, _) = request(
+        &store,
+        Method::GET,
+        format!("/.well-known/rasl/{missing}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _, _) = request(
+        &store,
+        Method::GET,
+        "/.well-known/rasl/invalid".into(),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let empty = Cid::empty_sha2_256(Codec::Raw);
+    let (status, _, _) = request(
+        &store,
+        Method::GET,
+        format!("/.well-known/rasl/{empty}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let corrupted = Cid::digest_sha2(Codec::Raw, b"original");
+    store.put(&corrupted, b"corrupted").unwrap();
+    let (status, _, _) = request(
+        &store,
+        Method::GET,
+        format!("/.well-known/rasl/{corrupted}"),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -1,12 +1,12 @@
 use std::{collections::BTreeMap, iter};
 
 use dasl::drisl::{
-    from_slice,
+    EncodeError, Value, from_slice,
     ser::{BufWriter, Serializer},
     to_vec,
 };
 use serde::{
-    Serialize,
+    Deserialize, Serialize,
     de::value::{self, MapDeserializer, SeqDeserializer},
 };
 use serde_bytes::{ByteBuf, Bytes};
@@ -48,6 +48,33 @@ fn test_f32() {
     assert_eq!(vec, b"\xfb\x40\xaf\x41\x00\x00\x00\x00\x00");
 }
 
+#[test]
+fn test_float_not_reduced() {
+    // Whole-valued floats and floats that fit in fewer bits are encoded as 64-bit floats.
+    for (float, encoded) in [
+        (1.0, "fb3ff0000000000000"),
+        (0.0, "fb0000000000000000"),
+        (-0.0, "fb8000000000000000"),
+        (100000.0, "fb40f86a0000000000"),
+        (0.5, "fb3fe0000000000000"),
+    ] {
+        assert_eq!(hex::encode(to_vec(&float).unwrap()), encoded);
+        assert_eq!(
+            hex::encode(to_vec(&dasl::drisl::Value::Float(float)).unwrap()),
+            encoded
+        );
+        let decoded: dasl::drisl::Value = from_slice(&hex::decode(encoded).unwrap()).unwrap();
+        assert_eq!(decoded, dasl::drisl::Value::Float(float));
+    }
+
+    // Reduced floats are rejected when decoding.
+    for reduced in ["f93c00", "f90000", "fa3f800000", "fa47c35000"] {
+        let reduced = hex::decode(reduced).unwrap();
+        assert!(from_slice::<dasl::drisl::Value>(&reduced).is_err());
+        assert!(from_slice::<f64>(&reduced).is_err());
+    }
+}
+
 #[test]
 fn test_infinity() {
     let vec = to_vec(&f64::INFINITY);
@@ -66,6 +93,52 @@ fn test_nan() {
     assert!(vec.is_err(), "Only finite numbers are supported.");
 }
 
+#[test]
+fn test_non_finite_floats_policy() {
+    use dasl::drisl::{
+        Value,
+        ser::{EncodeOptions, NonFiniteFloats, to_vec_with},
+    };
+
+    #[derive(Serialize)]
+    struct Reading {
+        temperature: f64,
+        humidity: f32,
+    }
+    let reading = Reading {
+        temperature: f64::NAN,
+        humidity: f32::INFINITY,
+    };
+    assert!(to_vec(&reading).is_err());
+    assert!(to_vec_with(&reading, EncodeOptions::new()).is_err());
+
+    let null = EncodeOptions::new().non_finite_floats(NonFiniteFloats::Null);
+    let encoded = to_vec_with(&reading, null).unwrap();
+    assert_eq!(
+        from_slice::<Value>(&encoded).unwrap(),
+        dasl::drisl!({ "temperature": null, "humidity": null })
+    );
+    // Nested and buffered values use the options as well.
+    let nested = vec![BTreeMap::from([("a", vec![f64::NEG_INFINITY])])];
+    assert_eq!(
+        to_vec_with(&nested, null).unwrap(),
+        to_vec(&dasl::drisl!([{ "a": [null] }])).unwrap()
+    );
+
+    let canonical = EncodeOptions::new().non_finite_floats(NonFiniteFloats::Canonicalize);
+    let other_nan = f64::from_bits(0xfff0_0000_0000_0001);
+    for nan in [f64::NAN, -f64::NAN, other_nan] {
+        assert_eq!(
+            hex::encode(to_vec_with(&nan, canonical).unwrap()),
+            "fb7ff8000000000000"
+        );
+    }
+    assert_eq!(
+        hex::encode(to_vec_with(&f64::NEG_INFINITY, canonical).unwrap()),
+        "fbfff0000000000000"
+    );
+}
+
 #[test]
 fn test_integer() {
     // u8
@@ -98,7 +171,14 @@ fn test_integer() {
     let vec = to_vec(&(-(u64::MAX as i128 + 1))).unwrap();
     assert_eq!(vec, b"\x3B\xff\xff\xff\xff\xff\xff\xff\xff");
     // i128 out of -u64 range
-    assert!(to_vec(&i128::MIN).is_err());
+    assert!(matches!(
+        dasl::drisl::ser::to_vec(&i128::MIN),
+        Err(EncodeError::IntegerOutOfRange)
+    ));
+    assert!(matches!(
+        dasl::drisl::ser::to_vec(&Value::Integer(Value::MAX_INTEGER + 1)),
+        Err(EncodeError::IntegerOutOfRange)
+    ));
 }
 
 #[test]
@@ -166,6 +246,26 @@ fn test_key_order_transcode_map() {
     assert_eq!(result, expected);
 }
 
+#[test]
+fn test_custom_writer() {
+    /// Collects every push separately.
+    struct Pushes(Vec<Vec<u8>>);
+
+    impl dasl::drisl::ser::Write for Pushes {
+        type Error = std::convert::Infallible;
+
+        fn push(&mut self, input: &[u8]) -> Result<(), Self::Error> {
+            self.0.push(input.to_vec());
+            Ok(())
+        }
+    }
+
+    let mut serializer = Serializer::new(Pushes(Vec::new()));
+    ("foo", 1).serialize(&mut serializer).unwrap();
+    let pushes = serializer.into_inner().0;
+    assert_eq!(pushes.concat(), to_vec(&("foo", 1)).unwrap());
+}
+
 // This test makes sure that even unbound lists are not encoded as such (as lists in DAG-CBOR need
 // to be finite).
 #[test]
@@ -241,3 +341,341 @@ fn test_struct_variant_canonical() {
         b"\xa1\x64Data\xa3\x61a\x01\x61b\x02\x63abc\x03"
     )
 }
+
+#[test]
+fn test_flatten() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Inner {
+        b: u8,
+        abc: u8,
+    }
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Outer {
+        a: u8,
+        #[serde(flatten)]
+        inner: Inner,
+        #[serde(flatten)]
+        extra: BTreeMap<String, u8>,
+    }
+
+    let outer = Outer {
+        a: 1,
+        inner: Inner { b: 2, abc: 3 },
+        extra: BTreeMap::from([("dd".to_string(), 4)]),
+    };
+    let bytes = dasl::drisl::to_vec(&outer).unwrap();
+    assert_eq!(bytes, b"\xa4\x61a\x01\x61b\x02\x62dd\x04\x63abc\x03");
+    assert_eq!(from_slice::<Outer>(&bytes).unwrap(), outer);
+
+    let duplicate = Outer {
+        a: 1,
+        inner: Inner { b: 2, abc: 3 },
+        extra: BTreeMap::from([("b".to_string(), 4)]),
+    };
+    let err = dasl::drisl::ser::to_vec(&duplicate).unwrap_err();
+    assert!(matches!(err, EncodeError::Msg(msg) if msg == "Duplicate map key: b"));
+}
+
+#[test]
+fn test_nested_error_message() {
+    let value = BTreeMap::from([("a", vec![f64::NAN])]);
+    let err = dasl::drisl::to_vec(&value).unwrap_err();
+    let nested = dasl::drisl::to_vec(&f64::NAN).unwrap_err();
+    assert_eq!(err.to_string(), nested.to_string());
+}
+
+#[test]
+fn test_to_vec_with_cid() {
+    use dasl::{
+        cid::{Cid, Codec, Multihash},
+        drisl::to_vec_with_cid,
+    };
+
+    // Map entries are buffered for sorting before they reach the writer.
+    let mut map = BTreeMap::new();
+    map.insert("zz", vec![1, 2, 3]);
+    map.insert("a", vec![]);
+    let value = (map, "text", 1.5);
+
+    let encoded = to_vec(&value).unwrap();
+    let (bytes, cid) = to_vec_with_cid(&value, Multihash::Sha2256).unwrap();
+    assert_eq!(bytes, encoded);
+    assert_eq!(cid, Cid::digest_sha2(Codec::Drisl, &encoded));
+
+    let (bytes, cid) = to_vec_with_cid(&value, Multihash::Blake3).unwrap();
+    assert_eq!(bytes, encoded);
+    assert_eq!(cid, Cid::digest_blake3(Codec::Drisl, &encoded));
+}
+
+#[test]
+fn test_cid_of() {
+    use dasl::{
+        cid::{Cid, Codec, Multihash},
+        drisl::cid_of,
+    };
+
+    // Mixes many small writes with ones larger than the internal buffer.
+    let mut map = BTreeMap::new();
+    for i in 0..200 {
+        map.insert(format!("key{i}"), "x".repeat(i * 20));
+    }
+    let value = (map, vec![1u8; 100], "short");
+
+    let encoded = to_vec(&value).unwrap();
+    assert_eq!(
+        cid_of(&value, Multihash::Sha2256).unwrap(),
+        Cid::digest_sha2(Codec::Drisl, &encoded)
+    );
+    assert_eq!(
+        cid_of(&value, Multihash::Blake3).unwrap(),
+        Cid::digest_blake3(Codec::Drisl, &encoded)
+    );
+    assert_eq!(
+        cid_of("", Multihash::Blake3).unwrap(),
+        Cid::digest_blake3(Codec::Drisl, to_vec("").unwrap())
+    );
+}
+
+#[test]
+fn test_to_vec_in() {
+    use dasl::drisl::to_vec_in;
+
+    let mut buf = Vec::new();
+    to_vec_in(&mut buf, "foo").unwrap();
+    to_vec_in(&mut buf, &[1u8, 2]).unwrap();
+    assert_eq!(
+        buf,
+        [to_vec("foo").unwrap(), to_vec(&[1u8, 2]).unwrap()].concat()
+    );
+
+    // The allocation is kept when clearing the buffer.
+    let capacity = buf.capacity();
+    buf.clear();
+    to_vec_in(&mut buf, &1u8).unwrap();
+    assert_eq!(buf, [0x01]);
+    assert_eq!(buf.capacity(), capacity);
+
+    // Nothing is appended on errors.
+    let result = to_vec_in(&mut buf, &("partial", f64::NAN));
+    assert!(result.is_err());
+    assert_eq!(buf, [0x01]);
+}
+
+#[test]
+fn test_encoded_len() {
+    use dasl::drisl::{Value, encoded_len};
+
+    let mut map = BTreeMap::new();
+    for i in 0..100 {
+        map.insert(format!("key{i}"), "x".repeat(i * 3));
+    }
+    let value = dasl::drisl!({
+        "int": -18446744073709551616i128,
+        "float": 1.5,
+        "bytes": Value::Bytes(vec![0; 300]),
+        "cid": dasl::cid::Cid::digest_sha2(dasl::cid::Codec::Raw, b"foo"),
+        "nested": [[], {}, null, true],
+    });
+    assert_eq!(encoded_len(&value).unwrap(), to_vec(&value).unwrap().len());
+    assert_eq!(encoded_len(&map).unwrap(), to_vec(&map).unwrap().len());
+    assert_eq!(encoded_len("").unwrap(), 1);
+    assert!(encoded_len(&f64::NAN).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_to_writer_buffered() {
+    use std::io::{self, IoSlice, Write};
+
+    use dasl::drisl::{ser::WriteOptions, to_writer, to_writer_with};
+
+    /// Records the length of every write.
+    #[derive(Default)]
+    struct Recorder {
+        out: Vec<u8>,
+        writes: Vec<usize>,
+        vectored: usize,
+    }
+
+    impl Write for Recorder {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.out.extend_from_slice(buf);
+            self.writes.push(buf.len());
+            Ok(buf.len())
+        }
+
+        fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+            // Only take part of the second slice, to exercise partial writes.
+            self.vectored += 1;
+            let len = bufs[0].len() + bufs.get(1).map_or(0, |buf| buf.len() / 2);
+            let data = bufs.iter().flat_map(|buf| buf.iter()).take(len);
+            self.out.extend(data);
+            self.writes.push(len);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let value = (vec!["a"; 1000], ByteBuf::from(vec![7; 10_000]), 1, "b");
+    let expected = to_vec(&value).unwrap();
+
+    // Small items are batched, only the large byte string is written on its own.
+    let mut recorder = Recorder::default();
+    to_writer(&mut recorder, &value).unwrap();
+    assert_eq!(recorder.out, expected);
+    assert_eq!(recorder.writes, [1 + 3 + 2000 + 3, 10_000, 3]);
+
+    let mut recorder = Recorder::default();
+    let options = WriteOptions::new().buffer_len(512);
+    to_writer_with(&mut recorder, &value, options).unwrap();
+    assert_eq!(recorder.out, expected);
+    assert!(
+        recorder
+            .writes
+            .iter()
+            .all(|&len| len <= 512 || len == 10_000)
+    );
+    assert_eq!(recorder.vectored, 0);
+
+    // The buffer is written together with the byte string.
+    let mut recorder = Recorder::default();
+    to_writer_with(&mut recorder, &value, options.vectored(true)).unwrap();
+    assert_eq!(recorder.out, expected);
+    assert_eq!(recorder.vectored, 1);
+
+    // Without a buffer, everything is passed on as is.
+    let mut recorder = Recorder::default();
+    to_writer_with(&mut recorder, &value, WriteOptions::new().buffer_len(0)).unwrap();
+    assert_eq!(recorder.out, expected);
+    assert!(recorder.writes.len() > 2000);
+}
+
+#[test]
+fn test_sorted_keys() {
+    use dasl::drisl::{
+        SortedMap,
+        ser::{EncodeOptions, to_vec_with},
+    };
+
+    #[derive(Serialize)]
+    struct Inner {
+        b: u8,
+        aa: u8,
+    }
+
+    #[derive(Serialize)]
+    enum Kind {
+        Inner { b: u8, aa: u8 },
+    }
+
+    #[derive(Serialize)]
+    struct Outer {
+        id: u8,
+        kind: Kind,
+        inner: Inner,
+        entries: SortedMap<u8>,
+    }
+
+    let sorted = EncodeOptions::new().sorted_keys(true);
+    let outer = Outer {
+        id: 1,
+        kind: Kind::Inner { b: 2, aa: 3 },
+        inner: Inner { b: 4, aa: 5 },
+        entries: [("bb".to_string(), 6), ("c".to_string(), 7)]
+            .into_iter()
+            .collect(),
+    };
+    assert_eq!(
+        to_vec_with(&outer, sorted).unwrap(),
+        to_vec(&outer).unwrap()
+    );
+
+    #[derive(Serialize)]
+    struct Unsorted {
+        name: u8,
+        id: u8,
+    }
+    let err = to_vec_with(&Unsorted { name: 1, id: 2 }, sorted).unwrap_err();
+    assert!(matches!(err, EncodeError::Msg(msg) if msg.contains("id")));
+
+    // A `BTreeMap` iterates in a different order than the canonical one.
+    let map = BTreeMap::from([("aa", 1), ("b", 2)]);
+    assert!(to_vec_with(&map, sorted).is_err());
+
+    // Duplicate keys are out of order as well.
+    let duplicates = [("a", 1), ("a", 2)];
+    let err = to_vec_with(&OrderedMap(&duplicates), sorted).unwrap_err();
+    assert!(matches!(err, EncodeError::Msg(msg) if msg.contains("canonical order")));
+
+    // Maps without a known length are still sorted.
+    #[derive(Serialize)]
+    struct Flattened {
+        name: u8,
+        #[serde(flatten)]
+        rest: BTreeMap<String, u8>,
+    }
+    let flattened = Flattened {
+        name: 1,
+        rest: BTreeMap::from([("id".to_string(), 2)]),
+    };
+    assert_eq!(
+        to_vec_with(&flattened, sorted).unwrap(),
+        to_vec(&flattened).unwrap()
+    );
+}
+
+/// Serializes the entries as a map, in the given order.
+struct OrderedMap<'a>(&'a [(&'a str, u8)]);
+
+impl Serialize for OrderedMap<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+    }
+}
+
+#[test]
+fn test_value_to_vec() {
+    use dasl::drisl::ser::{EncodeOptions, NonFiniteFloats, value_to_vec, value_to_vec_with};
+
+    // The same as encoding with Serde, including the order of map keys.
+    let value = dasl::drisl!({
+        "aa": [[1, 2], "xyz", null, 1.5, true, { "b": 1, "a": 2, "": 3 }],
+        "b": { "c": &b"bytes"[..], "": [] },
+        "cid": dasl::cid::Cid::digest_sha2(dasl::cid::Codec::Raw, b"foo"),
+    });
+    assert_eq!(value_to_vec(&value).unwrap(), to_vec(&value).unwrap());
+    assert_eq!(
+        value_to_vec(&Value::Text("foo".into())).unwrap(),
+        to_vec("foo").unwrap()
+    );
+
+    let mut serializer = Serializer::new(BufWriter::new(Vec::new()));
+    serializer.encode_value(&value).unwrap();
+    serializer.encode_value(&Value::Null).unwrap();
+    let mut expected = to_vec(&value).unwrap();
+    expected.push(0xf6);
+    assert_eq!(serializer.into_inner().into_inner(), expected);
+
+    // The options apply the same as with Serde.
+    let value = dasl::drisl!([1.5, f64::NAN]);
+    assert!(matches!(value_to_vec(&value), Err(EncodeError::Msg(_))));
+    let options = EncodeOptions::new().non_finite_floats(NonFiniteFloats::Null);
+    assert_eq!(
+        value_to_vec_with(&value, options).unwrap(),
+        to_vec(&dasl::drisl!([1.5, null])).unwrap()
+    );
+
+    // Nesting isn't limited by the stack.
+    let depth = 100_000;
+    let mut value = dasl::drisl!({});
+    for _ in 0..depth {
+        value = Value::Array(vec![value]);
+    }
+    let bytes = value_to_vec(&value).unwrap();
+    let mut expected = vec![0x81; depth];
+    expected.push(0xa0);
+    assert_eq!(bytes, expected);
+}
@@ -0,0 +1,135 @@
+use std::collections::{BTreeMap, HashMap};
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl::{self, Value, from_slice, serde_helpers, to_vec},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct Record {
+    #[serde(with = "serde_helpers::option_cid_string", default)]
+    parent: Option<Cid>,
+    #[serde(with = "serde_helpers::vec_cid_string")]
+    children: Vec<Cid>,
+    #[serde(with = "serde_helpers::cid_key_map")]
+    sizes: BTreeMap<Cid, u64>,
+    #[serde(with = "serde_helpers::cid_key_map")]
+    names: HashMap<Cid, String>,
+    #[serde(with = "serde_helpers::byte_array")]
+    key: [u8; 4],
+    #[serde(with = "serde_helpers::option_bytes", default)]
+    signature: Option<Vec<u8>>,
+    #[serde(with = "serde_helpers::option_bytes", default)]
+    digest: Option<[u8; 2]>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct StrongRef {
+    uri: String,
+    #[serde(with = "serde_helpers::cid_string")]
+    cid: Cid,
+}
+
+fn cids() -> [Cid; 3] {
+    [
+        Cid::digest_sha2(Codec::Drisl, b"foo"),
+        Cid::digest_blake3(Codec::Raw, b"bar"),
+        Cid::empty_sha2_256(Codec::Raw),
+    ]
+}
+
+#[test]
+fn test_cid_string() {
+    let [cid, ..] = cids();
+    let strong_ref = StrongRef {
+        uri: "at://did:plc:abc/app.bsky.feed.post/1".to_string(),
+        cid,
+    };
+    let bytes = to_vec(&strong_ref).unwrap();
+    let value: Value = from_slice(&bytes).unwrap();
+    assert_eq!(value.get("cid"), Some(&Value::Text(cid.to_string())));
+    assert_eq!(from_slice::<StrongRef>(&bytes).unwrap(), strong_ref);
+
+    let json = serde_json::to_string(&strong_ref).unwrap();
+    assert!(json.contains(&format!("\"cid\":\"{cid}\"")), "{json}");
+    assert_eq!(
+        serde_json::from_str::<StrongRef>(&json).unwrap(),
+        strong_ref
+    );
+
+    // Tag 42 CIDs and invalid strings are rejected.
+    let tagged = dasl::drisl!({ "uri": "at://x", "cid": cid });
+    assert!(drisl::from_value::<StrongRef>(tagged).is_err());
+    let invalid = to_vec(&dasl::drisl!({ "uri": "at://x", "cid": "bafy" })).unwrap();
+    let err = from_slice::<StrongRef>(&invalid).unwrap_err();
+    assert!(err.to_string().contains("\"bafy\""), "{err}");
+}
+
+#[test]
+fn test_field_shapes() {
+    let [a, b, c] = cids();
+    let record = Record {
+        parent: Some(a),
+        children: vec![b, c],
+        sizes: BTreeMap::from([(a, 1), (b, 2), (c, 3)]),
+        names: HashMap::from([(c, "c".to_string()), (a, "a".to_string())]),
+        key: [1, 2, 3, 4],
+        signature: Some(vec![5, 6]),
+        digest: Some([7, 8]),
+    };
+    let bytes = to_vec(&record).unwrap();
+    // The maps are written in canonical order, whatever the order of the map type.
+    drisl::validate(&bytes).unwrap();
+    assert_eq!(from_slice::<Record>(&bytes).unwrap(), record);
+
+    let value: Value = from_slice(&bytes).unwrap();
+    assert_eq!(value.get("parent"), Some(&Value::Text(a.to_string())));
+    assert_eq!(
+        value.get("children"),
+        Some(&Value::Array(vec![
+            Value::Text(b.to_string()),
+            Value::Text(c.to_string())
+        ]))
+    );
+    assert_eq!(
+        value
+            .get("sizes")
+            .and_then(|sizes| sizes.get(&c.to_string()[..])),
+        Some(&Value::Integer(3))
+    );
+    assert_eq!(value.get("key"), Some(&Value::Bytes(vec![1, 2, 3, 4])));
+    assert_eq!(value.get("signature"), Some(&Value::Bytes(vec![5, 6])));
+    assert_eq!(value.get("digest"), Some(&Value::Bytes(vec![7, 8])));
+
+    let json = serde_json::to_string(&record).unwrap();
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+}
+
+#[test]
+fn test_missing_and_null() {
+    let empty = Record::default();
+    let bytes = to_vec(&empty).unwrap();
+    let value: Value = from_slice(&bytes).unwrap();
+    assert_eq!(value.get("parent"), Some(&Value::Null));
+    assert_eq!(value.get("signature"), Some(&Value::Null));
+    assert_eq!(from_slice::<Record>(&bytes).unwrap(), empty);
+
+    // Fields with `#[serde(default)]` may be left out.
+    let value = dasl::drisl!({
+        "children": [],
+        "sizes": {},
+        "names": {},
+        "key": (Value::Bytes(vec![0; 4])),
+    });
+    assert_eq!(drisl::from_value::<Record>(value).unwrap(), empty);
+
+    // Byte arrays must have the exact length.
+    let value = dasl::drisl!({
+        "children": [],
+        "sizes": {},
+        "names": {},
+        "key": (Value::Bytes(vec![0; 3])),
+    });
+    assert!(drisl::from_value::<Record>(value).is_err());
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use dasl::{
+    drisl,
+    drisl::{
+        DecodeError, SortedMap, Value,
+        de::{DecodeOptions, DuplicateKeys},
+        sorted_map::{Entry, UnsortedKeyError},
+    },
+};
+
+#[test]
+fn test_sorted_map_roundtrip() {
+    let value = drisl!({ "ccc": 3, "a": 1, "bb": { "nested": [1, 2] } });
+    let bytes = drisl::to_vec(&value).unwrap();
+
+    let map: SortedMap<Value> = drisl::from_slice(&bytes).unwrap();
+    let keys: Vec<_> = map.keys().collect();
+    assert_eq!(keys, ["a", "bb", "ccc"]);
+    assert_eq!(drisl::to_vec(&map).unwrap(), bytes);
+
+    let btree: BTreeMap<String, Value> = map.clone().into();
+    assert_eq!(Value::Map(btree.clone()), value);
+    assert_eq!(SortedMap::from(btree), map);
+
+    // Out of order keys, as accepted by lenient decoding, are sorted.
+    // {"bb": 2, "a": 1}
+    let unsorted = [0xa2, 0x62, 0x62, 0x62, 0x02, 0x61, 0x61, 0x01];
+    let lenient = DecodeOptions::new().lenient(true);
+    let map: SortedMap<u8> = drisl::from_slice_with(&unsorted, lenient).unwrap();
+    assert_eq!(
+        map.as_slice(),
+        [("a".to_string(), 1), ("bb".to_string(), 2)]
+    );
+
+    // {"a": 1, "a": 2}
+    let duplicate = [0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02];
+    assert!(matches!(
+        drisl::de::from_slice::<SortedMap<u8>>(&duplicate),
+        Err(DecodeError::DuplicateKey { .. })
+    ));
+    let options = DecodeOptions::new()
+        .lenient(true)
+        .duplicate_keys(DuplicateKeys::LastWins);
+    let map: SortedMap<u8> = drisl::from_slice_with(&duplicate, options).unwrap();
+    assert_eq!(map.get("a"), Some(&2));
+}
+
+#[test]
+fn test_sorted_map_mutation() {
+    let mut map = SortedMap::new();
+    assert_eq!(map.insert("bb".to_string(), 2), None);
+    assert_eq!(map.insert("a".to_string(), 1), None);
+    assert_eq!(map.insert("ccc".to_string(), 3), None);
+    assert_eq!(map.insert("a".to_string(), 10), Some(1));
+    assert_eq!(map.len(), 3);
+    assert!(map.contains_key("bb"));
+    assert_eq!(map.get("d"), None);
+
+    *map.entry("bb".to_string()).or_insert(0) += 1;
+    *map.entry("d".to_string()).or_default() += 4;
+    map.entry("ccc".to_string()).and_modify(|value| *value *= 2);
+    if let Some(value) = map.get_mut("a") {
+        *value += 1;
+    }
+    match map.entry("d".to_string()) {
+        Entry::Occupied(entry) => assert_eq!(entry.remove(), 4),
+        Entry::Vacant(_) => panic!("expected an occupied entry"),
+    }
+    assert_eq!(map.remove("missing"), None);
+
+    let entries: Vec<_> = map.iter().collect();
+    assert_eq!(entries, [("a", &11), ("bb", &3), ("ccc", &6)]);
+    for (_, value) in map.iter_mut() {
+        *value = 0;
+    }
+    assert!(map.values().all(|value| *value == 0));
+
+    let collected: SortedMap<u8> = [
+        ("b".to_string(), 1),
+        ("a".to_string(), 2),
+        ("b".to_string(), 3),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        collected.into_vec(),
+        [("a".to_string(), 2), ("b".to_string(), 3)]
+    );
+}
+
+#[test]
+fn test_sorted_map_unsorted() {
+    let mut map = SortedMap::new();
+    map.push("b".to_string(), 1).unwrap();
+    map.push("aa".to_string(), 2).unwrap();
+    assert_eq!(
+        map.push("c".to_string(), 3),
+        Err(UnsortedKeyError {
+            key: "c".to_string()
+        })
+    );
+    assert_eq!(map.len(), 2);
+
+    assert!(SortedMap::from_sorted(vec![("a".to_string(), 1), ("bb".to_string(), 2)]).is_ok());
+    assert_eq!(
+        SortedMap::from_sorted(vec![("bb".to_string(), 1), ("a".to_string(), 2)])
+            .unwrap_err()
+            .key,
+        "a"
+    );
+    assert_eq!(
+        SortedMap::from_sorted(vec![("a".to_string(), 1), ("a".to_string(), 2)])
+            .unwrap_err()
+            .key,
+        "a"
+    );
+}
+
+#[test]
+fn test_sorted_map_reverse_order() {
+    // A large map with its keys in reverse order, sorted once after decoding.
+    let count: u32 = 200_000;
+    let mut bytes = vec![0xba];
+    bytes.extend_from_slice(&count.to_be_bytes());
+    for i in (0..count).rev() {
+        bytes.push(0x66);
+        bytes.extend_from_slice(format!("{i:06}").as_bytes());
+        bytes.push(0x00);
+    }
+
+    assert!(matches!(
+        drisl::de::from_slice::<SortedMap<u8>>(&bytes),
+        Err(DecodeError::UnsortedKey { .. })
+    ));
+    let lenient = DecodeOptions::new().lenient(true);
+    let map: SortedMap<u8> = drisl::from_slice_with(&bytes, lenient).unwrap();
+    assert_eq!(map.len(), count as usize);
+    assert!(map.keys().zip(map.keys().skip(1)).all(|(a, b)| a < b));
+}
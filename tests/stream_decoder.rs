@@ -0,0 +1,105 @@
+use dasl::drisl::{
+    DecodeError, Value,
+    de::{DecodeOptions, StreamDecoder},
+    to_vec,
+};
+
+#[test]
+fn test_stream_decoder_byte_by_byte() {
+    let values = [
+        dasl::drisl!({ "name": "foo", "list": [1, 2.5, null], "bytes": &b"abc"[..] }),
+        dasl::drisl!("bar"),
+        dasl::drisl!(dasl::cid::Cid::digest_sha2(dasl::cid::Codec::Raw, b"foo")),
+        dasl::drisl!([]),
+    ];
+    let bytes: Vec<u8> = values.iter().flat_map(|v| to_vec(v).unwrap()).collect();
+
+    let mut decoder = StreamDecoder::new();
+    assert_eq!(decoder.needed().unwrap(), 1);
+    let mut decoded = Vec::new();
+    for byte in &bytes {
+        assert!(decoder.needed().unwrap() > 0);
+        decoder.feed(&[*byte]);
+        while let Some(value) = decoder.decode::<Value>().unwrap() {
+            decoded.push(value);
+        }
+    }
+    assert_eq!(decoded, values);
+    assert_eq!(decoder.buffered(), 0);
+    decoder.finish().unwrap();
+
+    // All at once.
+    let mut decoder = StreamDecoder::new();
+    decoder.feed(&bytes);
+    assert_eq!(decoder.needed().unwrap(), 0);
+    for value in &values {
+        let encoded = decoder.next_encoded().unwrap().unwrap();
+        assert_eq!(encoded, to_vec(value).unwrap());
+    }
+    assert_eq!(decoder.next_encoded().unwrap(), None);
+}
+
+#[test]
+fn test_stream_decoder_needed() {
+    let bytes = to_vec(&serde_bytes::Bytes::new(&[7; 1000])).unwrap();
+    let mut decoder = StreamDecoder::new();
+    decoder.feed(&bytes[..1]);
+    // The length of the byte string isn't known yet.
+    assert_eq!(decoder.needed().unwrap(), 2);
+    decoder.feed(&bytes[1..3]);
+    assert_eq!(decoder.needed().unwrap(), 1000);
+    decoder.feed(&bytes[3..500]);
+    assert_eq!(decoder.needed().unwrap(), bytes.len() - 500);
+    decoder.feed(&bytes[500..]);
+    assert_eq!(decoder.needed().unwrap(), 0);
+
+    // Array items need at least a byte each.
+    let mut decoder = StreamDecoder::new();
+    decoder.feed(&[0x83, 0x01]);
+    assert_eq!(decoder.needed().unwrap(), 2);
+    assert!(decoder.finish().is_err());
+}
+
+#[test]
+fn test_stream_decoder_errors() {
+    // Malformed data is detected before the value is complete, and the error sticks.
+    let mut decoder = StreamDecoder::new();
+    decoder.feed(&[0x82, 0x01, 0x1f]);
+    assert!(decoder.decode::<Value>().is_err());
+    assert!(decoder.needed().is_err());
+    decoder.feed(&[0x01]);
+    assert!(decoder.decode::<Value>().is_err());
+
+    // Values that don't deserialize into the type are skipped.
+    let mut decoder = StreamDecoder::new();
+    decoder.feed(&to_vec("foo").unwrap());
+    decoder.feed(&to_vec(&1).unwrap());
+    assert!(decoder.decode::<u8>().is_err());
+    assert_eq!(decoder.decode::<u8>().unwrap(), Some(1));
+
+    // The options apply to decoding, and limit the buffered size.
+    let options = DecodeOptions::new().max_size(10);
+    let mut decoder = StreamDecoder::new().with_options(options);
+    decoder.feed(&to_vec(&[1, 2, 3]).unwrap());
+    assert_eq!(decoder.decode::<Vec<u8>>().unwrap(), Some(vec![1, 2, 3]));
+    decoder.feed(&[0x59, 0x01, 0x00]);
+    assert!(matches!(
+        decoder.needed(),
+        Err(DecodeError::SizeLimit { limit: 10 })
+    ));
+    let options = DecodeOptions::new().max_depth(1);
+    let mut decoder = StreamDecoder::new().with_options(options);
+    decoder.feed(&to_vec(&[[1]]).unwrap());
+    assert!(matches!(
+        decoder.decode::<Value>(),
+        Err(DecodeError::DepthLimit { limit: 1 })
+    ));
+}
+
+#[test]
+fn test_stream_decoder_borrows() {
+    let mut decoder = StreamDecoder::new();
+    decoder.feed(&to_vec("foo").unwrap());
+    let value: &str = decoder.decode().unwrap().unwrap();
+    assert_eq!(value, "foo");
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "test-utils")]
+
+use dasl::{
+    drisl::{self, Value},
+    testing::{Generator, ScalarMix},
+};
+
+/// Returns the nesting depth of arrays and maps and the largest number of items in one of them.
+fn shape(value: &Value) -> (usize, usize) {
+    let children: Vec<&Value> = match value {
+        Value::Array(array) => array.iter().collect(),
+        Value::Map(map) => map.values().collect(),
+        _ => return (0, 0),
+    };
+    children
+        .iter()
+        .map(|child| shape(child))
+        .fold((1, children.len()), |(depth, width), (d, w)| {
+            (depth.max(d + 1), width.max(w))
+        })
+}
+
+fn scalars(value: &Value, out: &mut Vec<Value>) {
+    match value {
+        Value::Array(array) => array.iter().for_each(|v| scalars(v, out)),
+        Value::Map(map) => map.values().for_each(|v| scalars(v, out)),
+        scalar => out.push(scalar.clone()),
+    }
+}
+
+#[test]
+fn test_generator_deterministic() {
+    let a: Vec<Value> = Generator::new(7).take(20).collect();
+    let b: Vec<Value> = Generator::new(7).take(20).collect();
+    let c: Vec<Value> = Generator::new(8).take(20).collect();
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn test_generator_shape() {
+    for (depth, width) in [(0, 8), (1, 3), (3, 5), (6, 2)] {
+        let mut generator = Generator::new(1).depth(depth).width(width);
+        let mut max_depth = 0;
+        for _ in 0..50 {
+            let value = generator.value();
+            if depth > 0 {
+                assert!(matches!(value, Value::Map(_)));
+            }
+            let (d, w) = shape(&value);
+            assert!(d <= depth, "{d} > {depth}");
+            assert!(w <= width, "{w} > {width}");
+            max_depth = max_depth.max(d);
+        }
+        assert_eq!(max_depth, depth);
+    }
+}
+
+#[test]
+fn test_generator_scalars_and_links() {
+    let only_text = ScalarMix {
+        null: 0,
+        bool: 0,
+        integer: 0,
+        float: 0,
+        text: 1,
+        bytes: 0,
+    };
+    let mut out = Vec::new();
+    for value in Generator::new(2).scalars(only_text).take(20) {
+        scalars(&value, &mut out);
+    }
+    assert!(!out.is_empty());
+    assert!(out.iter().all(|v| matches!(v, Value::Text(_))));
+
+    out.clear();
+    for value in Generator::new(3).link_density(1.0).take(20) {
+        scalars(&value, &mut out);
+    }
+    assert!(!out.is_empty());
+    assert!(out.iter().all(|v| matches!(v, Value::Cid(_))));
+
+    out.clear();
+    for value in Generator::new(4).link_density(0.3).take(50) {
+        scalars(&value, &mut out);
+    }
+    let links = out.iter().filter(|v| matches!(v, Value::Cid(_))).count();
+    assert!(
+        links > out.len() / 10 && links < out.len() / 2,
+        "{links}/{}",
+        out.len()
+    );
+}
+
+#[test]
+fn test_generator_encoded() {
+    let mut generator = Generator::new(5).depth(5).width(6).link_density(0.1);
+    for _ in 0..50 {
+        let (value, bytes) = generator.encoded();
+        drisl::validate(&bytes).unwrap();
+        assert_eq!(drisl::from_slice::<Value>(&bytes).unwrap(), value);
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "std")]
+
+use std::io::BufReader;
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{
+        DecodeError,
+        de::{Deserializer, IoReader},
+        ser::{BufIoWriter, Serializer, WriteOptions},
+        to_vec,
+    },
+};
+
+#[test]
+fn test_transcode_json_stream() {
+    let json = "{\"b\": [1, -2, 3.5], \"a\": null}\n{\"ccc\": true, \"dd\": \"x\"}\n";
+    let expected = [
+        to_vec(&drisl!({ "a": null, "b": [1, -2, 3.5] })).unwrap(),
+        to_vec(&drisl!({ "dd": "x", "ccc": true })).unwrap(),
+    ]
+    .concat();
+
+    // Each JSON record is transcoded into canonical DRISL, with the map keys sorted.
+    let mut writer = BufIoWriter::new(Vec::new(), WriteOptions::new());
+    let mut serializer = Serializer::new(&mut writer);
+    let mut deserializer = serde_json::Deserializer::from_reader(json.as_bytes());
+    for _ in 0..2 {
+        serde_transcode::transcode(&mut deserializer, &mut serializer).unwrap();
+    }
+    deserializer.end().unwrap();
+    assert_eq!(writer.into_inner().unwrap(), expected);
+
+    // And back, reading the concatenated DRISL values from a `std::io` reader.
+    let reader = IoReader::new(BufReader::new(&expected[..]));
+    let mut deserializer = Deserializer::from_reader(reader);
+    let mut back = Vec::new();
+    for _ in 0..2 {
+        serde_transcode::transcode(
+            &mut deserializer,
+            &mut serde_json::Serializer::new(&mut back),
+        )
+        .unwrap();
+        back.push(b'\n');
+    }
+    deserializer.end().unwrap();
+    assert_eq!(
+        String::from_utf8(back).unwrap(),
+        "{\"a\":null,\"b\":[1,-2,3.5]}\n{\"dd\":\"x\",\"ccc\":true}\n"
+    );
+}
+
+#[test]
+fn test_transcode_trailing_data() {
+    let mut bytes = to_vec(&drisl!([1, 2])).unwrap();
+    bytes.push(0x01);
+    let mut deserializer = Deserializer::from_slice(&bytes);
+    serde_transcode::transcode(
+        &mut deserializer,
+        &mut serde_json::Serializer::new(Vec::new()),
+    )
+    .unwrap();
+    assert!(matches!(deserializer.end(), Err(DecodeError::TrailingData)));
+}
+
+#[test]
+fn test_transcode_cid_as_bytes() {
+    // Serde has no CIDs, so transcoding turns them into their bytes.
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let bytes = to_vec(&drisl!({ "link": cid })).unwrap();
+    let mut deserializer = Deserializer::from_slice(&bytes);
+    let mut serializer = Serializer::new(drisl::ser::BufWriter::new(Vec::new()));
+    serde_transcode::transcode(&mut deserializer, &mut serializer).unwrap();
+    assert_eq!(
+        serializer.into_inner().into_inner(),
+        to_vec(&drisl!({ "link": cid.as_bytes().to_vec() })).unwrap()
+    );
+}
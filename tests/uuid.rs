@@ -0,0 +1,66 @@
+#![cfg(feature = "uuid")]
+
+use dasl::drisl::{self, Value, from_slice, serde_helpers, to_vec};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    #[serde(with = "serde_helpers::uuid_bytes")]
+    id: Uuid,
+    #[serde(with = "serde_helpers::option_uuid_bytes", default)]
+    parent: Option<Uuid>,
+}
+
+const ID: Uuid = Uuid::from_u128(0x67e5_5044_10b1_426f_9247_bb68_0e5f_e0c8);
+
+#[test]
+fn test_uuid_bytes() {
+    let record = Record {
+        id: ID,
+        parent: Some(Uuid::nil()),
+    };
+    let bytes = to_vec(&record).unwrap();
+    assert_eq!(
+        from_slice::<Value>(&bytes).unwrap(),
+        dasl::drisl!({
+            "id": (Value::Bytes(ID.as_bytes().to_vec())),
+            "parent": (Value::Bytes(vec![0; 16])),
+        })
+    );
+    assert_eq!(from_slice::<Record>(&bytes).unwrap(), record);
+
+    // Human-readable formats get the string form.
+    let json = serde_json::to_string(&record).unwrap();
+    assert_eq!(
+        json,
+        r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8","parent":"00000000-0000-0000-0000-000000000000"}"#
+    );
+    assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+
+    // Null and missing optional fields are `None`.
+    let value = dasl::drisl!({ "id": (Value::Bytes(ID.as_bytes().to_vec())) });
+    let expected = Record {
+        id: ID,
+        parent: None,
+    };
+    assert_eq!(drisl::from_value::<Record>(value).unwrap(), expected);
+    let bytes = to_vec(&expected).unwrap();
+    assert_eq!(
+        from_slice::<Value>(&bytes).unwrap().get("parent"),
+        Some(&Value::Null)
+    );
+}
+
+#[test]
+fn test_uuid_bytes_invalid() {
+    for value in [
+        dasl::drisl!({ "id": (Value::Bytes(vec![0; 15])) }),
+        dasl::drisl!({ "id": (Value::Bytes(vec![0; 17])) }),
+        dasl::drisl!({ "id": "67e55044" }),
+        dasl::drisl!({ "id": 1 }),
+    ] {
+        let err = drisl::from_value::<Record>(value).unwrap_err();
+        assert!(err.to_string().contains("UUID as 16 bytes"), "{err}");
+    }
+}
@@ -0,0 +1,76 @@
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::{CanonicityError, CanonicityRule, to_vec, validate},
+};
+
+fn rule_at(hex: &str) -> (CanonicityRule, usize) {
+    let bytes = hex::decode(hex).unwrap();
+    let CanonicityError { offset, rule } = validate(&bytes).unwrap_err();
+    (rule, offset)
+}
+
+#[test]
+fn test_validate() {
+    let cid = Cid::digest_sha2(Codec::Drisl, b"foo");
+    let value = drisl!({
+        "a": [1, -1, 24, -25, 1.5, u64::MAX, i64::MIN],
+        "bb": { "": null, "x": true, "yy": false, "zz": cid },
+        "ccc": "a somewhat longer string that needs a one byte length",
+        "bytes": vec![0u8; 300],
+    });
+    validate(&to_vec(&value).unwrap()).unwrap();
+    validate(&to_vec(&drisl!([])).unwrap()).unwrap();
+    validate(&to_vec(&drisl!({})).unwrap()).unwrap();
+}
+
+#[test]
+fn test_validate_errors() {
+    use CanonicityRule::*;
+
+    for (hex, expected) in [
+        ("", (Truncated, 0)),
+        ("8201", (Truncated, 2)),
+        ("6261", (Truncated, 2)),
+        ("0101", (TrailingData, 1)),
+        ("1c", (InvalidHead, 0)),
+        ("1817", (NonMinimal, 0)),
+        ("390010", (NonMinimal, 0)),
+        ("81980101", (NonMinimal, 1)),
+        ("9fff", (IndefiniteLength, 0)),
+        ("5f41ff", (IndefiniteLength, 0)),
+        ("ff", (UnsupportedSimple, 0)),
+        ("a10101", (NonStringKey, 1)),
+        ("a2616201616101", (UnsortedKeys, 4)),
+        ("a262616101610101", (UnsortedKeys, 5)),
+        ("a2616101616101", (DuplicateKey, 4)),
+        ("a161ff01", (InvalidUtf8, 1)),
+        ("8262c328", (InvalidUtf8, 1)),
+        ("c1187b", (UnsupportedTag, 0)),
+        ("d82a4100", (InvalidCid, 2)),
+        ("d82a6100", (InvalidCid, 2)),
+        ("f93c00", (NonDoubleFloat, 0)),
+        ("fa3f800000", (NonDoubleFloat, 0)),
+        ("fb7ff8000000000000", (NonFiniteFloat, 0)),
+        ("fb7ff0000000000000", (NonFiniteFloat, 0)),
+        ("f7", (UnsupportedSimple, 0)),
+    ] {
+        let bytes = hex::decode(hex).unwrap();
+        assert_eq!(
+            validate(&bytes).map_err(|err| (err.rule, err.offset)),
+            Err(expected),
+            "for input {hex}"
+        );
+    }
+}
+
+#[test]
+fn test_validate_nested_key_order() {
+    // Key order is checked per map, nested maps start over.
+    let value = drisl!({ "b": { "a": 1, "c": 2 }, "cc": { "a": 1 } });
+    validate(&to_vec(&value).unwrap()).unwrap();
+
+    // [{"a": 1, "b": {"z": 1, "y": 2}}]
+    let (rule, offset) = rule_at("81a26161016162a2617a01617902");
+    assert_eq!((rule, offset), (CanonicityRule::UnsortedKeys, 11));
+}
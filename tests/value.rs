@@ -0,0 +1,406 @@
+use std::collections::BTreeMap;
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    drisl::Value,
+};
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn test_value_accessors() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let value = drisl!({
+        "int": 7,
+        "float": 1.5,
+        "text": "hello",
+        "bytes": vec![1u8, 2],
+        "cid": cid,
+        "bool": true,
+        "null": null,
+        "array": [1, 2],
+        "map": { "a": 1 },
+    });
+
+    assert_eq!(value["int"].as_int(), Some(7));
+    assert_eq!(value["float"].as_float(), Some(1.5));
+    assert_eq!(value["text"].as_str(), Some("hello"));
+    assert_eq!(value["bytes"].as_bytes(), Some(&[1u8, 2][..]));
+    assert_eq!(value["cid"].as_cid(), Some(&cid));
+    assert_eq!(value["bool"].as_bool(), Some(true));
+    assert!(value["null"].is_null());
+    assert_eq!(value["array"].as_array().map(Vec::len), Some(2));
+    assert_eq!(value["map"].as_map().map(|map| map.len()), Some(1));
+
+    // Accessors of the wrong kind return `None`.
+    assert_eq!(value["text"].as_int(), None);
+    assert_eq!(value["int"].as_str(), None);
+    assert_eq!(value["bytes"].as_array(), None);
+    assert_eq!(value.as_cid(), None);
+}
+
+#[test]
+fn test_value_get() {
+    let mut value = drisl!({ "a": { "b": 1 } });
+    assert_eq!(
+        value.get("a").and_then(|a| a.get("b")),
+        Some(&Value::Integer(1))
+    );
+    assert_eq!(value.get("missing"), None);
+    assert_eq!(drisl!([1]).get("a"), None);
+
+    *value.get_mut("a").unwrap().get_mut("b").unwrap() = drisl!("changed");
+    assert_eq!(value["a"]["b"].as_str(), Some("changed"));
+
+    value.as_map_mut().unwrap().remove("a");
+    assert_eq!(value, drisl!({}));
+}
+
+#[test]
+fn test_value_index_missing() {
+    let value = drisl!({ "array": [1, 2, 3] });
+    assert_eq!(value["array"][2], Value::Integer(3));
+    assert_eq!(value["array"][3], Value::Null);
+    assert_eq!(value["array"]["key"], Value::Null);
+    assert_eq!(value["missing"], Value::Null);
+    assert_eq!(value[0], Value::Null);
+    assert_eq!(value["missing"]["deeper"][0], Value::Null);
+}
+
+#[test]
+fn test_value_at() {
+    let mut value = drisl!({
+        "posts": [
+            { "author": "alice", "tags": ["a"] },
+            { "author": "bob", "": "empty key" },
+        ],
+        "count": 2,
+    });
+
+    assert_eq!(value.at(""), Some(&value));
+    assert_eq!(value.at("/"), Some(&value));
+    assert_eq!(value.at("count"), Some(&Value::Integer(2)));
+    assert_eq!(value.at("/count"), Some(&Value::Integer(2)));
+    assert_eq!(
+        value.at("posts/0/author").and_then(Value::as_str),
+        Some("alice")
+    );
+    assert_eq!(
+        value.at("posts/0/tags/0").and_then(Value::as_str),
+        Some("a")
+    );
+    assert_eq!(
+        value.at("posts/1/").and_then(Value::as_str),
+        Some("empty key")
+    );
+
+    assert_eq!(value.at("posts/2/author"), None);
+    assert_eq!(value.at("posts/first"), None);
+    assert_eq!(value.at("posts/-1"), None);
+    assert_eq!(value.at("count/0"), None);
+    assert_eq!(value.at("missing"), None);
+
+    *value.at_mut("posts/1/author").unwrap() = drisl!("carol");
+    assert_eq!(value["posts"][1]["author"].as_str(), Some("carol"));
+    assert_eq!(value.at_mut("posts/5"), None);
+}
+
+#[test]
+fn test_value_merge() {
+    // Examples from RFC 7386, appendix A.
+    for (target, patch, result) in [
+        (
+            drisl!({ "a": "b" }),
+            drisl!({ "a": "c" }),
+            drisl!({ "a": "c" }),
+        ),
+        (
+            drisl!({ "a": "b" }),
+            drisl!({ "b": "c" }),
+            drisl!({ "a": "b", "b": "c" }),
+        ),
+        (drisl!({ "a": "b" }), drisl!({ "a": null }), drisl!({})),
+        (
+            drisl!({ "a": "b", "b": "c" }),
+            drisl!({ "a": null }),
+            drisl!({ "b": "c" }),
+        ),
+        (
+            drisl!({ "a": ["b"] }),
+            drisl!({ "a": "c" }),
+            drisl!({ "a": "c" }),
+        ),
+        (
+            drisl!({ "a": "c" }),
+            drisl!({ "a": ["b"] }),
+            drisl!({ "a": ["b"] }),
+        ),
+        (
+            drisl!({ "a": { "b": "c" } }),
+            drisl!({ "a": { "b": "d", "c": null } }),
+            drisl!({ "a": { "b": "d" } }),
+        ),
+        (
+            drisl!({ "a": [{ "b": "c" }] }),
+            drisl!({ "a": [1] }),
+            drisl!({ "a": [1] }),
+        ),
+        (drisl!(["a", "b"]), drisl!(["c", "d"]), drisl!(["c", "d"])),
+        (drisl!({ "a": "b" }), drisl!(["c"]), drisl!(["c"])),
+        (drisl!({ "a": "foo" }), drisl!(null), drisl!(null)),
+        (drisl!({ "a": "foo" }), drisl!("bar"), drisl!("bar")),
+        (
+            drisl!({ "e": null }),
+            drisl!({ "a": 1 }),
+            drisl!({ "e": null, "a": 1 }),
+        ),
+        (
+            drisl!([1, 2]),
+            drisl!({ "a": "b", "c": null }),
+            drisl!({ "a": "b" }),
+        ),
+        (
+            drisl!({}),
+            drisl!({ "a": { "bb": { "ccc": null } } }),
+            drisl!({ "a": { "bb": {} } }),
+        ),
+    ] {
+        let mut value = target.clone();
+        value.merge(&patch);
+        assert_eq!(value, result, "merging {patch:?} into {target:?}");
+    }
+}
+
+#[test]
+fn test_value_ord() {
+    // Values of different kinds are ordered by variant.
+    let mut values = vec![
+        drisl!({}),
+        drisl!([]),
+        drisl!(null),
+        drisl!(true),
+        drisl!(""),
+        drisl!(0.0),
+        drisl!(vec![0u8]),
+        drisl!(0),
+    ];
+    values.sort();
+    assert_eq!(
+        values,
+        vec![
+            drisl!(0),
+            drisl!(vec![0u8]),
+            drisl!(0.0),
+            drisl!(""),
+            drisl!(true),
+            drisl!(null),
+            drisl!([]),
+            drisl!({}),
+        ]
+    );
+
+    assert!(drisl!(-1) < drisl!(1));
+    assert!(drisl!(-0.0) < drisl!(0.0));
+    assert!(drisl!(1.0) < drisl!(f64::INFINITY));
+    assert!(drisl!([1, 2]) < drisl!([1, 3]));
+    assert!(drisl!({ "a": 1 }) < drisl!({ "b": 0 }));
+}
+
+#[test]
+fn test_value_eq_hash() {
+    use std::collections::{BTreeSet, HashSet};
+
+    assert_eq!(drisl!(f64::NAN), drisl!(f64::NAN));
+    assert_ne!(drisl!(0.0), drisl!(-0.0));
+    assert_ne!(drisl!(1), drisl!(1.0));
+
+    let values = [
+        drisl!(f64::NAN),
+        drisl!(f64::NAN),
+        drisl!(0.0),
+        drisl!(-0.0),
+        drisl!({ "a": [1, 2] }),
+        drisl!({ "a": [1, 2] }),
+        drisl!(Cid::digest_sha2(Codec::Raw, b"foo")),
+        drisl!(Cid::digest_sha2(Codec::Raw, b"foo")),
+    ];
+    assert_eq!(values.iter().collect::<HashSet<_>>().len(), 5);
+    assert_eq!(values.iter().collect::<BTreeSet<_>>().len(), 5);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Kind {
+    Unit,
+    Newtype(u8),
+    Tuple(u8, String),
+    Struct { a: i8 },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Typed {
+    cid: Cid,
+    #[serde(with = "serde_bytes")]
+    bytes: Vec<u8>,
+    float: f32,
+    min: i128,
+    max: u64,
+    maybe: Option<Cid>,
+    nothing: Option<u8>,
+    unit: (),
+    kinds: Vec<Kind>,
+    map: BTreeMap<String, (bool, char)>,
+}
+
+fn typed() -> Typed {
+    Typed {
+        cid: Cid::digest_sha2(Codec::Raw, b"foo"),
+        bytes: vec![1, 2],
+        float: 1.5,
+        min: -1 - i128::from(u64::MAX),
+        max: u64::MAX,
+        maybe: Some(Cid::digest_blake3(Codec::Drisl, b"bar")),
+        nothing: None,
+        unit: (),
+        kinds: vec![
+            Kind::Unit,
+            Kind::Newtype(1),
+            Kind::Tuple(2, "x".into()),
+            Kind::Struct { a: -3 },
+        ],
+        map: BTreeMap::from([("k".to_string(), (true, 'c'))]),
+    }
+}
+
+#[test]
+fn test_to_value() {
+    let typed = typed();
+    let value = drisl::to_value(&typed).unwrap();
+    let bytes = drisl::to_vec(&typed).unwrap();
+    assert_eq!(value, drisl::from_slice::<Value>(&bytes).unwrap());
+    assert_eq!(drisl::to_vec(&value).unwrap(), bytes);
+
+    assert!(drisl::to_value(&f64::NAN).is_err());
+    assert!(drisl::to_value(&u128::MAX).is_err());
+    assert!(drisl::to_value(&BTreeMap::from([(1, 2)])).is_err());
+}
+
+#[test]
+fn test_from_value() {
+    let typed = typed();
+    let value = drisl::from_slice::<Value>(&drisl::to_vec(&typed).unwrap()).unwrap();
+    assert_eq!(drisl::from_value::<Typed>(value.clone()).unwrap(), typed);
+    assert_eq!(drisl::from_value::<Value>(value.clone()).unwrap(), value);
+
+    // Patch the dynamic value and convert it back.
+    let mut value = value;
+    if let Value::Map(map) = &mut value {
+        map.insert("max".into(), 7.into());
+    }
+    assert_eq!(drisl::from_value::<Typed>(value).unwrap().max, 7);
+
+    // Bytes aren't a CID and the other way around, just like when decoding.
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    assert!(drisl::from_value::<Cid>(Value::Bytes(cid.as_bytes().to_vec())).is_err());
+    assert!(drisl::from_value::<serde_bytes::ByteBuf>(Value::Cid(cid)).is_err());
+    assert!(drisl::from_value::<u8>(Value::Integer(256)).is_err());
+    assert!(drisl::from_value::<(u8, u8)>(drisl!([1, 2, 3])).is_err());
+}
+
+#[test]
+fn test_value_int_range() {
+    assert_eq!(Value::MIN_INTEGER, -(u64::MAX as i128) - 1);
+    assert_eq!(Value::MAX_INTEGER, u64::MAX as i128);
+    for int in [Value::MIN_INTEGER, -1, 0, Value::MAX_INTEGER] {
+        assert_eq!(Value::int(int), Ok(Value::Integer(int)));
+    }
+    for int in [i128::MIN, Value::MIN_INTEGER - 1, Value::MAX_INTEGER + 1] {
+        let err = Value::int(int).unwrap_err();
+        assert_eq!(err, drisl::IntegerRangeError(int));
+        assert!(err.to_string().contains(&int.to_string()));
+    }
+
+    // `to_value` enforces the same range as encoding.
+    assert!(drisl::to_value(&(Value::MAX_INTEGER + 1)).is_err());
+    assert_eq!(
+        drisl::to_value(&Value::MIN_INTEGER).unwrap(),
+        Value::Integer(Value::MIN_INTEGER)
+    );
+}
+
+#[test]
+fn test_value_size() {
+    // CIDs are stored inline, make sure nothing else makes values larger.
+    assert!(std::mem::size_of::<Value>() <= std::mem::size_of::<Cid>() + 16);
+}
+
+#[test]
+fn test_value_stats() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"foo");
+    let value = dasl::drisl!({
+        "name": "foo",
+        "data": &b"bytes"[..],
+        "list": [1, -1000, 1.5, true, null, [cid, { "k": 70_000 }]],
+        "empty": {},
+    });
+    let stats = value.stats();
+    assert_eq!(stats.integers, 3);
+    assert_eq!(stats.bytes, 1);
+    assert_eq!(stats.floats, 1);
+    assert_eq!(stats.texts, 1);
+    assert_eq!(stats.bools, 1);
+    assert_eq!(stats.nulls, 1);
+    assert_eq!(stats.cids, 1);
+    assert_eq!(stats.arrays, 2);
+    assert_eq!(stats.maps, 3);
+    assert_eq!(stats.map_entries, 5);
+    assert_eq!(stats.nodes(), 14);
+    assert_eq!(stats.max_depth, 4);
+    assert_eq!(stats.text_len, 3 + 4 + 4 + 4 + 5 + 1);
+    assert_eq!(stats.bytes_len, 5);
+    assert_eq!(
+        stats.encoded_len,
+        Some(drisl::to_vec(&value).unwrap().len())
+    );
+
+    // The depth is the one the decoder checks.
+    let bytes = drisl::to_vec(&value).unwrap();
+    let options = drisl::de::DecodeOptions::new();
+    assert!(drisl::from_slice_with::<Value>(&bytes, options.max_depth(4)).is_ok());
+    assert!(drisl::from_slice_with::<Value>(&bytes, options.max_depth(3)).is_err());
+
+    // Lengths at the boundaries of the header sizes.
+    for len in [0, 23, 24, 255, 256, 65_535, 65_536] {
+        let value = dasl::drisl!({ "a": "x".repeat(len), "b": vec![Value::Null; len] });
+        let bytes = drisl::to_vec(&value).unwrap();
+        assert_eq!(value.stats().encoded_len, Some(bytes.len()), "{len}");
+    }
+    for int in [
+        0,
+        23,
+        24,
+        -24,
+        -25,
+        255,
+        256,
+        u32::MAX.into(),
+        u64::MAX.into(),
+    ] {
+        let value = Value::Integer(int);
+        let bytes = drisl::to_vec(&value).unwrap();
+        assert_eq!(value.stats().encoded_len, Some(bytes.len()), "{int}");
+        let value = Value::Integer(-1 - int);
+        let bytes = drisl::to_vec(&value).unwrap();
+        assert_eq!(value.stats().encoded_len, Some(bytes.len()), "{int}");
+    }
+    let cid = Cid::digest_blake3(Codec::Drisl, b"foo");
+    let bytes = drisl::to_vec(&Value::Cid(cid)).unwrap();
+    assert_eq!(Value::Cid(cid).stats().encoded_len, Some(bytes.len()));
+
+    // Values that can't be encoded.
+    assert_eq!(dasl::drisl!([f64::NAN]).stats().encoded_len, None);
+    assert_eq!(
+        Value::Integer(Value::MAX_INTEGER + 1).stats().encoded_len,
+        None
+    );
+    assert_eq!(Value::Null.stats().max_depth, 0);
+}
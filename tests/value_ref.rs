@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl::{Value, ValueRef, from_slice_ref, to_vec},
+};
+
+fn value() -> Value {
+    let mut map = BTreeMap::new();
+    map.insert("text".to_string(), Value::Text("hello".into()));
+    map.insert("bytes".to_string(), Value::Bytes(vec![1, 2, 3]));
+    map.insert("int".to_string(), Value::Integer(-7));
+    map.insert("float".to_string(), Value::Float(1.5));
+    map.insert("null".to_string(), Value::Null);
+    map.insert(
+        "cid".to_string(),
+        Value::Cid(Cid::digest_sha2(Codec::Raw, b"foo")),
+    );
+    map.insert(
+        "array".to_string(),
+        Value::Array(vec![Value::Bool(true), Value::Text("nested".into())]),
+    );
+    Value::Map(map)
+}
+
+#[test]
+fn test_value_ref_roundtrip() {
+    let value = value();
+    let encoded = to_vec(&value).unwrap();
+
+    let value_ref = from_slice_ref(&encoded).unwrap();
+    assert_eq!(value_ref.to_owned(), value);
+    assert_eq!(value_ref, ValueRef::from(&value));
+    assert_eq!(to_vec(&value_ref).unwrap(), encoded);
+
+    let ValueRef::Map(map) = &value_ref else {
+        panic!("expected a map");
+    };
+    let ValueRef::Text(text) = map["text"] else {
+        panic!("expected text");
+    };
+    assert!(encoded.as_ptr_range().contains(&text.as_ptr()));
+}
+
+#[test]
+fn test_value_ref_duplicate_keys() {
+    // {"a": 1, "a": 2}
+    let encoded = [0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02];
+    assert!(from_slice_ref(&encoded).is_err());
+}
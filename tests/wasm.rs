@@ -0,0 +1,103 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use dasl::{
+    cid::{Cid, Codec},
+    drisl,
+    wasm::{self, Cid as JsCid},
+};
+use js_sys::{Array, JSON, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_roundtrip() {
+    let json = r#"{"a":[1,2.5,"x",null,true],"b":{"c":-3}}"#;
+    let value = JSON::parse(json).unwrap();
+    let bytes = wasm::encode(value).ok().unwrap();
+    assert_eq!(
+        bytes,
+        drisl::to_vec(&drisl!({ "a": [1, 2.5, "x", null, true], "b": { "c": -3 } })).unwrap()
+    );
+
+    let decoded = wasm::decode(&bytes).ok().unwrap();
+    assert_eq!(JSON::stringify(&decoded).unwrap(), json);
+}
+
+#[wasm_bindgen_test]
+fn test_bytes_bigint_cid() {
+    let cid = Cid::digest_sha2(Codec::Raw, b"hello");
+    let value = drisl!({
+        "bytes": vec![1u8, 2],
+        "big": u64::MAX,
+        "link": cid,
+    });
+    let bytes = drisl::to_vec(&value).unwrap();
+
+    let decoded = wasm::decode(&bytes).ok().unwrap();
+    let data = get(&decoded, "bytes");
+    assert!(data.is_instance_of::<Uint8Array>());
+    assert_eq!(Uint8Array::from(data).to_vec(), [1, 2]);
+    let big = get(&decoded, "big");
+    assert!(big.is_bigint());
+    assert_eq!(u64::try_from(big).unwrap(), u64::MAX);
+    let link = get(&get(&decoded, "link"), "/");
+    assert_eq!(link.as_string().unwrap(), cid.to_string());
+
+    assert_eq!(wasm::encode(decoded).ok().unwrap(), bytes);
+}
+
+#[wasm_bindgen_test]
+fn test_errors() {
+    assert!(wasm::encode(JsValue::from_f64(f64::NAN)).is_err());
+    assert!(wasm::encode(JsValue::symbol(None)).is_err());
+    // Cycles fail at the depth limit instead of overflowing the stack.
+    let cycle = Array::new();
+    cycle.push(&cycle);
+    assert!(wasm::encode(cycle.into()).is_err());
+    let nested = JSON::parse(&format!("{}1{}", "[".repeat(256), "]".repeat(256))).unwrap();
+    assert!(wasm::encode(nested).is_ok());
+    assert!(wasm::decode(&[0x82, 0x01]).is_err());
+    // 1 as one-byte unsigned integer
+    assert!(wasm::validate(&[0x18, 0x01]).is_err());
+    assert!(wasm::validate(&[0x01]).is_ok());
+}
+
+#[wasm_bindgen_test]
+fn test_cid() {
+    let expected = Cid::digest_blake3(Codec::Drisl, b"hello");
+    let cid = JsCid::digest(b"hello", Some("drisl".into()), Some("blake3".into()))
+        .ok()
+        .unwrap();
+    assert_eq!(cid.to_bytes(), expected.as_bytes());
+    assert_eq!(cid.codec(), "drisl");
+    assert_eq!(cid.hash_type(), "blake3");
+    assert_eq!(cid.hash(), expected.hash());
+    assert!(cid.verify(b"hello"));
+    assert!(!cid.verify(b"world"));
+
+    let string = cid.to_string_js(None).ok().unwrap();
+    assert_eq!(string, expected.to_string());
+    assert!(JsCid::parse(&string).ok().unwrap().equals(&cid));
+    let base58 = cid.to_string_js(Some("z".into())).ok().unwrap();
+    assert!(JsCid::parse(&base58).ok().unwrap().equals(&cid));
+    assert!(
+        JsCid::from_bytes(&cid.to_bytes())
+            .ok()
+            .unwrap()
+            .equals(&cid)
+    );
+    assert!(JsCid::parse("nope").is_err());
+    assert!(JsCid::digest(b"", Some("json".into()), None).is_err());
+
+    let value = JSON::parse(r#"{"a":1}"#).unwrap();
+    let cid = wasm::cid_of(value, None).ok().unwrap();
+    let bytes = drisl::to_vec(&drisl!({ "a": 1 })).unwrap();
+    assert_eq!(
+        cid.to_bytes(),
+        Cid::digest_sha2(Codec::Drisl, bytes).as_bytes()
+    );
+}